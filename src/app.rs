@@ -8,7 +8,21 @@ use crate::module::{
     banner::route as banner_routes, 
     category::route as category_routes,
     brand::route as brand_routes,
-    product::route as product_routes  
+    product::route as product_routes,
+    cart::route as cart_routes,
+    wishlist::route as wishlist_routes,
+    order::route as order_routes,
+    payment::route as payment_routes,
+    returns::route as return_routes,
+    shipping::route as shipping_routes,
+    promotion::route as promotion_routes,
+    recovery::route as recovery_routes,
+    search::route as search_routes,
+    review::route as review_routes,
+    question::route as question_routes,
+    recommendation::route as recommendation_routes,
+    comparison::route as comparison_routes,
+    compatibility::route as compatibility_routes
 };
 use crate::middleware::{AdminAuthentication, Authentication};
 
@@ -33,7 +47,21 @@ pub fn configure_services(cfg: &mut web::ServiceConfig) {
             .configure(banner_routes::config) // Public banner routes
             .configure(category_routes::category_routes) // Category routes
             .configure(brand_routes::brand_routes) // Brand routes
+            .configure(comparison_routes::comparison_routes) // Product comparisons, ahead of the /products scope
             .configure(product_routes::product_routes) // Product routes 
+            .configure(cart_routes::cart_routes) // Cart routes (guests and users)
+            .configure(wishlist_routes::wishlist_routes) // Wishlist routes
+            .configure(order_routes::order_routes) // Customer order routes
+            .configure(payment_routes::payment_routes) // Payment intents and gateway webhook
+            .configure(return_routes::return_routes) // Customer return requests
+            .configure(shipping_routes::shipping_routes) // Delivery quotes
+            .configure(promotion_routes::promotion_routes) // Coupon code checks
+            .configure(recovery_routes::recovery_routes) // Basket reminder unsubscribe links
+            .configure(search_routes::search_routes) // Product search
+            .configure(review_routes::review_routes) // Product reviews and helpful votes
+            .configure(question_routes::question_routes) // Product questions and answers
+            .configure(recommendation_routes::recommendation_routes) // Related products and cross-sells
+            .configure(compatibility_routes::compatibility_routes) // PC build compatibility checks
     )
     .route("/health", web::get().to(health_check))
     .default_service(web::route().to(not_found));
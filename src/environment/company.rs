@@ -0,0 +1,26 @@
+use std::env;
+
+/// Our own details as printed on invoices and receipts.
+#[derive(Debug, Clone)]
+pub struct CompanyDetails {
+    pub name: String,
+    pub address_lines: Vec<String>,
+    pub email: String,
+    pub phone: String,
+    pub vat_number: Option<String>,
+}
+
+impl CompanyDetails {
+    pub fn from_env() -> Self {
+        Self {
+            name: env::var("COMPANY_NAME").unwrap_or_else(|_| "Computers Store".to_string()),
+            // Separate lines with `|`, e.g. "12 Allen Avenue|Ikeja, Lagos"
+            address_lines: env::var("COMPANY_ADDRESS")
+                .map(|value| value.split('|').map(|line| line.trim().to_string()).collect())
+                .unwrap_or_default(),
+            email: env::var("COMPANY_EMAIL").unwrap_or_default(),
+            phone: env::var("COMPANY_PHONE").unwrap_or_default(),
+            vat_number: env::var("COMPANY_VAT_NUMBER").ok(),
+        }
+    }
+}
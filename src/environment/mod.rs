@@ -1,4 +1,5 @@
+pub mod company;
 pub mod database;
 
+pub use company::CompanyDetails;
 pub use database::connect_to_mongodb;
-
@@ -20,6 +20,26 @@ use crate::module::brand::crud::BrandCrud;
 use crate::module::brand::controller::BrandController;
 use crate::module::product::crud::ProductCrud;        // Add this import
 use crate::module::product::controller::ProductController; // Add this import
+use crate::module::cart::crud::CartCrud;
+use crate::module::cart::controller::CartController;
+use crate::module::wishlist::crud::WishlistCrud;
+use crate::module::order::crud::OrderCrud;
+use crate::module::order::controller::OrderController;
+use crate::module::payment::crud::PaymentCrud;
+use crate::module::returns::crud::ReturnCrud;
+use crate::module::invoice::crud::InvoiceCrud;
+use crate::module::shipping::crud::ShippingCrud;
+use crate::module::promotion::crud::PromotionCrud;
+use crate::module::tax::crud::TaxCrud;
+use crate::module::recovery::crud::RecoveryCrud;
+use crate::module::recovery::job::BasketReminderJob;
+use crate::module::search::crud::SearchCrud;
+use crate::module::review::crud::ReviewCrud;
+use crate::module::question::crud::QuestionCrud;
+use crate::module::recommendation::crud::RecommendationCrud;
+use crate::module::recommendation::job::ProductAssociationJob;
+use crate::module::comparison::crud::ComparisonCrud;
+use crate::module::compatibility::crud::CompatibilityCrud;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -39,9 +59,31 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Failed to connect to MongoDB");
 
-    // Initialize user controller
+    // Bring indexes and data up to date; `--migrate` stops once that's done
+    match service::migration_service::run_migrations(&db, service::migration_service::all_migrations()).await {
+        Ok(applied) => {
+            for name in &applied {
+                println!("Applied migration {}", name);
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to migrate the database: {}", e);
+            std::process::exit(1);
+        }
+    }
+    if std::env::args().any(|arg| arg == "--migrate") {
+        println!("Database is up to date");
+        return Ok(());
+    }
+
+    // Initialize cart CRUD
+    let cart_crud = CartCrud::new(&db);
+    let cart_crud_data = web::Data::new(cart_crud.clone());
+
+    // Initialize user controller (merges guest carts on sign-in)
     let user_crud = UserCrud::new(db.clone());
-    let user_controller = web::Data::new(UserController::new(user_crud));
+    let user_cart_controller = CartController::new(cart_crud.clone(), ProductCrud::new(&db));
+    let user_controller = web::Data::new(UserController::new(user_crud, user_cart_controller));
 
     // Initialize admin controller and create default admin if not exists
     let admin_crud = AdminCrud::new(&db);
@@ -64,6 +106,122 @@ async fn main() -> std::io::Result<()> {
     let product_crud = ProductCrud::new(&db);
     let product_crud_data = web::Data::new(product_crud);
 
+    // Initialize wishlist CRUD
+    let wishlist_crud = WishlistCrud::new(&db);
+    let wishlist_crud_data = web::Data::new(wishlist_crud.clone());
+
+    // Initialize order CRUD
+    let order_crud = OrderCrud::new(&db);
+    let order_crud_data = web::Data::new(order_crud.clone());
+
+    // Initialize promotion CRUD (coupons and their redemptions)
+    let promotion_crud = PromotionCrud::new(&db);
+    let promotion_crud_data = web::Data::new(promotion_crud.clone());
+
+    // Initialize shipping CRUD (delivery zones and rate tables)
+    let shipping_crud = ShippingCrud::new(&db);
+    let shipping_crud_data = web::Data::new(shipping_crud);
+
+    // Initialize tax CRUD
+    let tax_crud = TaxCrud::new(&db);
+    let tax_crud_data = web::Data::new(tax_crud);
+
+    // Release stock held by unpaid orders once their payment window closes
+    let expiry_controller = OrderController::new(
+        order_crud,
+        product_crud_data.get_ref().clone(),
+        cart_crud,
+        promotion_crud,
+        tax_crud_data.get_ref().clone(),
+        category_crud_data.get_ref().clone(),
+        shipping_crud_data.get_ref().clone(),
+    );
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            match expiry_controller.expire_unpaid_orders().await {
+                Ok(0) => {}
+                Ok(count) => println!("Cancelled {} unpaid orders with expired stock holds", count),
+                Err(e) => eprintln!("Failed to expire unpaid orders: {}", e),
+            }
+        }
+    });
+
+    // Initialize payment CRUD and gateway
+    let payment_crud = PaymentCrud::new(&db);
+    let payment_crud_data = web::Data::new(payment_crud);
+    let payment_provider_data = match service::payment::provider_from_env() {
+        Ok(provider) => web::Data::from(provider),
+        Err(e) => {
+            eprintln!("Failed to configure payments: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Initialize returns CRUD
+    let return_crud = ReturnCrud::new(&db);
+    let return_crud_data = web::Data::new(return_crud);
+
+    // Initialize invoice CRUD (numbers come from the `counters` collection and are
+    // issued in transactions, so MongoDB must run as a replica set)
+    let invoice_crud = InvoiceCrud::new(&db);
+    let invoice_crud_data = web::Data::new(invoice_crud);
+
+    // Initialize basket recovery CRUD and email reminders about idle carts and wishlists
+    let recovery_crud = RecoveryCrud::new(&db);
+    let recovery_crud_data = web::Data::new(recovery_crud.clone());
+    let reminder_job = BasketReminderJob::new(
+        recovery_crud,
+        cart_crud_data.get_ref().clone(),
+        wishlist_crud,
+        product_crud_data.get_ref().clone(),
+        order_crud_data.get_ref().clone(),
+        UserCrud::new(db.clone()),
+    );
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
+        loop {
+            interval.tick().await;
+            match reminder_job.run_once().await {
+                Ok(0) => {}
+                Ok(count) => println!("Sent {} basket reminders", count),
+                Err(e) => eprintln!("Failed to send basket reminders: {}", e),
+            }
+        }
+    });
+
+    // Initialize search CRUD
+    let search_crud = SearchCrud::new(&db);
+    let search_crud_data = web::Data::new(search_crud);
+
+    // Initialize review CRUD (ratings are rolled up onto products)
+    let review_crud_data = web::Data::new(ReviewCrud::new(&db));
+
+    // Initialize product Q&A CRUD
+    let question_crud_data = web::Data::new(QuestionCrud::new(&db));
+
+    // Initialize recommendations and rebuild co-purchase statistics every six hours
+    let recommendation_crud = RecommendationCrud::new(&db);
+    let recommendation_crud_data = web::Data::new(recommendation_crud.clone());
+    let association_job = ProductAssociationJob::new(recommendation_crud);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(6 * 60 * 60));
+        loop {
+            interval.tick().await;
+            match association_job.run_once().await {
+                Ok(count) => println!("Rebuilt co-purchase statistics for {} products", count),
+                Err(e) => eprintln!("Failed to rebuild co-purchase statistics: {}", e),
+            }
+        }
+    });
+
+    // Initialize saved product comparison CRUD
+    let comparison_crud_data = web::Data::new(ComparisonCrud::new(&db));
+
+    // Initialize PC build compatibility rules CRUD
+    let compatibility_crud_data = web::Data::new(CompatibilityCrud::new(&db));
+
     let db_data = web::Data::new(db);
 
     println!("🚀 Server starting on http://127.0.0.1:8080");
@@ -87,6 +245,23 @@ async fn main() -> std::io::Result<()> {
             .app_data(category_crud_data.clone())
             .app_data(brand_crud_data.clone())
             .app_data(product_crud_data.clone())  // Add this line
+            .app_data(cart_crud_data.clone())
+            .app_data(wishlist_crud_data.clone())
+            .app_data(order_crud_data.clone())
+            .app_data(payment_crud_data.clone())
+            .app_data(payment_provider_data.clone())
+            .app_data(return_crud_data.clone())
+            .app_data(invoice_crud_data.clone())
+            .app_data(shipping_crud_data.clone())
+            .app_data(promotion_crud_data.clone())
+            .app_data(tax_crud_data.clone())
+            .app_data(recovery_crud_data.clone())
+            .app_data(search_crud_data.clone())
+            .app_data(review_crud_data.clone())
+            .app_data(question_crud_data.clone())
+            .app_data(recommendation_crud_data.clone())
+            .app_data(comparison_crud_data.clone())
+            .app_data(compatibility_crud_data.clone())
             .app_data(db_data.clone())
             // Configure services/routes
             .configure(app::configure_services)
@@ -2,12 +2,14 @@ use std::future::{ready, Ready};
 use actix_web::{
     body::{BoxBody, EitherBody},
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
-    Error, HttpMessage, HttpResponse,
+    error::ErrorUnauthorized,
+    http::header,
+    Error, HttpMessage, HttpRequest, HttpResponse,
 };
 use futures_util::future::LocalBoxFuture;
 use serde_json::json;
 
-use crate::service::jwt_service::JwtService;
+use crate::service::jwt_service::{Claims, JwtService, Role};
 
 pub struct Authentication;
 
@@ -15,6 +17,37 @@ impl Authentication {
     pub fn new() -> Self {
         Authentication
     }
+
+    // Returns the claims of a valid bearer token, if the request carries one.
+    // Used by routes that serve both guests and signed-in users.
+    pub fn optional_user(req: &HttpRequest) -> Option<Claims> {
+        let token = req.headers().get(header::AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.strip_prefix("Bearer "))?;
+
+        JwtService::new().verify_token(token).ok()
+    }
+
+    // Public function that can be used anywhere to require a signed-in user
+    pub async fn check_user(req: &HttpRequest) -> Result<Claims, Error> {
+        let auth_header = req.headers().get(header::AUTHORIZATION)
+            .ok_or_else(|| ErrorUnauthorized("No authorization header"))?;
+
+        let auth_str = auth_header.to_str()
+            .map_err(|_| ErrorUnauthorized("Invalid authorization header"))?;
+
+        let token = auth_str.strip_prefix("Bearer ")
+            .ok_or_else(|| ErrorUnauthorized("Invalid authorization format"))?;
+
+        let jwt_service = JwtService::new();
+        let claims = jwt_service.verify_token(token)
+            .map_err(|_| ErrorUnauthorized("Invalid token"))?;
+
+        match claims.role {
+            Role::User => Ok(claims),
+            _ => Err(ErrorUnauthorized("Insufficient permissions")),
+        }
+    }
 }
 
 impl<S, B> Transform<S, ServiceRequest> for Authentication
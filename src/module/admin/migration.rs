@@ -0,0 +1,14 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("admin", 1, "unique email index", "admins", vec![
+            IndexModel::builder()
+                .keys(doc! { "email": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+        ]),
+    ]
+}
@@ -1,6 +1,7 @@
 pub mod controller;
 pub mod crud;
 pub mod interface;
+pub mod migration;
 pub mod model;
 pub mod route;
 pub mod schema;
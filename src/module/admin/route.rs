@@ -1,5 +1,16 @@
 use actix_web::{web, post, Scope};
 use crate::module::admin::controller::AdminController;
+use crate::module::order::route as order_routes;
+use crate::module::payment::route as payment_routes;
+use crate::module::returns::route as return_routes;
+use crate::module::shipping::route as shipping_routes;
+use crate::module::promotion::route as promotion_routes;
+use crate::module::tax::route as tax_routes;
+use crate::module::recovery::route as recovery_routes;
+use crate::module::review::route as review_routes;
+use crate::module::question::route as question_routes;
+use crate::module::recommendation::route as recommendation_routes;
+use crate::module::compatibility::route as compatibility_routes;
 
 // Public routes (no authentication required)
 pub fn public_routes() -> Scope {
@@ -10,7 +21,17 @@ pub fn public_routes() -> Scope {
 // Protected routes (requires admin authentication)
 pub fn protected_routes(cfg: &mut web::ServiceConfig) {
     // Add protected admin routes here
-    cfg.service(web::scope("")); // Empty for now, will add protected routes later
+    cfg.configure(order_routes::admin_order_routes)
+        .configure(payment_routes::admin_payment_routes)
+        .configure(return_routes::admin_return_routes)
+        .configure(shipping_routes::admin_shipping_routes)
+        .configure(promotion_routes::admin_promotion_routes)
+        .configure(tax_routes::admin_tax_routes)
+        .configure(recovery_routes::admin_recovery_routes)
+        .configure(review_routes::admin_review_routes)
+        .configure(question_routes::admin_question_routes)
+        .configure(recommendation_routes::admin_recommendation_routes)
+        .configure(compatibility_routes::admin_compatibility_routes);
 }
 
 #[post("/login")]
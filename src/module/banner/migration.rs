@@ -0,0 +1,18 @@
+use bson::doc;
+use mongodb::IndexModel;
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        // Not unique: banners may share a position, and moving one banner onto
+        // another's position shouldn't need both to change at once
+        Migration::indexes("banner", 1, "display order indexes", "banners", vec![
+            IndexModel::builder()
+                .keys(doc! { "is_active": 1, "display_order": 1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "display_order": 1 })
+                .build(),
+        ]),
+    ]
+}
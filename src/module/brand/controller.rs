@@ -34,6 +34,11 @@ impl BrandController {
         self.crud.get_brand(id).await
     }
     
+    // By current or old slug; callers redirect when the slug is an old one
+    pub async fn get_brand_by_slug(&self, slug: &str) -> Result<Option<Brand>, String> {
+        self.crud.get_brand_by_slug(slug).await
+    }
+    
     pub async fn get_all_brands(&self) -> Result<Vec<Brand>, String> {
         self.crud.get_all_brands().await
     }
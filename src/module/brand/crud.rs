@@ -1,6 +1,7 @@
 use bson::{doc, oid::ObjectId};
 use mongodb::{Collection, Database};
 use futures_util::TryStreamExt; // Add this import
+use crate::service::slug_service;
 use crate::module::brand::{
     model::Brand, 
     schema::CreateBrandSchema
@@ -17,6 +18,11 @@ impl BrandCrud {
         Self { collection }
     }
     
+    // Give brands saved with the old slug rules clean, unique slugs
+    pub async fn backfill_slugs(&self) -> Result<u64, String> {
+        slug_service::backfill_slugs(&self.collection.clone_with_type(), "name").await
+    }
+    
     pub async fn create_brand(&self, brand_: CreateBrandSchema) -> Result<Brand, String> {
         // Create brand model
         let mut brand = Brand::new(
@@ -27,16 +33,21 @@ impl BrandCrud {
         // Set optional fields
         brand.website = brand_.website;
         
-        // Insert into database
-        match self.collection.insert_one(&brand).await {
-            Ok(result) => {
-                if let Some(id) = result.inserted_id.as_object_id() {
-                    brand.id = Some(id);
+        // Another insert may take the slug in between, so pick again if it does
+        for _ in 0..3 {
+            brand.slug = slug_service::unique_slug(&self.collection.clone_with_type(), &brand.name, None).await?;
+            match self.collection.insert_one(&brand).await {
+                Ok(result) => {
+                    if let Some(id) = result.inserted_id.as_object_id() {
+                        brand.id = Some(id);
+                    }
+                    return Ok(brand);
                 }
-                Ok(brand)
+                Err(e) if slug_service::is_duplicate_slug(&e) => continue,
+                Err(e) => return Err(format!("Failed to create brand: {}", e)),
             }
-            Err(e) => Err(format!("Failed to create brand: {}", e))
         }
+        Err("Failed to create brand: no free slug".to_string())
     }
 
     // Add these new methods:
@@ -51,6 +62,14 @@ impl BrandCrud {
         }
     }
     
+    // By current or old slug
+    pub async fn get_brand_by_slug(&self, slug: &str) -> Result<Option<Brand>, String> {
+        match self.collection.find_one(slug_service::slug_filter(slug)).await {
+            Ok(brand) => Ok(brand),
+            Err(e) => Err(format!("Failed to retrieve brand: {}", e))
+        }
+    }
+    
     pub async fn get_all_brands(&self) -> Result<Vec<Brand>, String> {
         match self.collection.find(doc! { "is_active": true }).await {
             Ok(mut cursor) => {
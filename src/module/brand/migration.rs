@@ -0,0 +1,13 @@
+use crate::module::brand::crud::BrandCrud;
+use crate::service::migration_service::Migration;
+use crate::service::slug_service;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        // Slugs are made unique before the unique indexes are built
+        Migration::data("brand", 1, "clean, unique slugs", |db| async move {
+            BrandCrud::new(&db).backfill_slugs().await
+        }),
+        Migration::indexes("brand", 2, "unique slug indexes", "brands", slug_service::slug_indexes()),
+    ]
+}
@@ -2,5 +2,6 @@ pub mod model;
 pub mod schema;
 pub mod interface;
 pub mod crud;
+pub mod migration;
 pub mod controller;
 pub mod route;
\ No newline at end of file
@@ -1,6 +1,7 @@
 use bson::oid::ObjectId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use crate::service::slug_service::slugify;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Brand {
@@ -10,6 +11,9 @@ pub struct Brand {
     pub name: String,
     pub description: String,
     pub slug: String,
+    // Slugs used before a rename, kept so old links redirect
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_slugs: Vec<String>,
     
     // Brand logo/image URL
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -42,13 +46,14 @@ impl Brand {
         description: String,
     ) -> Self {
         let now = Utc::now();
-        let slug = name.to_lowercase().replace(" ", "-");
+        let slug = slugify(&name);
         
         Self {
             id: None,
             name,
             description,
             slug,
+            previous_slugs: Vec::new(),
             logo_url: None,
             website: None,
             is_active: true,
@@ -54,12 +54,34 @@ async fn get_brand_handler(
     }
 }
 
+// GET brand by slug handler; an old slug redirects to the current one
+async fn get_brand_by_slug_handler(
+    slug: web::Path<String>,
+    crud: web::Data<BrandCrud>,
+) -> Result<HttpResponse> {
+    let controller = BrandController::new(crud.get_ref().clone());
+    
+    match controller.get_brand_by_slug(&slug).await {
+        Ok(Some(brand)) if brand.slug != *slug => Ok(HttpResponse::MovedPermanently()
+            .insert_header(("Location", format!("/api/brands/slug/{}", brand.slug)))
+            .finish()),
+        Ok(Some(brand)) => Ok(HttpResponse::Ok().json(brand)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Brand not found"
+        }))),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
 // Route configuration function
 pub fn brand_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/brands")
             .route("", web::post().to(create_brand_handler))
             .route("", web::get().to(get_brands_handler))  // GET all brands
+            .route("/slug/{slug}", web::get().to(get_brand_by_slug_handler))
             .route("/{id}", web::get().to(get_brand_handler))  // GET single brand
     );
 }
\ No newline at end of file
@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use bson::oid::ObjectId;
+use chrono::{Duration, Utc};
+use validator::Validate;
+use crate::module::cart::{
+    crud::CartCrud,
+    interface::CartService,
+    model::{Cart, CartItem, CartMergeRules, CartOwner},
+    schema::{AddCartItemSchema, CartItemResponse, CartResponse, CartWarning, CartWarningKind, UpdateCartItemSchema},
+};
+use crate::module::product::{crud::ProductCrud, model::Product};
+
+// Helper function to parse an optional variant ID from a request
+fn parse_variant_id(variant_id: Option<&str>) -> Result<Option<ObjectId>, String> {
+    variant_id
+        .map(|id| ObjectId::parse_str(id).map_err(|_| "Invalid variant ID".to_string()))
+        .transpose()
+}
+use crate::service::cart_token_service::{guest_cart_ttl_days, CartTokenService};
+
+pub struct CartController {
+    crud: CartCrud,
+    product_crud: ProductCrud,
+    token_service: CartTokenService,
+    merge_rules: CartMergeRules,
+}
+
+impl CartController {
+    pub fn new(crud: CartCrud, product_crud: ProductCrud) -> Self {
+        Self {
+            crud,
+            product_crud,
+            token_service: CartTokenService::new(),
+            merge_rules: CartMergeRules::from_env(),
+        }
+    }
+
+    // Work out whose cart this is: a signed-in user wins over a guest token.
+    // An invalid or expired token is treated as no cart at all.
+    pub fn owner_for(&self, user_id: Option<&str>, cart_token: Option<&str>) -> Result<Option<CartOwner>, String> {
+        if let Some(user_id) = user_id {
+            let oid = ObjectId::parse_str(user_id)
+                .map_err(|_| "Invalid user ID".to_string())?;
+            return Ok(Some(CartOwner::User(oid)));
+        }
+
+        Ok(cart_token
+            .and_then(|token| self.token_service.verify(token).ok())
+            .map(CartOwner::Guest))
+    }
+
+    // Start a new guest cart and return its signed token
+    pub fn new_guest(&self) -> Result<(CartOwner, String), String> {
+        let (cart_id, token) = self.token_service.issue()?;
+        Ok((CartOwner::Guest(cart_id), token))
+    }
+
+    async fn load_cart(&self, owner: &CartOwner) -> Result<Cart, String> {
+        Ok(self.crud.find_by_owner(owner).await?.unwrap_or_else(|| Cart::new(owner)))
+    }
+
+    // Refresh timestamps (and the guest expiry) and persist the cart
+    async fn save_cart(&self, owner: &CartOwner, cart: &mut Cart) -> Result<(), String> {
+        let now = Utc::now();
+        cart.updated_at = now;
+        if let CartOwner::Guest(_) = owner {
+            cart.expires_at = Some(now + Duration::days(guest_cart_ttl_days()));
+        }
+        self.crud.save_cart(owner, cart).await
+    }
+
+    async fn get_active_product(&self, product_id: &ObjectId) -> Result<Product, String> {
+        let product = self.product_crud.get_product(&product_id.to_hex()).await?
+            .ok_or_else(|| "Product not found".to_string())?;
+
+        if !product.is_active {
+            return Err("Product is no longer available".to_string());
+        }
+        Ok(product)
+    }
+
+    // Check every line against the live product: clamp quantities to the stock
+    // on hand, take up the current price and collect warnings the UI can show.
+    // The changes are saved, so each warning is shown once.
+    async fn revalidate(&self, owner: &CartOwner, mut cart: Cart) -> Result<CartResponse, String> {
+        let ids: Vec<ObjectId> = cart.items.iter().map(|item| item.product_id).collect();
+        let products: HashMap<ObjectId, Product> = self.product_crud.get_products_by_ids(&ids).await?
+            .into_iter()
+            .filter_map(|product| product.id.map(|id| (id, product)))
+            .collect();
+
+        let mut items = Vec::new();
+        let mut warnings = Vec::new();
+        let mut changed = false;
+
+        for item in cart.items.iter_mut() {
+            let product_id = item.product_id.to_hex();
+            let variant_id = item.variant_id.map(|id| id.to_hex());
+
+            // A line whose variant was removed or disabled is unavailable too
+            let option = products.get(&item.product_id)
+                .filter(|product| product.is_active)
+                .and_then(|product| product.purchase_option(item.variant_id.as_ref()).ok());
+            let option = match option {
+                Some(option) => option,
+                None => {
+                    let mut warning = cart_warning(&product_id, CartWarningKind::ProductUnavailable,
+                        "This product is no longer available");
+                    warning.variant_id = variant_id.clone();
+                    warnings.push(warning);
+                    items.push(CartItemResponse {
+                        product_id,
+                        variant_id,
+                        name: None,
+                        sku: None,
+                        quantity: item.quantity,
+                        unit_price: item.unit_price,
+                        price_when_added: item.unit_price,
+                        line_total: 0,
+                        in_stock: false,
+                        available: false,
+                        added_at: item.added_at.to_rfc3339(),
+                    });
+                    continue;
+                }
+            };
+
+            let name = option.name();
+            let stock_quantity = option.stock_quantity();
+
+            // Out-of-stock lines are clamped to nothing and dropped once the shopper has been told
+            if stock_quantity <= 0 {
+                let mut warning = cart_warning(&product_id, CartWarningKind::OutOfStock,
+                    &format!("{} is out of stock", name));
+                warning.variant_id = variant_id.clone();
+                warning.requested_quantity = Some(item.quantity);
+                warning.available_quantity = Some(0);
+                warnings.push(warning);
+
+                item.quantity = 0;
+                changed = true;
+            } else if item.quantity > stock_quantity {
+                let mut warning = cart_warning(&product_id, CartWarningKind::StockReduced,
+                    &format!("Only {} of {} left, so your quantity was reduced", stock_quantity, name));
+                warning.variant_id = variant_id.clone();
+                warning.requested_quantity = Some(item.quantity);
+                warning.available_quantity = Some(stock_quantity);
+                warnings.push(warning);
+
+                item.quantity = stock_quantity;
+                changed = true;
+            }
+
+            let current_price = option.unit_price();
+            let previous_price = item.unit_price;
+            if current_price != previous_price {
+                let mut warning = cart_warning(&product_id, CartWarningKind::PriceChanged,
+                    &format!("The price of {} has changed since you added it", name));
+                warning.variant_id = variant_id.clone();
+                warning.previous_price = Some(previous_price);
+                warning.current_price = Some(current_price);
+                warnings.push(warning);
+
+                item.unit_price = current_price;
+                changed = true;
+            }
+
+            let in_stock = stock_quantity > 0;
+            items.push(CartItemResponse {
+                product_id,
+                variant_id,
+                name: Some(name),
+                sku: Some(option.sku().to_string()),
+                quantity: item.quantity,
+                unit_price: current_price,
+                price_when_added: previous_price,
+                line_total: if in_stock { current_price * item.quantity as i64 } else { 0 },
+                in_stock,
+                available: true,
+                added_at: item.added_at.to_rfc3339(),
+            });
+        }
+
+        if changed {
+            cart.items.retain(|item| item.quantity > 0);
+            self.save_cart(owner, &mut cart).await?;
+        }
+
+        let purchasable = items.iter().filter(|item| item.available && item.in_stock);
+        Ok(CartResponse {
+            cart_token: None,
+            total_quantity: purchasable.clone().map(|item| item.quantity).sum(),
+            subtotal: purchasable.map(|item| item.line_total).sum(),
+            items,
+            warnings,
+            updated_at: cart.updated_at.to_rfc3339(),
+        })
+    }
+}
+
+// Build a warning with only the required fields set
+fn cart_warning(product_id: &str, kind: CartWarningKind, message: &str) -> CartWarning {
+    CartWarning {
+        product_id: product_id.to_string(),
+        variant_id: None,
+        kind,
+        message: message.to_string(),
+        requested_quantity: None,
+        available_quantity: None,
+        previous_price: None,
+        current_price: None,
+    }
+}
+
+#[async_trait::async_trait]
+impl CartService for CartController {
+    async fn get_cart(&self, owner: &CartOwner) -> Result<CartResponse, String> {
+        let cart = self.load_cart(owner).await?;
+        self.revalidate(owner, cart).await
+    }
+
+    async fn add_item(&self, owner: &CartOwner, data: AddCartItemSchema) -> Result<CartResponse, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let product_id = ObjectId::parse_str(&data.product_id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let variant_id = parse_variant_id(data.variant_id.as_deref())?;
+        let product = self.get_active_product(&product_id).await?;
+        let option = product.purchase_option(variant_id.as_ref())?;
+
+        let mut cart = self.load_cart(owner).await?;
+        let existing = cart.item_mut(&product_id, variant_id.as_ref()).map(|item| item.quantity).unwrap_or(0);
+        let quantity = existing + data.quantity;
+
+        if quantity > option.stock_quantity() {
+            return Err(format!("Only {} of this product in stock", option.stock_quantity()));
+        }
+
+        match cart.item_mut(&product_id, variant_id.as_ref()) {
+            Some(item) => item.quantity = quantity,
+            None => cart.items.push(CartItem {
+                product_id,
+                variant_id,
+                quantity,
+                unit_price: option.unit_price(),
+                added_at: Utc::now(),
+            }),
+        }
+
+        self.save_cart(owner, &mut cart).await?;
+        self.revalidate(owner, cart).await
+    }
+
+    async fn update_item(&self, owner: &CartOwner, product_id: &str, variant_id: Option<&str>, data: UpdateCartItemSchema) -> Result<CartResponse, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let product_id = ObjectId::parse_str(product_id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let variant_id = parse_variant_id(variant_id)?;
+
+        let mut cart = self.load_cart(owner).await?;
+        if cart.item_mut(&product_id, variant_id.as_ref()).is_none() {
+            return Err("Product is not in the cart".to_string());
+        }
+
+        if data.quantity == 0 {
+            cart.items.retain(|item| item.product_id != product_id || item.variant_id != variant_id);
+        } else {
+            let product = self.get_active_product(&product_id).await?;
+            let stock_quantity = product.purchase_option(variant_id.as_ref())?.stock_quantity();
+            if data.quantity > stock_quantity {
+                return Err(format!("Only {} of this product in stock", stock_quantity));
+            }
+            if let Some(item) = cart.item_mut(&product_id, variant_id.as_ref()) {
+                item.quantity = data.quantity;
+            }
+        }
+
+        self.save_cart(owner, &mut cart).await?;
+        self.revalidate(owner, cart).await
+    }
+
+    async fn remove_item(&self, owner: &CartOwner, product_id: &str, variant_id: Option<&str>) -> Result<CartResponse, String> {
+        self.update_item(owner, product_id, variant_id, UpdateCartItemSchema { quantity: 0 }).await
+    }
+
+    async fn clear_cart(&self, owner: &CartOwner) -> Result<(), String> {
+        self.crud.delete_by_owner(owner).await
+    }
+
+    async fn merge_guest_cart(&self, cart_token: &str, user_id: &str) -> Result<(), String> {
+        let guest_owner = CartOwner::Guest(self.token_service.verify(cart_token)?);
+        let user_owner = CartOwner::User(ObjectId::parse_str(user_id)
+            .map_err(|_| "Invalid user ID".to_string())?);
+
+        let guest_cart = match self.crud.find_by_owner(&guest_owner).await? {
+            Some(cart) => cart,
+            None => return Ok(()),
+        };
+
+        let mut user_cart = self.load_cart(&user_owner).await?;
+        for guest_item in guest_cart.items {
+            match user_cart.item_mut(&guest_item.product_id, guest_item.variant_id.as_ref()) {
+                Some(item) => {
+                    item.quantity = self.merge_rules.strategy.combine(item.quantity, guest_item.quantity);
+                }
+                None => user_cart.items.push(guest_item),
+            }
+        }
+
+        if self.merge_rules.cap_at_stock {
+            let ids: Vec<ObjectId> = user_cart.items.iter().map(|item| item.product_id).collect();
+            let products: HashMap<ObjectId, Product> = self.product_crud.get_products_by_ids(&ids).await?
+                .into_iter()
+                .filter_map(|product| product.id.map(|id| (id, product)))
+                .collect();
+
+            for item in user_cart.items.iter_mut() {
+                let available = products.get(&item.product_id)
+                    .and_then(|product| product.purchase_option(item.variant_id.as_ref()).ok())
+                    .map(|option| option.stock_quantity())
+                    .unwrap_or(0);
+                item.quantity = item.quantity.min(available);
+            }
+            user_cart.items.retain(|item| item.quantity > 0);
+        }
+
+        self.save_cart(&user_owner, &mut user_cart).await?;
+        self.crud.delete_by_owner(&guest_owner).await
+    }
+}
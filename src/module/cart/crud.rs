@@ -0,0 +1,81 @@
+use bson::{doc, Document};
+use chrono::{DateTime, Utc};
+use mongodb::{
+    options::{FindOptions, ReplaceOptions},
+    Collection, Database,
+};
+use futures_util::TryStreamExt;
+use crate::module::cart::model::{Cart, CartOwner};
+
+#[derive(Clone)]
+pub struct CartCrud {
+    collection: Collection<Cart>,
+}
+
+impl CartCrud {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("carts");
+        Self { collection }
+    }
+
+    fn owner_filter(owner: &CartOwner) -> Document {
+        match owner {
+            CartOwner::User(id) => doc! { "user_id": id },
+            CartOwner::Guest(id) => doc! { "guest_id": id },
+        }
+    }
+
+    pub async fn find_by_owner(&self, owner: &CartOwner) -> Result<Option<Cart>, String> {
+        match self.collection.find_one(Self::owner_filter(owner)).await {
+            Ok(cart) => Ok(cart),
+            Err(e) => Err(format!("Failed to retrieve cart: {}", e))
+        }
+    }
+
+    // Insert or replace the owner's cart
+    pub async fn save_cart(&self, owner: &CartOwner, cart: &Cart) -> Result<(), String> {
+        let options = ReplaceOptions::builder().upsert(true).build();
+
+        match self.collection.replace_one(Self::owner_filter(owner), cart).with_options(options).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Failed to save cart: {}", e))
+        }
+    }
+
+    pub async fn delete_by_owner(&self, owner: &CartOwner) -> Result<(), String> {
+        match self.collection.delete_one(Self::owner_filter(owner)).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Failed to delete cart: {}", e))
+        }
+    }
+
+    // Signed-in users' carts that still hold items and were last changed in the window
+    pub async fn find_idle_user_carts(
+        &self,
+        changed_after: DateTime<Utc>,
+        changed_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Cart>, String> {
+        let filter = doc! {
+            "user_id": { "$exists": true },
+            "items.0": { "$exists": true },
+            "updated_at": {
+                "$gt": bson::DateTime::from_chrono(changed_after),
+                "$lte": bson::DateTime::from_chrono(changed_before),
+            },
+        };
+        let options = FindOptions::builder().sort(doc! { "updated_at": 1 }).limit(limit).build();
+
+        match self.collection.find(filter).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut carts = Vec::new();
+                while let Some(cart) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve carts: {}", e))? {
+                    carts.push(cart);
+                }
+                Ok(carts)
+            }
+            Err(e) => Err(format!("Failed to retrieve carts: {}", e))
+        }
+    }
+}
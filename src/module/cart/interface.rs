@@ -0,0 +1,25 @@
+use crate::module::cart::{
+    model::CartOwner,
+    schema::{AddCartItemSchema, CartResponse, UpdateCartItemSchema},
+};
+
+#[async_trait::async_trait]
+pub trait CartService {
+    // Get the owner's cart (empty if none exists yet)
+    async fn get_cart(&self, owner: &CartOwner) -> Result<CartResponse, String>;
+
+    // Add a product, or increase its quantity if it is already in the cart
+    async fn add_item(&self, owner: &CartOwner, data: AddCartItemSchema) -> Result<CartResponse, String>;
+
+    // Set the quantity of a line; 0 removes it
+    async fn update_item(&self, owner: &CartOwner, product_id: &str, variant_id: Option<&str>, data: UpdateCartItemSchema) -> Result<CartResponse, String>;
+
+    // Remove a line
+    async fn remove_item(&self, owner: &CartOwner, product_id: &str, variant_id: Option<&str>) -> Result<CartResponse, String>;
+
+    // Empty the cart
+    async fn clear_cart(&self, owner: &CartOwner) -> Result<(), String>;
+
+    // Fold a guest cart into a user's cart after they sign in
+    async fn merge_guest_cart(&self, cart_token: &str, user_id: &str) -> Result<(), String>;
+}
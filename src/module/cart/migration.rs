@@ -0,0 +1,24 @@
+use std::time::Duration;
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        // Guest carts expire through a TTL index; each owner has at most one cart
+        Migration::indexes("cart", 1, "cart owner and expiry indexes", "carts", vec![
+            IndexModel::builder()
+                .keys(doc! { "expires_at": 1 })
+                .options(IndexOptions::builder().expire_after(Duration::from_secs(0)).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "user_id": 1 })
+                .options(IndexOptions::builder().unique(true).sparse(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "guest_id": 1 })
+                .options(IndexOptions::builder().unique(true).sparse(true).build())
+                .build(),
+        ]),
+    ]
+}
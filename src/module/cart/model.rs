@@ -0,0 +1,122 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CartItem {
+    pub product_id: ObjectId,
+    // Set when the product is sold in variants
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant_id: Option<ObjectId>,
+    pub quantity: i32,
+
+    // Display price in kobo at the time the item was added
+    pub unit_price: i64,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Cart {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    // Exactly one owner is set: a signed-in user or a guest cart ID from the cart token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<ObjectId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guest_id: Option<String>,
+
+    pub items: Vec<CartItem>,
+
+    // Timestamps
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+
+    // Guest carts are removed by a TTL index once this passes; user carts never expire
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Identifies whose cart a request is working with.
+#[derive(Debug, Clone)]
+pub enum CartOwner {
+    User(ObjectId),
+    Guest(String),
+}
+
+impl Cart {
+    pub fn new(owner: &CartOwner) -> Self {
+        let now = Utc::now();
+        let (user_id, guest_id) = match owner {
+            CartOwner::User(id) => (Some(*id), None),
+            CartOwner::Guest(id) => (None, Some(id.clone())),
+        };
+
+        Self {
+            id: None,
+            user_id,
+            guest_id,
+            items: Vec::new(),
+            created_at: now,
+            updated_at: now,
+            expires_at: None,
+        }
+    }
+
+    // Helper method to find a line by product and variant
+    pub fn item_mut(&mut self, product_id: &ObjectId, variant_id: Option<&ObjectId>) -> Option<&mut CartItem> {
+        self.items.iter_mut().find(|item| &item.product_id == product_id && item.variant_id.as_ref() == variant_id)
+    }
+}
+
+/// How quantities combine when a guest cart is merged into a user's cart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CartMergeStrategy {
+    Sum,
+    Max,
+    KeepUser,
+    KeepGuest,
+}
+
+impl CartMergeStrategy {
+    pub fn combine(&self, user_quantity: i32, guest_quantity: i32) -> i32 {
+        match self {
+            CartMergeStrategy::Sum => user_quantity + guest_quantity,
+            CartMergeStrategy::Max => user_quantity.max(guest_quantity),
+            CartMergeStrategy::KeepUser => user_quantity,
+            CartMergeStrategy::KeepGuest => guest_quantity,
+        }
+    }
+}
+
+/// Conflict rules for merge-on-login, read from `CART_MERGE_STRATEGY`
+/// (sum | max | keep_user | keep_guest) and `CART_MERGE_CAP_AT_STOCK`.
+#[derive(Debug, Clone, Copy)]
+pub struct CartMergeRules {
+    pub strategy: CartMergeStrategy,
+    pub cap_at_stock: bool,
+}
+
+impl CartMergeRules {
+    pub fn from_env() -> Self {
+        let strategy = match std::env::var("CART_MERGE_STRATEGY").as_deref() {
+            Ok("max") => CartMergeStrategy::Max,
+            Ok("keep_user") => CartMergeStrategy::KeepUser,
+            Ok("keep_guest") => CartMergeStrategy::KeepGuest,
+            _ => CartMergeStrategy::Sum,
+        };
+        let cap_at_stock = std::env::var("CART_MERGE_CAP_AT_STOCK")
+            .map(|v| v != "false")
+            .unwrap_or(true);
+
+        Self { strategy, cap_at_stock }
+    }
+}
@@ -0,0 +1,203 @@
+use actix_web::{cookie::{time, Cookie}, web, HttpRequest, HttpResponse, Result};
+use crate::middleware::Authentication;
+use crate::module::cart::{
+    controller::CartController,
+    crud::CartCrud,
+    interface::CartService,
+    model::CartOwner,
+    schema::{AddCartItemSchema, CartItemQuery, CartResponse, UpdateCartItemSchema},
+};
+use crate::module::product::crud::ProductCrud;
+use crate::service::cart_token_service::{guest_cart_ttl_days, CartTokenService, CART_TOKEN_COOKIE, CART_TOKEN_HEADER};
+
+// Resolve the cart owner from the bearer token or the guest cart token
+fn resolve_owner(req: &HttpRequest, controller: &CartController) -> std::result::Result<Option<CartOwner>, String> {
+    let user_id = Authentication::optional_user(req).map(|claims| claims.sub);
+    let cart_token = CartTokenService::token_from_request(req);
+
+    controller.owner_for(user_id.as_deref(), cart_token.as_deref())
+}
+
+// Same as `resolve_owner`, but starts a guest cart (and returns its new token) when there is no owner
+fn resolve_or_create_owner(
+    req: &HttpRequest,
+    controller: &CartController,
+) -> std::result::Result<(CartOwner, Option<String>), String> {
+    match resolve_owner(req, controller)? {
+        Some(owner) => Ok((owner, None)),
+        None => {
+            let (owner, token) = controller.new_guest()?;
+            Ok((owner, Some(token)))
+        }
+    }
+}
+
+// Build a cart response, handing a newly issued guest token back as header, cookie and body field
+fn cart_response(mut cart: CartResponse, new_token: Option<String>) -> HttpResponse {
+    let mut response = HttpResponse::Ok();
+
+    if let Some(token) = new_token {
+        response.insert_header((CART_TOKEN_HEADER, token.clone()));
+        response.cookie(
+            Cookie::build(CART_TOKEN_COOKIE, token.clone())
+                .path("/")
+                .http_only(true)
+                .max_age(time::Duration::days(guest_cart_ttl_days()))
+                .finish(),
+        );
+        cart.cart_token = Some(token);
+    }
+
+    response.json(cart)
+}
+
+// GET cart handler
+async fn get_cart_handler(
+    req: HttpRequest,
+    crud: web::Data<CartCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    let controller = CartController::new(crud.get_ref().clone(), product_crud.get_ref().clone());
+
+    let owner = match resolve_owner(&req, &controller) {
+        Ok(owner) => owner,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    };
+
+    // Nobody has started a cart yet, so there is nothing to look up
+    let owner = match owner {
+        Some(owner) => owner,
+        None => return Ok(HttpResponse::Ok().json(serde_json::json!({
+            "items": [],
+            "warnings": [],
+            "total_quantity": 0,
+            "subtotal": 0
+        }))),
+    };
+
+    match controller.get_cart(&owner).await {
+        Ok(cart) => Ok(cart_response(cart, None)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST cart item handler
+async fn add_item_handler(
+    req: HttpRequest,
+    item: web::Json<AddCartItemSchema>,
+    crud: web::Data<CartCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    let controller = CartController::new(crud.get_ref().clone(), product_crud.get_ref().clone());
+
+    let (owner, new_token) = match resolve_or_create_owner(&req, &controller) {
+        Ok(resolved) => resolved,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    };
+
+    match controller.add_item(&owner, item.into_inner()).await {
+        Ok(cart) => Ok(cart_response(cart, new_token)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// PUT cart item handler
+async fn update_item_handler(
+    req: HttpRequest,
+    product_id: web::Path<String>,
+    query: web::Query<CartItemQuery>,
+    item: web::Json<UpdateCartItemSchema>,
+    crud: web::Data<CartCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    let controller = CartController::new(crud.get_ref().clone(), product_crud.get_ref().clone());
+
+    let owner = match resolve_owner(&req, &controller) {
+        Ok(Some(owner)) => owner,
+        Ok(None) => return Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Cart not found"
+        }))),
+        Err(error) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    };
+
+    match controller.update_item(&owner, &product_id, query.variant_id.as_deref(), item.into_inner()).await {
+        Ok(cart) => Ok(cart_response(cart, None)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE cart item handler
+async fn remove_item_handler(
+    req: HttpRequest,
+    product_id: web::Path<String>,
+    query: web::Query<CartItemQuery>,
+    crud: web::Data<CartCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    let controller = CartController::new(crud.get_ref().clone(), product_crud.get_ref().clone());
+
+    let owner = match resolve_owner(&req, &controller) {
+        Ok(Some(owner)) => owner,
+        Ok(None) => return Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Cart not found"
+        }))),
+        Err(error) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    };
+
+    match controller.remove_item(&owner, &product_id, query.variant_id.as_deref()).await {
+        Ok(cart) => Ok(cart_response(cart, None)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE cart handler
+async fn clear_cart_handler(
+    req: HttpRequest,
+    crud: web::Data<CartCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    let controller = CartController::new(crud.get_ref().clone(), product_crud.get_ref().clone());
+
+    let owner = match resolve_owner(&req, &controller) {
+        Ok(Some(owner)) => owner,
+        Ok(None) => return Ok(HttpResponse::NoContent().finish()),
+        Err(error) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    };
+
+    match controller.clear_cart(&owner).await {
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// Route configuration function
+pub fn cart_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/cart")
+            .route("", web::get().to(get_cart_handler))
+            .route("", web::delete().to(clear_cart_handler))
+            .route("/items", web::post().to(add_item_handler))
+            .route("/items/{product_id}", web::put().to(update_item_handler))
+            .route("/items/{product_id}", web::delete().to(remove_item_handler))
+    );
+}
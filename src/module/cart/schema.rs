@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct AddCartItemSchema {
+    pub product_id: String,  // Will be converted to ObjectId
+    // Required when the product is sold in variants
+    pub variant_id: Option<String>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub quantity: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct UpdateCartItemSchema {
+    // Setting the quantity to 0 removes the line
+    #[validate(range(min = 0, max = 100))]
+    pub quantity: i32,
+}
+
+// Picks the variant line of a product, e.g. ?variant_id=...
+#[derive(Debug, Deserialize, Clone)]
+pub struct CartItemQuery {
+    pub variant_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CartItemResponse {
+    pub product_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sku: Option<String>,
+    pub quantity: i32,
+    pub unit_price: i64,        // Current display price in kobo
+    pub price_when_added: i64,  // Price in kobo the shopper last saw for the line
+    pub line_total: i64,
+    pub in_stock: bool,
+    pub available: bool,        // False once the product is deleted or deactivated
+    pub added_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CartWarningKind {
+    ProductUnavailable,
+    OutOfStock,
+    StockReduced,
+    PriceChanged,
+}
+
+// A problem found while checking a cart line against the live product
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CartWarning {
+    pub product_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant_id: Option<String>,
+    pub kind: CartWarningKind,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_quantity: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_quantity: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_price: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_price: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CartResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cart_token: Option<String>,
+    pub items: Vec<CartItemResponse>,
+    pub warnings: Vec<CartWarning>,
+    pub total_quantity: i32,
+    pub subtotal: i64,  // In kobo, available lines only
+    pub updated_at: String,
+}
@@ -1,9 +1,12 @@
+use bson::{oid::ObjectId, Document};
 use validator::Validate;
 use crate::module::category::{
-    model::Category,
-    schema::CreateCategorySchema,
+    model::{AttributeDefinition, AttributeType, Category},
+    schema::{CategoryAttributesSchema, CreateCategorySchema, UpdateCategorySchema},
     crud::CategoryCrud,
 };
+use crate::module::product::schema::LISTING_PARAMS;
+use crate::service::slug_service;
 
 pub struct CategoryController {
     crud: CategoryCrud,
@@ -33,6 +36,11 @@ impl CategoryController {
     pub async fn get_category(&self, id: &str) -> Result<Option<Category>, String> {
         self.crud.get_category(id).await
     }
+    
+    // By current or old slug; callers redirect when the slug is an old one
+    pub async fn get_category_by_slug(&self, slug: &str) -> Result<Option<Category>, String> {
+        self.crud.get_category_by_slug(slug).await
+    }
 
 
 
@@ -41,4 +49,124 @@ impl CategoryController {
     pub async fn get_all_categories(&self) -> Result<Vec<Category>, String> {
         self.crud.get_all_categories().await
     }
+    
+    // Only the fields sent are changed. A rename that changes the slug keeps
+    // the old one so links to it redirect.
+    pub async fn update_category(&self, id: &str, category_: UpdateCategorySchema) -> Result<Option<Category>, String> {
+        if let Err(e) = category_.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid category ID".to_string())?;
+        if category_.clear_tax_class && category_.tax_class.is_some() {
+            return Err("Send either tax_class or clear_tax_class, not both".to_string());
+        }
+        
+        let current = match self.crud.get_category(id).await? {
+            Some(category) => category,
+            None => return Ok(None),
+        };
+        
+        let mut set = Document::new();
+        if let Some(name) = category_.name {
+            let name = name.trim().to_string();
+            if !slug_service::slug_fits(&current.slug, &name) {
+                let slug = self.crud.unique_slug(&name, Some(&object_id)).await?;
+                set.insert("previous_slugs", slug_service::previous_slugs_after(&current.previous_slugs, &current.slug, &slug));
+                set.insert("slug", slug);
+            }
+            set.insert("name", name);
+        }
+        if let Some(description) = category_.description {
+            set.insert("description", description);
+        }
+        if let Some(parent_id) = category_.parent_id {
+            let parent_id = ObjectId::parse_str(&parent_id)
+                .map_err(|_| "Invalid parent category ID".to_string())?;
+            if parent_id == object_id {
+                return Err("A category can't be its own parent".to_string());
+            }
+            set.insert("parent_id", parent_id);
+        }
+        if let Some(is_active) = category_.is_active {
+            set.insert("is_active", is_active);
+        }
+        if let Some(display_order) = category_.display_order {
+            set.insert("display_order", display_order);
+        }
+        if let Some(tax_class) = category_.tax_class {
+            set.insert("tax_class", self.crud.check_tax_class(&tax_class).await?);
+        }
+        if category_.clear_tax_class {
+            set.insert("tax_class", bson::Bson::Null);
+        }
+        
+        self.crud.update_category(&object_id, set).await
+    }
+    
+    // Replace the attribute schema. Products already in the category are
+    // checked against it the next time their specs or category change.
+    pub async fn set_attributes(&self, id: &str, data: CategoryAttributesSchema) -> Result<Category, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid category ID".to_string())?;
+        
+        let mut attributes: Vec<AttributeDefinition> = Vec::new();
+        for attribute in data.attributes {
+            let key = attribute.key.trim().to_string();
+            let valid_key = key.starts_with(|c: char| c.is_ascii_lowercase())
+                && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+            if !valid_key {
+                return Err(format!("{} must use lowercase letters, digits and underscores", key));
+            }
+            if LISTING_PARAMS.contains(&key.as_str()) {
+                return Err(format!("{} is reserved for product listing filters", key));
+            }
+            if attributes.iter().any(|existing| existing.key == key) {
+                return Err(format!("Attribute {} is listed twice", key));
+            }
+            
+            let is_enum = attribute.attribute_type == AttributeType::Enum;
+            let is_number = attribute.attribute_type == AttributeType::Number;
+            let mut values: Vec<String> = Vec::new();
+            for value in &attribute.values {
+                let value = AttributeDefinition::normalize_value(value);
+                if value.is_empty() || value.len() > 50 {
+                    return Err(format!("Values of {} must be 1 to 50 characters", key));
+                }
+                if !values.contains(&value) {
+                    values.push(value);
+                }
+            }
+            if is_enum && values.is_empty() {
+                return Err(format!("Enum attribute {} needs at least one value", key));
+            }
+            if !is_enum && !values.is_empty() {
+                return Err(format!("Only enum attributes take values, not {}", key));
+            }
+            if !is_number && (attribute.unit.is_some() || attribute.min.is_some() || attribute.max.is_some()) {
+                return Err(format!("Only number attributes take a unit or bounds, not {}", key));
+            }
+            if let (Some(min), Some(max)) = (attribute.min, attribute.max)
+                && min > max {
+                return Err(format!("The min of {} can't be more than its max", key));
+            }
+            
+            attributes.push(AttributeDefinition {
+                key,
+                label: attribute.label.trim().to_string(),
+                attribute_type: attribute.attribute_type,
+                required: attribute.required,
+                values,
+                unit: attribute.unit.map(|unit| unit.trim().to_string()),
+                min: attribute.min,
+                max: attribute.max,
+            });
+        }
+        
+        self.crud.set_attributes(&object_id, &attributes).await?
+            .ok_or_else(|| "Category not found".to_string())
+    }
 }
\ No newline at end of file
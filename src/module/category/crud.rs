@@ -1,20 +1,39 @@
-use bson::{doc, oid::ObjectId};
+use bson::{doc, oid::ObjectId, Document};
 use mongodb::{Collection, Database};
 use futures_util::TryStreamExt;
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use crate::service::slug_service;
+use crate::module::tax::crud::TaxCrud;
 use crate::module::category::{
-    model::Category, 
+    model::{AttributeDefinition, Category},
     schema::CreateCategorySchema
 };
 
 #[derive(Clone)]
 pub struct CategoryCrud {
     collection: Collection<Category>,
+    tax: TaxCrud,
 }
 
 impl CategoryCrud {
     pub fn new(db: &Database) -> Self {
         let collection = db.collection("categories");
-        Self { collection }
+        let tax = TaxCrud::new(db);
+        Self { collection, tax }
+    }
+    
+    pub async fn unique_slug(&self, name: &str, except: Option<&ObjectId>) -> Result<String, String> {
+        slug_service::unique_slug(&self.collection.clone_with_type(), name, except).await
+    }
+    
+    // The class normalised for storage; unknown classes are refused
+    pub async fn check_tax_class(&self, tax_class: &str) -> Result<String, String> {
+        self.tax.check_tax_class(tax_class).await
+    }
+    
+    // Give categories saved with the old slug rules clean, unique slugs
+    pub async fn backfill_slugs(&self) -> Result<u64, String> {
+        slug_service::backfill_slugs(&self.collection.clone_with_type(), "name").await
     }
     
     pub async fn create_category(&self, category_data: CreateCategorySchema) -> Result<Category, String> {  // Fixed this line
@@ -33,17 +52,26 @@ impl CategoryCrud {
             category_data.description,
             parent_id,
         );
+        category.tax_class = match category_data.tax_class {
+            Some(tax_class) => Some(self.check_tax_class(&tax_class).await?),
+            None => None,
+        };
         
-        // Insert into database
-        match self.collection.insert_one(&category).await {
-            Ok(result) => {
-                if let Some(id) = result.inserted_id.as_object_id() {
-                    category.id = Some(id);
+        // Another insert may take the slug in between, so pick again if it does
+        for _ in 0..3 {
+            category.slug = slug_service::unique_slug(&self.collection.clone_with_type(), &category.name, None).await?;
+            match self.collection.insert_one(&category).await {
+                Ok(result) => {
+                    if let Some(id) = result.inserted_id.as_object_id() {
+                        category.id = Some(id);
+                    }
+                    return Ok(category);
                 }
-                Ok(category)
+                Err(e) if slug_service::is_duplicate_slug(&e) => continue,
+                Err(e) => return Err(format!("Failed to create category: {}", e)),
             }
-            Err(e) => Err(format!("Failed to create category: {}", e))
         }
+        Err("Failed to create category: no free slug".to_string())
     }
 
      
@@ -59,6 +87,65 @@ impl CategoryCrud {
         }
     }
     
+    pub async fn update_category(&self, id: &ObjectId, mut set: Document) -> Result<Option<Category>, String> {
+        set.insert("updated_at", bson::DateTime::from_chrono(chrono::Utc::now()));
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        
+        self.collection
+            .find_one_and_update(doc! { "_id": id }, doc! { "$set": set })
+            .with_options(options)
+            .await
+            .map_err(|e| if slug_service::is_duplicate_slug(&e) {
+                "Another category already uses this slug; try again".to_string()
+            } else {
+                format!("Failed to update category: {}", e)
+            })
+    }
+    
+    pub async fn set_attributes(&self, id: &ObjectId, attributes: &[AttributeDefinition]) -> Result<Option<Category>, String> {
+        let attributes = bson::to_bson(attributes)
+            .map_err(|e| format!("Failed to encode attributes: {}", e))?;
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id },
+                doc! { "$set": {
+                    "attributes": attributes,
+                    "updated_at": bson::DateTime::from_chrono(chrono::Utc::now()),
+                } },
+            )
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to update category: {}", e))
+    }
+    
+    // By current or old slug
+    pub async fn get_category_by_slug(&self, slug: &str) -> Result<Option<Category>, String> {
+        match self.collection.find_one(slug_service::slug_filter(slug)).await {
+            Ok(category) => Ok(category),
+            Err(e) => Err(format!("Failed to retrieve category: {}", e))
+        }
+    }
+    
+    pub async fn get_categories_by_ids(&self, ids: &[ObjectId]) -> Result<Vec<Category>, String> {
+        match self.collection.find(doc! { "_id": { "$in": ids } }).await {
+            Ok(mut cursor) => {
+                let mut categories = Vec::new();
+                while let Some(category) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve categories: {}", e))? {
+                    categories.push(category);
+                }
+                Ok(categories)
+            }
+            Err(e) => Err(format!("Failed to retrieve categories: {}", e))
+        }
+    }
+    
     pub async fn get_all_categories(&self) -> Result<Vec<Category>, String> {
         use mongodb::options::FindOptions;
         
@@ -0,0 +1,13 @@
+use crate::module::category::crud::CategoryCrud;
+use crate::service::migration_service::Migration;
+use crate::service::slug_service;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        // Slugs are made unique before the unique indexes are built
+        Migration::data("category", 1, "clean, unique slugs", |db| async move {
+            CategoryCrud::new(&db).backfill_slugs().await
+        }),
+        Migration::indexes("category", 2, "unique slug indexes", "categories", slug_service::slug_indexes()),
+    ]
+}
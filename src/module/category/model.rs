@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
 use bson::oid::ObjectId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use crate::service::slug_service::slugify;
+use crate::module::product::model::SpecValue;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Category {
@@ -10,6 +13,9 @@ pub struct Category {
     pub name: String,
     pub description: String,
     pub slug: String,
+    // Slugs used before a rename, kept so old links redirect
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_slugs: Vec<String>,
     
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_id: Option<ObjectId>,
@@ -17,6 +23,14 @@ pub struct Category {
     pub is_active: bool,
     pub display_order: i32,
     
+    // Tax class for the category's products unless a product sets its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tax_class: Option<String>,
+    
+    // Technical specifications the category's products give, e.g. RAM or GPU
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeDefinition>,
+    
     // Timestamps
     #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
     pub created_at: DateTime<Utc>,
@@ -31,18 +45,117 @@ impl Category {
         parent_id: Option<ObjectId>,
     ) -> Self {
         let now = Utc::now();
-        let slug = name.to_lowercase().replace(" ", "-");
+        let slug = slugify(&name);
         
         Self {
             id: None,
             name,
             description,
             slug,
+            previous_slugs: Vec::new(),
             parent_id,
             is_active: true,
             display_order: 0,
+            tax_class: None,
+            attributes: Vec::new(),
             created_at: now,
             updated_at: now,
         }
     }
-}
\ No newline at end of file
+    
+    // Check a product's specs against the category's attributes, returning
+    // them normalised for storage. Unknown keys and missing required ones fail.
+    pub fn check_specs(&self, specs: BTreeMap<String, SpecValue>) -> Result<BTreeMap<String, SpecValue>, String> {
+        if let Some(key) = specs.keys().find(|key| self.attribute(key).is_none()) {
+            return Err(format!("{} is not a specification of {}", key, self.name));
+        }
+        
+        let mut checked = BTreeMap::new();
+        for attribute in &self.attributes {
+            match specs.get(&attribute.key) {
+                Some(value) => {
+                    checked.insert(attribute.key.clone(), attribute.check(value)?);
+                }
+                None if attribute.required => {
+                    return Err(format!("{} is required for {}", attribute.label, self.name));
+                }
+                None => {}
+            }
+        }
+        Ok(checked)
+    }
+    
+    pub fn attribute(&self, key: &str) -> Option<&AttributeDefinition> {
+        self.attributes.iter().find(|attribute| attribute.key == key)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributeType {
+    Enum,
+    Number,
+    Boolean,
+    Text,
+}
+
+// One typed specification, e.g. { key: "ram_gb", label: "RAM", type: "number", unit: "GB" }
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AttributeDefinition {
+    // Spec key on products and the name of its listing filter
+    pub key: String,
+    pub label: String,
+    #[serde(rename = "type")]
+    pub attribute_type: AttributeType,
+    #[serde(default)]
+    pub required: bool,
+    
+    // Allowed values of an enum, stored lowercase and hyphenated, e.g. "rtx-4060"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+    
+    // Unit and bounds of a number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+}
+
+impl AttributeDefinition {
+    // Helper method to bring enum values to their stored form
+    pub fn normalize_value(value: &str) -> String {
+        value.split_whitespace().collect::<Vec<_>>().join("-").to_lowercase()
+    }
+    
+    // Check one value against the attribute's type, returning it normalised
+    pub fn check(&self, value: &SpecValue) -> Result<SpecValue, String> {
+        match (self.attribute_type, value) {
+            (AttributeType::Enum, SpecValue::Text(text)) => {
+                let text = Self::normalize_value(text);
+                if !self.values.contains(&text) {
+                    return Err(format!("{} must be one of: {}", self.label, self.values.join(", ")));
+                }
+                Ok(SpecValue::Text(text))
+            }
+            (AttributeType::Number, SpecValue::Number(number)) => {
+                if self.min.is_some_and(|min| *number < min) || self.max.is_some_and(|max| *number > max) {
+                    return Err(format!("{} is out of range", self.label));
+                }
+                Ok(SpecValue::Number(*number))
+            }
+            (AttributeType::Boolean, SpecValue::Boolean(flag)) => Ok(SpecValue::Boolean(*flag)),
+            (AttributeType::Text, SpecValue::Text(text)) => {
+                let text = text.trim();
+                if text.is_empty() || text.len() > 200 {
+                    return Err(format!("{} must be 1 to 200 characters", self.label));
+                }
+                Ok(SpecValue::Text(text.to_string()))
+            }
+            (AttributeType::Enum | AttributeType::Text, _) => Err(format!("{} must be text", self.label)),
+            (AttributeType::Number, _) => Err(format!("{} must be a number", self.label)),
+            (AttributeType::Boolean, _) => Err(format!("{} must be true or false", self.label)),
+        }
+    }
+}
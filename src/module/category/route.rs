@@ -1,6 +1,7 @@
-use actix_web::{web, HttpResponse, Result};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::AdminAuthentication;
 use crate::module::category::{
-    schema::CreateCategorySchema,
+    schema::{CategoryAttributesSchema, CreateCategorySchema, UpdateCategorySchema},
     crud::CategoryCrud,
     controller::CategoryController,
 };
@@ -52,12 +53,79 @@ async fn get_category_handler(
     }
 }
 
+// HTTP handler function - get category by slug; an old slug redirects
+async fn get_category_by_slug_handler(
+    slug: web::Path<String>,
+    crud: web::Data<CategoryCrud>,
+) -> Result<HttpResponse> {
+    let controller = CategoryController::new(crud.get_ref().clone());
+    
+    match controller.get_category_by_slug(&slug).await {
+        Ok(Some(category)) if category.slug != *slug => Ok(HttpResponse::MovedPermanently()
+            .insert_header(("Location", format!("/api/categories/slug/{}", category.slug)))
+            .finish()),
+        Ok(Some(category)) => Ok(HttpResponse::Ok().json(category)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Category not found"
+        }))),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// HTTP handler function - update category (admin)
+async fn update_category_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<UpdateCategorySchema>,
+    crud: web::Data<CategoryCrud>,
+) -> Result<HttpResponse> {
+    if let Err(e) = AdminAuthentication::check_admin(&req).await {
+        return Ok(e.error_response());
+    }
+    let controller = CategoryController::new(crud.get_ref().clone());
+    
+    match controller.update_category(&id, body.into_inner()).await {
+        Ok(Some(category)) => Ok(HttpResponse::Ok().json(category)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Category not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// HTTP handler function - replace the attribute schema (admin)
+async fn set_attributes_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<CategoryAttributesSchema>,
+    crud: web::Data<CategoryCrud>,
+) -> Result<HttpResponse> {
+    if let Err(e) = AdminAuthentication::check_admin(&req).await {
+        return Ok(e.error_response());
+    }
+    let controller = CategoryController::new(crud.get_ref().clone());
+    
+    match controller.set_attributes(&id, body.into_inner()).await {
+        Ok(category) => Ok(HttpResponse::Ok().json(category)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
 // Route configuration function
 pub fn category_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/categories")
             .route("", web::post().to(create_category_handler))
             .route("", web::get().to(get_categories_handler))  // GET all categories
+            .route("/slug/{slug}", web::get().to(get_category_by_slug_handler))
             .route("/{id}", web::get().to(get_category_handler))  // GET single category
+            .route("/{id}", web::patch().to(update_category_handler))
+            .route("/{id}/attributes", web::put().to(set_attributes_handler))
     );
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use validator::Validate;
+use crate::module::category::model::AttributeType;
 
 #[derive(Debug, Serialize, Deserialize, Validate, Clone)]
 pub struct CreateCategorySchema {
@@ -10,6 +11,9 @@ pub struct CreateCategorySchema {
     pub description: String,
     
     pub parent_id: Option<String>,
+    
+    #[validate(length(min = 1, max = 30))]
+    pub tax_class: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate, Clone)]
@@ -23,4 +27,44 @@ pub struct UpdateCategorySchema {
     pub parent_id: Option<String>,
     pub is_active: Option<bool>,
     pub display_order: Option<i32>,
-}
\ No newline at end of file
+    
+    #[validate(length(min = 1, max = 30))]
+    pub tax_class: Option<String>,
+    
+    // Falls back to the standard class; can't be sent together with `tax_class`
+    #[serde(default)]
+    pub clear_tax_class: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct AttributeDefinitionSchema {
+    // Lowercase letters, digits and underscores, e.g. "ram_gb"
+    #[validate(length(min = 1, max = 40))]
+    pub key: String,
+    
+    #[validate(length(min = 1, max = 60))]
+    pub label: String,
+    
+    #[serde(rename = "type")]
+    pub attribute_type: AttributeType,
+    #[serde(default)]
+    pub required: bool,
+    
+    // Enum attributes only
+    #[validate(length(max = 100))]
+    #[serde(default)]
+    pub values: Vec<String>,
+    
+    // Number attributes only
+    #[validate(length(min = 1, max = 20))]
+    pub unit: Option<String>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+// Replaces the category's attribute schema
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct CategoryAttributesSchema {
+    #[validate(length(max = 50), nested)]
+    pub attributes: Vec<AttributeDefinitionSchema>,
+}
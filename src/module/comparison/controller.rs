@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use bson::oid::ObjectId;
+use serde_json::{json, Value};
+use validator::Validate;
+use crate::module::category::model::{AttributeDefinition, Category};
+use crate::module::comparison::{
+    crud::ComparisonCrud,
+    interface::ComparisonService,
+    model::{SavedComparison, MAX_COMPARED, MIN_COMPARED},
+    schema::{
+        CompareProductsSchema, ComparedProduct, ComparisonCell, ComparisonResponse, ComparisonRow,
+        SavedComparisonResponse, SharedComparisonResponse,
+    },
+};
+use crate::module::product::{crud::ProductCrud, model::{Product, SpecValue}};
+use crate::service::{currency_service::format_naira, link_service::storefront_url};
+
+// Most saved comparisons listed for a user
+const SAVED_LIST_LIMIT: i64 = 50;
+
+const NOT_SPECIFIED: &str = "Not specified";
+
+pub struct ComparisonController {
+    crud: ComparisonCrud,
+    product_crud: ProductCrud,
+}
+
+impl ComparisonController {
+    pub fn new(crud: ComparisonCrud, product_crud: ProductCrud) -> Self {
+        Self { crud, product_crud }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    fn share_url(token: &str) -> String {
+        format!("{}/compare/{}", storefront_url(), token)
+    }
+
+    // Distinct product IDs in the order given
+    fn product_ids(data: &CompareProductsSchema) -> Result<Vec<ObjectId>, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let mut ids = Vec::new();
+        for value in &data.product_ids {
+            let id = Self::parse_id(value, "product")?;
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        if !(MIN_COMPARED..=MAX_COMPARED).contains(&ids.len()) {
+            return Err(format!("Compare {} to {} different products", MIN_COMPARED, MAX_COMPARED));
+        }
+        Ok(ids)
+    }
+
+    // The products in column order. Deleted or hidden products can't be compared.
+    async fn load_products(&self, ids: &[ObjectId]) -> Result<Vec<Product>, String> {
+        let mut found = self.product_crud.get_products_by_ids(ids).await?;
+        let mut products = Vec::new();
+        for id in ids {
+            let position = found.iter()
+                .position(|product| product.id.as_ref() == Some(id) && product.is_active && product.deleted_at.is_none())
+                .ok_or_else(|| format!("Product {} not found", id.to_hex()))?;
+            products.push(found.swap_remove(position));
+        }
+        Ok(products)
+    }
+
+    fn cell(value: Option<(Value, String)>) -> ComparisonCell {
+        match value {
+            Some((value, label)) => ComparisonCell { value, label, missing: false },
+            None => ComparisonCell { value: Value::Null, label: NOT_SPECIFIED.to_string(), missing: true },
+        }
+    }
+
+    fn row(key: &str, label: &str, group: &str, unit: Option<String>, cells: Vec<ComparisonCell>) -> ComparisonRow {
+        let differs = cells.windows(2).any(|pair| pair[0].value != pair[1].value);
+        ComparisonRow {
+            key: key.to_string(),
+            label: label.to_string(),
+            group: group.to_string(),
+            unit,
+            cells,
+            differs,
+        }
+    }
+
+    fn spec_label(value: &SpecValue, unit: Option<&str>) -> String {
+        let text = match value {
+            SpecValue::Boolean(true) => "Yes".to_string(),
+            SpecValue::Boolean(false) => "No".to_string(),
+            SpecValue::Number(number) if number.fract() == 0.0 => format!("{}", *number as i64),
+            SpecValue::Number(number) => number.to_string(),
+            SpecValue::Text(text) => text.clone(),
+        };
+        match (value, unit) {
+            (SpecValue::Number(_), Some(unit)) => format!("{} {}", text, unit),
+            _ => text,
+        }
+    }
+
+    fn spec_json(value: &SpecValue) -> Value {
+        match value {
+            SpecValue::Boolean(flag) => json!(flag),
+            SpecValue::Number(number) => json!(number),
+            SpecValue::Text(text) => json!(text),
+        }
+    }
+
+    // Price, availability, brand, category and the like, which every product has
+    async fn overview_rows(&self, products: &[Product], categories: &HashMap<ObjectId, Category>) -> Result<Vec<ComparisonRow>, String> {
+        let mut brands = Vec::new();
+        for product in products {
+            let brand = if product.brand_name.is_empty() {
+                self.product_crud.brand_name(&product.brand_id).await?
+            } else {
+                Some(product.brand_name.clone())
+            };
+            brands.push(brand);
+        }
+
+        let rows = vec![
+            Self::row("display_price", "Price", "overview", None, products.iter()
+                .map(|product| Self::cell(Some((json!(product.display_price()), format_naira(product.display_price())))))
+                .collect()),
+            Self::row("price", "Regular price", "overview", None, products.iter()
+                .map(|product| Self::cell(Some((json!(product.price), format_naira(product.price)))))
+                .collect()),
+            Self::row("is_on_sale", "On sale", "overview", None, products.iter()
+                .map(|product| {
+                    let on_sale = product.is_on_sale();
+                    Self::cell(Some((json!(on_sale), if on_sale { "Yes" } else { "No" }.to_string())))
+                })
+                .collect()),
+            Self::row("stock_status", "Availability", "overview", None, products.iter()
+                .map(|product| Self::cell(Some(if product.stock_quantity > 0 {
+                    (json!("in_stock"), "In stock".to_string())
+                } else {
+                    (json!("out_of_stock"), "Out of stock".to_string())
+                })))
+                .collect()),
+            Self::row("brand", "Brand", "overview", None, brands.into_iter()
+                .map(|brand| Self::cell(brand.map(|name| (json!(name), name))))
+                .collect()),
+            Self::row("category", "Category", "overview", None, products.iter()
+                .map(|product| Self::cell(categories.get(&product.category_id)
+                    .map(|category| (json!(category.name), category.name.clone()))))
+                .collect()),
+            Self::row("rating", "Rating", "overview", None, products.iter()
+                .map(|product| Self::cell((product.rating.count > 0).then(|| (
+                    json!(product.rating.average),
+                    format!("{:.1} ({} reviews)", product.rating.average, product.rating.count),
+                ))))
+                .collect()),
+            Self::row("description", "Description", "overview", None, products.iter()
+                .map(|product| Self::cell((!product.description.trim().is_empty())
+                    .then(|| (json!(product.description), product.description.clone()))))
+                .collect()),
+        ];
+        Ok(rows)
+    }
+
+    // One row per specification any of the products has: the categories'
+    // attributes in their order, then keys no category defines
+    fn spec_rows(products: &[Product], categories: &HashMap<ObjectId, Category>) -> Vec<ComparisonRow> {
+        let mut definitions: Vec<&AttributeDefinition> = Vec::new();
+        for product in products {
+            if let Some(category) = categories.get(&product.category_id) {
+                for definition in &category.attributes {
+                    if !definitions.iter().any(|known| known.key == definition.key) {
+                        definitions.push(definition);
+                    }
+                }
+            }
+        }
+
+        let mut rows: Vec<ComparisonRow> = definitions.iter()
+            .map(|definition| {
+                let unit = definition.unit.as_deref();
+                let cells = products.iter()
+                    .map(|product| Self::cell(product.specs.get(&definition.key)
+                        .map(|value| (Self::spec_json(value), Self::spec_label(value, unit)))))
+                    .collect();
+                Self::row(&definition.key, &definition.label, "specs", definition.unit.clone(), cells)
+            })
+            .collect();
+
+        let mut undefined: Vec<&String> = products.iter()
+            .flat_map(|product| product.specs.keys())
+            .filter(|key| !definitions.iter().any(|definition| &definition.key == *key))
+            .collect();
+        undefined.sort();
+        undefined.dedup();
+        for key in undefined {
+            let cells = products.iter()
+                .map(|product| Self::cell(product.specs.get(key)
+                    .map(|value| (Self::spec_json(value), Self::spec_label(value, None)))))
+                .collect();
+            rows.push(Self::row(key, key, "specs", None, cells));
+        }
+        rows
+    }
+
+    async fn matrix(&self, ids: &[ObjectId]) -> Result<ComparisonResponse, String> {
+        let products = self.load_products(ids).await?;
+
+        let mut category_ids: Vec<ObjectId> = products.iter().map(|product| product.category_id).collect();
+        category_ids.sort();
+        category_ids.dedup();
+        let mut categories = HashMap::new();
+        for category_id in category_ids {
+            if let Some(category) = self.product_crud.category(&category_id).await? {
+                categories.insert(category_id, category);
+            }
+        }
+
+        let mut rows = self.overview_rows(&products, &categories).await?;
+        rows.extend(Self::spec_rows(&products, &categories));
+
+        let columns = products.iter()
+            .filter_map(|product| Some(ComparedProduct {
+                id: product.id?,
+                name: product.name.clone(),
+                slug: product.slug.clone(),
+                image: product.primary_image().map(|image| image.url.clone()),
+            }))
+            .collect();
+        Ok(ComparisonResponse { products: columns, rows })
+    }
+}
+
+#[async_trait::async_trait]
+impl ComparisonService for ComparisonController {
+    async fn compare_products(&self, data: CompareProductsSchema) -> Result<ComparisonResponse, String> {
+        let ids = Self::product_ids(&data)?;
+        self.matrix(&ids).await
+    }
+
+    async fn save_comparison(&self, user_id: &str, data: CompareProductsSchema) -> Result<SavedComparisonResponse, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let ids = Self::product_ids(&data)?;
+        self.load_products(&ids).await?;
+
+        let comparison = self.crud.create_comparison(SavedComparison::new(user_oid, ids)).await?;
+        let share_url = Self::share_url(&comparison.token);
+        Ok(SavedComparisonResponse::new(comparison, share_url))
+    }
+
+    async fn get_shared_comparison(&self, token: &str) -> Result<Option<SharedComparisonResponse>, String> {
+        let comparison = match self.crud.get_by_token(token).await? {
+            Some(comparison) => comparison,
+            None => return Ok(None),
+        };
+
+        // Products removed since the link was made drop out of the comparison
+        let available: Vec<ObjectId> = self.product_crud.get_products_by_ids(&comparison.product_ids).await?
+            .into_iter()
+            .filter(|product| product.is_active && product.deleted_at.is_none())
+            .filter_map(|product| product.id)
+            .collect();
+        let (ids, unavailable_product_ids): (Vec<ObjectId>, Vec<ObjectId>) = comparison.product_ids.iter()
+            .copied()
+            .partition(|id| available.contains(id));
+
+        // The link still opens with what's left, marked as no longer a comparison
+        let matrix = self.matrix(&ids).await?;
+        let share_url = Self::share_url(&comparison.token);
+        Ok(Some(SharedComparisonResponse {
+            comparison: SavedComparisonResponse::new(comparison, share_url),
+            matrix,
+            unavailable_product_ids,
+            comparable: ids.len() >= MIN_COMPARED,
+        }))
+    }
+
+    async fn get_user_comparisons(&self, user_id: &str) -> Result<Vec<SavedComparisonResponse>, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let comparisons = self.crud.find_user_comparisons(&user_oid, SAVED_LIST_LIMIT).await?;
+        Ok(comparisons.into_iter()
+            .map(|comparison| {
+                let share_url = Self::share_url(&comparison.token);
+                SavedComparisonResponse::new(comparison, share_url)
+            })
+            .collect())
+    }
+
+    async fn delete_comparison(&self, user_id: &str, token: &str) -> Result<bool, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        self.crud.delete_user_comparison(token, &user_oid).await
+    }
+}
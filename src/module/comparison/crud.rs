@@ -0,0 +1,61 @@
+use bson::{doc, oid::ObjectId};
+use mongodb::{options::FindOptions, Collection, Database};
+use futures_util::TryStreamExt;
+use crate::module::comparison::model::SavedComparison;
+
+#[derive(Clone)]
+pub struct ComparisonCrud {
+    collection: Collection<SavedComparison>,
+}
+
+impl ComparisonCrud {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("comparisons");
+        Self { collection }
+    }
+
+    pub async fn create_comparison(&self, mut comparison: SavedComparison) -> Result<SavedComparison, String> {
+        match self.collection.insert_one(&comparison).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    comparison.id = Some(id);
+                }
+                Ok(comparison)
+            }
+            Err(e) => Err(format!("Failed to save comparison: {}", e))
+        }
+    }
+
+    pub async fn get_by_token(&self, token: &str) -> Result<Option<SavedComparison>, String> {
+        match self.collection.find_one(doc! { "token": token }).await {
+            Ok(comparison) => Ok(comparison),
+            Err(e) => Err(format!("Failed to retrieve comparison: {}", e))
+        }
+    }
+
+    pub async fn find_user_comparisons(&self, user_id: &ObjectId, limit: i64) -> Result<Vec<SavedComparison>, String> {
+        let options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .limit(limit)
+            .build();
+
+        match self.collection.find(doc! { "user_id": user_id }).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut comparisons = Vec::new();
+                while let Some(comparison) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve comparisons: {}", e))? {
+                    comparisons.push(comparison);
+                }
+                Ok(comparisons)
+            }
+            Err(e) => Err(format!("Failed to retrieve comparisons: {}", e))
+        }
+    }
+
+    pub async fn delete_user_comparison(&self, token: &str, user_id: &ObjectId) -> Result<bool, String> {
+        match self.collection.delete_one(doc! { "token": token, "user_id": user_id }).await {
+            Ok(result) => Ok(result.deleted_count > 0),
+            Err(e) => Err(format!("Failed to delete comparison: {}", e))
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use crate::module::comparison::schema::{
+    CompareProductsSchema, ComparisonResponse, SavedComparisonResponse, SharedComparisonResponse,
+};
+
+#[async_trait::async_trait]
+pub trait ComparisonService {
+    async fn compare_products(&self, data: CompareProductsSchema) -> Result<ComparisonResponse, String>;
+    async fn save_comparison(&self, user_id: &str, data: CompareProductsSchema) -> Result<SavedComparisonResponse, String>;
+    async fn get_shared_comparison(&self, token: &str) -> Result<Option<SharedComparisonResponse>, String>;
+    async fn get_user_comparisons(&self, user_id: &str) -> Result<Vec<SavedComparisonResponse>, String>;
+    async fn delete_comparison(&self, user_id: &str, token: &str) -> Result<bool, String>;
+}
@@ -0,0 +1,17 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("comparison", 1, "saved comparison indexes", "comparisons", vec![
+            IndexModel::builder()
+                .keys(doc! { "token": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "user_id": 1, "created_at": -1 })
+                .build(),
+        ]),
+    ]
+}
@@ -0,0 +1,36 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// How many products one comparison can hold
+pub const MIN_COMPARED: usize = 2;
+pub const MAX_COMPARED: usize = 4;
+
+// A comparison a customer saved to share. Only the product list is kept;
+// prices and stock are read afresh whenever the link is opened.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SavedComparison {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    // Goes in the share link
+    pub token: String,
+    pub user_id: ObjectId,
+    pub product_ids: Vec<ObjectId>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl SavedComparison {
+    pub fn new(user_id: ObjectId, product_ids: Vec<ObjectId>) -> Self {
+        Self {
+            id: None,
+            token: Uuid::new_v4().simple().to_string()[..12].to_string(),
+            user_id,
+            product_ids,
+            created_at: Utc::now(),
+        }
+    }
+}
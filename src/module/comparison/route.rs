@@ -0,0 +1,119 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::Authentication;
+use crate::module::comparison::{
+    controller::ComparisonController,
+    crud::ComparisonCrud,
+    interface::ComparisonService,
+    schema::CompareProductsSchema,
+};
+use crate::module::product::crud::ProductCrud;
+
+fn controller(crud: &web::Data<ComparisonCrud>, product_crud: &web::Data<ProductCrud>) -> ComparisonController {
+    ComparisonController::new(crud.get_ref().clone(), product_crud.get_ref().clone())
+}
+
+// POST compare products handler
+async fn compare_products_handler(
+    body: web::Json<CompareProductsSchema>,
+    crud: web::Data<ComparisonCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).compare_products(body.into_inner()).await {
+        Ok(comparison) => Ok(HttpResponse::Ok().json(comparison)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST save comparison handler
+async fn save_comparison_handler(
+    req: HttpRequest,
+    body: web::Json<CompareProductsSchema>,
+    crud: web::Data<ComparisonCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud).save_comparison(&claims.sub, body.into_inner()).await {
+        Ok(comparison) => Ok(HttpResponse::Created().json(comparison)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET user's saved comparisons handler
+async fn get_my_comparisons_handler(
+    req: HttpRequest,
+    crud: web::Data<ComparisonCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud).get_user_comparisons(&claims.sub).await {
+        Ok(comparisons) => Ok(HttpResponse::Ok().json(comparisons)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET shared comparison handler
+async fn get_shared_comparison_handler(
+    token: web::Path<String>,
+    crud: web::Data<ComparisonCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).get_shared_comparison(&token).await {
+        Ok(Some(comparison)) => Ok(HttpResponse::Ok().json(comparison)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Comparison not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE own saved comparison handler
+async fn delete_comparison_handler(
+    req: HttpRequest,
+    token: web::Path<String>,
+    crud: web::Data<ComparisonCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud).delete_comparison(&claims.sub, &token).await {
+        Ok(true) => Ok(HttpResponse::NoContent().finish()),
+        Ok(false) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Comparison not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// Route configuration function. Configured before the product routes, whose
+// `/products` scope would otherwise take these requests.
+pub fn comparison_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/products/compare")
+            .route("", web::post().to(compare_products_handler))
+            .route("/saved", web::post().to(save_comparison_handler))
+            .route("/saved", web::get().to(get_my_comparisons_handler))
+            .route("/saved/{token}", web::get().to(get_shared_comparison_handler))
+            .route("/saved/{token}", web::delete().to(delete_comparison_handler))
+    );
+}
@@ -0,0 +1,84 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::module::comparison::model::SavedComparison;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CompareProductsSchema {
+    #[validate(length(min = 2, max = 4, message = "Compare 2 to 4 products"))]
+    pub product_ids: Vec<String>,
+}
+
+// A column of the comparison
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ComparedProduct {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub name: String,
+    pub slug: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+// One product's value in a row. `value` is null when the product doesn't
+// give one, and `label` then says so.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ComparisonCell {
+    pub value: serde_json::Value,
+    pub label: String,
+    pub missing: bool,
+}
+
+// One line of the matrix, with a cell per product in column order
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ComparisonRow {
+    pub key: String,
+    pub label: String,
+    // "overview" or "specs"
+    pub group: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    pub cells: Vec<ComparisonCell>,
+    // Whether the products differ here, a missing value counting as different
+    pub differs: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ComparisonResponse {
+    pub products: Vec<ComparedProduct>,
+    pub rows: Vec<ComparisonRow>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SavedComparisonResponse {
+    pub token: String,
+    pub share_url: String,
+    pub product_ids: Vec<ObjectId>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl SavedComparisonResponse {
+    pub fn new(comparison: SavedComparison, share_url: String) -> Self {
+        Self {
+            token: comparison.token,
+            share_url,
+            product_ids: comparison.product_ids,
+            created_at: comparison.created_at,
+        }
+    }
+}
+
+// A shared comparison as the link opens it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SharedComparisonResponse {
+    pub comparison: SavedComparisonResponse,
+    pub matrix: ComparisonResponse,
+    // Saved products since deleted or hidden; they are left out of the matrix
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unavailable_product_ids: Vec<ObjectId>,
+    // False once fewer than two products are left to compare
+    pub comparable: bool,
+}
@@ -0,0 +1,229 @@
+use bson::{doc, oid::ObjectId};
+use validator::Validate;
+use crate::module::compatibility::{
+    crud::CompatibilityCrud,
+    engine::{self, BuildPart},
+    interface::CompatibilityService,
+    model::{CompatibilityRule, RuleCheck, RuleSeverity},
+    schema::{
+        BuildAlternatives, BuildCheckResponse, BuildIssue, CheckBuildSchema, CreateRuleSchema,
+        UpdateRuleSchema,
+    },
+};
+use crate::module::product::{crud::ProductCrud, model::Product};
+use crate::service::slug_service::slugify;
+
+// Best sellers of a kind tried as replacements for a part
+const CANDIDATE_POOL: i64 = 30;
+
+// Alternatives suggested per part
+const MAX_ALTERNATIVES: usize = 5;
+
+pub struct CompatibilityController {
+    crud: CompatibilityCrud,
+    product_crud: ProductCrud,
+}
+
+impl CompatibilityController {
+    pub fn new(crud: CompatibilityCrud, product_crud: ProductCrud) -> Self {
+        Self { crud, product_crud }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    fn part(product: &Product, quantity: i32) -> Option<BuildPart> {
+        Some(BuildPart {
+            product_id: product.id?,
+            name: product.name.clone(),
+            profile: product.component.clone()?,
+            quantity,
+        })
+    }
+
+    // In-stock parts of the same kind that could take `part`'s place without
+    // an error involving them. The rules `part` failed must also be checked
+    // for the replacement; one missing the data they read doesn't count.
+    async fn alternatives(
+        &self,
+        rules: &[CompatibilityRule],
+        parts: &[BuildPart],
+        part: &BuildPart,
+        failed_rules: &[String],
+    ) -> Result<Vec<Product>, String> {
+        let in_build: Vec<ObjectId> = parts.iter().map(|part| part.product_id).collect();
+        let filter = doc! {
+            "_id": { "$nin": in_build },
+            "component.kind": part.profile.kind.as_str(),
+            "is_active": true,
+            "deleted_at": null,
+            "stock_quantity": { "$gt": 0 },
+        };
+        let (candidates, _) = self.product_crud
+            .find_products(filter, None, None, doc! { "sold_count": -1, "_id": -1 }, 0, CANDIDATE_POOL)
+            .await?;
+
+        let mut alternatives = Vec::new();
+        for candidate in candidates {
+            let replacement = match Self::part(&candidate, part.quantity) {
+                Some(replacement) => replacement,
+                None => continue,
+            };
+            let build: Vec<BuildPart> = parts.iter()
+                .map(|existing| if existing.product_id == part.product_id { replacement.clone() } else { existing.clone() })
+                .collect();
+            let clears = !engine::evaluate(rules, &build).iter()
+                .filter(|issue| issue.product_ids.contains(&replacement.product_id))
+                .any(|issue| issue.severity == RuleSeverity::Error || failed_rules.contains(&issue.rule));
+            if clears {
+                alternatives.push(candidate);
+                if alternatives.len() == MAX_ALTERNATIVES {
+                    break;
+                }
+            }
+        }
+        Ok(alternatives)
+    }
+}
+
+#[async_trait::async_trait]
+impl CompatibilityService for CompatibilityController {
+    async fn check_build(&self, data: CheckBuildSchema) -> Result<BuildCheckResponse, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        // The same product listed twice is one line with the quantities added
+        let mut lines: Vec<(ObjectId, i32)> = Vec::new();
+        for item in &data.items {
+            let product_id = Self::parse_id(&item.product_id, "product")?;
+            match lines.iter_mut().find(|(id, _)| *id == product_id) {
+                Some((_, quantity)) => *quantity += item.quantity,
+                None => lines.push((product_id, item.quantity)),
+            }
+        }
+
+        let ids: Vec<ObjectId> = lines.iter().map(|(id, _)| *id).collect();
+        let products = self.product_crud.get_products_by_ids(&ids).await?;
+
+        let mut parts = Vec::new();
+        let mut not_components = Vec::new();
+        for (product_id, quantity) in &lines {
+            let product = products.iter()
+                .find(|product| product.id.as_ref() == Some(product_id) && product.deleted_at.is_none())
+                .ok_or_else(|| format!("Product {} not found", product_id.to_hex()))?;
+            match Self::part(product, *quantity) {
+                Some(part) => parts.push(part),
+                None => not_components.push(BuildIssue {
+                    rule: "component".to_string(),
+                    name: "PC component".to_string(),
+                    severity: RuleSeverity::Warning,
+                    message: format!("{} isn't a PC component we have compatibility details for, so it wasn't checked", product.name),
+                    product_ids: vec![*product_id],
+                }),
+            }
+        }
+
+        let rules = self.crud.find_rules(true).await?;
+        let (errors, mut warnings): (Vec<BuildIssue>, Vec<BuildIssue>) = engine::evaluate(&rules, &parts)
+            .into_iter()
+            .partition(|issue| issue.severity == RuleSeverity::Error);
+        warnings.extend(not_components);
+
+        // Swapping a part can't fix a build that has too many of a kind
+        let mut to_replace: Vec<ObjectId> = Vec::new();
+        for error in &errors {
+            let counts = rules.iter()
+                .any(|rule| rule.key == error.rule && matches!(rule.check, RuleCheck::MaxCount { .. }));
+            if counts {
+                continue;
+            }
+            for product_id in &error.product_ids {
+                if !to_replace.contains(product_id) {
+                    to_replace.push(*product_id);
+                }
+            }
+        }
+
+        let mut alternatives = Vec::new();
+        for product_id in to_replace {
+            if let Some(part) = parts.iter().find(|part| part.product_id == product_id) {
+                let failed_rules: Vec<String> = errors.iter()
+                    .filter(|error| error.product_ids.contains(&product_id))
+                    .map(|error| error.rule.clone())
+                    .collect();
+                let products = self.alternatives(&rules, &parts, part, &failed_rules).await?;
+                alternatives.push(BuildAlternatives { product_id, kind: part.profile.kind, products });
+            }
+        }
+
+        Ok(BuildCheckResponse {
+            compatible: errors.is_empty(),
+            errors,
+            warnings,
+            alternatives,
+        })
+    }
+
+    async fn get_rules(&self) -> Result<Vec<CompatibilityRule>, String> {
+        self.crud.find_rules(false).await
+    }
+
+    async fn create_rule(&self, data: CreateRuleSchema) -> Result<CompatibilityRule, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let key = slugify(&data.key);
+        if key.is_empty() {
+            return Err("Rule key must contain letters or digits".to_string());
+        }
+        let mut rule = CompatibilityRule::new(
+            key,
+            data.name.trim().to_string(),
+            data.severity,
+            data.check.normalized()?,
+            data.message.trim().to_string(),
+        );
+        if let Some(is_active) = data.is_active {
+            rule.is_active = is_active;
+        }
+        self.crud.create_rule(rule).await
+    }
+
+    async fn update_rule(&self, id: &str, data: UpdateRuleSchema) -> Result<Option<CompatibilityRule>, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+        let id = Self::parse_id(id, "rule")?;
+
+        let mut set = doc! {};
+        if let Some(name) = data.name {
+            set.insert("name", name.trim());
+        }
+        if let Some(severity) = data.severity {
+            let severity = bson::to_bson(&severity)
+                .map_err(|e| format!("Failed to encode severity: {}", e))?;
+            set.insert("severity", severity);
+        }
+        if let Some(check) = data.check {
+            let check = bson::to_bson(&check.normalized()?)
+                .map_err(|e| format!("Failed to encode check: {}", e))?;
+            set.insert("check", check);
+        }
+        if let Some(message) = data.message {
+            set.insert("message", message.trim());
+        }
+        if let Some(is_active) = data.is_active {
+            set.insert("is_active", is_active);
+        }
+
+        self.crud.update_rule(&id, set).await
+    }
+
+    async fn delete_rule(&self, id: &str) -> Result<bool, String> {
+        let id = Self::parse_id(id, "rule")?;
+        self.crud.delete_rule(&id).await
+    }
+}
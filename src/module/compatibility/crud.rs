@@ -0,0 +1,94 @@
+use bson::{doc, oid::ObjectId, Document};
+use chrono::Utc;
+use mongodb::{
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Collection, Database,
+};
+use futures_util::TryStreamExt;
+use crate::module::compatibility::model::{default_rules, CompatibilityRule};
+use crate::service::migration_service;
+
+#[derive(Clone)]
+pub struct CompatibilityCrud {
+    collection: Collection<CompatibilityRule>,
+}
+
+impl CompatibilityCrud {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("compatibility_rules");
+        Self { collection }
+    }
+
+    pub async fn find_rules(&self, active_only: bool) -> Result<Vec<CompatibilityRule>, String> {
+        let filter = if active_only { doc! { "is_active": true } } else { doc! {} };
+        let options = FindOptions::builder().sort(doc! { "key": 1 }).build();
+
+        match self.collection.find(filter).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut rules = Vec::new();
+                while let Some(rule) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve compatibility rules: {}", e))? {
+                    rules.push(rule);
+                }
+                Ok(rules)
+            }
+            Err(e) => Err(format!("Failed to retrieve compatibility rules: {}", e))
+        }
+    }
+
+    pub async fn create_rule(&self, mut rule: CompatibilityRule) -> Result<CompatibilityRule, String> {
+        match self.collection.insert_one(&rule).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    rule.id = Some(id);
+                }
+                Ok(rule)
+            }
+            Err(e) if migration_service::is_duplicate_key(&e, "key") => {
+                Err(format!("A rule with key {} already exists", rule.key))
+            }
+            Err(e) => Err(format!("Failed to create compatibility rule: {}", e))
+        }
+    }
+
+    // Apply a `$set` to a rule, refreshing `updated_at`
+    pub async fn update_rule(&self, id: &ObjectId, mut set: Document) -> Result<Option<CompatibilityRule>, String> {
+        set.insert("updated_at", bson::DateTime::from_chrono(Utc::now()));
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(doc! { "_id": id }, doc! { "$set": set })
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to update compatibility rule: {}", e))
+    }
+
+    pub async fn delete_rule(&self, id: &ObjectId) -> Result<bool, String> {
+        match self.collection.delete_one(doc! { "_id": id }).await {
+            Ok(result) => Ok(result.deleted_count > 0),
+            Err(e) => Err(format!("Failed to delete compatibility rule: {}", e))
+        }
+    }
+
+    // Add the default rules that aren't there yet, leaving any an admin has
+    // changed alone. Returns how many were added.
+    pub async fn seed_default_rules(&self) -> Result<u64, String> {
+        let mut added = 0;
+        for rule in default_rules() {
+            let rule = bson::to_document(&rule)
+                .map_err(|e| format!("Failed to encode compatibility rule: {}", e))?;
+            let key = rule.get_str("key").unwrap_or_default().to_string();
+            let result = self.collection
+                .update_one(doc! { "key": &key }, doc! { "$setOnInsert": rule })
+                .upsert(true)
+                .await
+                .map_err(|e| format!("Failed to seed compatibility rule {}: {}", key, e))?;
+            if result.upserted_id.is_some() {
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+}
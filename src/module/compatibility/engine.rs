@@ -0,0 +1,206 @@
+use bson::oid::ObjectId;
+use crate::module::compatibility::{
+    model::{CompatibilityRule, RuleCheck, RuleOperand, RuleSeverity},
+    schema::BuildIssue,
+};
+use crate::module::product::model::{ComponentProfile, ComponentValue};
+
+// One line of a proposed build
+#[derive(Debug, Clone)]
+pub struct BuildPart {
+    pub product_id: ObjectId,
+    pub name: String,
+    pub profile: ComponentProfile,
+    pub quantity: i32,
+}
+
+// A value one side of a rule compares, and the parts it came from
+struct SideValue {
+    name: String,
+    product_ids: Vec<ObjectId>,
+    value: ComponentValue,
+}
+
+// Parts a rule couldn't check because they don't list the attribute
+type Unchecked<'a> = Vec<(&'a BuildPart, &'a str)>;
+
+fn parts_of<'a>(operand: &RuleOperand, parts: &'a [BuildPart]) -> Vec<&'a BuildPart> {
+    parts.iter()
+        .filter(|part| operand.kinds.is_empty() || operand.kinds.contains(&part.profile.kind))
+        .collect()
+}
+
+// The values a side of a rule compares, or None when the build has none of
+// the parts it reads, in which case the rule doesn't apply
+fn side<'a>(operand: &'a RuleOperand, parts: &'a [BuildPart], unchecked: &mut Unchecked<'a>) -> Option<Vec<SideValue>> {
+    let parts = parts_of(operand, parts);
+    if parts.is_empty() {
+        return None;
+    }
+
+    if !operand.sum {
+        let mut values = Vec::new();
+        for part in parts {
+            match part.profile.attribute(&operand.attribute) {
+                Some(value) => values.push(SideValue {
+                    name: part.name.clone(),
+                    product_ids: vec![part.product_id],
+                    value: value.clone(),
+                }),
+                None => unchecked.push((part, &operand.attribute)),
+            }
+        }
+        return Some(values);
+    }
+
+    // A total over every part only counts the parts that list the attribute,
+    // e.g. drives have no TDP worth adding to the power draw
+    let mut total = 0.0;
+    let mut product_ids = Vec::new();
+    for part in parts {
+        match part.profile.attribute(&operand.attribute) {
+            Some(ComponentValue::Number(number)) => {
+                total += number * part.quantity as f64;
+                product_ids.push(part.product_id);
+            }
+            _ if !operand.kinds.is_empty() => unchecked.push((part, &operand.attribute)),
+            _ => {}
+        }
+    }
+    if product_ids.is_empty() {
+        return Some(Vec::new());
+    }
+    Some(vec![SideValue { name: "the build".to_string(), product_ids, value: ComponentValue::Number(total) }])
+}
+
+fn same_value(left: &ComponentValue, right: &ComponentValue) -> bool {
+    match (left, right) {
+        (ComponentValue::Number(left), ComponentValue::Number(right)) => (left - right).abs() < 1e-9,
+        (left, right) => left.label() == right.label(),
+    }
+}
+
+fn list_contains(list: &ComponentValue, value: &ComponentValue) -> bool {
+    let list = match list {
+        ComponentValue::List(values) => values.clone(),
+        other => vec![other.label()],
+    };
+    match value {
+        ComponentValue::List(values) => values.iter().all(|value| list.contains(value)),
+        other => list.contains(&other.label()),
+    }
+}
+
+fn fill(message: &str, values: &[(&str, String)]) -> String {
+    values.iter().fold(message.to_string(), |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+fn issue(rule: &CompatibilityRule, severity: RuleSeverity, message: String, mut product_ids: Vec<ObjectId>) -> BuildIssue {
+    product_ids.sort();
+    product_ids.dedup();
+    BuildIssue {
+        rule: rule.key.clone(),
+        name: rule.name.clone(),
+        severity,
+        message,
+        product_ids,
+    }
+}
+
+// Compare every left value with every right value from a different part.
+// `passes` returns None when the two values can't be compared, which is
+// reported as a check that wasn't made rather than a pass.
+fn compare(
+    rule: &CompatibilityRule,
+    left: &[SideValue],
+    right: &[SideValue],
+    passes: impl Fn(&ComponentValue, &ComponentValue) -> Option<bool>,
+) -> Vec<BuildIssue> {
+    let mut issues = Vec::new();
+    for l in left {
+        for r in right {
+            if l.product_ids.iter().any(|id| r.product_ids.contains(id)) {
+                continue;
+            }
+            let product_ids = l.product_ids.iter().chain(&r.product_ids).copied().collect();
+            match passes(&l.value, &r.value) {
+                Some(true) => {}
+                Some(false) => {
+                    let message = fill(&rule.message, &[
+                        ("left_value", l.value.label()),
+                        ("right_value", r.value.label()),
+                        ("left", l.name.clone()),
+                        ("right", r.name.clone()),
+                    ]);
+                    issues.push(issue(rule, rule.severity, message, product_ids));
+                }
+                None => {
+                    let message = format!(
+                        "We couldn't check {} for {} and {}: \"{}\" and \"{}\" can't be compared",
+                        rule.name.to_lowercase(), l.name, r.name, l.value.label(), r.value.label()
+                    );
+                    issues.push(issue(rule, RuleSeverity::Warning, message, product_ids));
+                }
+            }
+        }
+    }
+    issues
+}
+
+fn evaluate_rule(rule: &CompatibilityRule, parts: &[BuildPart]) -> Vec<BuildIssue> {
+    let (left, right) = match &rule.check {
+        RuleCheck::MaxCount { kind, max } => {
+            let parts: Vec<&BuildPart> = parts.iter().filter(|part| part.profile.kind == *kind).collect();
+            let count: i32 = parts.iter().map(|part| part.quantity).sum();
+            if count <= *max {
+                return Vec::new();
+            }
+            let message = fill(&rule.message, &[
+                ("kind", kind.label().to_string()),
+                ("count", count.to_string()),
+                ("max", max.to_string()),
+            ]);
+            return vec![issue(rule, rule.severity, message, parts.iter().map(|part| part.product_id).collect())];
+        }
+        RuleCheck::Equal { left, right }
+        | RuleCheck::Contains { left, right }
+        | RuleCheck::AtMost { left, right, .. } => (left, right),
+    };
+
+    let mut unchecked = Vec::new();
+    let (left_values, right_values) = match (side(left, parts, &mut unchecked), side(right, parts, &mut unchecked)) {
+        (Some(left_values), Some(right_values)) => (left_values, right_values),
+        _ => return Vec::new(),
+    };
+
+    let mut issues = match &rule.check {
+        RuleCheck::Equal { .. } => compare(rule, &left_values, &right_values, |l, r| Some(same_value(l, r))),
+        RuleCheck::Contains { .. } => compare(rule, &left_values, &right_values, |l, r| Some(list_contains(r, l))),
+        RuleCheck::AtMost { factor, .. } => compare(rule, &left_values, &right_values, |l, r| match (l, r) {
+            (ComponentValue::Number(l), ComponentValue::Number(r)) => Some(l * factor <= r + 1e-9),
+            // Only numbers can be compared this way
+            _ => None,
+        }),
+        RuleCheck::MaxCount { .. } => Vec::new(),
+    };
+
+    // Missing data never blocks a build, but the customer should know what
+    // wasn't checked
+    for (part, attribute) in unchecked {
+        let message = format!("We couldn't check {} for {}: it doesn't list its {}", rule.name.to_lowercase(), part.name, attribute.replace('_', " "));
+        issues.push(issue(rule, RuleSeverity::Warning, message, vec![part.product_id]));
+    }
+    issues
+}
+
+/// Runs every rule against the build and returns what fails, errors first.
+pub fn evaluate(rules: &[CompatibilityRule], parts: &[BuildPart]) -> Vec<BuildIssue> {
+    let mut issues: Vec<BuildIssue> = rules.iter()
+        .filter(|rule| rule.is_active)
+        .flat_map(|rule| evaluate_rule(rule, parts))
+        .collect();
+    issues.sort_by_key(|issue| issue.severity != RuleSeverity::Error);
+    issues
+}
@@ -0,0 +1,16 @@
+use crate::module::compatibility::{
+    model::CompatibilityRule,
+    schema::{BuildCheckResponse, CheckBuildSchema, CreateRuleSchema, UpdateRuleSchema},
+};
+
+#[async_trait::async_trait]
+pub trait CompatibilityService {
+    // Shoppers
+    async fn check_build(&self, data: CheckBuildSchema) -> Result<BuildCheckResponse, String>;
+
+    // Admin
+    async fn get_rules(&self) -> Result<Vec<CompatibilityRule>, String>;
+    async fn create_rule(&self, data: CreateRuleSchema) -> Result<CompatibilityRule, String>;
+    async fn update_rule(&self, id: &str, data: UpdateRuleSchema) -> Result<Option<CompatibilityRule>, String>;
+    async fn delete_rule(&self, id: &str) -> Result<bool, String>;
+}
@@ -0,0 +1,18 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::module::compatibility::crud::CompatibilityCrud;
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("compatibility", 1, "unique rule key index", "compatibility_rules", vec![
+            IndexModel::builder()
+                .keys(doc! { "key": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+        ]),
+        Migration::data("compatibility", 2, "default compatibility rules", |db| async move {
+            CompatibilityCrud::new(&db).seed_default_rules().await
+        }),
+    ]
+}
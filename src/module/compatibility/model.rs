@@ -0,0 +1,204 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::module::product::model::ComponentKind;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleSeverity {
+    // The parts won't work together
+    Error,
+    // The parts work, but the customer should know something
+    Warning,
+}
+
+// One side of a rule: an attribute of the build's components of some kinds
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RuleOperand {
+    // Empty means every component in the build
+    #[serde(default)]
+    pub kinds: Vec<ComponentKind>,
+    pub attribute: String,
+    // Add the attribute up over the components, times their quantity,
+    // instead of checking each component on its own
+    #[serde(default)]
+    pub sum: bool,
+}
+
+fn default_factor() -> f64 {
+    1.0
+}
+
+// What a rule checks
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleCheck {
+    // Left and right have the same value, e.g. the CPU's and the motherboard's socket
+    Equal { left: RuleOperand, right: RuleOperand },
+    // The right side's list holds the left value, e.g. a case's supported
+    // form factors and the motherboard's form factor
+    Contains { left: RuleOperand, right: RuleOperand },
+    // Left times `factor` is no more than right, e.g. GPU length and the
+    // case's clearance
+    AtMost {
+        left: RuleOperand,
+        right: RuleOperand,
+        #[serde(default = "default_factor")]
+        factor: f64,
+    },
+    // A build has no more than `max` components of a kind
+    MaxCount { kind: ComponentKind, max: i32 },
+}
+
+impl RuleCheck {
+    // Attribute names are matched as stored on products, trimmed and lowercase
+    pub fn normalized(self) -> Result<Self, String> {
+        let operand = |operand: RuleOperand| -> Result<RuleOperand, String> {
+            let attribute = operand.attribute.trim().to_lowercase();
+            if attribute.is_empty() {
+                return Err("Rule attributes can't be empty".to_string());
+            }
+            Ok(RuleOperand { attribute, ..operand })
+        };
+        match self {
+            RuleCheck::Equal { left, right } => Ok(RuleCheck::Equal { left: operand(left)?, right: operand(right)? }),
+            RuleCheck::Contains { left, right } => Ok(RuleCheck::Contains { left: operand(left)?, right: operand(right)? }),
+            RuleCheck::AtMost { left, right, factor } => {
+                if !factor.is_finite() || factor <= 0.0 {
+                    return Err("Factor must be a positive number".to_string());
+                }
+                Ok(RuleCheck::AtMost { left: operand(left)?, right: operand(right)?, factor })
+            }
+            RuleCheck::MaxCount { kind, max } => {
+                if max < 0 {
+                    return Err("Max must be zero or more".to_string());
+                }
+                Ok(RuleCheck::MaxCount { kind, max })
+            }
+        }
+    }
+}
+
+// A compatibility rule. Admins can change the defaults or add their own.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CompatibilityRule {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    // Stable name, e.g. "cpu-motherboard-socket"
+    pub key: String,
+    pub name: String,
+    pub severity: RuleSeverity,
+    pub check: RuleCheck,
+    // Shown when the rule fails. {left} and {right} become the products'
+    // names and {left_value} and {right_value} the values compared; a
+    // `max_count` rule fills in {kind}, {count} and {max}.
+    pub message: String,
+    pub is_active: bool,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CompatibilityRule {
+    pub fn new(key: String, name: String, severity: RuleSeverity, check: RuleCheck, message: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            key,
+            name,
+            severity,
+            check,
+            message,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+fn operand(kinds: &[ComponentKind], attribute: &str, sum: bool) -> RuleOperand {
+    RuleOperand { kinds: kinds.to_vec(), attribute: attribute.to_string(), sum }
+}
+
+/// The rules a new database starts with.
+pub fn default_rules() -> Vec<CompatibilityRule> {
+    use ComponentKind::*;
+    use RuleSeverity::*;
+
+    let rule = |key: &str, name: &str, severity, check, message: &str| {
+        CompatibilityRule::new(key.to_string(), name.to_string(), severity, check, message.to_string())
+    };
+    let single = |kind: ComponentKind| rule(
+        &format!("single-{}", kind.as_str()),
+        &format!("One {} per build", kind.label()),
+        Error,
+        RuleCheck::MaxCount { kind, max: 1 },
+        "A build takes one {kind}, but this one has {count}",
+    );
+
+    vec![
+        single(Cpu),
+        single(Motherboard),
+        single(Psu),
+        single(Case),
+        rule(
+            "cpu-motherboard-socket",
+            "CPU socket",
+            Error,
+            RuleCheck::Equal { left: operand(&[Cpu], "socket", false), right: operand(&[Motherboard], "socket", false) },
+            "{left} needs a {left_value} socket, but {right} has {right_value}",
+        ),
+        rule(
+            "cpu-motherboard-chipset",
+            "CPU chipset support",
+            Warning,
+            RuleCheck::Contains { left: operand(&[Motherboard], "chipset", false), right: operand(&[Cpu], "supported_chipsets", false) },
+            "{left} has the {left_value} chipset, which {right} doesn't list; it may need a BIOS update first",
+        ),
+        rule(
+            "memory-type",
+            "Memory type",
+            Error,
+            RuleCheck::Equal { left: operand(&[Memory], "memory_type", false), right: operand(&[Motherboard], "memory_type", false) },
+            "{left} is {left_value}, but {right} takes {right_value}",
+        ),
+        rule(
+            "memory-slots",
+            "Memory slots",
+            Error,
+            RuleCheck::AtMost { left: operand(&[Memory], "memory_modules", true), right: operand(&[Motherboard], "memory_slots", false), factor: 1.0 },
+            "The build has {left_value} memory modules, but {right} has {right_value} slots",
+        ),
+        rule(
+            "case-form-factor",
+            "Motherboard fits the case",
+            Error,
+            RuleCheck::Contains { left: operand(&[Motherboard], "form_factor", false), right: operand(&[Case], "supported_form_factors", false) },
+            "{left} is {left_value}, but {right} takes {right_value}",
+        ),
+        rule(
+            "gpu-length",
+            "Graphics card fits the case",
+            Error,
+            RuleCheck::AtMost { left: operand(&[Gpu], "length_mm", false), right: operand(&[Case], "max_gpu_length_mm", false), factor: 1.0 },
+            "{left} is {left_value}mm long, but {right} fits cards up to {right_value}mm",
+        ),
+        rule(
+            "psu-wattage",
+            "Power supply wattage",
+            Error,
+            RuleCheck::AtMost { left: operand(&[], "tdp_watts", true), right: operand(&[Psu], "wattage", false), factor: 1.0 },
+            "The parts draw up to {left_value}W, more than {right}'s {right_value}W",
+        ),
+        rule(
+            "psu-headroom",
+            "Power supply headroom",
+            Warning,
+            RuleCheck::AtMost { left: operand(&[], "tdp_watts", true), right: operand(&[Psu], "wattage", false), factor: 1.3 },
+            "The parts draw up to {left_value}W; {right} at {right_value}W leaves less than 30% headroom",
+        ),
+    ]
+}
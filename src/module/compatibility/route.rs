@@ -0,0 +1,107 @@
+use actix_web::{web, HttpResponse, Result};
+use crate::module::compatibility::{
+    controller::CompatibilityController,
+    crud::CompatibilityCrud,
+    interface::CompatibilityService,
+    schema::{CheckBuildSchema, CreateRuleSchema, UpdateRuleSchema},
+};
+use crate::module::product::crud::ProductCrud;
+
+fn controller(crud: &web::Data<CompatibilityCrud>, product_crud: &web::Data<ProductCrud>) -> CompatibilityController {
+    CompatibilityController::new(crud.get_ref().clone(), product_crud.get_ref().clone())
+}
+
+// POST build check handler
+async fn check_build_handler(
+    body: web::Json<CheckBuildSchema>,
+    crud: web::Data<CompatibilityCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).check_build(body.into_inner()).await {
+        Ok(result) => Ok(HttpResponse::Ok().json(result)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET compatibility rules handler (admin)
+async fn admin_get_rules_handler(
+    crud: web::Data<CompatibilityCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).get_rules().await {
+        Ok(rules) => Ok(HttpResponse::Ok().json(rules)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST compatibility rule handler (admin)
+async fn admin_create_rule_handler(
+    body: web::Json<CreateRuleSchema>,
+    crud: web::Data<CompatibilityCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).create_rule(body.into_inner()).await {
+        Ok(rule) => Ok(HttpResponse::Created().json(rule)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// PATCH compatibility rule handler (admin)
+async fn admin_update_rule_handler(
+    id: web::Path<String>,
+    body: web::Json<UpdateRuleSchema>,
+    crud: web::Data<CompatibilityCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).update_rule(&id, body.into_inner()).await {
+        Ok(Some(rule)) => Ok(HttpResponse::Ok().json(rule)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Rule not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE compatibility rule handler (admin)
+async fn admin_delete_rule_handler(
+    id: web::Path<String>,
+    crud: web::Data<CompatibilityCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).delete_rule(&id).await {
+        Ok(true) => Ok(HttpResponse::NoContent().finish()),
+        Ok(false) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Rule not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// Route configuration function
+pub fn compatibility_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/compatibility")
+            .route("/check", web::post().to(check_build_handler))
+    );
+}
+
+// Admin routes, mounted inside the admin-authenticated `/api/admin` scope
+pub fn admin_compatibility_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/compatibility")
+            .route("/rules", web::get().to(admin_get_rules_handler))
+            .route("/rules", web::post().to(admin_create_rule_handler))
+            .route("/rules/{id}", web::patch().to(admin_update_rule_handler))
+            .route("/rules/{id}", web::delete().to(admin_delete_rule_handler))
+    );
+}
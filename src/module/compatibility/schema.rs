@@ -0,0 +1,86 @@
+use bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::module::compatibility::model::{RuleCheck, RuleSeverity};
+use crate::module::product::model::{ComponentKind, Product};
+
+fn default_quantity() -> i32 {
+    1
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct BuildItemSchema {
+    pub product_id: String,
+
+    #[serde(default = "default_quantity")]
+    #[validate(range(min = 1, max = 16))]
+    pub quantity: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct CheckBuildSchema {
+    #[validate(length(min = 1, max = 20, message = "A build has 1 to 20 parts"), nested)]
+    pub items: Vec<BuildItemSchema>,
+}
+
+// A rule the build fails, or a check that couldn't be made
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildIssue {
+    // Key of the rule
+    pub rule: String,
+    pub name: String,
+    pub severity: RuleSeverity,
+    pub message: String,
+    // The parts involved
+    pub product_ids: Vec<ObjectId>,
+}
+
+// In-stock parts that could replace one in the build and clear its errors
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildAlternatives {
+    pub product_id: ObjectId,
+    pub kind: ComponentKind,
+    pub products: Vec<Product>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildCheckResponse {
+    // No errors; there may still be warnings
+    pub compatible: bool,
+    pub errors: Vec<BuildIssue>,
+    pub warnings: Vec<BuildIssue>,
+    pub alternatives: Vec<BuildAlternatives>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct CreateRuleSchema {
+    #[validate(length(min = 1, max = 60))]
+    pub key: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    pub severity: RuleSeverity,
+    pub check: RuleCheck,
+
+    #[validate(length(min = 1, max = 300))]
+    pub message: String,
+
+    pub is_active: Option<bool>,
+}
+
+// Only the fields sent are changed; the key stays
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct UpdateRuleSchema {
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+
+    pub severity: Option<RuleSeverity>,
+    pub check: Option<RuleCheck>,
+
+    #[validate(length(min = 1, max = 300))]
+    pub message: Option<String>,
+
+    pub is_active: Option<bool>,
+}
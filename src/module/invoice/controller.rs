@@ -0,0 +1,123 @@
+use bson::oid::ObjectId;
+use crate::environment::CompanyDetails;
+use crate::module::invoice::{
+    crud::InvoiceCrud,
+    interface::InvoiceService,
+    model::{Invoice, InvoiceKind},
+    pdf::render_invoice,
+    schema::InvoiceDocument,
+};
+use crate::module::order::{
+    crud::OrderCrud,
+    model::{Order, OrderStatus},
+};
+use crate::service::{email_service, email_templates};
+
+pub struct InvoiceController {
+    crud: InvoiceCrud,
+    order_crud: OrderCrud,
+    company: CompanyDetails,
+}
+
+impl InvoiceController {
+    pub fn new(crud: InvoiceCrud, order_crud: OrderCrud) -> Self {
+        Self { crud, order_crud, company: CompanyDetails::from_env() }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    fn parse_kind(kind: &str) -> Result<InvoiceKind, String> {
+        InvoiceKind::parse(kind).ok_or_else(|| format!("Unknown document type: {}", kind))
+    }
+
+    // The order's invoice or receipt, issuing it with the next number the first time
+    async fn issue(&self, order: &Order, kind: InvoiceKind) -> Result<Invoice, String> {
+        if matches!(order.status, OrderStatus::PendingPayment | OrderStatus::Cancelled) {
+            return Err("Invoices and receipts are only available for paid orders".to_string());
+        }
+
+        let order_id = order.id.ok_or_else(|| "Order has no ID".to_string())?;
+        if let Some(invoice) = self.crud.find_for_order(&order_id, kind).await? {
+            return Ok(invoice);
+        }
+
+        match self.crud.create_numbered(kind, |sequence| Invoice::from_order(order, kind, sequence)).await? {
+            Some(invoice) => Ok(invoice),
+            // A concurrent request issued it first
+            None => self.crud.find_for_order(&order_id, kind).await?
+                .ok_or_else(|| format!("Failed to issue {}", kind.as_str())),
+        }
+    }
+
+    fn render(&self, invoice: &Invoice) -> InvoiceDocument {
+        InvoiceDocument {
+            invoice_number: invoice.invoice_number.clone(),
+            filename: format!("{}.pdf", invoice.invoice_number),
+            data: render_invoice(invoice, &self.company),
+        }
+    }
+
+    async fn user_document(&self, user_id: &str, order_id: &str, kind: &str) -> Result<Option<(InvoiceKind, Order, InvoiceDocument)>, String> {
+        let kind = Self::parse_kind(kind)?;
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let order_oid = Self::parse_id(order_id, "order")?;
+
+        let order = match self.order_crud.get_user_order(&order_oid, &user_oid).await? {
+            Some(order) => order,
+            None => return Ok(None),
+        };
+        let invoice = self.issue(&order, kind).await?;
+        Ok(Some((kind, order, self.render(&invoice))))
+    }
+}
+
+#[async_trait::async_trait]
+impl InvoiceService for InvoiceController {
+    async fn get_user_document(&self, user_id: &str, order_id: &str, kind: &str) -> Result<Option<InvoiceDocument>, String> {
+        Ok(self.user_document(user_id, order_id, kind).await?.map(|(_, _, document)| document))
+    }
+
+    async fn email_user_document(&self, user_id: &str, email: &str, name: &str, order_id: &str, kind: &str) -> Result<Option<InvoiceDocument>, String> {
+        let (kind, order, document) = match self.user_document(user_id, order_id, kind).await? {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+
+        let (subject, body) = email_templates::get_invoice_email_template(
+            name,
+            kind.as_str(),
+            &document.invoice_number,
+            &order.order_number,
+        );
+        let attachment = email_service::EmailAttachment {
+            filename: document.filename.clone(),
+            content_type: "application/pdf".to_string(),
+            data: document.data.clone(),
+        };
+        let email = email.to_string();
+        let name = name.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = email_service::send_email_with_attachments(&email, &name, &subject, &body, vec![attachment]) {
+                eprintln!("Failed to send invoice email: {}", e);
+            }
+        });
+
+        Ok(Some(document))
+    }
+
+    async fn get_document(&self, order_id: &str, kind: &str) -> Result<Option<InvoiceDocument>, String> {
+        let kind = Self::parse_kind(kind)?;
+        let order_oid = Self::parse_id(order_id, "order")?;
+
+        match self.order_crud.get_order(&order_oid).await? {
+            Some(order) => {
+                let invoice = self.issue(&order, kind).await?;
+                Ok(Some(self.render(&invoice)))
+            }
+            None => Ok(None),
+        }
+    }
+}
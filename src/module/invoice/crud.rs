@@ -0,0 +1,99 @@
+use bson::{doc, oid::ObjectId, Document};
+use mongodb::{
+    error::{Error, TRANSIENT_TRANSACTION_ERROR, UNKNOWN_TRANSACTION_COMMIT_RESULT},
+    options::{FindOneAndUpdateOptions, ReturnDocument},
+    Client, ClientSession, Collection, Database,
+};
+use crate::module::invoice::model::{Invoice, InvoiceKind};
+use crate::service::migration_service;
+
+// Times a numbering transaction is retried after a write conflict
+const MAX_ATTEMPTS: u32 = 5;
+
+#[derive(Clone)]
+pub struct InvoiceCrud {
+    client: Client,
+    collection: Collection<Invoice>,
+    counters: Collection<Document>,
+}
+
+impl InvoiceCrud {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            client: db.client().clone(),
+            collection: db.collection("invoices"),
+            counters: db.collection("counters"),
+        }
+    }
+
+    pub async fn find_for_order(&self, order_id: &ObjectId, kind: InvoiceKind) -> Result<Option<Invoice>, String> {
+        match self.collection.find_one(doc! { "order_id": order_id, "kind": kind.as_str() }).await {
+            Ok(invoice) => Ok(invoice),
+            Err(e) => Err(format!("Failed to retrieve invoice: {}", e))
+        }
+    }
+
+    // Take the next number in the kind's sequence and save the invoice under
+    // it, in one transaction. If the insert fails, e.g. because the order's
+    // invoice was just issued by another request, the number goes back with
+    // the aborted transaction, so the sequence has no gaps. Returns None when
+    // the order already has this kind of document.
+    pub async fn create_numbered(&self, kind: InvoiceKind, build: impl Fn(i64) -> Invoice) -> Result<Option<Invoice>, String> {
+        let mut session = self.client.start_session().await
+            .map_err(|e| format!("Failed to start invoice transaction: {}", e))?;
+
+        for _ in 0..MAX_ATTEMPTS {
+            session.start_transaction().await
+                .map_err(|e| format!("Failed to start invoice transaction: {}", e))?;
+
+            let result = match self.number_and_insert(&mut session, kind, &build).await {
+                Ok(invoice) => Self::commit(&mut session).await.map(|_| invoice),
+                Err(e) => {
+                    session.abort_transaction().await.ok();
+                    Err(e)
+                }
+            };
+            match result {
+                Ok(invoice) => return Ok(Some(invoice)),
+                // Another request numbered a document at the same time
+                Err(e) if e.contains_label(TRANSIENT_TRANSACTION_ERROR) => continue,
+                Err(e) if migration_service::is_duplicate_key(&e, "order_id") => return Ok(None),
+                Err(e) => return Err(format!("Failed to issue {}: {}", kind.as_str(), e)),
+            }
+        }
+        Err(format!("Failed to issue {}: too many concurrent requests, please retry", kind.as_str()))
+    }
+
+    async fn number_and_insert(
+        &self,
+        session: &mut ClientSession,
+        kind: InvoiceKind,
+        build: &impl Fn(i64) -> Invoice,
+    ) -> Result<Invoice, Error> {
+        let options = FindOneAndUpdateOptions::builder()
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .build();
+        let counter = self.counters
+            .find_one_and_update(doc! { "_id": kind.as_str() }, doc! { "$inc": { "seq": 1_i64 } })
+            .with_options(options)
+            .session(&mut *session)
+            .await?;
+        let sequence = counter.and_then(|counter| counter.get_i64("seq").ok()).unwrap_or(1);
+
+        let mut invoice = build(sequence);
+        let result = self.collection.insert_one(&invoice).session(&mut *session).await?;
+        invoice.id = result.inserted_id.as_object_id();
+        Ok(invoice)
+    }
+
+    // A commit whose outcome is unknown can safely be sent again
+    async fn commit(session: &mut ClientSession) -> Result<(), Error> {
+        loop {
+            match session.commit_transaction().await {
+                Err(e) if e.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) => continue,
+                result => return result,
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use crate::module::invoice::schema::InvoiceDocument;
+
+#[async_trait::async_trait]
+pub trait InvoiceService {
+    // Customer: `kind` is "invoice" or "receipt"; issued on first request
+    async fn get_user_document(&self, user_id: &str, order_id: &str, kind: &str) -> Result<Option<InvoiceDocument>, String>;
+    async fn email_user_document(&self, user_id: &str, email: &str, name: &str, order_id: &str, kind: &str) -> Result<Option<InvoiceDocument>, String>;
+
+    // Admin
+    async fn get_document(&self, order_id: &str, kind: &str) -> Result<Option<InvoiceDocument>, String>;
+}
@@ -0,0 +1,19 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("invoice", 1, "invoice number and order indexes", "invoices", vec![
+            // One invoice and one receipt per order
+            IndexModel::builder()
+                .keys(doc! { "order_id": 1, "kind": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "invoice_number": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+        ]),
+    ]
+}
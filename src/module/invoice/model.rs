@@ -0,0 +1,136 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::module::order::model::{Order, OrderStatus, ShippingAddress};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceKind {
+    Invoice,
+    Receipt,
+}
+
+impl InvoiceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InvoiceKind::Invoice => "invoice",
+            InvoiceKind::Receipt => "receipt",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "invoice" => Some(InvoiceKind::Invoice),
+            "receipt" => Some(InvoiceKind::Receipt),
+            _ => None,
+        }
+    }
+
+    // Each kind is numbered in its own gap-free sequence
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            InvoiceKind::Invoice => "INV",
+            InvoiceKind::Receipt => "RCT",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            InvoiceKind::Invoice => "TAX INVOICE",
+            InvoiceKind::Receipt => "RECEIPT",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InvoiceLine {
+    pub name: String,
+    pub sku: String,
+    pub unit_price: i64, // In kobo
+    pub quantity: i32,
+    pub line_total: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaxLine {
+    pub label: String,
+    pub amount: i64, // In kobo
+}
+
+// An issued invoice or receipt. Once issued it never changes, so reprints
+// match what the customer first received.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Invoice {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub kind: InvoiceKind,
+    pub sequence: i64,
+    pub invoice_number: String,
+
+    pub order_id: ObjectId,
+    pub order_number: String,
+    pub user_id: ObjectId,
+    pub bill_to: ShippingAddress,
+
+    pub lines: Vec<InvoiceLine>,
+
+    // Totals in kobo
+    pub subtotal: i64,
+    pub shipping_fee: i64,
+    pub discount_total: i64,
+    pub tax_lines: Vec<TaxLine>,
+    pub total: i64,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub paid_at: Option<DateTime<Utc>>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub issued_at: DateTime<Utc>,
+}
+
+impl Invoice {
+    pub fn from_order(order: &Order, kind: InvoiceKind, sequence: i64) -> Self {
+        let paid_at = order.status_history.iter()
+            .find(|transition| transition.to == OrderStatus::Paid)
+            .map(|transition| transition.at);
+
+        Self {
+            id: None,
+            kind,
+            sequence,
+            invoice_number: format!("{}-{:06}", kind.prefix(), sequence),
+            order_id: order.id.unwrap_or_default(),
+            order_number: order.order_number.clone(),
+            user_id: order.user_id,
+            bill_to: order.shipping_address.clone(),
+            lines: order.items.iter()
+                .map(|item| InvoiceLine {
+                    name: item.name.clone(),
+                    sku: item.sku.clone(),
+                    unit_price: item.unit_price,
+                    quantity: item.quantity,
+                    line_total: item.line_total,
+                })
+                .collect(),
+            subtotal: order.subtotal,
+            shipping_fee: order.shipping_fee,
+            discount_total: order.discount_total,
+            // No VAT line on orders that weren't charged any
+            tax_lines: if order.tax_total != 0 {
+                let label = if order.prices_include_tax { "VAT (included)" } else { "VAT" };
+                vec![TaxLine { label: label.to_string(), amount: order.tax_total }]
+            } else {
+                Vec::new()
+            },
+            total: order.total,
+            paid_at,
+            issued_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::environment::CompanyDetails;
+use crate::module::invoice::model::{Invoice, InvoiceKind};
+use crate::service::currency_service::format_naira_code;
+use crate::service::pdf_service::{text_width, Font, PdfDocument, PAGE_HEIGHT, PAGE_WIDTH};
+
+const MARGIN: f32 = 50.0;
+const RIGHT: f32 = PAGE_WIDTH - MARGIN;
+const ROW_HEIGHT: f32 = 18.0;
+// Start a new page when the next row would run into the footer
+const BOTTOM_LIMIT: f32 = 110.0;
+
+// Table columns: description, SKU, quantity (right aligned), unit price and amount (right aligned)
+const COL_SKU: f32 = 300.0;
+const COL_QTY: f32 = 390.0;
+const COL_UNIT: f32 = 465.0;
+
+// Cut text down so it fits in `width`, marking the cut with "..."
+fn fit(text: &str, font: Font, size: f32, width: f32) -> String {
+    if text_width(text, font, size) <= width {
+        return text.to_string();
+    }
+    let mut fitted: String = text.to_string();
+    while !fitted.is_empty() && text_width(&format!("{}...", fitted), font, size) > width {
+        fitted.pop();
+    }
+    format!("{}...", fitted)
+}
+
+fn table_header(pdf: &mut PdfDocument, y: f32) {
+    pdf.fill_color(0.93, 0.93, 0.93);
+    pdf.fill_rect(MARGIN, y - 6.0, RIGHT - MARGIN, ROW_HEIGHT);
+    pdf.fill_color(0.0, 0.0, 0.0);
+    pdf.text(MARGIN + 4.0, y, Font::Bold, 9.0, "Description");
+    pdf.text(COL_SKU, y, Font::Bold, 9.0, "SKU");
+    pdf.text_right(COL_QTY, y, Font::Bold, 9.0, "Qty");
+    pdf.text_right(COL_UNIT, y, Font::Bold, 9.0, "Unit price");
+    pdf.text_right(RIGHT - 4.0, y, Font::Bold, 9.0, "Amount");
+}
+
+fn footer(pdf: &mut PdfDocument, company: &CompanyDetails) {
+    pdf.line(MARGIN, 70.0, RIGHT, 70.0, 0.5);
+    let mut details = vec![company.name.clone()];
+    if let Some(vat_number) = &company.vat_number {
+        details.push(format!("VAT/TIN: {}", vat_number));
+    }
+    if !company.email.is_empty() {
+        details.push(company.email.clone());
+    }
+    if !company.phone.is_empty() {
+        details.push(company.phone.clone());
+    }
+    pdf.fill_color(0.4, 0.4, 0.4);
+    pdf.text(MARGIN, 55.0, Font::Regular, 8.0, &details.join("  |  "));
+    pdf.text(MARGIN, 43.0, Font::Regular, 8.0, "Thank you for your business.");
+    pdf.fill_color(0.0, 0.0, 0.0);
+}
+
+/// Renders an invoice or receipt as a branded A4 PDF.
+pub fn render_invoice(invoice: &Invoice, company: &CompanyDetails) -> Vec<u8> {
+    let mut pdf = PdfDocument::new();
+
+    // Brand band
+    pdf.fill_color(0.11, 0.25, 0.45);
+    pdf.fill_rect(0.0, PAGE_HEIGHT - 12.0, PAGE_WIDTH, 12.0);
+    pdf.fill_color(0.0, 0.0, 0.0);
+
+    // Company details on the left, document details on the right
+    let mut y = PAGE_HEIGHT - 60.0;
+    pdf.text(MARGIN, y, Font::Bold, 18.0, &company.name);
+    pdf.text_right(RIGHT, y, Font::Bold, 16.0, invoice.kind.title());
+
+    let mut left = y - 18.0;
+    for line in company.address_lines.iter().chain([&company.email, &company.phone]) {
+        if !line.is_empty() {
+            pdf.text(MARGIN, left, Font::Regular, 9.0, line);
+            left -= 12.0;
+        }
+    }
+    if let Some(vat_number) = &company.vat_number {
+        pdf.text(MARGIN, left, Font::Regular, 9.0, &format!("VAT/TIN: {}", vat_number));
+        left -= 12.0;
+    }
+
+    let mut right = y - 18.0;
+    let number_label = match invoice.kind {
+        InvoiceKind::Invoice => "Invoice no.",
+        InvoiceKind::Receipt => "Receipt no.",
+    };
+    let mut facts = vec![
+        (number_label, invoice.invoice_number.clone()),
+        ("Date", invoice.issued_at.format("%d %b %Y").to_string()),
+        ("Order", invoice.order_number.clone()),
+    ];
+    if let Some(paid_at) = invoice.paid_at {
+        facts.push(("Paid", paid_at.format("%d %b %Y").to_string()));
+    }
+    for (label, value) in facts {
+        pdf.text_right(RIGHT - 110.0, right, Font::Bold, 9.0, label);
+        pdf.text_right(RIGHT, right, Font::Regular, 9.0, &value);
+        right -= 12.0;
+    }
+
+    // Bill to
+    y = left.min(right) - 20.0;
+    pdf.text(MARGIN, y, Font::Bold, 10.0, "Bill to");
+    y -= 14.0;
+    let address = &invoice.bill_to;
+    let mut bill_to = vec![address.full_name.clone(), address.address_line1.clone()];
+    if let Some(line2) = &address.address_line2 {
+        bill_to.push(line2.clone());
+    }
+    bill_to.push(format!("{}, {}", address.city, address.state));
+    bill_to.push(match &address.postal_code {
+        Some(postal_code) => format!("{} {}", address.country, postal_code),
+        None => address.country.clone(),
+    });
+    bill_to.push(address.phone_number.clone());
+    for line in bill_to {
+        pdf.text(MARGIN, y, Font::Regular, 9.0, &line);
+        y -= 12.0;
+    }
+
+    // Line items, continuing on new pages as needed
+    y -= 16.0;
+    table_header(&mut pdf, y);
+    y -= ROW_HEIGHT + 2.0;
+    for line in &invoice.lines {
+        if y < BOTTOM_LIMIT {
+            footer(&mut pdf, company);
+            pdf.new_page();
+            y = PAGE_HEIGHT - MARGIN;
+            table_header(&mut pdf, y);
+            y -= ROW_HEIGHT + 2.0;
+        }
+        pdf.text(MARGIN + 4.0, y, Font::Regular, 9.0, &fit(&line.name, Font::Regular, 9.0, COL_SKU - MARGIN - 12.0));
+        pdf.text(COL_SKU, y, Font::Regular, 9.0, &fit(&line.sku, Font::Regular, 9.0, COL_QTY - COL_SKU - 30.0));
+        pdf.text_right(COL_QTY, y, Font::Regular, 9.0, &line.quantity.to_string());
+        pdf.text_right(COL_UNIT, y, Font::Regular, 9.0, &format_naira_code(line.unit_price));
+        pdf.text_right(RIGHT - 4.0, y, Font::Regular, 9.0, &format_naira_code(line.line_total));
+        y -= ROW_HEIGHT;
+    }
+    pdf.line(MARGIN, y + 10.0, RIGHT, y + 10.0, 0.5);
+
+    // Totals
+    let mut totals = vec![
+        ("Subtotal".to_string(), invoice.subtotal),
+        ("Shipping".to_string(), invoice.shipping_fee),
+    ];
+    if invoice.discount_total != 0 {
+        totals.push(("Discount".to_string(), -invoice.discount_total));
+    }
+    for tax in &invoice.tax_lines {
+        totals.push((tax.label.clone(), tax.amount));
+    }
+
+    if y - (totals.len() as f32 + 2.0) * 14.0 < BOTTOM_LIMIT {
+        footer(&mut pdf, company);
+        pdf.new_page();
+        y = PAGE_HEIGHT - MARGIN;
+    }
+    y -= 8.0;
+    for (label, amount) in totals {
+        pdf.text_right(COL_UNIT, y, Font::Regular, 9.0, &label);
+        pdf.text_right(RIGHT - 4.0, y, Font::Regular, 9.0, &format_naira_code(amount));
+        y -= 14.0;
+    }
+    let total_label = match invoice.kind {
+        InvoiceKind::Invoice => "Total",
+        InvoiceKind::Receipt => "Amount paid",
+    };
+    pdf.text_right(COL_UNIT, y - 2.0, Font::Bold, 11.0, total_label);
+    pdf.text_right(RIGHT - 4.0, y - 2.0, Font::Bold, 11.0, &format_naira_code(invoice.total));
+
+    footer(&mut pdf, company);
+    pdf.to_bytes()
+}
@@ -0,0 +1,100 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::Authentication;
+use crate::module::invoice::{
+    controller::InvoiceController,
+    crud::InvoiceCrud,
+    interface::InvoiceService,
+    schema::InvoiceDocument,
+};
+use crate::module::order::crud::OrderCrud;
+
+// Invoice routes hang off an order, e.g. /orders/{id}/invoice or /orders/{id}/receipt
+const DOCUMENT_PATH: &str = "/{id}/{kind:invoice|receipt}";
+
+fn controller(crud: &web::Data<InvoiceCrud>, order_crud: &web::Data<OrderCrud>) -> InvoiceController {
+    InvoiceController::new(crud.get_ref().clone(), order_crud.get_ref().clone())
+}
+
+fn pdf_response(result: std::result::Result<Option<InvoiceDocument>, String>) -> HttpResponse {
+    match result {
+        Ok(Some(document)) => HttpResponse::Ok()
+            .content_type("application/pdf")
+            .insert_header((
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", document.filename),
+            ))
+            .body(document.data),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Order not found"
+        })),
+        Err(error) => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        })),
+    }
+}
+
+// GET invoice or receipt PDF handler
+async fn download_handler(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    crud: web::Data<InvoiceCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+    let (id, kind) = path.into_inner();
+
+    let result = controller(&crud, &order_crud).get_user_document(&claims.sub, &id, &kind).await;
+    Ok(pdf_response(result))
+}
+
+// POST email invoice or receipt handler
+async fn email_handler(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    crud: web::Data<InvoiceCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+    let (id, kind) = path.into_inner();
+    let name = format!("{} {}", claims.first_name, claims.last_name);
+
+    match controller(&crud, &order_crud).email_user_document(&claims.sub, &claims.email, &name, &id, &kind).await {
+        Ok(Some(document)) => Ok(HttpResponse::Accepted().json(serde_json::json!({
+            "message": format!("{} sent to {}", document.invoice_number, claims.email)
+        }))),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Order not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET invoice or receipt PDF handler (admin)
+async fn admin_download_handler(
+    path: web::Path<(String, String)>,
+    crud: web::Data<InvoiceCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let (id, kind) = path.into_inner();
+    let result = controller(&crud, &order_crud).get_document(&id, &kind).await;
+    Ok(pdf_response(result))
+}
+
+// Mounted inside the customer `/orders` scope
+pub fn order_invoice_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route(DOCUMENT_PATH, web::get().to(download_handler))
+        .route(&format!("{}/email", DOCUMENT_PATH), web::post().to(email_handler));
+}
+
+// Mounted inside the admin `/orders` scope
+pub fn admin_order_invoice_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route(DOCUMENT_PATH, web::get().to(admin_download_handler));
+}
@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+// A rendered invoice or receipt, ready to download or attach
+#[derive(Debug, Clone, Serialize)]
+pub struct InvoiceDocument {
+    pub invoice_number: String,
+    pub filename: String,
+    #[serde(skip)]
+    pub data: Vec<u8>,
+}
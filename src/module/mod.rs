@@ -4,3 +4,20 @@ pub mod banner;
 pub mod product;
 pub mod category;  // Add this
 pub mod brand; 
+
+pub mod cart;
+pub mod wishlist;
+pub mod order;
+pub mod payment;
+pub mod returns;
+pub mod invoice;
+pub mod shipping;
+pub mod promotion;
+pub mod tax;
+pub mod recovery;
+pub mod search;
+pub mod review;
+pub mod question;
+pub mod recommendation;
+pub mod comparison;
+pub mod compatibility;
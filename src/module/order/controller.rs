@@ -0,0 +1,407 @@
+use std::collections::HashMap;
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use validator::Validate;
+use crate::module::category::crud::CategoryCrud;
+use crate::module::cart::{
+    controller::CartController,
+    crud::CartCrud,
+    interface::CartService,
+    model::CartOwner,
+    schema::CartWarningKind,
+};
+use crate::module::order::{
+    crud::OrderCrud,
+    interface::OrderService,
+    model::{Order, OrderActor, OrderItem, OrderStatus, StatusTransition},
+    schema::{CheckoutItemSchema, CheckoutSchema, OrderListQuery, OrderListResponse, UpdateOrderStatusSchema},
+};
+use crate::module::product::{
+    crud::{stock_reservation_ttl, ProductCrud},
+    model::{Product, ReservedItem},
+};
+use crate::module::promotion::{controller::PromotionController, crud::PromotionCrud};
+use crate::module::shipping::{controller::ShippingController, crud::ShippingCrud};
+use crate::module::tax::{calculator::TaxCalculator, controller::TaxController, crud::TaxCrud};
+use crate::service::pagination_service::paginate;
+
+pub struct OrderController {
+    crud: OrderCrud,
+    product_crud: ProductCrud,
+    cart_controller: CartController,
+    promotion_controller: PromotionController,
+    tax_controller: TaxController,
+    shipping_controller: ShippingController,
+}
+
+impl OrderController {
+    pub fn new(
+        crud: OrderCrud,
+        product_crud: ProductCrud,
+        cart_crud: CartCrud,
+        promotion_crud: PromotionCrud,
+        tax_crud: TaxCrud,
+        category_crud: CategoryCrud,
+        shipping_crud: ShippingCrud,
+    ) -> Self {
+        let cart_controller = CartController::new(cart_crud, product_crud.clone());
+        let promotion_controller = PromotionController::new(promotion_crud, product_crud.clone());
+        let tax_controller = TaxController::new(tax_crud, category_crud);
+        let shipping_controller = ShippingController::new(shipping_crud, product_crud.clone());
+        Self { crud, product_crud, cart_controller, promotion_controller, tax_controller, shipping_controller }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    // The cart lines to order. Each cart warning is only reported once, so a
+    // cart that just changed (lines dropped, quantities cut, new prices) is
+    // refused; the shopper sees why and the next attempt goes through.
+    async fn items_from_cart(&self, user_id: ObjectId) -> Result<Vec<CheckoutItemSchema>, String> {
+        let cart = self.cart_controller.get_cart(&CartOwner::User(user_id)).await?;
+
+        if !cart.warnings.is_empty() {
+            let messages: Vec<String> = cart.warnings.iter().map(|warning| warning.message.clone()).collect();
+            let reason = if cart.warnings.iter().any(|warning| warning.kind == CartWarningKind::ProductUnavailable) {
+                "Cart has items that can't be ordered"
+            } else {
+                "Your cart was updated, please review it"
+            };
+            return Err(format!("{}: {}", reason, messages.join("; ")));
+        }
+        if cart.items.is_empty() {
+            return Err("Cart is empty".to_string());
+        }
+
+        Ok(cart.items.into_iter()
+            .map(|item| CheckoutItemSchema { product_id: item.product_id, variant_id: item.variant_id, quantity: item.quantity })
+            .collect())
+    }
+
+    // Snapshot each product's name, SKU, current price and tax into order lines
+    async fn build_order_items(
+        &self,
+        requested: Vec<CheckoutItemSchema>,
+        calculator: &TaxCalculator,
+    ) -> Result<Vec<OrderItem>, String> {
+        // Combine repeated products (and variants) into one line
+        let mut quantities: Vec<(ObjectId, Option<ObjectId>, i32)> = Vec::new();
+        for item in requested {
+            let product_id = Self::parse_id(&item.product_id, "product")?;
+            let variant_id = item.variant_id.as_deref()
+                .map(|id| Self::parse_id(id, "variant"))
+                .transpose()?;
+            match quantities.iter_mut().find(|(id, variant, _)| *id == product_id && *variant == variant_id) {
+                Some((_, _, quantity)) => *quantity += item.quantity,
+                None => quantities.push((product_id, variant_id, item.quantity)),
+            }
+        }
+
+        let ids: Vec<ObjectId> = quantities.iter().map(|(id, _, _)| *id).collect();
+        let products: HashMap<ObjectId, Product> = self.product_crud.get_products_by_ids(&ids).await?
+            .into_iter()
+            .filter_map(|product| product.id.map(|id| (id, product)))
+            .collect();
+        let tax_classes = self.tax_controller.tax_classes(&products.values().collect::<Vec<_>>()).await?;
+
+        let now = Utc::now();
+        let mut items = Vec::new();
+        for (product_id, variant_id, quantity) in quantities {
+            let product = products.get(&product_id)
+                .filter(|product| product.is_active)
+                .ok_or_else(|| format!("Product {} is not available", product_id.to_hex()))?;
+            let option = product.purchase_option(variant_id.as_ref())?;
+
+            if quantity > option.stock_quantity() {
+                return Err(format!("Only {} of {} in stock", option.stock_quantity(), option.name()));
+            }
+
+            let unit_price = option.unit_price();
+            let line_total = unit_price * quantity as i64;
+            let tax_class = tax_classes.get(&product_id)
+                .ok_or_else(|| format!("No tax class for {}", option.name()))?;
+            let tax = calculator.breakdown(line_total, tax_class, now)?.tax;
+            items.push(OrderItem {
+                product_id,
+                variant_id,
+                name: option.name(),
+                sku: option.sku().to_string(),
+                unit_price,
+                quantity,
+                line_total,
+                tax,
+            });
+        }
+        Ok(items)
+    }
+
+    // Apply a state machine move and record who made it
+    async fn transition(
+        &self,
+        order: Order,
+        next: OrderStatus,
+        actor: OrderActor,
+        note: Option<String>,
+    ) -> Result<Order, String> {
+        if !order.status.can_transition_to(next) {
+            return Err(format!(
+                "Cannot move order from {} to {}",
+                order.status.as_str(),
+                next.as_str()
+            ));
+        }
+
+        let id = order.id.ok_or_else(|| "Order has no ID".to_string())?;
+        let previous = order.status;
+
+        // Stock held at checkout must be committed before the order counts as
+        // paid; if it can't be, the order stays unpaid rather than oversell
+        if next == OrderStatus::Paid {
+            self.product_crud.commit_reservation(&order.order_number).await?;
+        }
+
+        let transition = StatusTransition {
+            from: Some(previous),
+            to: next,
+            actor,
+            note,
+            at: Utc::now(),
+        };
+
+        let order = self.crud.transition_status(&id, previous, transition).await?
+            .ok_or_else(|| "Order status changed concurrently, please retry".to_string())?;
+
+        // Goods that never left the warehouse go back on sale. Refunds after
+        // delivery are restocked by the returns inspection instead.
+        let restock = match next {
+            OrderStatus::Cancelled => true,
+            OrderStatus::Refunded => matches!(previous, OrderStatus::Paid | OrderStatus::Processing),
+            _ => false,
+        };
+        if restock && let Err(e) = self.product_crud.release_reservation(&order.order_number).await {
+            eprintln!("Failed to return stock for {}: {}", order.order_number, e);
+        }
+
+        // A cancelled order no longer uses up its coupon
+        if next == OrderStatus::Cancelled && order.coupon_code.is_some()
+            && let Err(e) = self.promotion_controller.void_redemptions(&order.order_number).await
+        {
+            eprintln!("Failed to void coupon redemption for {}: {}", order.order_number, e);
+        }
+
+        Ok(order)
+    }
+
+    // Cancel orders whose payment window has run out, releasing their stock.
+    // The order is cancelled first, only while still awaiting payment, so a
+    // payment landing at the same time keeps its stock. Returns how many
+    // orders were cancelled.
+    pub async fn expire_unpaid_orders(&self) -> Result<usize, String> {
+        let expired = self.product_crud.find_expired_reservations().await?;
+
+        let mut cancelled = 0;
+        for order_number in expired {
+            let order = match self.crud.get_order_by_number(&order_number).await? {
+                Some(order) => order,
+                // Left behind by a checkout that failed before the order was saved
+                None => {
+                    if let Err(e) = self.product_crud.release_reservation(&order_number).await {
+                        eprintln!("Failed to release stock for {}: {}", order_number, e);
+                    }
+                    continue;
+                }
+            };
+
+            let result = match order.status {
+                // Releases the stock once the order is cancelled
+                OrderStatus::PendingPayment => self
+                    .transition(order, OrderStatus::Cancelled, OrderActor::system(), Some("Payment window expired".to_string()))
+                    .await
+                    .map(|_| cancelled += 1),
+                OrderStatus::Cancelled => self.product_crud.release_reservation(&order_number).await.map(|_| ()),
+                // Paid, but the commit didn't stick; the stock is the customer's
+                _ => self.product_crud.commit_reservation(&order_number).await.map(|_| ()),
+            };
+            if let Err(e) = result {
+                eprintln!("Failed to expire order {}: {}", order_number, e);
+            }
+        }
+        Ok(cancelled)
+    }
+}
+
+#[async_trait::async_trait]
+impl OrderService for OrderController {
+    async fn checkout(&self, user_id: &str, data: CheckoutSchema) -> Result<Order, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let from_cart = data.items.is_none();
+
+        let requested = match data.items {
+            Some(items) if items.is_empty() => return Err("No items to order".to_string()),
+            Some(items) => items,
+            None => self.items_from_cart(user_oid).await?,
+        };
+
+        let calculator = self.tax_controller.calculator().await?;
+        let items = self.build_order_items(requested, &calculator).await?;
+        let mut order = Order::new(
+            user_oid,
+            items,
+            data.shipping_address.into(),
+            calculator.prices_include_tax(),
+            OrderActor::user(user_id),
+        );
+
+        // Quote delivery to the order's address and charge the chosen service
+        let lines: Vec<(ObjectId, Option<ObjectId>, i32)> = order.items.iter()
+            .map(|item| (item.product_id, item.variant_id, item.quantity))
+            .collect();
+        let quote = self.shipping_controller
+            .quote_items(&order.shipping_address.state, Some(&order.shipping_address.city), &lines)
+            .await?;
+        let delivery = match &data.delivery_service {
+            Some(code) => quote.options.iter()
+                .find(|option| option.code.eq_ignore_ascii_case(code.trim()))
+                .ok_or_else(|| format!("Delivery service {} is not available to {}", code, quote.zone_name))?,
+            None => quote.options.iter()
+                .min_by_key(|option| option.price)
+                .ok_or_else(|| format!("No delivery option to {}", quote.zone_name))?,
+        };
+        order.delivery_service = Some(delivery.code.clone());
+        order.shipping_fee = delivery.price;
+        order.recalculate_totals();
+
+        // Price the coupon against the order's lines before anything is held
+        let coupon = match &data.coupon_code {
+            Some(code) => {
+                let lines = order.items.iter().map(|item| (item.product_id, item.variant_id, item.quantity)).collect();
+                let (coupon, breakdown) = self.promotion_controller.evaluate(code, Some(&user_oid), lines).await?;
+                order.coupon_code = Some(coupon.code.clone());
+                order.discount_total = breakdown.discount_total;
+                if breakdown.free_shipping {
+                    order.shipping_fee = 0;
+                }
+                order.recalculate_totals();
+                Some(coupon)
+            }
+            None => None,
+        };
+
+        // Hold the stock before the order exists, so two checkouts can't both get the last unit
+        let reserved: Vec<ReservedItem> = order.items.iter()
+            .map(|item| ReservedItem { product_id: item.product_id, variant_id: item.variant_id, quantity: item.quantity })
+            .collect();
+        self.product_crud.reserve_stock(&order.order_number, &reserved, stock_reservation_ttl()).await?;
+
+        let order_number = order.order_number.clone();
+        let redeemed = match &coupon {
+            Some(coupon) => self.promotion_controller
+                .redeem(coupon, user_oid, &order_number, order.discount_total)
+                .await
+                .map(|_| ()),
+            None => Ok(()),
+        };
+        let order = match redeemed {
+            Ok(()) => self.crud.create_order(order).await,
+            Err(e) => Err(e),
+        };
+        let order = match order {
+            Ok(order) => order,
+            Err(e) => {
+                if let Err(release_error) = self.product_crud.release_reservation(&order_number).await {
+                    eprintln!("Failed to release stock for {}: {}", order_number, release_error);
+                }
+                if coupon.is_some() && let Err(void_error) = self.promotion_controller.void_redemptions(&order_number).await {
+                    eprintln!("Failed to void coupon redemption for {}: {}", order_number, void_error);
+                }
+                return Err(e);
+            }
+        };
+
+        // The order now holds the items, so the cart can go
+        if from_cart && let Err(e) = self.cart_controller.clear_cart(&CartOwner::User(user_oid)).await {
+            eprintln!("Failed to clear cart after checkout: {}", e);
+        }
+
+        Ok(order)
+    }
+
+    async fn get_user_orders(&self, user_id: &str, query: OrderListQuery) -> Result<OrderListResponse, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let (orders, total) = self.crud
+            .find_orders(doc! { "user_id": user_oid }, skip, limit)
+            .await?;
+
+        Ok(OrderListResponse { orders, total, page, limit })
+    }
+
+    async fn get_user_order(&self, user_id: &str, id: &str) -> Result<Option<Order>, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let id = Self::parse_id(id, "order")?;
+        self.crud.get_user_order(&id, &user_oid).await
+    }
+
+    async fn cancel_order(&self, user_id: &str, id: &str) -> Result<Order, String> {
+        let order = self.get_user_order(user_id, id).await?
+            .ok_or_else(|| "Order not found".to_string())?;
+
+        // Customers can only back out before paying; later cancellations go through support
+        if order.status != OrderStatus::PendingPayment {
+            return Err("Only orders awaiting payment can be cancelled".to_string());
+        }
+
+        self.transition(order, OrderStatus::Cancelled, OrderActor::user(user_id), None).await
+    }
+
+    async fn list_orders(&self, query: OrderListQuery) -> Result<OrderListResponse, String> {
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let mut filter = doc! {};
+        if let Some(status) = &query.status {
+            let status = OrderStatus::parse(status)
+                .ok_or_else(|| format!("Unknown order status: {}", status))?;
+            filter.insert("status", status.as_str());
+        }
+        if let Some(user_id) = &query.user_id {
+            filter.insert("user_id", Self::parse_id(user_id, "user")?);
+        }
+        if query.from.is_some() || query.to.is_some() {
+            let mut range = doc! {};
+            if let Some(from) = query.from {
+                range.insert("$gte", bson::DateTime::from_chrono(from));
+            }
+            if let Some(to) = query.to {
+                range.insert("$lte", bson::DateTime::from_chrono(to));
+            }
+            filter.insert("created_at", range);
+        }
+
+        let (orders, total) = self.crud.find_orders(filter, skip, limit).await?;
+        Ok(OrderListResponse { orders, total, page, limit })
+    }
+
+    async fn get_order(&self, id: &str) -> Result<Option<Order>, String> {
+        let id = Self::parse_id(id, "order")?;
+        self.crud.get_order(&id).await
+    }
+
+    async fn update_status(&self, id: &str, data: UpdateOrderStatusSchema, actor: OrderActor) -> Result<Order, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let next = OrderStatus::parse(&data.status)
+            .ok_or_else(|| format!("Unknown order status: {}", data.status))?;
+        let order = self.get_order(id).await?
+            .ok_or_else(|| "Order not found".to_string())?;
+
+        self.transition(order, next, actor, data.note).await
+    }
+}
@@ -0,0 +1,150 @@
+use bson::{doc, oid::ObjectId, Document};
+use chrono::{DateTime, Utc};
+use mongodb::{
+    options::{FindOneAndUpdateOptions, FindOneOptions, FindOptions, ReturnDocument},
+    Collection, Database,
+};
+use futures_util::TryStreamExt;
+use crate::module::order::model::{Order, OrderStatus, StatusTransition};
+
+#[derive(Clone)]
+pub struct OrderCrud {
+    collection: Collection<Order>,
+}
+
+impl OrderCrud {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("orders");
+        Self { collection }
+    }
+
+    pub async fn create_order(&self, mut order: Order) -> Result<Order, String> {
+        match self.collection.insert_one(&order).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    order.id = Some(id);
+                }
+                Ok(order)
+            }
+            Err(e) => Err(format!("Failed to create order: {}", e))
+        }
+    }
+
+    pub async fn get_order(&self, id: &ObjectId) -> Result<Option<Order>, String> {
+        match self.collection.find_one(doc! { "_id": id }).await {
+            Ok(order) => Ok(order),
+            Err(e) => Err(format!("Failed to retrieve order: {}", e))
+        }
+    }
+
+    pub async fn get_order_by_number(&self, order_number: &str) -> Result<Option<Order>, String> {
+        match self.collection.find_one(doc! { "order_number": order_number }).await {
+            Ok(order) => Ok(order),
+            Err(e) => Err(format!("Failed to retrieve order: {}", e))
+        }
+    }
+
+    // The user's first order placed since `since`, ignoring cancelled ones
+    pub async fn find_user_order_since(&self, user_id: &ObjectId, since: DateTime<Utc>) -> Result<Option<Order>, String> {
+        let filter = doc! {
+            "user_id": user_id,
+            "status": { "$ne": OrderStatus::Cancelled.as_str() },
+            "created_at": { "$gte": bson::DateTime::from_chrono(since) },
+        };
+        let options = FindOneOptions::builder().sort(doc! { "created_at": 1 }).build();
+
+        match self.collection.find_one(filter).with_options(options).await {
+            Ok(order) => Ok(order),
+            Err(e) => Err(format!("Failed to retrieve order: {}", e))
+        }
+    }
+
+    // The user's most recent delivered order that included the product
+    pub async fn find_delivered_purchase(&self, user_id: &ObjectId, product_id: &ObjectId) -> Result<Option<Order>, String> {
+        let filter = doc! {
+            "user_id": user_id,
+            "status": OrderStatus::Delivered.as_str(),
+            "items.product_id": product_id,
+        };
+        let options = FindOneOptions::builder().sort(doc! { "created_at": -1 }).build();
+
+        match self.collection.find_one(filter).with_options(options).await {
+            Ok(order) => Ok(order),
+            Err(e) => Err(format!("Failed to retrieve order: {}", e))
+        }
+    }
+
+    pub async fn get_user_order(&self, id: &ObjectId, user_id: &ObjectId) -> Result<Option<Order>, String> {
+        match self.collection.find_one(doc! { "_id": id, "user_id": user_id }).await {
+            Ok(order) => Ok(order),
+            Err(e) => Err(format!("Failed to retrieve order: {}", e))
+        }
+    }
+
+    // Newest first, with the total number of matches for pagination
+    pub async fn find_orders(&self, filter: Document, skip: u64, limit: i64) -> Result<(Vec<Order>, u64), String> {
+        let total = self.collection.count_documents(filter.clone()).await
+            .map_err(|e| format!("Failed to count orders: {}", e))?;
+
+        let options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .skip(skip)
+            .limit(limit)
+            .build();
+
+        match self.collection.find(filter).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut orders = Vec::new();
+                while let Some(order) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve orders: {}", e))? {
+                    orders.push(order);
+                }
+                Ok((orders, total))
+            }
+            Err(e) => Err(format!("Failed to retrieve orders: {}", e))
+        }
+    }
+
+    // Bump the order's returns version, but only from `version`. Returns false
+    // when another return was opened against the order since it was read.
+    pub async fn bump_returns_version(&self, id: &ObjectId, version: i64) -> Result<bool, String> {
+        // Orders from before returns were versioned have no field yet
+        let current = if version == 0 { bson::bson!({ "$in": [0_i64, null] }) } else { bson::bson!(version) };
+        let result = self.collection
+            .update_one(
+                doc! { "_id": id, "returns_version": current },
+                doc! { "$inc": { "returns_version": 1_i64 } },
+            )
+            .await
+            .map_err(|e| format!("Failed to update order: {}", e))?;
+        Ok(result.modified_count > 0)
+    }
+
+    // Move an order to a new status, but only if it is still in the status the
+    // caller checked, so two concurrent updates can't both succeed.
+    pub async fn transition_status(
+        &self,
+        id: &ObjectId,
+        from: OrderStatus,
+        transition: StatusTransition,
+    ) -> Result<Option<Order>, String> {
+        let transition_doc = bson::to_bson(&transition)
+            .map_err(|e| format!("Failed to encode status change: {}", e))?;
+        let update = doc! {
+            "$set": {
+                "status": transition.to.as_str(),
+                "updated_at": bson::DateTime::from_chrono(Utc::now()),
+            },
+            "$push": { "status_history": transition_doc },
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(doc! { "_id": id, "status": from.as_str() }, update)
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to update order status: {}", e))
+    }
+}
@@ -0,0 +1,20 @@
+use crate::module::order::{
+    model::{Order, OrderActor},
+    schema::{CheckoutSchema, OrderListQuery, OrderListResponse, UpdateOrderStatusSchema},
+};
+
+#[async_trait::async_trait]
+pub trait OrderService {
+    // Turn the user's cart, or an explicit item list, into an order
+    async fn checkout(&self, user_id: &str, data: CheckoutSchema) -> Result<Order, String>;
+
+    // Customer order history
+    async fn get_user_orders(&self, user_id: &str, query: OrderListQuery) -> Result<OrderListResponse, String>;
+    async fn get_user_order(&self, user_id: &str, id: &str) -> Result<Option<Order>, String>;
+    async fn cancel_order(&self, user_id: &str, id: &str) -> Result<Order, String>;
+
+    // Admin
+    async fn list_orders(&self, query: OrderListQuery) -> Result<OrderListResponse, String>;
+    async fn get_order(&self, id: &str) -> Result<Option<Order>, String>;
+    async fn update_status(&self, id: &str, data: UpdateOrderStatusSchema, actor: OrderActor) -> Result<Order, String>;
+}
@@ -0,0 +1,20 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("order", 1, "order number and listing indexes", "orders", vec![
+            IndexModel::builder()
+                .keys(doc! { "order_number": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "user_id": 1, "created_at": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "status": 1, "created_at": -1 })
+                .build(),
+        ]),
+    ]
+}
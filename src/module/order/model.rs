@@ -0,0 +1,242 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::module::tax::calculator::round_div;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    PendingPayment,
+    Paid,
+    Processing,
+    Shipped,
+    Delivered,
+    Cancelled,
+    Refunded,
+}
+
+impl OrderStatus {
+    // The order state machine: every allowed move from one status to the next
+    pub fn can_transition_to(&self, next: OrderStatus) -> bool {
+        use OrderStatus::*;
+
+        matches!(
+            (self, next),
+            (PendingPayment, Paid)
+                | (PendingPayment, Cancelled)
+                | (Paid, Processing)
+                | (Paid, Cancelled)
+                | (Paid, Refunded)
+                | (Processing, Shipped)
+                | (Processing, Cancelled)
+                | (Processing, Refunded)
+                | (Shipped, Delivered)
+                | (Delivered, Refunded)
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::PendingPayment => "pending_payment",
+            OrderStatus::Paid => "paid",
+            OrderStatus::Processing => "processing",
+            OrderStatus::Shipped => "shipped",
+            OrderStatus::Delivered => "delivered",
+            OrderStatus::Cancelled => "cancelled",
+            OrderStatus::Refunded => "refunded",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending_payment" => Some(OrderStatus::PendingPayment),
+            "paid" => Some(OrderStatus::Paid),
+            "processing" => Some(OrderStatus::Processing),
+            "shipped" => Some(OrderStatus::Shipped),
+            "delivered" => Some(OrderStatus::Delivered),
+            "cancelled" => Some(OrderStatus::Cancelled),
+            "refunded" => Some(OrderStatus::Refunded),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorRole {
+    User,
+    Admin,
+    System,
+}
+
+// Who caused a status change
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderActor {
+    pub role: ActorRole,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+impl OrderActor {
+    pub fn user(id: &str) -> Self {
+        Self { role: ActorRole::User, id: Some(id.to_string()) }
+    }
+
+    pub fn admin(id: &str) -> Self {
+        Self { role: ActorRole::Admin, id: Some(id.to_string()) }
+    }
+
+    // Changes made by background jobs rather than a person
+    pub fn system() -> Self {
+        Self { role: ActorRole::System, id: None }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StatusTransition {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<OrderStatus>,
+    pub to: OrderStatus,
+    pub actor: OrderActor,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub at: DateTime<Utc>,
+}
+
+// Snapshot of a product at the time of purchase; later product edits don't change it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderItem {
+    pub product_id: ObjectId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant_id: Option<ObjectId>,
+    pub name: String,
+    pub sku: String,
+    pub unit_price: i64,  // In kobo
+    pub quantity: i32,
+    pub line_total: i64,  // In kobo
+    // Tax on the line before any coupon, in kobo
+    #[serde(default)]
+    pub tax: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShippingAddress {
+    pub full_name: String,
+    pub phone_number: String,
+    pub address_line1: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address_line2: Option<String>,
+    pub city: String,
+    pub state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    pub country: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Order {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub order_number: String,
+    pub user_id: ObjectId,
+
+    pub items: Vec<OrderItem>,
+    pub shipping_address: ShippingAddress,
+    // Delivery service the shipping fee was quoted for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivery_service: Option<String>,
+
+    // Totals in kobo
+    pub subtotal: i64,
+    pub shipping_fee: i64,
+    pub discount_total: i64,
+    pub tax_total: i64,
+    pub total: i64,
+    // Whether `tax_total` is already inside the line prices or added on top
+    #[serde(default)]
+    pub prices_include_tax: bool,
+
+    // Coupon applied at checkout; its discount is in `discount_total`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coupon_code: Option<String>,
+
+    pub status: OrderStatus,
+    pub status_history: Vec<StatusTransition>,
+
+    // Bumped each time a return is opened against the order, so returns
+    // opened at the same time can't take more than was bought
+    #[serde(default)]
+    pub returns_version: i64,
+
+    // Timestamps
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Order {
+    pub fn new(
+        user_id: ObjectId,
+        items: Vec<OrderItem>,
+        shipping_address: ShippingAddress,
+        prices_include_tax: bool,
+        actor: OrderActor,
+    ) -> Self {
+        let now = Utc::now();
+        let subtotal = items.iter().map(|item| item.line_total).sum();
+        let order_number = format!(
+            "ORD-{}-{}",
+            now.format("%Y%m%d"),
+            Uuid::new_v4().simple().to_string()[..8].to_uppercase()
+        );
+
+        let mut order = Self {
+            id: None,
+            order_number,
+            user_id,
+            items,
+            shipping_address,
+            delivery_service: None,
+            subtotal,
+            shipping_fee: 0,
+            discount_total: 0,
+            tax_total: 0,
+            total: subtotal,
+            prices_include_tax,
+            coupon_code: None,
+            status: OrderStatus::PendingPayment,
+            status_history: vec![StatusTransition {
+                from: None,
+                to: OrderStatus::PendingPayment,
+                actor,
+                note: None,
+                at: now,
+            }],
+            returns_version: 0,
+            created_at: now,
+            updated_at: now,
+        };
+        order.recalculate_totals();
+        order
+    }
+
+    // Work the tax and grand total out again after the shipping fee or
+    // discount changes. A coupon lowers the taxable amount, so the lines'
+    // tax is scaled down by the share of the subtotal the discount takes.
+    pub fn recalculate_totals(&mut self) {
+        let line_tax: i64 = self.items.iter().map(|item| item.tax).sum();
+        self.tax_total = if self.subtotal > 0 {
+            round_div(line_tax as i128 * (self.subtotal - self.discount_total) as i128, self.subtotal as i128)
+        } else {
+            0
+        };
+
+        let added_tax = if self.prices_include_tax { 0 } else { self.tax_total };
+        self.total = self.subtotal + self.shipping_fee - self.discount_total + added_tax;
+    }
+}
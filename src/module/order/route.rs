@@ -0,0 +1,239 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::Authentication;
+use crate::module::cart::crud::CartCrud;
+use crate::module::category::crud::CategoryCrud;
+use crate::module::invoice::route as invoice_routes;
+use crate::module::order::{
+    controller::OrderController,
+    crud::OrderCrud,
+    interface::OrderService,
+    model::OrderActor,
+    schema::{CheckoutSchema, OrderListQuery, UpdateOrderStatusSchema},
+};
+use crate::module::product::crud::ProductCrud;
+use crate::module::promotion::crud::PromotionCrud;
+use crate::module::shipping::crud::ShippingCrud;
+use crate::module::tax::crud::TaxCrud;
+
+fn controller(
+    crud: &web::Data<OrderCrud>,
+    product_crud: &web::Data<ProductCrud>,
+    cart_crud: &web::Data<CartCrud>,
+    promotion_crud: &web::Data<PromotionCrud>,
+    tax_crud: &web::Data<TaxCrud>,
+    category_crud: &web::Data<CategoryCrud>,
+    shipping_crud: &web::Data<ShippingCrud>,
+) -> OrderController {
+    OrderController::new(
+        crud.get_ref().clone(),
+        product_crud.get_ref().clone(),
+        cart_crud.get_ref().clone(),
+        promotion_crud.get_ref().clone(),
+        tax_crud.get_ref().clone(),
+        category_crud.get_ref().clone(),
+        shipping_crud.get_ref().clone(),
+    )
+}
+
+// POST checkout handler
+#[allow(clippy::too_many_arguments)]
+async fn checkout_handler(
+    req: HttpRequest,
+    body: web::Json<CheckoutSchema>,
+    crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+    promotion_crud: web::Data<PromotionCrud>,
+    tax_crud: web::Data<TaxCrud>,
+    category_crud: web::Data<CategoryCrud>,
+    shipping_crud: web::Data<ShippingCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &cart_crud, &promotion_crud, &tax_crud, &category_crud, &shipping_crud).checkout(&claims.sub, body.into_inner()).await {
+        Ok(order) => Ok(HttpResponse::Created().json(order)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET order history handler
+#[allow(clippy::too_many_arguments)]
+async fn get_orders_handler(
+    req: HttpRequest,
+    query: web::Query<OrderListQuery>,
+    crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+    promotion_crud: web::Data<PromotionCrud>,
+    tax_crud: web::Data<TaxCrud>,
+    category_crud: web::Data<CategoryCrud>,
+    shipping_crud: web::Data<ShippingCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &cart_crud, &promotion_crud, &tax_crud, &category_crud, &shipping_crud).get_user_orders(&claims.sub, query.into_inner()).await {
+        Ok(orders) => Ok(HttpResponse::Ok().json(orders)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET single order handler
+#[allow(clippy::too_many_arguments)]
+async fn get_order_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+    promotion_crud: web::Data<PromotionCrud>,
+    tax_crud: web::Data<TaxCrud>,
+    category_crud: web::Data<CategoryCrud>,
+    shipping_crud: web::Data<ShippingCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &cart_crud, &promotion_crud, &tax_crud, &category_crud, &shipping_crud).get_user_order(&claims.sub, &id).await {
+        Ok(Some(order)) => Ok(HttpResponse::Ok().json(order)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Order not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST cancel order handler
+#[allow(clippy::too_many_arguments)]
+async fn cancel_order_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+    promotion_crud: web::Data<PromotionCrud>,
+    tax_crud: web::Data<TaxCrud>,
+    category_crud: web::Data<CategoryCrud>,
+    shipping_crud: web::Data<ShippingCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &cart_crud, &promotion_crud, &tax_crud, &category_crud, &shipping_crud).cancel_order(&claims.sub, &id).await {
+        Ok(order) => Ok(HttpResponse::Ok().json(order)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET all orders handler (admin)
+#[allow(clippy::too_many_arguments)]
+async fn admin_list_orders_handler(
+    query: web::Query<OrderListQuery>,
+    crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+    promotion_crud: web::Data<PromotionCrud>,
+    tax_crud: web::Data<TaxCrud>,
+    category_crud: web::Data<CategoryCrud>,
+    shipping_crud: web::Data<ShippingCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud, &cart_crud, &promotion_crud, &tax_crud, &category_crud, &shipping_crud).list_orders(query.into_inner()).await {
+        Ok(orders) => Ok(HttpResponse::Ok().json(orders)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET single order handler (admin)
+#[allow(clippy::too_many_arguments)]
+async fn admin_get_order_handler(
+    id: web::Path<String>,
+    crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+    promotion_crud: web::Data<PromotionCrud>,
+    tax_crud: web::Data<TaxCrud>,
+    category_crud: web::Data<CategoryCrud>,
+    shipping_crud: web::Data<ShippingCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud, &cart_crud, &promotion_crud, &tax_crud, &category_crud, &shipping_crud).get_order(&id).await {
+        Ok(Some(order)) => Ok(HttpResponse::Ok().json(order)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Order not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// PATCH order status handler (admin)
+#[allow(clippy::too_many_arguments)]
+async fn admin_update_status_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<UpdateOrderStatusSchema>,
+    crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+    promotion_crud: web::Data<PromotionCrud>,
+    tax_crud: web::Data<TaxCrud>,
+    category_crud: web::Data<CategoryCrud>,
+    shipping_crud: web::Data<ShippingCrud>,
+) -> Result<HttpResponse> {
+    // The admin middleware already checked the token; this only reads who it belongs to
+    let admin_id = Authentication::optional_user(&req)
+        .map(|claims| claims.sub)
+        .unwrap_or_else(|| "unknown".to_string());
+
+    match controller(&crud, &product_crud, &cart_crud, &promotion_crud, &tax_crud, &category_crud, &shipping_crud)
+        .update_status(&id, body.into_inner(), OrderActor::admin(&admin_id))
+        .await
+    {
+        Ok(order) => Ok(HttpResponse::Ok().json(order)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// Route configuration function
+pub fn order_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/orders")
+            .route("", web::post().to(checkout_handler))
+            .route("", web::get().to(get_orders_handler))
+            .route("/{id}", web::get().to(get_order_handler))
+            .route("/{id}/cancel", web::post().to(cancel_order_handler))
+            .configure(invoice_routes::order_invoice_routes)
+    );
+}
+
+// Admin routes, mounted inside the admin-authenticated `/api/admin` scope
+pub fn admin_order_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/orders")
+            .route("", web::get().to(admin_list_orders_handler))
+            .route("/{id}", web::get().to(admin_get_order_handler))
+            .route("/{id}/status", web::patch().to(admin_update_status_handler))
+            .configure(invoice_routes::admin_order_invoice_routes)
+    );
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::module::order::model::{Order, ShippingAddress};
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct ShippingAddressSchema {
+    #[validate(length(min = 2, max = 100))]
+    pub full_name: String,
+
+    #[validate(length(min = 10, max = 15, message = "Phone number must be between 10 and 15 characters"))]
+    pub phone_number: String,
+
+    #[validate(length(min = 3, max = 200))]
+    pub address_line1: String,
+
+    #[validate(length(max = 200))]
+    pub address_line2: Option<String>,
+
+    #[validate(length(min = 2, max = 100))]
+    pub city: String,
+
+    #[validate(length(min = 2, max = 100))]
+    pub state: String,
+
+    #[validate(length(max = 20))]
+    pub postal_code: Option<String>,
+
+    #[serde(default = "default_country")]
+    pub country: String,
+}
+
+impl From<ShippingAddressSchema> for ShippingAddress {
+    fn from(data: ShippingAddressSchema) -> Self {
+        Self {
+            full_name: data.full_name,
+            phone_number: data.phone_number,
+            address_line1: data.address_line1,
+            address_line2: data.address_line2,
+            city: data.city,
+            state: data.state,
+            postal_code: data.postal_code,
+            country: data.country,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct CheckoutItemSchema {
+    pub product_id: String,  // Will be converted to ObjectId
+    // Required when the product is sold in variants
+    pub variant_id: Option<String>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub quantity: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct CheckoutSchema {
+    // When omitted, the order is built from the user's cart
+    #[validate(nested)]
+    pub items: Option<Vec<CheckoutItemSchema>>,
+
+    #[validate(nested)]
+    pub shipping_address: ShippingAddressSchema,
+
+    #[validate(length(min = 1, max = 30))]
+    pub coupon_code: Option<String>,
+
+    // Code of the delivery service from the shipping quote; the cheapest when omitted
+    #[validate(length(min = 1, max = 30))]
+    pub delivery_service: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct UpdateOrderStatusSchema {
+    pub status: String,
+
+    #[validate(length(max = 500))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderListQuery {
+    pub page: Option<u64>,
+    pub limit: Option<i64>,
+
+    // Admin filters
+    pub status: Option<String>,
+    pub user_id: Option<String>,
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderListResponse {
+    pub orders: Vec<Order>,
+    pub total: u64,
+    pub page: u64,
+    pub limit: i64,
+}
+
+fn default_country() -> String {
+    "Nigeria".to_string()
+}
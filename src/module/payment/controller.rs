@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use validator::Validate;
+use crate::module::order::{
+    crud::OrderCrud,
+    model::{Order, OrderActor, OrderStatus, StatusTransition},
+};
+use crate::module::payment::{
+    crud::PaymentCrud,
+    interface::PaymentService,
+    model::{PaymentEvent, PaymentIntent, PaymentIntentItem, PaymentIntentStatus},
+    schema::{CreatePaymentIntentSchema, PaymentIntentItemSchema, RefundPaymentSchema, WebhookError},
+};
+use crate::module::product::{
+    crud::{stock_reservation_ttl, ProductCrud},
+    model::{Product, ReservationStatus, ReservedItem},
+};
+use crate::service::payment::{
+    provider::{InitializePayment, ProviderPaymentStatus},
+    PaymentProvider,
+};
+
+pub struct PaymentController {
+    crud: PaymentCrud,
+    product_crud: ProductCrud,
+    order_crud: OrderCrud,
+    provider: Arc<dyn PaymentProvider>,
+}
+
+impl PaymentController {
+    pub fn new(crud: PaymentCrud, product_crud: ProductCrud, order_crud: OrderCrud, provider: Arc<dyn PaymentProvider>) -> Self {
+        Self { crud, product_crud, order_crud, provider }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    // Snapshot each product's name and current price
+    async fn build_items(&self, requested: Vec<PaymentIntentItemSchema>) -> Result<Vec<PaymentIntentItem>, String> {
+        // Combine repeated products (and variants) into one line
+        let mut quantities: Vec<(ObjectId, Option<ObjectId>, i32)> = Vec::new();
+        for item in requested {
+            let product_id = Self::parse_id(&item.product_id, "product")?;
+            let variant_id = item.variant_id.as_deref()
+                .map(|id| Self::parse_id(id, "variant"))
+                .transpose()?;
+            match quantities.iter_mut().find(|(id, variant, _)| *id == product_id && *variant == variant_id) {
+                Some((_, _, quantity)) => *quantity += item.quantity,
+                None => quantities.push((product_id, variant_id, item.quantity)),
+            }
+        }
+
+        let ids: Vec<ObjectId> = quantities.iter().map(|(id, _, _)| *id).collect();
+        let products: HashMap<ObjectId, Product> = self.product_crud.get_products_by_ids(&ids).await?
+            .into_iter()
+            .filter_map(|product| product.id.map(|id| (id, product)))
+            .collect();
+
+        quantities.into_iter()
+            .map(|(product_id, variant_id, quantity)| {
+                let product = products.get(&product_id)
+                    .filter(|product| product.is_active)
+                    .ok_or_else(|| format!("Product {} is not available", product_id.to_hex()))?;
+                let option = product.purchase_option(variant_id.as_ref())?;
+
+                let unit_price = option.unit_price();
+                Ok(PaymentIntentItem {
+                    product_id,
+                    variant_id,
+                    name: option.name(),
+                    unit_price,
+                    quantity,
+                    line_total: unit_price * quantity as i64,
+                })
+            })
+            .collect()
+    }
+
+    // Best effort: the hold expires on its own if this fails
+    async fn release_stock(&self, reference: &str) {
+        if let Err(e) = self.product_crud.release_reservation(reference).await {
+            eprintln!("Failed to release stock for {}: {}", reference, e);
+        }
+    }
+
+    // Apply a gateway result to an intent. Intents only ever leave `pending` once,
+    // so replays of the same result are harmless. Returns the outcome to log.
+    async fn apply_result(
+        &self,
+        intent: PaymentIntent,
+        status: ProviderPaymentStatus,
+        amount: i64,
+        paid_at: Option<DateTime<Utc>>,
+    ) -> Result<(PaymentIntent, &'static str), String> {
+        let (next, outcome) = match status {
+            ProviderPaymentStatus::Pending => return Ok((intent, "ignored")),
+            // Never mark an intent paid for less than it asked for
+            ProviderPaymentStatus::Success if amount != intent.amount => return Ok((intent, "amount_mismatch")),
+            ProviderPaymentStatus::Success => (PaymentIntentStatus::Succeeded, "applied"),
+            ProviderPaymentStatus::Failed | ProviderPaymentStatus::Abandoned => (PaymentIntentStatus::Failed, "applied"),
+        };
+
+        if intent.status != PaymentIntentStatus::Pending {
+            return Ok((intent, "already_processed"));
+        }
+
+        // Paid stock has to stay taken, and a paid-for order marked paid, before
+        // the payment counts. If either fails, the error goes back to the
+        // gateway, which sends the event again.
+        if next == PaymentIntentStatus::Succeeded {
+            if let Err(e) = self.product_crud.commit_reservation(intent.stock_reference()).await {
+                // Retrying can't help once the hold is gone
+                if !self.hold_released(intent.stock_reference()).await? {
+                    return Err(e);
+                }
+                if !self.take_stock_again(&intent).await? {
+                    return self.refund_late_payment(intent, paid_at).await;
+                }
+            }
+            if let Some(order_number) = &intent.order_number {
+                self.order_paid(order_number, &intent.reference).await?;
+            }
+        }
+
+        let paid_at = (next == PaymentIntentStatus::Succeeded).then(|| paid_at.unwrap_or_else(Utc::now));
+        let updated = match self.crud.transition_status(&intent.reference, PaymentIntentStatus::Pending, next, paid_at).await? {
+            Some(updated) => updated,
+            None => return Ok((intent, "already_processed")),
+        };
+
+        // Failed payments give the stock back. An order keeps its stock until
+        // its own payment window closes, so the customer can try again.
+        if next == PaymentIntentStatus::Failed && updated.order_number.is_none() {
+            self.release_stock(&updated.reference).await;
+        }
+
+        Ok((updated, outcome))
+    }
+
+    async fn hold_released(&self, reference: &str) -> Result<bool, String> {
+        let reservation = self.product_crud.get_reservation(reference).await?;
+        Ok(reservation.is_none_or(|reservation| reservation.status == ReservationStatus::Released))
+    }
+
+    // Hold and commit the stock of a payment that landed after its hold was
+    // released. False when the stock can't be had. An order's hold is only
+    // released once the order is cancelled, and a cancelled order isn't
+    // brought back (its coupon use has been handed back), so it's never retaken.
+    async fn take_stock_again(&self, intent: &PaymentIntent) -> Result<bool, String> {
+        if intent.order_number.is_some() {
+            return Ok(false);
+        }
+
+        let reserved: Vec<ReservedItem> = intent.items.iter()
+            .map(|item| ReservedItem { product_id: item.product_id, variant_id: item.variant_id, quantity: item.quantity })
+            .collect();
+        if let Err(e) = self.product_crud.reserve_again(&intent.reference, &reserved, stock_reservation_ttl()).await {
+            eprintln!("Couldn't hold stock again for late payment {}: {}", intent.reference, e);
+            return Ok(false);
+        }
+        self.product_crud.commit_reservation(&intent.reference).await?;
+        Ok(true)
+    }
+
+    // Pay back a payment whose goods can no longer be had. The intent is
+    // settled first, so a replayed event can't refund twice; if the gateway
+    // refuses, it goes back to pending and the event is retried.
+    async fn refund_late_payment(
+        &self,
+        intent: PaymentIntent,
+        paid_at: Option<DateTime<Utc>>,
+    ) -> Result<(PaymentIntent, &'static str), String> {
+        let refunded = match self.crud.claim_late_refund(&intent.reference, paid_at.unwrap_or_else(Utc::now)).await? {
+            Some(refunded) => refunded,
+            None => return Ok((intent, "already_processed")),
+        };
+
+        if let Err(e) = self.provider.refund(&intent.reference, Some(intent.amount)).await {
+            if let Err(undo_error) = self.crud.undo_late_refund(&intent.reference).await {
+                eprintln!("Failed to undo refund claim for {}: {}", intent.reference, undo_error);
+            }
+            return Err(e);
+        }
+        Ok((refunded, "refunded_unavailable"))
+    }
+
+    fn order_transition(order: &Order, next: OrderStatus, note: String) -> StatusTransition {
+        StatusTransition {
+            from: Some(order.status),
+            to: next,
+            actor: OrderActor::system(),
+            note: Some(note),
+            at: Utc::now(),
+        }
+    }
+
+    // Move a paid-for order on to paid. Its stock is committed by the caller.
+    async fn order_paid(&self, order_number: &str, reference: &str) -> Result<(), String> {
+        let order = self.order_crud.get_order_by_number(order_number).await?
+            .ok_or_else(|| format!("Order {} not found", order_number))?;
+        if order.status != OrderStatus::PendingPayment {
+            return Ok(());
+        }
+
+        let id = order.id.ok_or_else(|| "Order has no ID".to_string())?;
+        let transition = Self::order_transition(&order, OrderStatus::Paid, format!("Paid with {}", reference));
+        self.order_crud.transition_status(&id, order.status, transition).await?
+            .ok_or_else(|| format!("Order {} changed while being paid, please retry", order_number))?;
+        Ok(())
+    }
+
+    // Move an order whose payment has been paid back in full on to refunded.
+    // Stock for goods that never shipped goes back on sale; returned goods are
+    // restocked when they're inspected.
+    async fn order_refunded(&self, order_number: &str) -> Result<(), String> {
+        let order = match self.order_crud.get_order_by_number(order_number).await? {
+            Some(order) if order.status.can_transition_to(OrderStatus::Refunded) => order,
+            _ => return Ok(()),
+        };
+
+        let id = order.id.ok_or_else(|| "Order has no ID".to_string())?;
+        let transition = Self::order_transition(&order, OrderStatus::Refunded, "Payment refunded".to_string());
+        self.order_crud.transition_status(&id, order.status, transition).await?
+            .ok_or_else(|| format!("Order {} changed while being refunded", order_number))?;
+
+        if matches!(order.status, OrderStatus::Paid | OrderStatus::Processing) {
+            self.product_crud.release_reservation(order_number).await?;
+        }
+        Ok(())
+    }
+
+    // Pay back part or all of what's left of a payment
+    async fn refund(&self, intent: PaymentIntent, amount: Option<i64>) -> Result<PaymentIntent, String> {
+        if intent.status != PaymentIntentStatus::Succeeded {
+            return Err("Only successful payments can be refunded".to_string());
+        }
+        let remaining = intent.amount - intent.refunded_amount;
+        let amount = amount.unwrap_or(remaining);
+        if amount > remaining {
+            return Err(format!("Refund can't be more than the {} kobo not yet refunded", remaining));
+        }
+        if amount <= 0 {
+            return Err("This payment has already been refunded in full".to_string());
+        }
+
+        // Claim the amount first, so two refunds at once can't both take the same money
+        let claimed = self.crud.claim_refund(&intent.reference, amount).await?
+            .ok_or_else(|| "Refund can't be more than the amount not yet refunded".to_string())?;
+
+        if let Err(e) = self.provider.refund(&intent.reference, Some(amount)).await {
+            if let Err(undo_error) = self.crud.undo_refund(&intent.reference, amount).await {
+                eprintln!("Failed to undo refund claim for {}: {}", intent.reference, undo_error);
+            }
+            return Err(e);
+        }
+
+        // Partial refunds leave the payment standing until it's all paid back
+        if claimed.refunded_amount < claimed.amount {
+            return Ok(claimed);
+        }
+        let refunded = self.crud.transition_status(&intent.reference, PaymentIntentStatus::Succeeded, PaymentIntentStatus::Refunded, None).await?
+            .ok_or_else(|| "Payment status changed concurrently, please retry".to_string())?;
+
+        // The money is back with the customer either way; the order catches up
+        if let Some(order_number) = &refunded.order_number
+            && let Err(e) = self.order_refunded(order_number).await
+        {
+            eprintln!("Failed to mark order {} refunded: {}", order_number, e);
+        }
+        Ok(refunded)
+    }
+
+    // Refund an order through the payment that paid for it
+    pub async fn refund_order(&self, order_number: &str, amount: i64) -> Result<PaymentIntent, String> {
+        let intent = self.crud.get_paid_order_intent(order_number).await?
+            .ok_or_else(|| format!("No payment to refund was found for order {}", order_number))?;
+        self.refund(intent, Some(amount)).await
+    }
+
+    // Work out what a webhook means for us. Unknown events are stored but not acted on.
+    async fn process_event(&self, event: &str, data: &Value) -> Result<&'static str, String> {
+        let status = match event {
+            "charge.success" => ProviderPaymentStatus::Success,
+            "charge.failed" => ProviderPaymentStatus::Failed,
+            _ => return Ok("ignored"),
+        };
+
+        let reference = match data["reference"].as_str() {
+            Some(reference) => reference,
+            None => return Ok("missing_reference"),
+        };
+        let intent = match self.crud.get_intent_by_reference(reference).await? {
+            Some(intent) => intent,
+            None => return Ok("unknown_reference"),
+        };
+
+        let amount = data["amount"].as_i64().unwrap_or_default();
+        let paid_at = data["paid_at"].as_str()
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|value| value.with_timezone(&Utc));
+
+        let (_, outcome) = self.apply_result(intent, status, amount, paid_at).await?;
+        Ok(outcome)
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentService for PaymentController {
+    async fn create_intent(&self, user_id: &str, email: &str, data: CreatePaymentIntentSchema) -> Result<PaymentIntent, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let mut intent = match &data.order_id {
+            Some(order_id) => {
+                if !data.items.is_empty() {
+                    return Err("Pay for either items or an order, not both".to_string());
+                }
+                let order_id = Self::parse_id(order_id, "order")?;
+                let order = self.order_crud.get_user_order(&order_id, &user_oid).await?
+                    .ok_or_else(|| "Order not found".to_string())?;
+                if order.status != OrderStatus::PendingPayment {
+                    return Err("Only orders awaiting payment can be paid".to_string());
+                }
+                // The order already holds its stock
+                PaymentIntent::for_order(email.to_string(), &order, self.provider.name())
+            }
+            None => {
+                if data.items.is_empty() {
+                    return Err("No items to pay for".to_string());
+                }
+                let items = self.build_items(data.items).await?;
+                let intent = PaymentIntent::new(user_oid, email.to_string(), items, self.provider.name());
+
+                let reserved: Vec<ReservedItem> = intent.items.iter()
+                    .map(|item| ReservedItem { product_id: item.product_id, variant_id: item.variant_id, quantity: item.quantity })
+                    .collect();
+                self.product_crud.reserve_stock(&intent.reference, &reserved, stock_reservation_ttl()).await?;
+                intent
+            }
+        };
+        let holds_stock = intent.order_number.is_none();
+
+        let reference = intent.reference.clone();
+        intent = match self.crud.create_intent(intent).await {
+            Ok(intent) => intent,
+            Err(e) => {
+                if holds_stock {
+                    self.release_stock(&reference).await;
+                }
+                return Err(e);
+            }
+        };
+
+        let request = InitializePayment {
+            reference: intent.reference.clone(),
+            email: intent.email.clone(),
+            amount: intent.amount,
+            currency: intent.currency.clone(),
+            callback_url: data.callback_url,
+            metadata: json!({ "user_id": user_id }),
+        };
+        let session = match self.provider.initialize(&request).await {
+            Ok(session) => session,
+            Err(e) => {
+                self.crud.transition_status(&reference, PaymentIntentStatus::Pending, PaymentIntentStatus::Failed, None).await?;
+                if holds_stock {
+                    self.release_stock(&reference).await;
+                }
+                return Err(e);
+            }
+        };
+
+        self.crud.set_session(&intent.reference, &session.authorization_url, &session.access_code).await?;
+        intent.authorization_url = Some(session.authorization_url);
+        intent.access_code = Some(session.access_code);
+        Ok(intent)
+    }
+
+    async fn get_intents(&self, user_id: &str) -> Result<Vec<PaymentIntent>, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        self.crud.get_user_intents(&user_oid).await
+    }
+
+    async fn get_intent(&self, user_id: &str, id: &str) -> Result<Option<PaymentIntent>, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let id = Self::parse_id(id, "payment")?;
+        self.crud.get_user_intent(&id, &user_oid).await
+    }
+
+    async fn verify_intent(&self, user_id: &str, id: &str) -> Result<PaymentIntent, String> {
+        let intent = self.get_intent(user_id, id).await?
+            .ok_or_else(|| "Payment not found".to_string())?;
+        if intent.status != PaymentIntentStatus::Pending {
+            return Ok(intent);
+        }
+
+        let verification = self.provider.verify(&intent.reference).await?;
+        let (intent, _) = self.apply_result(intent, verification.status, verification.amount, verification.paid_at).await?;
+        Ok(intent)
+    }
+
+    async fn handle_webhook(&self, payload: &[u8], signature: &str) -> Result<(), WebhookError> {
+        if !self.provider.verify_webhook_signature(payload, signature) {
+            return Err(WebhookError::Rejected("Invalid webhook signature".to_string()));
+        }
+
+        let body: Value = serde_json::from_slice(payload)
+            .map_err(|e| WebhookError::Rejected(format!("Invalid webhook payload: {}", e)))?;
+        let event = body["event"].as_str().unwrap_or("unknown").to_string();
+        let reference = body["data"]["reference"].as_str().map(|value| value.to_string());
+        let document = bson::to_document(&body)
+            .map_err(|e| WebhookError::Rejected(format!("Invalid webhook payload: {}", e)))?;
+
+        let processed = self.process_event(&event, &body["data"]).await;
+        let outcome = match &processed {
+            Ok(outcome) => outcome.to_string(),
+            Err(e) => format!("error: {}", e),
+        };
+
+        // Every signed event is kept, whatever came of it
+        self.crud.record_event(&PaymentEvent {
+            id: None,
+            provider: self.provider.name().to_string(),
+            event,
+            reference,
+            payload: document,
+            outcome,
+            received_at: Utc::now(),
+        }).await.map_err(WebhookError::Failed)?;
+
+        processed.map(|_| ()).map_err(WebhookError::Failed)
+    }
+
+    async fn refund_intent(&self, id: &str, data: RefundPaymentSchema) -> Result<PaymentIntent, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let id = Self::parse_id(id, "payment")?;
+        let intent = self.crud.get_intent(&id).await?
+            .ok_or_else(|| "Payment not found".to_string())?;
+        self.refund(intent, data.amount).await
+    }
+}
@@ -0,0 +1,213 @@
+use bson::{doc, oid::ObjectId};
+use chrono::{DateTime, Utc};
+use mongodb::{
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Collection, Database,
+};
+use futures_util::TryStreamExt;
+use crate::module::payment::model::{PaymentEvent, PaymentIntent, PaymentIntentStatus};
+
+#[derive(Clone)]
+pub struct PaymentCrud {
+    intents: Collection<PaymentIntent>,
+    events: Collection<PaymentEvent>,
+}
+
+impl PaymentCrud {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            intents: db.collection("payment_intents"),
+            events: db.collection("payment_events"),
+        }
+    }
+
+    pub async fn create_intent(&self, mut intent: PaymentIntent) -> Result<PaymentIntent, String> {
+        match self.intents.insert_one(&intent).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    intent.id = Some(id);
+                }
+                Ok(intent)
+            }
+            Err(e) => Err(format!("Failed to create payment intent: {}", e))
+        }
+    }
+
+    pub async fn get_intent(&self, id: &ObjectId) -> Result<Option<PaymentIntent>, String> {
+        match self.intents.find_one(doc! { "_id": id }).await {
+            Ok(intent) => Ok(intent),
+            Err(e) => Err(format!("Failed to retrieve payment intent: {}", e))
+        }
+    }
+
+    pub async fn get_user_intent(&self, id: &ObjectId, user_id: &ObjectId) -> Result<Option<PaymentIntent>, String> {
+        match self.intents.find_one(doc! { "_id": id, "user_id": user_id }).await {
+            Ok(intent) => Ok(intent),
+            Err(e) => Err(format!("Failed to retrieve payment intent: {}", e))
+        }
+    }
+
+    pub async fn get_intent_by_reference(&self, reference: &str) -> Result<Option<PaymentIntent>, String> {
+        match self.intents.find_one(doc! { "reference": reference }).await {
+            Ok(intent) => Ok(intent),
+            Err(e) => Err(format!("Failed to retrieve payment intent: {}", e))
+        }
+    }
+
+    // The payment that went through for an order, if any
+    pub async fn get_paid_order_intent(&self, order_number: &str) -> Result<Option<PaymentIntent>, String> {
+        let filter = doc! { "order_number": order_number, "status": PaymentIntentStatus::Succeeded.as_str() };
+        match self.intents.find_one(filter).await {
+            Ok(intent) => Ok(intent),
+            Err(e) => Err(format!("Failed to retrieve payment intent: {}", e))
+        }
+    }
+
+    pub async fn get_user_intents(&self, user_id: &ObjectId) -> Result<Vec<PaymentIntent>, String> {
+        let options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .limit(100)
+            .build();
+
+        match self.intents.find(doc! { "user_id": user_id }).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut intents = Vec::new();
+                while let Some(intent) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve payment intents: {}", e))? {
+                    intents.push(intent);
+                }
+                Ok(intents)
+            }
+            Err(e) => Err(format!("Failed to retrieve payment intents: {}", e))
+        }
+    }
+
+    // Store what the gateway gave us to send the customer to
+    pub async fn set_session(&self, reference: &str, authorization_url: &str, access_code: &str) -> Result<(), String> {
+        self.intents
+            .update_one(
+                doc! { "reference": reference },
+                doc! { "$set": {
+                    "authorization_url": authorization_url,
+                    "access_code": access_code,
+                    "updated_at": bson::DateTime::from_chrono(Utc::now()),
+                } },
+            )
+            .await
+            .map_err(|e| format!("Failed to update payment intent: {}", e))?;
+        Ok(())
+    }
+
+    // Move an intent on only if it is still in `from`. Returns None when another
+    // request (or a replayed webhook) got there first, which keeps processing idempotent.
+    pub async fn transition_status(
+        &self,
+        reference: &str,
+        from: PaymentIntentStatus,
+        to: PaymentIntentStatus,
+        paid_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<PaymentIntent>, String> {
+        let mut set = doc! {
+            "status": to.as_str(),
+            "updated_at": bson::DateTime::from_chrono(Utc::now()),
+        };
+        if let Some(paid_at) = paid_at {
+            set.insert("paid_at", bson::DateTime::from_chrono(paid_at));
+        }
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.intents
+            .find_one_and_update(doc! { "reference": reference, "status": from.as_str() }, doc! { "$set": set })
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to update payment intent: {}", e))
+    }
+
+    // Settle a pending payment as paid and refunded in full in one step, before
+    // the gateway is asked for the money back. Returns None if it isn't pending.
+    pub async fn claim_late_refund(&self, reference: &str, paid_at: DateTime<Utc>) -> Result<Option<PaymentIntent>, String> {
+        let update = vec![doc! { "$set": {
+            "status": PaymentIntentStatus::Refunded.as_str(),
+            "refunded_amount": "$amount",
+            "paid_at": bson::DateTime::from_chrono(paid_at),
+            "updated_at": bson::DateTime::from_chrono(Utc::now()),
+        } }];
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.intents
+            .find_one_and_update(doc! { "reference": reference, "status": PaymentIntentStatus::Pending.as_str() }, update)
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to record refund: {}", e))
+    }
+
+    // Put a claimed late refund the gateway didn't go through with back to pending
+    pub async fn undo_late_refund(&self, reference: &str) -> Result<(), String> {
+        self.intents
+            .update_one(
+                doc! { "reference": reference, "status": PaymentIntentStatus::Refunded.as_str() },
+                doc! {
+                    "$set": {
+                        "status": PaymentIntentStatus::Pending.as_str(),
+                        "refunded_amount": 0_i64,
+                        "updated_at": bson::DateTime::from_chrono(Utc::now()),
+                    },
+                    "$unset": { "paid_at": "" },
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to undo refund: {}", e))?;
+        Ok(())
+    }
+
+    // Add to what's been refunded, but only while it stays within what was paid.
+    // Returns None when the payment can't take that much more refunded.
+    pub async fn claim_refund(&self, reference: &str, amount: i64) -> Result<Option<PaymentIntent>, String> {
+        let filter = doc! {
+            "reference": reference,
+            "status": PaymentIntentStatus::Succeeded.as_str(),
+            "$expr": { "$lte": [
+                { "$add": [{ "$ifNull": ["$refunded_amount", 0_i64] }, amount] },
+                "$amount",
+            ] },
+        };
+        let update = doc! {
+            "$inc": { "refunded_amount": amount },
+            "$set": { "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.intents
+            .find_one_and_update(filter, update)
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to record refund: {}", e))
+    }
+
+    // Give back a claimed refund the gateway didn't go through with
+    pub async fn undo_refund(&self, reference: &str, amount: i64) -> Result<(), String> {
+        self.intents
+            .update_one(
+                doc! { "reference": reference },
+                doc! {
+                    "$inc": { "refunded_amount": -amount },
+                    "$set": { "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to undo refund: {}", e))?;
+        Ok(())
+    }
+
+    pub async fn record_event(&self, event: &PaymentEvent) -> Result<(), String> {
+        self.events.insert_one(event).await
+            .map_err(|e| format!("Failed to store payment event: {}", e))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use crate::module::payment::{
+    model::PaymentIntent,
+    schema::{CreatePaymentIntentSchema, RefundPaymentSchema, WebhookError},
+};
+
+#[async_trait::async_trait]
+pub trait PaymentService {
+    // Hold stock for the products and start a payment with the gateway
+    async fn create_intent(&self, user_id: &str, email: &str, data: CreatePaymentIntentSchema) -> Result<PaymentIntent, String>;
+    async fn get_intents(&self, user_id: &str) -> Result<Vec<PaymentIntent>, String>;
+    async fn get_intent(&self, user_id: &str, id: &str) -> Result<Option<PaymentIntent>, String>;
+
+    // Ask the gateway for the latest status, for when a webhook is slow or lost
+    async fn verify_intent(&self, user_id: &str, id: &str) -> Result<PaymentIntent, String>;
+
+    // Signed gateway callback; the raw body is needed to check the signature.
+    // Failures on our side are errors so the gateway retries the event.
+    async fn handle_webhook(&self, payload: &[u8], signature: &str) -> Result<(), WebhookError>;
+
+    // Admin
+    async fn refund_intent(&self, id: &str, data: RefundPaymentSchema) -> Result<PaymentIntent, String>;
+}
@@ -0,0 +1,31 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("payment", 1, "payment intent indexes", "payment_intents", vec![
+            IndexModel::builder()
+                .keys(doc! { "reference": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "user_id": 1, "created_at": -1 })
+                .build(),
+        ]),
+        Migration::indexes("payment", 2, "payment event index", "payment_events", vec![
+            IndexModel::builder()
+                .keys(doc! { "reference": 1, "received_at": -1 })
+                .build(),
+        ]),
+        // Finds an order's payments when it is paid or refunded
+        Migration::indexes("payment", 3, "payment intent order index", "payment_intents", vec![
+            IndexModel::builder()
+                .keys(doc! { "order_number": 1, "status": 1 })
+                .options(IndexOptions::builder()
+                    .partial_filter_expression(doc! { "order_number": { "$exists": true } })
+                    .build())
+                .build(),
+        ]),
+    ]
+}
@@ -0,0 +1,146 @@
+use bson::{oid::ObjectId, Document};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::module::order::model::Order;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentIntentStatus {
+    Pending,
+    Succeeded,
+    Failed,
+    Refunded,
+}
+
+impl PaymentIntentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentIntentStatus::Pending => "pending",
+            PaymentIntentStatus::Succeeded => "succeeded",
+            PaymentIntentStatus::Failed => "failed",
+            PaymentIntentStatus::Refunded => "refunded",
+        }
+    }
+}
+
+// Product snapshot at the time the intent was created
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PaymentIntentItem {
+    pub product_id: ObjectId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant_id: Option<ObjectId>,
+    pub name: String,
+    pub unit_price: i64, // In kobo
+    pub quantity: i32,
+    pub line_total: i64,
+}
+
+// A request to collect money for a set of products
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PaymentIntent {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    // Our reference, also sent to the gateway; stock is held under it too,
+    // unless the intent pays for an order
+    pub reference: String,
+    // The order being paid for; its stock is held under the order number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_number: Option<String>,
+    pub user_id: ObjectId,
+    pub email: String,
+    pub items: Vec<PaymentIntentItem>,
+    pub amount: i64, // In kobo
+    // Paid back so far; the intent is `refunded` once this reaches `amount`
+    #[serde(default)]
+    pub refunded_amount: i64,
+    pub currency: String,
+    pub provider: String,
+    pub status: PaymentIntentStatus,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_code: Option<String>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub paid_at: Option<DateTime<Utc>>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PaymentIntent {
+    pub fn new(user_id: ObjectId, email: String, items: Vec<PaymentIntentItem>, provider: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            reference: format!("PAY-{}", Uuid::new_v4().simple()),
+            order_number: None,
+            user_id,
+            email,
+            amount: items.iter().map(|item| item.line_total).sum(),
+            items,
+            refunded_amount: 0,
+            currency: "NGN".to_string(),
+            provider: provider.to_string(),
+            status: PaymentIntentStatus::Pending,
+            authorization_url: None,
+            access_code: None,
+            paid_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    // Collect an order's total, shipping, tax and discounts included
+    pub fn for_order(email: String, order: &Order, provider: &str) -> Self {
+        let items = order.items.iter()
+            .map(|item| PaymentIntentItem {
+                product_id: item.product_id,
+                variant_id: item.variant_id,
+                name: item.name.clone(),
+                unit_price: item.unit_price,
+                quantity: item.quantity,
+                line_total: item.line_total,
+            })
+            .collect();
+        let mut intent = Self::new(order.user_id, email, items, provider);
+        intent.order_number = Some(order.order_number.clone());
+        intent.amount = order.total;
+        intent
+    }
+
+    // The reference the paid-for stock is held under
+    pub fn stock_reference(&self) -> &str {
+        self.order_number.as_deref().unwrap_or(&self.reference)
+    }
+}
+
+// Every webhook we receive, kept as-is along with what we did with it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PaymentEvent {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub provider: String,
+    pub event: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    pub payload: Document,
+
+    // e.g. "applied", "already_processed", "amount_mismatch", "ignored"
+    pub outcome: String,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub received_at: DateTime<Utc>,
+}
@@ -0,0 +1,181 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::Authentication;
+use crate::module::order::crud::OrderCrud;
+use crate::module::payment::{
+    controller::PaymentController,
+    crud::PaymentCrud,
+    interface::PaymentService,
+    schema::{CreatePaymentIntentSchema, RefundPaymentSchema, WebhookError},
+};
+use crate::module::product::crud::ProductCrud;
+use crate::service::payment::PaymentProvider;
+
+// Header Paystack puts the HMAC-SHA512 body signature in
+const SIGNATURE_HEADER: &str = "x-paystack-signature";
+
+fn controller(
+    crud: &web::Data<PaymentCrud>,
+    product_crud: &web::Data<ProductCrud>,
+    order_crud: &web::Data<OrderCrud>,
+    provider: &web::Data<dyn PaymentProvider>,
+) -> PaymentController {
+    PaymentController::new(
+        crud.get_ref().clone(),
+        product_crud.get_ref().clone(),
+        order_crud.get_ref().clone(),
+        provider.clone().into_inner(),
+    )
+}
+
+// POST payment intent handler
+async fn create_intent_handler(
+    req: HttpRequest,
+    body: web::Json<CreatePaymentIntentSchema>,
+    crud: web::Data<PaymentCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &order_crud, &provider).create_intent(&claims.sub, &claims.email, body.into_inner()).await {
+        Ok(intent) => Ok(HttpResponse::Created().json(intent)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET user's payment intents handler
+async fn get_intents_handler(
+    req: HttpRequest,
+    crud: web::Data<PaymentCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &order_crud, &provider).get_intents(&claims.sub).await {
+        Ok(intents) => Ok(HttpResponse::Ok().json(intents)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET single payment intent handler
+async fn get_intent_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    crud: web::Data<PaymentCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &order_crud, &provider).get_intent(&claims.sub, &id).await {
+        Ok(Some(intent)) => Ok(HttpResponse::Ok().json(intent)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Payment not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST verify payment intent handler
+async fn verify_intent_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    crud: web::Data<PaymentCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &order_crud, &provider).verify_intent(&claims.sub, &id).await {
+        Ok(intent) => Ok(HttpResponse::Ok().json(intent)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST gateway webhook handler (public, authenticated by signature)
+async fn webhook_handler(
+    req: HttpRequest,
+    body: web::Bytes,
+    crud: web::Data<PaymentCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let signature = req.headers()
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    match controller(&crud, &product_crud, &order_crud, &provider).handle_webhook(&body, signature).await {
+        Ok(()) => Ok(HttpResponse::Ok().finish()),
+        Err(WebhookError::Rejected(error)) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+        // Anything but a 2xx makes the gateway retry
+        Err(WebhookError::Failed(error)) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST refund handler (admin)
+async fn admin_refund_handler(
+    id: web::Path<String>,
+    body: web::Json<RefundPaymentSchema>,
+    crud: web::Data<PaymentCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud, &order_crud, &provider).refund_intent(&id, body.into_inner()).await {
+        Ok(intent) => Ok(HttpResponse::Ok().json(intent)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// Route configuration function
+pub fn payment_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/payments")
+            .route("/webhook", web::post().to(webhook_handler))
+            .route("/intents", web::post().to(create_intent_handler))
+            .route("/intents", web::get().to(get_intents_handler))
+            .route("/intents/{id}", web::get().to(get_intent_handler))
+            .route("/intents/{id}/verify", web::post().to(verify_intent_handler))
+    );
+}
+
+// Admin routes, mounted inside the admin-authenticated `/api/admin` scope
+pub fn admin_payment_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/payments")
+            .route("/intents/{id}/refund", web::post().to(admin_refund_handler))
+    );
+}
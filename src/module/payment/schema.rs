@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct PaymentIntentItemSchema {
+    #[validate(length(equal = 24, message = "Invalid product ID"))]
+    pub product_id: String,
+    // Required when the product is sold in variants
+    #[validate(length(equal = 24, message = "Invalid variant ID"))]
+    pub variant_id: Option<String>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub quantity: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreatePaymentIntentSchema {
+    // Pay for these products...
+    #[serde(default)]
+    #[validate(length(max = 50), nested)]
+    pub items: Vec<PaymentIntentItemSchema>,
+
+    // ...or for one of the customer's orders awaiting payment
+    #[validate(length(equal = 24, message = "Invalid order ID"))]
+    pub order_id: Option<String>,
+
+    // Where the gateway sends the customer after paying
+    #[validate(url)]
+    pub callback_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct RefundPaymentSchema {
+    // Partial refund in kobo; the whole amount when omitted
+    #[validate(range(min = 1))]
+    pub amount: Option<i64>,
+}
+
+// Why a webhook wasn't accepted
+#[derive(Debug, Clone)]
+pub enum WebhookError {
+    // Bad signature or payload; sending it again won't help
+    Rejected(String),
+    // Something failed on our side; the gateway should send it again
+    Failed(String),
+}
@@ -1,10 +1,35 @@
+use std::collections::{BTreeMap, HashSet};
+use bson::{doc, oid::ObjectId, Bson, Document};
 use validator::Validate;
-use actix_web::{web, HttpResponse, Result};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::AdminAuthentication;
+use crate::module::category::{
+    crud::CategoryCrud,
+    model::{AttributeDefinition, AttributeType},
+};
 use crate::module::product::{
-    model::Product,
-    schema::CreateProductSchema,
+    model::{Product, ProductDimensions, ProductImage, ProductOption, ProductVariant, SpecValue},
+    schema::{
+        BulkProductResult, BulkProductUpdateResponse, BulkProductUpdateSchema,
+        CreateProductSchema, CreateVariantSchema, OptionAvailability, OptionValueAvailability,
+        ProductFilter, ProductListResponse, ProductOptionsSchema, ReorderProductImagesSchema,
+        UpdateProductImageSchema, UpdateProductSchema, UpdateVariantSchema, UploadProductImagesSchema,
+        VariantListResponse, VariantSelection, LISTING_PARAMS,
+    },
     crud::ProductCrud,
 };
+use crate::module::tax::{
+    controller::TaxController,
+    crud::TaxCrud,
+    interface::TaxService,
+    schema::TaxPriceQuery,
+};
+use crate::service::slug_service;
+use crate::service::upload::product::ProductImageUploadService;
+use crate::service::pagination_service::paginate;
+
+// Largest gallery a product can have
+const MAX_PRODUCT_IMAGES: usize = 20;
 
 pub struct ProductController {
     crud: ProductCrud,
@@ -21,6 +46,9 @@ impl ProductController {
             Ok(_) => {},
             Err(e) => return Err(format!("Validation error: {}", e))
         }
+        Self::check_sale_price(product_.price, product_.sale_price)?;
+        // Product SKUs share one namespace with variant SKUs
+        self.check_sku(&product_.sku, None).await?;
         
         // Create the product
         let product = self.crud.create_product(product_).await?;
@@ -34,8 +62,726 @@ impl ProductController {
         self.crud.get_product(id).await
     }
     
-    pub async fn get_all_products(&self) -> Result<Vec<Product>, String> {
-        self.crud.get_all_products().await
+    // By current or old slug; callers redirect when the slug is an old one
+    pub async fn get_product_by_slug(&self, slug: &str) -> Result<Option<Product>, String> {
+        self.crud.get_product_by_slug(slug).await
+    }
+    
+    // Active products matching the filter, one page at a time. `params` is
+    // the whole query string, read for spec filters such as `ram_gb>=16`.
+    pub async fn get_products(&self, filter: ProductFilter, params: Vec<(String, String)>) -> Result<ProductListResponse<Product>, String> {
+        let (page, limit, skip) = paginate(filter.page, filter.limit);
+        
+        let mut query = doc! { "is_active": true };
+        let category_id = filter.category_id.as_deref()
+            .map(|id| ObjectId::parse_str(id).map_err(|_| "Invalid category ID".to_string()))
+            .transpose()?;
+        if let Some(category_id) = category_id {
+            query.insert("category_id", category_id);
+        }
+        query.extend(self.spec_filters(category_id.as_ref(), params).await?);
+        if let Some(brand_id) = &filter.brand_id {
+            let brand_id = ObjectId::parse_str(brand_id)
+                .map_err(|_| "Invalid brand ID".to_string())?;
+            query.insert("brand_id", brand_id);
+        }
+        for (field, value) in [
+            ("is_featured", filter.is_featured),
+            ("is_new_arrival", filter.is_new_arrival),
+            ("is_best_seller", filter.is_best_seller),
+        ] {
+            if let Some(value) = value {
+                query.insert(field, value);
+            }
+        }
+        if filter.in_stock == Some(true) {
+            query.insert("stock_quantity", doc! { "$gt": 0 });
+        }
+        if let Some(search) = filter.search.as_deref().map(str::trim).filter(|search| !search.is_empty()) {
+            let pattern = Self::escape_regex(search);
+            query.insert("$or", vec![
+                doc! { "name": { "$regex": &pattern, "$options": "i" } },
+                doc! { "sku": { "$regex": &pattern, "$options": "i" } },
+            ]);
+        }
+        
+        if let (Some(min_price), Some(max_price)) = (filter.min_price, filter.max_price)
+            && min_price > max_price {
+            return Err("min_price can't be more than max_price".to_string());
+        }
+        
+        let sort = Self::sort_for(filter.sort.as_deref())?;
+        let (products, total) = self.crud
+            .find_products(query, filter.min_price, filter.max_price, sort, skip, limit)
+            .await?;
+        
+        Ok(ProductListResponse { products, total, page, limit })
+    }
+    
+    // Turn spec parameters into conditions on `specs`. In a query string
+    // `ram_gb>=16` arrives as the key "ram_gb>", so a trailing > or < marks a
+    // bound. Repeating `key=value` matches any of the values. Parameters that
+    // name no category attribute, e.g. `utm_source`, are ignored.
+    async fn spec_filters(&self, category_id: Option<&ObjectId>, params: Vec<(String, String)>) -> Result<Document, String> {
+        let mut conditions: BTreeMap<String, Vec<(&str, String)>> = BTreeMap::new();
+        for (name, value) in params {
+            if LISTING_PARAMS.contains(&name.as_str()) {
+                continue;
+            }
+            let (key, operator) = match (name.strip_suffix('>'), name.strip_suffix('<')) {
+                (Some(key), _) => (key.to_string(), "$gte"),
+                (_, Some(key)) => (key.to_string(), "$lte"),
+                _ => (name, "$in"),
+            };
+            conditions.entry(key).or_default().push((operator, value));
+        }
+        if conditions.is_empty() {
+            return Ok(doc! {});
+        }
+        
+        let keys: Vec<String> = conditions.keys().cloned().collect();
+        let categories = self.crud.attribute_categories(category_id, &keys).await?;
+        
+        let mut filters = doc! {};
+        for (key, bounds) in conditions {
+            let Some(attribute) = categories.iter().find_map(|category| category.attribute(&key)) else {
+                continue;
+            };
+            
+            let mut condition = doc! {};
+            let mut any_of: Vec<Bson> = Vec::new();
+            for (operator, raw) in bounds {
+                let value = match attribute.attribute_type {
+                    AttributeType::Number => raw.trim().parse::<f64>()
+                        .map(SpecValue::Number)
+                        .map_err(|_| format!("{} must be a number", key))?,
+                    AttributeType::Boolean => raw.trim().parse::<bool>()
+                        .map(SpecValue::Boolean)
+                        .map_err(|_| format!("{} must be true or false", key))?,
+                    AttributeType::Enum => SpecValue::Text(AttributeDefinition::normalize_value(&raw)),
+                    AttributeType::Text => SpecValue::Text(raw.trim().to_string()),
+                };
+                let value = bson::to_bson(&value)
+                    .map_err(|e| format!("Failed to encode filter: {}", e))?;
+                
+                if operator == "$in" {
+                    any_of.push(value);
+                } else if attribute.attribute_type == AttributeType::Number {
+                    condition.insert(operator, value);
+                } else {
+                    return Err(format!("{} can only be matched with =", key));
+                }
+            }
+            if !any_of.is_empty() {
+                condition.insert("$in", any_of);
+            }
+            filters.insert(format!("specs.{}", key), condition);
+        }
+        Ok(filters)
+    }
+    
+    // The `_id` tie-breaker keeps pages stable when sort values repeat
+    fn sort_for(sort: Option<&str>) -> Result<Document, String> {
+        match sort.unwrap_or("newest") {
+            "newest" => Ok(doc! { "created_at": -1, "_id": -1 }),
+            "price_asc" => Ok(doc! { "display_price": 1, "_id": 1 }),
+            "price_desc" => Ok(doc! { "display_price": -1, "_id": -1 }),
+            "name" => Ok(doc! { "name": 1, "_id": 1 }),
+            "popularity" => Ok(doc! { "sold_count": -1, "_id": -1 }),
+            "rating" => Ok(doc! { "rating.average": -1, "rating.count": -1, "_id": -1 }),
+            other => Err(format!("Unknown sort '{}'", other)),
+        }
+    }
+    
+    // Helper method to match user input literally in a `$regex`
+    fn escape_regex(text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if "\\^$.|?*+()[]{}".contains(c) {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+    
+    // Only the fields sent are changed. A rename that changes the slug keeps
+    // the old one so links to it redirect.
+    pub async fn update_product(&self, id: &str, product_: UpdateProductSchema) -> Result<Option<Product>, String> {
+        if let Err(e) = product_.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        
+        let current = match self.crud.get_product(id).await?.filter(|product| product.deleted_at.is_none()) {
+            Some(product) => product,
+            None => return Ok(None),
+        };
+        
+        // Prices and stock of products sold in variants are kept on the variants
+        let sets_price_or_stock = product_.price.is_some()
+            || product_.sale_price.is_some()
+            || product_.clear_sale_price
+            || product_.stock_quantity.is_some();
+        if sets_price_or_stock && !current.variants.is_empty() {
+            return Err("This product is sold in variants; change prices and stock on the variants".to_string());
+        }
+        if product_.clear_sale_price && product_.sale_price.is_some() {
+            return Err("Send either sale_price or clear_sale_price, not both".to_string());
+        }
+        if product_.clear_tax_class && product_.tax_class.is_some() {
+            return Err("Send either tax_class or clear_tax_class, not both".to_string());
+        }
+        let sale_price = if product_.clear_sale_price { None } else { product_.sale_price.or(current.sale_price) };
+        Self::check_sale_price(product_.price.unwrap_or(current.price), sale_price)?;
+        // Stock moves by the difference from what the admin saw
+        let stock_delta = product_.stock_quantity
+            .map(|stock_quantity| stock_quantity - current.stock_quantity)
+            .filter(|delta| *delta != 0);
+        
+        let mut set = doc! {};
+        if let Some(name) = product_.name {
+            let name = name.trim().to_string();
+            if !slug_service::slug_fits(&current.slug, &name) {
+                let slug = self.crud.unique_slug(&name, Some(&object_id)).await?;
+                set.insert("previous_slugs", slug_service::previous_slugs_after(&current.previous_slugs, &current.slug, &slug));
+                set.insert("slug", slug);
+            }
+            set.insert("name", name);
+        }
+        if let Some(description) = product_.description {
+            set.insert("description", description);
+        }
+        if let Some(price) = product_.price {
+            set.insert("price", price);
+        }
+        if let Some(sale_price) = product_.sale_price {
+            set.insert("sale_price", sale_price);
+        }
+        if product_.clear_sale_price {
+            set.insert("sale_price", bson::Bson::Null);
+        }
+        // Specs must fit the category; moving a product checks the specs it already has
+        if product_.category_id.is_some() || product_.specs.is_some() {
+            let category_id = match product_.category_id {
+                Some(category_id) => ObjectId::parse_str(&category_id)
+                    .map_err(|_| "Invalid category ID".to_string())?,
+                None => current.category_id,
+            };
+            let specs = self.crud.category(&category_id).await?
+                .ok_or_else(|| "Category not found".to_string())?
+                .check_specs(product_.specs.unwrap_or(current.specs))?;
+            let specs = bson::to_bson(&specs)
+                .map_err(|e| format!("Failed to encode specs: {}", e))?;
+            set.insert("category_id", category_id);
+            set.insert("specs", specs);
+        }
+        if let Some(component) = product_.component {
+            let component = bson::to_bson(&component.normalized()?)
+                .map_err(|e| format!("Failed to encode component: {}", e))?;
+            set.insert("component", component);
+        }
+        if let Some(brand_id) = product_.brand_id {
+            let brand_id = ObjectId::parse_str(&brand_id)
+                .map_err(|_| "Invalid brand ID".to_string())?;
+            let brand_name = self.crud.brand_name(&brand_id).await?
+                .ok_or_else(|| "Brand not found".to_string())?;
+            set.insert("brand_id", brand_id);
+            set.insert("brand_name", brand_name);
+        }
+        if let Some(tax_class) = product_.tax_class {
+            set.insert("tax_class", self.crud.check_tax_class(&tax_class).await?);
+        }
+        if product_.clear_tax_class {
+            set.insert("tax_class", bson::Bson::Null);
+        }
+        if let Some(weight_grams) = product_.weight_grams {
+            set.insert("weight_grams", weight_grams);
+        }
+        if let Some(dimensions) = product_.dimensions {
+            let dimensions = bson::to_bson(&ProductDimensions::from(dimensions))
+                .map_err(|e| format!("Failed to encode dimensions: {}", e))?;
+            set.insert("dimensions", dimensions);
+        }
+        if let Some(is_active) = product_.is_active {
+            set.insert("is_active", is_active);
+        }
+        if let Some(is_featured) = product_.is_featured {
+            set.insert("is_featured", is_featured);
+        }
+        if let Some(is_new_arrival) = product_.is_new_arrival {
+            set.insert("is_new_arrival", is_new_arrival);
+        }
+        if let Some(is_best_seller) = product_.is_best_seller {
+            set.insert("is_best_seller", is_best_seller);
+        }
+        
+        let product = self.crud.update_product(&object_id, set, stock_delta).await?;
+        if product.is_none() && stock_delta.is_some() {
+            return Err("Product not found or its stock changed, please try again".to_string());
+        }
+        Ok(product)
+    }
+    
+    pub async fn delete_product(&self, id: &str) -> Result<bool, String> {
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        self.crud.delete_product(&object_id).await
+    }
+    
+    async fn get_live_product(&self, id: &ObjectId) -> Result<Product, String> {
+        self.crud.get_product(&id.to_hex()).await?
+            .filter(|product| product.deleted_at.is_none())
+            .ok_or_else(|| "Product not found".to_string())
+    }
+    
+    // A sale price has to undercut the regular price
+    fn check_sale_price(price: i64, sale_price: Option<i64>) -> Result<(), String> {
+        match sale_price {
+            Some(sale_price) if sale_price >= price => Err("Sale price must be lower than the price".to_string()),
+            _ => Ok(()),
+        }
+    }
+    
+    // A variant must pick exactly one listed value for every option, in a
+    // combination no other variant of the product has
+    fn check_variant_options(product: &Product, options: &BTreeMap<String, String>, except: Option<&ObjectId>) -> Result<(), String> {
+        if product.options.is_empty() {
+            return Err("Set the product's options before adding variants".to_string());
+        }
+        for option in &product.options {
+            let value = options.get(&option.name)
+                .ok_or_else(|| format!("Choose a value for {}", option.name))?;
+            if !option.values.contains(value) {
+                return Err(format!("{} is not a value of {}", value, option.name));
+            }
+        }
+        if let Some(unknown) = options.keys().find(|name| !product.options.iter().any(|option| &option.name == *name)) {
+            return Err(format!("{} is not an option of this product", unknown));
+        }
+        if product.variants.iter().any(|variant| Some(&variant.id) != except && &variant.options == options) {
+            return Err("Another variant already has these options".to_string());
+        }
+        Ok(())
+    }
+    
+    async fn check_sku(&self, sku: &str, except: Option<&ObjectId>) -> Result<(), String> {
+        if self.crud.sku_in_use(sku, except).await? {
+            return Err(format!("SKU {} is already in use", sku));
+        }
+        Ok(())
+    }
+    
+    // Recompute the product's summary prices and stock after a variant change
+    async fn refresh_variant_summary(&self, product: Product) -> Result<Product, String> {
+        let id = product.id.ok_or_else(|| "Product has no ID".to_string())?;
+        self.crud.refresh_variant_summary(&id, product.variant_summary()).await?
+            .ok_or_else(|| "Product not found".to_string())
+    }
+    
+    pub async fn set_options(&self, id: &str, data: ProductOptionsSchema) -> Result<Product, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let product = self.get_live_product(&object_id).await?;
+        
+        let mut options: Vec<ProductOption> = Vec::new();
+        for option in data.options {
+            let name = option.name.trim().to_string();
+            if options.iter().any(|existing| existing.name.eq_ignore_ascii_case(&name)) {
+                return Err(format!("Option {} is listed twice", name));
+            }
+            let mut values: Vec<String> = Vec::new();
+            for value in option.values {
+                let value = value.trim().to_string();
+                if value.is_empty() || value.len() > 50 {
+                    return Err(format!("Values of {} must be 1 to 50 characters", name));
+                }
+                if !values.contains(&value) {
+                    values.push(value);
+                }
+            }
+            options.push(ProductOption { name, values });
+        }
+        
+        let mut reshaped = product.clone();
+        reshaped.options = options.clone();
+        for variant in &product.variants {
+            Self::check_variant_options(&reshaped, &variant.options, Some(&variant.id))
+                .map_err(|e| format!("Variant {} doesn't fit the new options: {}", variant.sku, e))?;
+        }
+        
+        self.crud.set_options(&object_id, &options).await?
+            .ok_or_else(|| "Product not found".to_string())
+    }
+    
+    pub async fn add_variant(&self, id: &str, data: CreateVariantSchema) -> Result<Product, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let product = self.get_live_product(&object_id).await?;
+        
+        let sku = data.sku.trim().to_string();
+        let options: BTreeMap<String, String> = data.options.into_iter()
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect();
+        Self::check_variant_options(&product, &options, None)?;
+        Self::check_sale_price(data.price, data.sale_price)?;
+        self.check_sku(&sku, None).await?;
+        
+        let variant = ProductVariant {
+            id: ObjectId::new(),
+            sku,
+            options,
+            price: data.price,
+            sale_price: data.sale_price,
+            stock_quantity: data.stock_quantity,
+            images: data.images,
+            is_active: data.is_active,
+        };
+        let product = self.crud.add_variant(&object_id, &variant).await?
+            .ok_or_else(|| "Product not found".to_string())?;
+        self.refresh_variant_summary(product).await
+    }
+    
+    pub async fn update_variant(&self, id: &str, variant_id: &str, data: UpdateVariantSchema) -> Result<Product, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let variant_oid = ObjectId::parse_str(variant_id)
+            .map_err(|_| "Invalid variant ID".to_string())?;
+        let product = self.get_live_product(&object_id).await?;
+        let variant = product.variant(&variant_oid)
+            .ok_or_else(|| "Variant not found".to_string())?;
+        if data.clear_sale_price && data.sale_price.is_some() {
+            return Err("Send either sale_price or clear_sale_price, not both".to_string());
+        }
+        let sale_price = if data.clear_sale_price { None } else { data.sale_price.or(variant.sale_price) };
+        Self::check_sale_price(data.price.unwrap_or(variant.price), sale_price)?;
+        // Stock moves by the difference from what the admin saw, so sales
+        // made in the meantime aren't written over
+        let stock_delta = data.stock_quantity
+            .map(|stock_quantity| stock_quantity - variant.stock_quantity)
+            .filter(|delta| *delta != 0);
+        
+        let mut fields = doc! {};
+        if let Some(sku) = data.sku {
+            let sku = sku.trim().to_string();
+            self.check_sku(&sku, Some(&variant_oid)).await?;
+            fields.insert("sku", sku);
+        }
+        if let Some(options) = data.options {
+            let options: BTreeMap<String, String> = options.into_iter()
+                .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                .collect();
+            Self::check_variant_options(&product, &options, Some(&variant_oid))?;
+            let options = bson::to_bson(&options)
+                .map_err(|e| format!("Failed to encode options: {}", e))?;
+            fields.insert("options", options);
+        }
+        if let Some(price) = data.price {
+            fields.insert("price", price);
+        }
+        if let Some(sale_price) = data.sale_price {
+            fields.insert("sale_price", sale_price);
+        }
+        if data.clear_sale_price {
+            fields.insert("sale_price", bson::Bson::Null);
+        }
+        if let Some(images) = data.images {
+            fields.insert("images", images);
+        }
+        if let Some(is_active) = data.is_active {
+            fields.insert("is_active", is_active);
+        }
+        
+        let product = self.crud.update_variant(&object_id, &variant_oid, fields, stock_delta).await?
+            .ok_or_else(|| "Variant not found or its stock changed, please try again".to_string())?;
+        self.refresh_variant_summary(product).await
+    }
+    
+    pub async fn remove_variant(&self, id: &str, variant_id: &str) -> Result<Product, String> {
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let variant_oid = ObjectId::parse_str(variant_id)
+            .map_err(|_| "Invalid variant ID".to_string())?;
+        
+        let product = self.crud.remove_variant(&object_id, &variant_oid).await?
+            .ok_or_else(|| "Variant not found".to_string())?;
+        self.refresh_variant_summary(product).await
+    }
+    
+    // Upload images to the end of the gallery. If one fails, those already
+    // uploaded are deleted again and the gallery is left as it was.
+    pub async fn upload_images(&self, id: &str, data: UploadProductImagesSchema) -> Result<Product, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let product = self.get_live_product(&object_id).await?;
+        if product.images.len() + data.images.len() > MAX_PRODUCT_IMAGES {
+            return Err(format!("A product can have at most {} images", MAX_PRODUCT_IMAGES));
+        }
+        
+        let upload_service = ProductImageUploadService::new();
+        let mut images = Vec::new();
+        for image in data.images {
+            match upload_service.upload_product_image(&image.data, &object_id.to_hex()).await {
+                Ok(uploaded) => images.push(ProductImage {
+                    id: ObjectId::new(),
+                    url: uploaded.url,
+                    public_id: uploaded.public_id,
+                    alt_text: image.alt_text.map(|alt_text| alt_text.trim().to_string()),
+                }),
+                Err(e) => {
+                    Self::discard_uploads(&upload_service, &images).await;
+                    return Err(format!("Image upload failed: {:?}", e));
+                }
+            }
+        }
+        
+        match self.crud.add_images(&object_id, &images).await {
+            Ok(Some(product)) => Ok(product),
+            Ok(None) => {
+                Self::discard_uploads(&upload_service, &images).await;
+                Err("Product not found".to_string())
+            }
+            Err(e) => {
+                Self::discard_uploads(&upload_service, &images).await;
+                Err(e)
+            }
+        }
+    }
+    
+    // Best effort: an asset left behind only costs storage
+    async fn discard_uploads(upload_service: &ProductImageUploadService, images: &[ProductImage]) {
+        for image in images {
+            if let Err(e) = upload_service.delete_product_image(&image.public_id).await {
+                eprintln!("Failed to delete uploaded image {}: {:?}", image.public_id, e);
+            }
+        }
+    }
+    
+    pub async fn reorder_images(&self, id: &str, data: ReorderProductImagesSchema) -> Result<Product, String> {
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let product = self.get_live_product(&object_id).await?;
+        
+        let mut images: Vec<ProductImage> = Vec::new();
+        for image_id in &data.image_ids {
+            let image_id = ObjectId::parse_str(image_id)
+                .map_err(|_| "Invalid image ID".to_string())?;
+            if images.iter().any(|image| image.id == image_id) {
+                return Err(format!("Image {} is listed twice", image_id.to_hex()));
+            }
+            let image = product.images.iter()
+                .find(|image| image.id == image_id)
+                .ok_or_else(|| format!("Image {} is not part of this product", image_id.to_hex()))?;
+            images.push(image.clone());
+        }
+        if images.len() != product.images.len() {
+            return Err("List every image of the product".to_string());
+        }
+        
+        self.save_image_order(&object_id, &images).await
+    }
+    
+    // The primary image is the first one; the rest keep their order
+    pub async fn set_primary_image(&self, id: &str, image_id: &str) -> Result<Product, String> {
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let image_oid = ObjectId::parse_str(image_id)
+            .map_err(|_| "Invalid image ID".to_string())?;
+        let mut product = self.get_live_product(&object_id).await?;
+        
+        let position = product.images.iter()
+            .position(|image| image.id == image_oid)
+            .ok_or_else(|| "Image not found".to_string())?;
+        if position == 0 {
+            return Ok(product);
+        }
+        let image = product.images.remove(position);
+        product.images.insert(0, image);
+        
+        self.save_image_order(&object_id, &product.images).await
+    }
+    
+    async fn save_image_order(&self, id: &ObjectId, images: &[ProductImage]) -> Result<Product, String> {
+        self.crud.set_image_order(id, images).await?
+            .ok_or_else(|| "The product's images changed meanwhile, please try again".to_string())
+    }
+    
+    pub async fn update_image(&self, id: &str, image_id: &str, data: UpdateProductImageSchema) -> Result<Product, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let image_oid = ObjectId::parse_str(image_id)
+            .map_err(|_| "Invalid image ID".to_string())?;
+        
+        let alt_text = data.alt_text
+            .map(|alt_text| alt_text.trim().to_string())
+            .filter(|alt_text| !alt_text.is_empty());
+        self.crud.set_image_alt_text(&object_id, &image_oid, alt_text).await?
+            .ok_or_else(|| "Image not found".to_string())
+    }
+    
+    // Removes the Cloudinary asset first, so a failed delete leaves the image in place
+    pub async fn delete_image(&self, id: &str, image_id: &str) -> Result<Product, String> {
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let image_oid = ObjectId::parse_str(image_id)
+            .map_err(|_| "Invalid image ID".to_string())?;
+        let product = self.get_live_product(&object_id).await?;
+        
+        let image = product.images.iter()
+            .find(|image| image.id == image_oid)
+            .ok_or_else(|| "Image not found".to_string())?;
+        ProductImageUploadService::new().delete_product_image(&image.public_id).await
+            .map_err(|e| format!("Image delete failed: {:?}", e))?;
+        
+        self.crud.remove_image(&object_id, &image_oid).await?
+            .ok_or_else(|| "Image not found".to_string())
+    }
+    
+    // Options and the variants customers can choose from
+    pub async fn get_variants(&self, id: &str) -> Result<VariantListResponse, String> {
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let product = self.get_live_product(&object_id).await?;
+        if !product.is_active {
+            return Err("Product not found".to_string());
+        }
+        
+        Ok(VariantListResponse {
+            options: product.options,
+            variants: product.variants.into_iter().filter(|variant| variant.is_active).collect(),
+            price_range: product.price_range,
+        })
+    }
+    
+    // Resolve chosen option values to a variant, and mark which other values
+    // still lead to something that can be bought
+    pub async fn select_variant(&self, id: &str, selection: BTreeMap<String, String>) -> Result<VariantSelection, String> {
+        let object_id = ObjectId::parse_str(id)
+            .map_err(|_| "Invalid product ID".to_string())?;
+        let product = self.get_live_product(&object_id).await?;
+        if !product.is_active {
+            return Err("Product not found".to_string());
+        }
+        if product.options.is_empty() {
+            return Err("This product has no options to choose from".to_string());
+        }
+        
+        for (name, value) in &selection {
+            let option = product.options.iter().find(|option| &option.name == name)
+                .ok_or_else(|| format!("{} is not an option of this product", name))?;
+            if !option.values.contains(value) {
+                return Err(format!("{} is not a value of {}", value, name));
+            }
+        }
+        
+        let buyable: Vec<&ProductVariant> = product.variants.iter()
+            .filter(|variant| variant.is_active && variant.stock_quantity > 0)
+            .collect();
+        let options = product.options.iter()
+            .map(|option| {
+                let values = option.values.iter()
+                    .map(|value| {
+                        let available = buyable.iter().any(|variant| {
+                            variant.options.get(&option.name) == Some(value)
+                                && selection.iter()
+                                    .filter(|(name, _)| *name != &option.name)
+                                    .all(|(name, chosen)| variant.options.get(name) == Some(chosen))
+                        });
+                        OptionValueAvailability {
+                            value: value.clone(),
+                            selected: selection.get(&option.name) == Some(value),
+                            available,
+                        }
+                    })
+                    .collect();
+                OptionAvailability { name: option.name.clone(), values }
+            })
+            .collect();
+        
+        let chosen: HashSet<&String> = selection.keys().collect();
+        let variant = if product.options.iter().all(|option| chosen.contains(&option.name)) {
+            product.variants.iter()
+                .find(|variant| variant.is_active && variant.options == selection)
+                .cloned()
+        } else {
+            None
+        };
+        
+        Ok(VariantSelection { selected: selection, variant, options })
+    }
+    
+    // Set status flags on many products, reporting the outcome for each ID
+    pub async fn bulk_update_products(&self, data: BulkProductUpdateSchema) -> Result<BulkProductUpdateResponse, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+        
+        let mut set = doc! {};
+        for (field, value) in [
+            ("is_active", data.is_active),
+            ("is_featured", data.is_featured),
+            ("is_new_arrival", data.is_new_arrival),
+            ("is_best_seller", data.is_best_seller),
+        ] {
+            if let Some(value) = value {
+                set.insert(field, value);
+            }
+        }
+        if set.is_empty() {
+            return Err("No changes given".to_string());
+        }
+        
+        let mut ids = Vec::new();
+        for id in &data.ids {
+            if let Ok(object_id) = ObjectId::parse_str(id) {
+                ids.push(object_id);
+            }
+        }
+        ids.sort();
+        ids.dedup();
+        let updated = if ids.is_empty() {
+            Vec::new()
+        } else {
+            self.crud.update_products(&ids, set).await?
+        };
+        
+        let results: Vec<BulkProductResult> = data.ids.iter()
+            .map(|id| {
+                let error = match ObjectId::parse_str(id) {
+                    Err(_) => Some("Invalid product ID"),
+                    Ok(object_id) if !updated.contains(&object_id) => Some("Product not found"),
+                    Ok(_) => None,
+                };
+                BulkProductResult {
+                    id: id.clone(),
+                    success: error.is_none(),
+                    error: error.map(str::to_string),
+                }
+            })
+            .collect();
+        
+        let failed = results.iter().filter(|result| !result.success).count();
+        Ok(BulkProductUpdateResponse {
+            updated: results.len() - failed,
+            failed,
+            results,
+        })
     }
     
     pub async fn get_featured_products(&self, limit: Option<i64>) -> Result<Vec<Product>, String> {
@@ -50,6 +796,27 @@ impl ProductController {
         self.crud.get_best_sellers(limit).await
     }
     
+    // Respond with a product list, adding VAT inclusive and exclusive
+    // prices when the caller asked for them with ?include_tax=true
+    async fn products_response(
+        products: Vec<Product>,
+        query: &TaxPriceQuery,
+        tax_crud: &web::Data<TaxCrud>,
+        category_crud: &web::Data<CategoryCrud>,
+    ) -> HttpResponse {
+        if !query.include_tax {
+            return HttpResponse::Ok().json(products);
+        }
+        
+        let tax_controller = TaxController::new(tax_crud.get_ref().clone(), category_crud.get_ref().clone());
+        match tax_controller.price_products(products).await {
+            Ok(products) => HttpResponse::Ok().json(products),
+            Err(error) => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": error
+            })),
+        }
+    }
+    
     // HTTP handler functions:
     
     // Create product handler
@@ -67,14 +834,316 @@ impl ProductController {
         }
     }
     
-    // GET all products handler
+    // PATCH product handler (admin)
+    pub async fn update_product_handler(
+        req: HttpRequest,
+        id: web::Path<String>,
+        product_: web::Json<UpdateProductSchema>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.update_product(&id, product_.into_inner()).await {
+            Ok(Some(product)) => Ok(HttpResponse::Ok().json(product)),
+            Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Product not found"
+            }))),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // DELETE product handler (admin)
+    pub async fn delete_product_handler(
+        req: HttpRequest,
+        id: web::Path<String>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.delete_product(&id).await {
+            Ok(true) => Ok(HttpResponse::NoContent().finish()),
+            Ok(false) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Product not found"
+            }))),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // POST product images handler (admin)
+    pub async fn upload_images_handler(
+        req: HttpRequest,
+        id: web::Path<String>,
+        body: web::Json<UploadProductImagesSchema>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.upload_images(&id, body.into_inner()).await {
+            Ok(product) => Ok(HttpResponse::Created().json(product)),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // PUT product image order handler (admin)
+    pub async fn reorder_images_handler(
+        req: HttpRequest,
+        id: web::Path<String>,
+        body: web::Json<ReorderProductImagesSchema>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.reorder_images(&id, body.into_inner()).await {
+            Ok(product) => Ok(HttpResponse::Ok().json(product)),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // PUT primary product image handler (admin)
+    pub async fn set_primary_image_handler(
+        req: HttpRequest,
+        path: web::Path<(String, String)>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let (id, image_id) = path.into_inner();
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.set_primary_image(&id, &image_id).await {
+            Ok(product) => Ok(HttpResponse::Ok().json(product)),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // PATCH product image handler (admin)
+    pub async fn update_image_handler(
+        req: HttpRequest,
+        path: web::Path<(String, String)>,
+        body: web::Json<UpdateProductImageSchema>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let (id, image_id) = path.into_inner();
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.update_image(&id, &image_id, body.into_inner()).await {
+            Ok(product) => Ok(HttpResponse::Ok().json(product)),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // DELETE product image handler (admin)
+    pub async fn delete_image_handler(
+        req: HttpRequest,
+        path: web::Path<(String, String)>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let (id, image_id) = path.into_inner();
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.delete_image(&id, &image_id).await {
+            Ok(product) => Ok(HttpResponse::Ok().json(product)),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // PUT product options handler (admin)
+    pub async fn set_options_handler(
+        req: HttpRequest,
+        id: web::Path<String>,
+        body: web::Json<ProductOptionsSchema>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.set_options(&id, body.into_inner()).await {
+            Ok(product) => Ok(HttpResponse::Ok().json(product)),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // POST variant handler (admin)
+    pub async fn add_variant_handler(
+        req: HttpRequest,
+        id: web::Path<String>,
+        body: web::Json<CreateVariantSchema>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.add_variant(&id, body.into_inner()).await {
+            Ok(product) => Ok(HttpResponse::Created().json(product)),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // PATCH variant handler (admin)
+    pub async fn update_variant_handler(
+        req: HttpRequest,
+        path: web::Path<(String, String)>,
+        body: web::Json<UpdateVariantSchema>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let (id, variant_id) = path.into_inner();
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.update_variant(&id, &variant_id, body.into_inner()).await {
+            Ok(product) => Ok(HttpResponse::Ok().json(product)),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // DELETE variant handler (admin)
+    pub async fn remove_variant_handler(
+        req: HttpRequest,
+        path: web::Path<(String, String)>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let (id, variant_id) = path.into_inner();
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.remove_variant(&id, &variant_id).await {
+            Ok(product) => Ok(HttpResponse::Ok().json(product)),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // GET variants handler
+    pub async fn get_variants_handler(
+        id: web::Path<String>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.get_variants(&id).await {
+            Ok(variants) => Ok(HttpResponse::Ok().json(variants)),
+            Err(error) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // GET variant selection handler, e.g. ?RAM=16GB&Storage=512GB
+    pub async fn select_variant_handler(
+        id: web::Path<String>,
+        selection: web::Query<BTreeMap<String, String>>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.select_variant(&id, selection.into_inner()).await {
+            Ok(selection) => Ok(HttpResponse::Ok().json(selection)),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // PATCH bulk product flags handler (admin)
+    pub async fn bulk_update_products_handler(
+        req: HttpRequest,
+        body: web::Json<BulkProductUpdateSchema>,
+        crud: web::Data<ProductCrud>,
+    ) -> Result<HttpResponse> {
+        if let Err(e) = AdminAuthentication::check_admin(&req).await {
+            return Ok(e.error_response());
+        }
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.bulk_update_products(body.into_inner()).await {
+            Ok(response) => Ok(HttpResponse::Ok().json(response)),
+            Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // GET products handler: filtered, sorted and paginated
     pub async fn get_products_handler(
+        req: HttpRequest,
+        filter: web::Query<ProductFilter>,
+        query: web::Query<TaxPriceQuery>,
         crud: web::Data<ProductCrud>,
+        tax_crud: web::Data<TaxCrud>,
+        category_crud: web::Data<CategoryCrud>,
     ) -> Result<HttpResponse> {
         let controller = ProductController::new(crud.get_ref().clone());
         
-        match controller.get_all_products().await {
-            Ok(products) => Ok(HttpResponse::Ok().json(products)),
+        let params = web::Query::<Vec<(String, String)>>::from_query(req.query_string())
+            .map(|params| params.into_inner())
+            .unwrap_or_default();
+        
+        let list = match controller.get_products(filter.into_inner(), params).await {
+            Ok(list) => list,
+            Err(error) => return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            }))),
+        };
+        if !query.include_tax {
+            return Ok(HttpResponse::Ok().json(list));
+        }
+        
+        let tax_controller = TaxController::new(tax_crud.get_ref().clone(), category_crud.get_ref().clone());
+        match tax_controller.price_products(list.products).await {
+            Ok(products) => Ok(HttpResponse::Ok().json(ProductListResponse {
+                products,
+                total: list.total,
+                page: list.page,
+                limit: list.limit,
+            })),
             Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
                 "error": error
             }))),
@@ -84,11 +1153,66 @@ impl ProductController {
     // GET single product handler
     pub async fn get_product_handler(
         id: web::Path<String>,
+        query: web::Query<TaxPriceQuery>,
         crud: web::Data<ProductCrud>,
+        tax_crud: web::Data<TaxCrud>,
+        category_crud: web::Data<CategoryCrud>,
     ) -> Result<HttpResponse> {
         let controller = ProductController::new(crud.get_ref().clone());
         
         match controller.get_product(&id).await {
+            Ok(Some(product)) if product.deleted_at.is_some() => Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Product not found"
+            }))),
+            Ok(Some(product)) if query.include_tax => {
+                let tax_controller = TaxController::new(tax_crud.get_ref().clone(), category_crud.get_ref().clone());
+                match tax_controller.price_products(vec![product]).await {
+                    Ok(mut priced) => Ok(HttpResponse::Ok().json(priced.remove(0))),
+                    Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                        "error": error
+                    }))),
+                }
+            }
+            Ok(Some(product)) => Ok(HttpResponse::Ok().json(product)),
+            Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Product not found"
+            }))),
+            Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": error
+            }))),
+        }
+    }
+    
+    // GET product by slug handler; an old slug redirects to the current one
+    pub async fn get_product_by_slug_handler(
+        req: HttpRequest,
+        slug: web::Path<String>,
+        query: web::Query<TaxPriceQuery>,
+        crud: web::Data<ProductCrud>,
+        tax_crud: web::Data<TaxCrud>,
+        category_crud: web::Data<CategoryCrud>,
+    ) -> Result<HttpResponse> {
+        let controller = ProductController::new(crud.get_ref().clone());
+        
+        match controller.get_product_by_slug(&slug).await {
+            Ok(Some(product)) if product.slug != *slug => {
+                let mut location = format!("/api/products/slug/{}", product.slug);
+                if !req.query_string().is_empty() {
+                    location = format!("{}?{}", location, req.query_string());
+                }
+                Ok(HttpResponse::MovedPermanently()
+                    .insert_header(("Location", location))
+                    .finish())
+            }
+            Ok(Some(product)) if query.include_tax => {
+                let tax_controller = TaxController::new(tax_crud.get_ref().clone(), category_crud.get_ref().clone());
+                match tax_controller.price_products(vec![product]).await {
+                    Ok(mut priced) => Ok(HttpResponse::Ok().json(priced.remove(0))),
+                    Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                        "error": error
+                    }))),
+                }
+            }
             Ok(Some(product)) => Ok(HttpResponse::Ok().json(product)),
             Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
                 "error": "Product not found"
@@ -101,12 +1225,15 @@ impl ProductController {
     
     // GET featured products handler
     pub async fn get_featured_products_handler(
+        query: web::Query<TaxPriceQuery>,
         crud: web::Data<ProductCrud>,
+        tax_crud: web::Data<TaxCrud>,
+        category_crud: web::Data<CategoryCrud>,
     ) -> Result<HttpResponse> {
         let controller = ProductController::new(crud.get_ref().clone());
         
         match controller.get_featured_products(Some(10)).await {
-            Ok(products) => Ok(HttpResponse::Ok().json(products)),
+            Ok(products) => Ok(Self::products_response(products, &query, &tax_crud, &category_crud).await),
             Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
                 "error": error
             }))),
@@ -115,12 +1242,15 @@ impl ProductController {
     
     // GET new arrivals handler
     pub async fn get_new_arrivals_handler(
+        query: web::Query<TaxPriceQuery>,
         crud: web::Data<ProductCrud>,
+        tax_crud: web::Data<TaxCrud>,
+        category_crud: web::Data<CategoryCrud>,
     ) -> Result<HttpResponse> {
         let controller = ProductController::new(crud.get_ref().clone());
         
         match controller.get_new_arrivals(Some(10)).await {
-            Ok(products) => Ok(HttpResponse::Ok().json(products)),
+            Ok(products) => Ok(Self::products_response(products, &query, &tax_crud, &category_crud).await),
             Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
                 "error": error
             }))),
@@ -129,12 +1259,15 @@ impl ProductController {
     
     // GET best sellers handler
     pub async fn get_best_sellers_handler(
+        query: web::Query<TaxPriceQuery>,
         crud: web::Data<ProductCrud>,
+        tax_crud: web::Data<TaxCrud>,
+        category_crud: web::Data<CategoryCrud>,
     ) -> Result<HttpResponse> {
         let controller = ProductController::new(crud.get_ref().clone());
         
         match controller.get_best_sellers(Some(10)).await {
-            Ok(products) => Ok(HttpResponse::Ok().json(products)),
+            Ok(products) => Ok(Self::products_response(products, &query, &tax_crud, &category_crud).await),
             Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
                 "error": error
             }))),
@@ -1,28 +1,65 @@
-use bson::{doc, oid::ObjectId};
-use mongodb::{Collection, Database};
+use bson::{doc, oid::ObjectId, Document};
+use chrono::{Duration, Utc};
+use mongodb::{
+    error::ErrorKind,
+    options::{FindOneAndUpdateOptions, FindOptions, IndexOptions, ReturnDocument},
+    Collection, Database, IndexModel,
+};
 use futures_util::TryStreamExt; 
+use crate::module::brand::model::Brand;
+use crate::module::category::model::Category;
+use crate::module::tax::crud::TaxCrud;
+use crate::service::{migration_service, slug_service};
 use crate::module::product::{
-    model::Product, 
+    model::{
+        ComponentProfile, InventoryReservation, PriceRange, Product, ProductImage, ProductOption, ProductVariant,
+        ReservationStatus, ReservedItem,
+    },
     schema::CreateProductSchema
 };
 
+// Server error code for dropping an index that doesn't exist
+const INDEX_NOT_FOUND: i32 = 27;
+
+// How long a purchase may hold stock while waiting for payment
+pub fn stock_reservation_ttl() -> Duration {
+    let minutes = std::env::var("STOCK_RESERVATION_MINUTES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(30);
+    Duration::minutes(minutes)
+}
+
 #[derive(Clone)]
 pub struct ProductCrud {
     collection: Collection<Product>,
+    reservations: Collection<InventoryReservation>,
+    brands: Collection<Brand>,
+    categories: Collection<Category>,
+    tax: TaxCrud,
 }
 
 impl ProductCrud {
     pub fn new(db: &Database) -> Self {
         let collection = db.collection("products");
-        Self { collection }
+        let reservations = db.collection("inventory_reservations");
+        let brands = db.collection("brands");
+        let categories = db.collection("categories");
+        let tax = TaxCrud::new(db);
+        Self { collection, reservations, brands, categories, tax }
     }
-    
+
     pub async fn create_product(&self, product_: CreateProductSchema) -> Result<Product, String> {  // Fixed: added colon
         // Convert string IDs to ObjectIds
         let category_id = ObjectId::parse_str(&product_.category_id)  // Fixed: use correct parameter name
             .map_err(|_| "Invalid category ID".to_string())?;
         let brand_id = ObjectId::parse_str(&product_.brand_id)  // Fixed: use correct parameter name
             .map_err(|_| "Invalid brand ID".to_string())?;
+        let brand_name = self.brand_name(&brand_id).await?
+            .ok_or_else(|| "Brand not found".to_string())?;
+        let specs = self.category(&category_id).await?
+            .ok_or_else(|| "Category not found".to_string())?
+            .check_specs(product_.specs)?;
         
         // Create product model
         let mut product = Product::new(
@@ -35,23 +72,100 @@ impl ProductCrud {
         );
         
         // Set optional fields
+        product.brand_name = brand_name;
+        product.specs = specs;
+        product.component = product_.component.map(ComponentProfile::normalized).transpose()?;
         product.sale_price = product_.sale_price;  // Fixed: use correct parameter name
         product.stock_quantity = product_.stock_quantity;  // Fixed: use correct parameter name
         product.is_featured = product_.is_featured;  // Fixed: use correct parameter name
         product.is_best_seller = product_.is_best_seller;  // Fixed: use correct parameter name
+        product.tax_class = match product_.tax_class {
+            Some(tax_class) => Some(self.check_tax_class(&tax_class).await?),
+            None => None,
+        };
+        product.weight_grams = product_.weight_grams;
+        product.dimensions = product_.dimensions.map(Into::into);
         
-        // Insert into database
-        match self.collection.insert_one(&product).await {
-            Ok(result) => {
-                if let Some(id) = result.inserted_id.as_object_id() {
-                    product.id = Some(id);
+        // Another insert may take the slug in between, so pick again if it does
+        for _ in 0..3 {
+            product.slug = slug_service::unique_slug(&self.collection.clone_with_type(), &product.name, None).await?;
+            match self.collection.insert_one(&product).await {
+                Ok(result) => {
+                    if let Some(id) = result.inserted_id.as_object_id() {
+                        product.id = Some(id);
+                    }
+                    return Ok(product);
                 }
-                Ok(product)
+                Err(e) if slug_service::is_duplicate_slug(&e) => continue,
+                Err(e) if migration_service::is_duplicate_key(&e, "sku") => {
+                    return Err(format!("SKU {} is already in use", product.sku));
+                }
+                Err(e) => return Err(format!("Failed to create product: {}", e)),
             }
-            Err(e) => Err(format!("Failed to create product: {}", e))
         }
+        Err("Failed to create product: no free slug".to_string())
     }
 
+    pub async fn category(&self, id: &ObjectId) -> Result<Option<Category>, String> {
+        match self.categories.find_one(doc! { "_id": id }).await {
+            Ok(category) => Ok(category),
+            Err(e) => Err(format!("Failed to retrieve category: {}", e))
+        }
+    }
+    
+    // The class normalised for storage; unknown classes are refused
+    pub async fn check_tax_class(&self, tax_class: &str) -> Result<String, String> {
+        self.tax.check_tax_class(tax_class).await
+    }
+    
+    // Categories defining any of the attribute keys, or only the given
+    // category when the listing is narrowed to one
+    pub async fn attribute_categories(&self, category_id: Option<&ObjectId>, keys: &[String]) -> Result<Vec<Category>, String> {
+        let mut filter = doc! { "attributes.key": { "$in": keys } };
+        if let Some(category_id) = category_id {
+            filter.insert("_id", category_id);
+        }
+        
+        match self.categories.find(filter).await {
+            Ok(mut cursor) => {
+                let mut categories = Vec::new();
+                while let Some(category) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve categories: {}", e))? {
+                    categories.push(category);
+                }
+                Ok(categories)
+            }
+            Err(e) => Err(format!("Failed to retrieve categories: {}", e))
+        }
+    }
+    
+    pub async fn brand_name(&self, brand_id: &ObjectId) -> Result<Option<String>, String> {
+        match self.brands.find_one(doc! { "_id": brand_id }).await {
+            Ok(brand) => Ok(brand.map(|brand| brand.name)),
+            Err(e) => Err(format!("Failed to retrieve brand: {}", e))
+        }
+    }
+    
+    // Copy brand names onto products saved before products carried them
+    pub async fn backfill_brand_names(&self) -> Result<u64, String> {
+        let mut cursor = self.brands.find(doc! {}).await
+            .map_err(|e| format!("Failed to retrieve brands: {}", e))?;
+        let mut updated = 0;
+        while let Some(brand) = cursor.try_next().await
+            .map_err(|e| format!("Failed to retrieve brands: {}", e))? {
+            let Some(brand_id) = brand.id else { continue };
+            let result = self.collection
+                .update_many(
+                    doc! { "brand_id": brand_id, "brand_name": { "$exists": false } },
+                    doc! { "$set": { "brand_name": &brand.name } },
+                )
+                .await
+                .map_err(|e| format!("Failed to copy brand names: {}", e))?;
+            updated += result.modified_count;
+        }
+        Ok(updated)
+    }
+    
     // Add these new methods:
     
     pub async fn get_product(&self, id: &str) -> Result<Option<Product>, String> {
@@ -64,8 +178,30 @@ impl ProductCrud {
         }
     }
     
-    pub async fn get_all_products(&self) -> Result<Vec<Product>, String> {
-        match self.collection.find(doc! { "is_active": true }).await {
+    // By current or old slug; deleted products are left out
+    pub async fn get_product_by_slug(&self, slug: &str) -> Result<Option<Product>, String> {
+        let mut filter = slug_service::slug_filter(slug);
+        filter.insert("deleted_at", bson::Bson::Null);
+        
+        match self.collection.find_one(filter).await {
+            Ok(product) => Ok(product),
+            Err(e) => Err(format!("Failed to retrieve product: {}", e))
+        }
+    }
+    
+    // A slug for `name` no other product answers to
+    pub async fn unique_slug(&self, name: &str, except: Option<&ObjectId>) -> Result<String, String> {
+        slug_service::unique_slug(&self.collection.clone_with_type(), name, except).await
+    }
+
+    // Give products saved with the old slug rules clean, unique slugs
+    pub async fn backfill_slugs(&self) -> Result<u64, String> {
+        slug_service::backfill_slugs(&self.collection.clone_with_type(), "name").await
+    }
+    
+    // Get several products at once, e.g. to price the lines of a cart
+    pub async fn get_products_by_ids(&self, ids: &[ObjectId]) -> Result<Vec<Product>, String> {
+        match self.collection.find(doc! { "_id": { "$in": ids } }).await {
             Ok(mut cursor) => {
                 let mut products = Vec::new();
                 while let Some(product) = cursor.try_next().await
@@ -78,65 +214,703 @@ impl ProductCrud {
         }
     }
     
-    // Get featured products
-    pub async fn get_featured_products(&self, limit: Option<i64>) -> Result<Vec<Product>, String> {
-        let _limit = limit.unwrap_or(10);
-        // Note: MongoDB driver version differences, removing options for now
-        match self.collection.find(doc! { "is_active": true, "is_featured": true }).await {
+    // One page of products matching `filter`, with the total number of matches.
+    // The price range and price sorts use `display_price`, the sale price when
+    // there is one, which is worked out in the pipeline.
+    pub async fn find_products(
+        &self,
+        filter: Document,
+        min_price: Option<i64>,
+        max_price: Option<i64>,
+        sort: Document,
+        skip: u64,
+        limit: i64,
+    ) -> Result<(Vec<Product>, u64), String> {
+        let mut price_range = doc! {};
+        if let Some(min_price) = min_price {
+            price_range.insert("$gte", min_price);
+        }
+        if let Some(max_price) = max_price {
+            price_range.insert("$lte", max_price);
+        }
+        let sorts_by_price = sort.contains_key("display_price");
+        let needs_price = sorts_by_price || !price_range.is_empty();
+
+        let mut pipeline = vec![doc! { "$match": filter }];
+        if !sorts_by_price {
+            // Sorting straight after the match lets the index do the work
+            pipeline.push(doc! { "$sort": sort.clone() });
+        }
+        if needs_price {
+            pipeline.push(doc! { "$addFields": { "display_price": { "$ifNull": ["$sale_price", "$price"] } } });
+        }
+        if !price_range.is_empty() {
+            pipeline.push(doc! { "$match": { "display_price": price_range } });
+        }
+
+        let mut count_pipeline = pipeline.clone();
+        count_pipeline.push(doc! { "$count": "total" });
+        let total = match self.collection.aggregate(count_pipeline).await {
+            Ok(mut cursor) => cursor.try_next().await
+                .map_err(|e| format!("Failed to count products: {}", e))?
+                .and_then(|result| result.get("total").and_then(|total| match total {
+                    bson::Bson::Int32(total) => Some(*total as u64),
+                    bson::Bson::Int64(total) => Some(*total as u64),
+                    _ => None,
+                }))
+                .unwrap_or(0),
+            Err(e) => return Err(format!("Failed to count products: {}", e)),
+        };
+
+        if sorts_by_price {
+            pipeline.push(doc! { "$sort": sort });
+        }
+        pipeline.push(doc! { "$skip": skip as i64 });
+        pipeline.push(doc! { "$limit": limit });
+
+        match self.collection.aggregate(pipeline).await {
             Ok(mut cursor) => {
                 let mut products = Vec::new();
-                while let Some(product) = cursor.try_next().await
-                    .map_err(|e| format!("Failed to retrieve featured products: {}", e))? {
+                while let Some(document) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve products: {}", e))? {
+                    let product: Product = bson::from_document(document)
+                        .map_err(|e| format!("Failed to decode product: {}", e))?;
                     products.push(product);
                 }
-                // In a real implementation, you'd apply the limit here
-                Ok(products.into_iter().take(_limit as usize).collect())
+                Ok((products, total))
             }
-            Err(e) => Err(format!("Failed to retrieve featured products: {}", e))
+            Err(e) => Err(format!("Failed to retrieve products: {}", e))
         }
     }
-    
-    // Get new arrivals (created within last 30 days)
-    pub async fn get_new_arrivals(&self, limit: Option<i64>) -> Result<Vec<Product>, String> {
-        use chrono::{Utc, Duration};
-        let _limit = limit.unwrap_or(10);
-        let thirty_days_ago = Utc::now() - Duration::days(30);
-        
-        // Note: MongoDB driver version differences, removing options for now
-        match self.collection.find(
-            doc! { 
-                "is_active": true, 
-                "created_at": { "$gte": thirty_days_ago }
-            }
-        ).await {
+
+    async fn find_section(&self, filter: Document, sort: Document, limit: Option<i64>, what: &str) -> Result<Vec<Product>, String> {
+        let options = FindOptions::builder()
+            .sort(sort)
+            .limit(limit.unwrap_or(10))
+            .build();
+
+        match self.collection.find(filter).with_options(options).await {
             Ok(mut cursor) => {
                 let mut products = Vec::new();
                 while let Some(product) = cursor.try_next().await
-                    .map_err(|e| format!("Failed to retrieve new arrivals: {}", e))? {
+                    .map_err(|e| format!("Failed to retrieve {}: {}", what, e))? {
                     products.push(product);
                 }
-                // In a real implementation, you'd apply the limit here
-                Ok(products.into_iter().take(_limit as usize).collect())
+                Ok(products)
             }
-            Err(e) => Err(format!("Failed to retrieve new arrivals: {}", e))
+            Err(e) => Err(format!("Failed to retrieve {}: {}", what, e))
         }
     }
     
+    // Get featured products
+    pub async fn get_featured_products(&self, limit: Option<i64>) -> Result<Vec<Product>, String> {
+        self.find_section(
+            doc! { "is_active": true, "is_featured": true },
+            doc! { "created_at": -1 },
+            limit,
+            "featured products",
+        ).await
+    }
+    
+    // Get new arrivals (created within last 30 days)
+    pub async fn get_new_arrivals(&self, limit: Option<i64>) -> Result<Vec<Product>, String> {
+        let thirty_days_ago = Utc::now() - Duration::days(30);
+        self.find_section(
+            doc! { "is_active": true, "created_at": { "$gte": bson::DateTime::from_chrono(thirty_days_ago) } },
+            doc! { "created_at": -1 },
+            limit,
+            "new arrivals",
+        ).await
+    }
+    
     // Get best sellers
     pub async fn get_best_sellers(&self, limit: Option<i64>) -> Result<Vec<Product>, String> {
-        let _limit = limit.unwrap_or(10);
-        // Note: MongoDB driver version differences, removing options for now
-        match self.collection.find(doc! { "is_active": true, "is_best_seller": true }).await {
-            Ok(mut cursor) => {
-                let mut products = Vec::new();
-                while let Some(product) = cursor.try_next().await
-                    .map_err(|e| format!("Failed to retrieve best sellers: {}", e))? {
-                    products.push(product);
-                }
-                // In a real implementation, you'd apply the limit here
-                Ok(products.into_iter().take(_limit as usize).collect())
+        self.find_section(
+            doc! { "is_active": true, "is_best_seller": true },
+            doc! { "sold_count": -1 },
+            limit,
+            "best sellers",
+        ).await
+    }
+
+    // Apply a `$set` to a product that hasn't been deleted, refreshing `updated_at`
+    // `stock_delta` moves the stock by that much rather than setting it, so
+    // units taken by concurrent checkouts aren't written over
+    pub async fn update_product(&self, id: &ObjectId, mut set: Document, stock_delta: Option<i32>) -> Result<Option<Product>, String> {
+        set.insert("updated_at", bson::DateTime::from_chrono(Utc::now()));
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+        
+        let mut filter = doc! { "_id": id, "deleted_at": null };
+        let mut update = doc! { "$set": set };
+        if let Some(delta) = stock_delta {
+            update.insert("$inc", doc! { "stock_quantity": delta });
+            // Never take stock below zero
+            if delta < 0 {
+                filter.insert("stock_quantity", doc! { "$gte": -delta });
             }
-            Err(e) => Err(format!("Failed to retrieve best sellers: {}", e))
         }
+        
+        self.collection
+            .find_one_and_update(filter, update)
+            .with_options(options)
+            .await
+            .map_err(|e| if slug_service::is_duplicate_slug(&e) {
+                "Another product already uses this slug; try again".to_string()
+            } else {
+                format!("Failed to update product: {}", e)
+            })
+    }
+    
+    // Same as `update_product` for many products; returns the IDs that matched
+    pub async fn update_products(&self, ids: &[ObjectId], mut set: Document) -> Result<Vec<ObjectId>, String> {
+        let filter = doc! { "_id": { "$in": ids }, "deleted_at": null };
+        let mut matched = Vec::new();
+        let mut cursor = self.collection.find(filter).await
+            .map_err(|e| format!("Failed to retrieve products: {}", e))?;
+        while let Some(product) = cursor.try_next().await
+            .map_err(|e| format!("Failed to retrieve products: {}", e))? {
+            matched.extend(product.id);
+        }
+        if matched.is_empty() {
+            return Ok(matched);
+        }
+        
+        set.insert("updated_at", bson::DateTime::from_chrono(Utc::now()));
+        self.collection
+            .update_many(doc! { "_id": { "$in": &matched }, "deleted_at": null }, doc! { "$set": set })
+            .await
+            .map_err(|e| format!("Failed to update products: {}", e))?;
+        Ok(matched)
+    }
+    
+    // Soft delete: hide the product from the catalogue but keep the document
+    pub async fn delete_product(&self, id: &ObjectId) -> Result<bool, String> {
+        let now = bson::DateTime::from_chrono(Utc::now());
+        let result = self.collection
+            .update_one(
+                doc! { "_id": id, "deleted_at": null },
+                doc! { "$set": { "is_active": false, "deleted_at": now, "updated_at": now } },
+            )
+            .await
+            .map_err(|e| format!("Failed to delete product: {}", e))?;
+        Ok(result.matched_count > 0)
     }
-}
\ No newline at end of file
+
+    // Count (`change` = 1) or uncount (-1) an approved review's stars. One
+    // pipeline update adjusts the totals and the average together, so
+    // concurrent moderation can't leave them out of step.
+    pub async fn apply_review_rating(&self, id: &ObjectId, stars: i32, change: i32) -> Result<(), String> {
+        let bucket = format!("rating.histogram.{}", stars);
+        let pipeline = vec![
+            doc! { "$set": {
+                "rating.count": { "$add": [{ "$ifNull": ["$rating.count", 0] }, change] },
+                "rating.total": { "$add": [{ "$ifNull": ["$rating.total", 0_i64] }, (stars * change) as i64] },
+                &bucket: { "$add": [{ "$ifNull": [format!("${}", bucket), 0] }, change] },
+            } },
+            doc! { "$set": {
+                "rating.average": { "$cond": [
+                    { "$gt": ["$rating.count", 0] },
+                    { "$round": [{ "$divide": ["$rating.total", "$rating.count"] }, 2] },
+                    0.0,
+                ] },
+            } },
+        ];
+
+        self.collection
+            .update_one(doc! { "_id": id }, pipeline)
+            .await
+            .map_err(|e| format!("Failed to update product rating: {}", e))?;
+        Ok(())
+    }
+
+    // Variants
+    //
+    // Variant stock is only ever changed in place on the variant, and the
+    // product's total is summed from the array in the same update, so admin
+    // edits can't overwrite stock taken by concurrent checkouts.
+
+    // Whether a product or variant other than `except_variant` already uses the SKU
+    pub async fn sku_in_use(&self, sku: &str, except_variant: Option<&ObjectId>) -> Result<bool, String> {
+        let variant_match = match except_variant {
+            Some(variant_id) => doc! { "variants": { "$elemMatch": { "sku": sku, "_id": { "$ne": variant_id } } } },
+            None => doc! { "variants.sku": sku },
+        };
+        let filter = doc! { "$or": [{ "sku": sku }, variant_match] };
+
+        match self.collection.count_documents(filter).await {
+            Ok(count) => Ok(count > 0),
+            Err(e) => Err(format!("Failed to check SKU: {}", e))
+        }
+    }
+
+    pub async fn set_options(&self, id: &ObjectId, options: &[ProductOption]) -> Result<Option<Product>, String> {
+        let options = bson::to_bson(options)
+            .map_err(|e| format!("Failed to encode options: {}", e))?;
+        self.update_product(id, doc! { "options": options }, None).await
+    }
+
+    pub async fn add_variant(&self, id: &ObjectId, variant: &ProductVariant) -> Result<Option<Product>, String> {
+        let variant_doc = bson::to_bson(variant)
+            .map_err(|e| format!("Failed to encode variant: {}", e))?;
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let result = self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "deleted_at": null },
+                doc! {
+                    "$push": { "variants": variant_doc },
+                    "$set": { "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+                },
+            )
+            .with_options(options)
+            .await;
+
+        match result {
+            Ok(product) => Ok(product),
+            Err(e) if e.to_string().contains("E11000") => Err(format!("SKU {} is already in use", variant.sku)),
+            Err(e) => Err(format!("Failed to add variant: {}", e))
+        }
+    }
+
+    // Apply a `$set` of variant fields, e.g. `{ "price": 150000 }`, and move
+    // the variant's stock by `stock_delta`
+    pub async fn update_variant(
+        &self,
+        id: &ObjectId,
+        variant_id: &ObjectId,
+        fields: Document,
+        stock_delta: Option<i32>,
+    ) -> Result<Option<Product>, String> {
+        let mut set = doc! { "updated_at": bson::DateTime::from_chrono(Utc::now()) };
+        for (field, value) in fields {
+            set.insert(format!("variants.$.{}", field), value);
+        }
+        let mut update = doc! { "$set": set };
+        let mut variant_filter = doc! { "_id": variant_id };
+        if let Some(delta) = stock_delta {
+            update.insert("$inc", doc! { "variants.$.stock_quantity": delta });
+            // Never take stock below zero
+            if delta < 0 {
+                variant_filter.insert("stock_quantity", doc! { "$gte": -delta });
+            }
+        }
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let result = self.collection
+            .find_one_and_update(doc! { "_id": id, "deleted_at": null, "variants": { "$elemMatch": variant_filter } }, update)
+            .with_options(options)
+            .await;
+
+        match result {
+            Ok(product) => Ok(product),
+            Err(e) if e.to_string().contains("E11000") => Err("SKU is already in use".to_string()),
+            Err(e) => Err(format!("Failed to update variant: {}", e))
+        }
+    }
+
+    pub async fn remove_variant(&self, id: &ObjectId, variant_id: &ObjectId) -> Result<Option<Product>, String> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "deleted_at": null, "variants._id": variant_id },
+                doc! {
+                    "$pull": { "variants": { "_id": variant_id } },
+                    "$set": { "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+                },
+            )
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to remove variant: {}", e))
+    }
+
+    // Bring the product's price, price range and total stock in line with its
+    // active variants. Prices come from the caller; stock is summed in place.
+    pub async fn refresh_variant_summary(
+        &self,
+        id: &ObjectId,
+        summary: Option<(i64, Option<i64>, PriceRange)>,
+    ) -> Result<Option<Product>, String> {
+        let mut set = doc! {
+            "stock_quantity": { "$sum": {
+                "$map": {
+                    "input": { "$filter": { "input": { "$ifNull": ["$variants", []] }, "cond": "$$this.is_active" } },
+                    "in": "$$this.stock_quantity",
+                },
+            } },
+            "updated_at": bson::DateTime::from_chrono(Utc::now()),
+        };
+        match summary {
+            Some((price, sale_price, price_range)) => {
+                let price_range = bson::to_bson(&price_range)
+                    .map_err(|e| format!("Failed to encode price range: {}", e))?;
+                set.insert("price", doc! { "$literal": price });
+                set.insert("sale_price", match sale_price {
+                    Some(sale_price) => bson::Bson::from(doc! { "$literal": sale_price }),
+                    None => bson::Bson::from("$$REMOVE"),
+                });
+                set.insert("price_range", doc! { "$literal": price_range });
+            }
+            None => {
+                set.insert("price_range", "$$REMOVE");
+            }
+        }
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(doc! { "_id": id }, vec![doc! { "$set": set }])
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to update product prices: {}", e))
+    }
+
+    // Gallery
+
+    pub async fn add_images(&self, id: &ObjectId, images: &[ProductImage]) -> Result<Option<Product>, String> {
+        let images = bson::to_bson(images)
+            .map_err(|e| format!("Failed to encode images: {}", e))?;
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "deleted_at": null },
+                doc! {
+                    "$push": { "images": { "$each": images } },
+                    "$set": { "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+                },
+            )
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to add images: {}", e))
+    }
+
+    // Save the gallery in a new order. Only matches while the product still has
+    // exactly these images, so an upload or delete in between isn't lost.
+    pub async fn set_image_order(&self, id: &ObjectId, images: &[ProductImage]) -> Result<Option<Product>, String> {
+        let image_ids: Vec<ObjectId> = images.iter().map(|image| image.id).collect();
+        let images = bson::to_bson(images)
+            .map_err(|e| format!("Failed to encode images: {}", e))?;
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(
+                doc! {
+                    "_id": id,
+                    "deleted_at": null,
+                    "images": { "$size": image_ids.len() as i64 },
+                    "images._id": { "$all": &image_ids },
+                },
+                doc! { "$set": {
+                    "images": images,
+                    "updated_at": bson::DateTime::from_chrono(Utc::now()),
+                } },
+            )
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to reorder images: {}", e))
+    }
+
+    // Set or, with None, clear an image's alt text
+    pub async fn set_image_alt_text(&self, id: &ObjectId, image_id: &ObjectId, alt_text: Option<String>) -> Result<Option<Product>, String> {
+        let now = bson::DateTime::from_chrono(Utc::now());
+        let update = match alt_text {
+            Some(alt_text) => doc! { "$set": { "images.$.alt_text": alt_text, "updated_at": now } },
+            None => doc! { "$unset": { "images.$.alt_text": "" }, "$set": { "updated_at": now } },
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(doc! { "_id": id, "deleted_at": null, "images._id": image_id }, update)
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to update image: {}", e))
+    }
+
+    pub async fn remove_image(&self, id: &ObjectId, image_id: &ObjectId) -> Result<Option<Product>, String> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "deleted_at": null, "images._id": image_id },
+                doc! {
+                    "$pull": { "images": { "_id": image_id } },
+                    "$set": { "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+                },
+            )
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to remove image: {}", e))
+    }
+
+    // Inventory
+    //
+    // Stock is taken with conditional `$inc` updates that only match while enough
+    // is left, so concurrent checkouts can never drive `stock_quantity` negative.
+
+    // Hold stock for a purchase. Either every item is reserved or none is.
+    pub async fn reserve_stock(
+        &self,
+        reference: &str,
+        items: &[ReservedItem],
+        ttl: Duration,
+    ) -> Result<InventoryReservation, String> {
+        if items.is_empty() {
+            return Err("Nothing to reserve".to_string());
+        }
+        if items.iter().any(|item| item.quantity <= 0) {
+            return Err("Reserved quantities must be positive".to_string());
+        }
+
+        let now = Utc::now();
+        let mut reservation = InventoryReservation {
+            id: None,
+            reference: reference.to_string(),
+            items: Vec::new(),
+            status: ReservationStatus::Active,
+            expires_at: now + ttl,
+            closed_at: None,
+            created_at: now,
+        };
+
+        let result = self.reservations.insert_one(&reservation).await
+            .map_err(|e| format!("Failed to reserve stock for {}: {}", reference, e))?;
+        reservation.id = result.inserted_id.as_object_id();
+
+        for item in items {
+            // Variant holds come off the variant and the product's total together
+            let (filter, inc) = match item.variant_id {
+                Some(variant_id) => (
+                    doc! {
+                        "_id": item.product_id,
+                        "is_active": true,
+                        "variants": { "$elemMatch": {
+                            "_id": variant_id,
+                            "is_active": true,
+                            "stock_quantity": { "$gte": item.quantity },
+                        } },
+                    },
+                    doc! { "stock_quantity": -item.quantity, "variants.$.stock_quantity": -item.quantity },
+                ),
+                None => (
+                    doc! {
+                        "_id": item.product_id,
+                        "is_active": true,
+                        "stock_quantity": { "$gte": item.quantity },
+                    },
+                    doc! { "stock_quantity": -item.quantity },
+                ),
+            };
+            let taken = self.collection
+                .update_one(
+                    filter,
+                    doc! {
+                        "$inc": inc,
+                        "$set": { "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+                    },
+                )
+                .await
+                .map_err(|e| format!("Failed to reserve stock: {}", e))?;
+
+            if taken.modified_count == 0 {
+                // Give back what was taken so far
+                self.release_reservation(reference).await?;
+                return Err(format!("Insufficient stock for product {}", item.product_id.to_hex()));
+            }
+
+            // Recorded only after the stock is taken, so a crash can leak a hold but never over-release
+            let item_doc = bson::to_bson(item)
+                .map_err(|e| format!("Failed to encode reserved item: {}", e))?;
+            self.reservations
+                .update_one(doc! { "reference": reference }, doc! { "$push": { "items": item_doc } })
+                .await
+                .map_err(|e| format!("Failed to record reserved item: {}", e))?;
+            reservation.items.push(item.clone());
+        }
+
+        Ok(reservation)
+    }
+
+    // The hold made under `reference`, whatever its status. None once a
+    // released hold has been purged.
+    pub async fn get_reservation(&self, reference: &str) -> Result<Option<InventoryReservation>, String> {
+        self.reservations
+            .find_one(doc! { "reference": reference })
+            .await
+            .map_err(|e| format!("Failed to retrieve stock reservation: {}", e))
+    }
+
+    // Hold stock again for a purchase whose hold was released, e.g. one paid
+    // for after its window closed. The released record makes way for the new one.
+    pub async fn reserve_again(
+        &self,
+        reference: &str,
+        items: &[ReservedItem],
+        ttl: Duration,
+    ) -> Result<InventoryReservation, String> {
+        self.reservations
+            .delete_one(doc! { "reference": reference, "status": "released" })
+            .await
+            .map_err(|e| format!("Failed to clear released reservation for {}: {}", reference, e))?;
+        self.reserve_stock(reference, items, ttl).await
+    }
+
+    // Make a hold permanent once the purchase goes through. Committing a hold
+    // that is already committed succeeds, so a retried payment can't fail on it.
+    pub async fn commit_reservation(&self, reference: &str) -> Result<InventoryReservation, String> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let committed = self.reservations
+            .find_one_and_update(
+                doc! { "reference": reference, "status": "active" },
+                doc! { "$set": {
+                    "status": "committed",
+                    "closed_at": bson::DateTime::from_chrono(Utc::now()),
+                } },
+            )
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to commit stock reservation: {}", e))?;
+
+        let reservation = match committed {
+            Some(reservation) => reservation,
+            None => {
+                return self.reservations
+                    .find_one(doc! { "reference": reference, "status": "committed" })
+                    .await
+                    .map_err(|e| format!("Failed to commit stock reservation: {}", e))?
+                    .ok_or_else(|| format!("No active stock reservation for {}", reference));
+            }
+        };
+
+        // The stock is committed by now; units sold only feed the popularity
+        // sort, so a failure here is logged rather than failing the commit
+        for item in &reservation.items {
+            if let Err(e) = self.collection
+                .update_one(doc! { "_id": item.product_id }, doc! { "$inc": { "sold_count": item.quantity as i64 } })
+                .await {
+                eprintln!("Failed to record units sold for product {}: {}", item.product_id, e);
+            }
+        }
+
+        Ok(reservation)
+    }
+
+    // Put reserved stock back. Works on active holds and on committed ones
+    // (e.g. a paid order that gets cancelled). Returns None if nothing was held.
+    pub async fn release_reservation(&self, reference: &str) -> Result<Option<InventoryReservation>, String> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        // Claiming the document first means a reservation is only ever released once
+        let reservation = self.reservations
+            .find_one_and_update(
+                doc! { "reference": reference, "status": { "$in": ["active", "committed"] } },
+                doc! { "$set": {
+                    "status": "released",
+                    "closed_at": bson::DateTime::from_chrono(Utc::now()),
+                } },
+            )
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to release stock reservation: {}", e))?;
+
+        if let Some(reservation) = &reservation {
+            for item in &reservation.items {
+                self.return_stock(&item.product_id, item.variant_id.as_ref(), item.quantity).await
+                    .map_err(|e| format!("Failed to return reserved stock: {}", e))?;
+            }
+        }
+
+        Ok(reservation)
+    }
+
+    // Add units to a product, and to the variant when one is given. Units of
+    // a variant that has since been removed can't be sold, so they're dropped.
+    async fn return_stock(&self, product_id: &ObjectId, variant_id: Option<&ObjectId>, quantity: i32) -> Result<(), mongodb::error::Error> {
+        let (filter, inc) = match variant_id {
+            Some(variant_id) => (
+                doc! { "_id": product_id, "variants._id": variant_id },
+                doc! { "stock_quantity": quantity, "variants.$.stock_quantity": quantity },
+            ),
+            None => (doc! { "_id": product_id }, doc! { "stock_quantity": quantity }),
+        };
+
+        self.collection
+            .update_one(
+                filter,
+                doc! {
+                    "$inc": inc,
+                    "$set": { "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    // Put units back on sale, e.g. returned goods that passed inspection
+    pub async fn restock(&self, product_id: &ObjectId, variant_id: Option<&ObjectId>, quantity: i32) -> Result<(), String> {
+        self.return_stock(product_id, variant_id, quantity).await
+            .map_err(|e| format!("Failed to restock product: {}", e))
+    }
+
+    // References of active holds whose time is up. The caller decides whether
+    // each one is released, since a payment may have landed in the meantime.
+    pub async fn find_expired_reservations(&self) -> Result<Vec<String>, String> {
+        let filter = doc! {
+            "status": "active",
+            "expires_at": { "$lt": bson::DateTime::from_chrono(Utc::now()) },
+        };
+
+        let mut expired = Vec::new();
+        let mut cursor = self.reservations.find(filter).await
+            .map_err(|e| format!("Failed to retrieve expired reservations: {}", e))?;
+        while let Some(reservation) = cursor.try_next().await
+            .map_err(|e| format!("Failed to retrieve expired reservations: {}", e))? {
+            expired.push(reservation.reference);
+        }
+        Ok(expired)
+    }
+
+    // Rebuild the purge index so only released reservations expire. Committed
+    // ones stay, so a paid order that's later cancelled can still return its stock.
+    pub async fn keep_committed_reservations(&self) -> Result<u64, String> {
+        match self.reservations.drop_index("closed_at_1").await {
+            Ok(()) => {}
+            // Already dropped by an earlier run
+            Err(e) if matches!(e.kind.as_ref(), ErrorKind::Command(command) if command.code == INDEX_NOT_FOUND) => {}
+            Err(e) => return Err(format!("Failed to drop reservation purge index: {}", e)),
+        }
+
+        let index = IndexModel::builder()
+            .keys(doc! { "closed_at": 1 })
+            .options(IndexOptions::builder()
+                .expire_after(std::time::Duration::from_secs(30 * 24 * 60 * 60))
+                .partial_filter_expression(doc! { "status": "released" })
+                .build())
+            .build();
+        self.reservations.create_index(index).await
+            .map_err(|e| format!("Failed to create reservation purge index: {}", e))?;
+        Ok(0)
+    }
+}
@@ -0,0 +1,102 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::module::product::crud::ProductCrud;
+use crate::service::migration_service::Migration;
+use crate::service::slug_service;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        // Listing filters always include `is_active`, so it leads every index
+        Migration::indexes("product", 1, "listing, spec and variant SKU indexes", "products", vec![
+            IndexModel::builder()
+                .keys(doc! { "is_active": 1, "created_at": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "is_active": 1, "category_id": 1, "created_at": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "is_active": 1, "brand_id": 1, "created_at": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "is_active": 1, "is_featured": 1, "created_at": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "is_active": 1, "is_new_arrival": 1, "created_at": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "is_active": 1, "is_best_seller": 1, "sold_count": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "is_active": 1, "sold_count": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "is_active": 1, "name": 1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "is_active": 1, "stock_quantity": 1 })
+                .build(),
+            // Spec filters, whichever attributes a category defines
+            IndexModel::builder()
+                .keys(doc! { "specs.$**": 1 })
+                .build(),
+            // Stops two products claiming the same variant SKU; clashes within
+            // one product and with product SKUs are checked before saving
+            IndexModel::builder()
+                .keys(doc! { "variants.sku": 1 })
+                .options(IndexOptions::builder()
+                    .unique(true)
+                    .partial_filter_expression(doc! { "variants.sku": { "$exists": true } })
+                    .build())
+                .build(),
+        ]),
+        Migration::indexes("product", 2, "inventory reservation indexes", "inventory_reservations", vec![
+            IndexModel::builder()
+                .keys(doc! { "reference": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "status": 1, "expires_at": 1 })
+                .build(),
+            // Finished reservations are kept for 30 days for auditing
+            IndexModel::builder()
+                .keys(doc! { "closed_at": 1 })
+                .options(IndexOptions::builder()
+                    .expire_after(std::time::Duration::from_secs(30 * 24 * 60 * 60))
+                    .build())
+                .build(),
+        ]),
+        Migration::data("product", 3, "copy brand names onto products", |db| async move {
+            ProductCrud::new(&db).backfill_brand_names().await
+        }),
+        // Slugs are made unique before the unique indexes are built
+        Migration::data("product", 4, "clean, unique slugs", |db| async move {
+            ProductCrud::new(&db).backfill_slugs().await
+        }),
+        Migration::indexes("product", 5, "unique slug indexes", "products", slug_service::slug_indexes()),
+        // Deleted products keep their SKU, so it can't be reused
+        Migration::indexes("product", 6, "unique SKU index", "products", vec![
+            IndexModel::builder()
+                .keys(doc! { "sku": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+        ]),
+        Migration::indexes("product", 7, "rating sort index", "products", vec![
+            IndexModel::builder()
+                .keys(doc! { "is_active": 1, "rating.average": -1, "rating.count": -1 })
+                .build(),
+        ]),
+        // Compatible alternatives are looked up by component kind
+        Migration::indexes("product", 8, "component kind index", "products", vec![
+            IndexModel::builder()
+                .keys(doc! { "component.kind": 1, "is_active": 1, "sold_count": -1 })
+                .options(IndexOptions::builder()
+                    .partial_filter_expression(doc! { "component.kind": { "$exists": true } })
+                    .build())
+                .build(),
+        ]),
+        // Replaces the v2 purge index, which also removed committed reservations
+        Migration::data("product", 9, "purge only released reservations", |db| async move {
+            ProductCrud::new(&db).keep_committed_reservations().await
+        }),
+    ]
+}
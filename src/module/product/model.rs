@@ -1,6 +1,10 @@
+use std::collections::BTreeMap;
 use bson::oid::ObjectId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use crate::module::category::model::AttributeDefinition;
+use crate::service::currency_service::format_naira;
+use crate::service::slug_service::slugify;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Product {
@@ -10,6 +14,9 @@ pub struct Product {
     pub name: String,
     pub description: String,
     pub slug: String,
+    // Slugs used before a rename, kept so old links redirect
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_slugs: Vec<String>,
     
     // Pricing in kobo (₦1 = 100 kobo)
     pub price: i64,
@@ -20,14 +27,55 @@ pub struct Product {
     pub sku: String,
     pub stock_quantity: i32,
     
+    // Units sold through completed purchases, used to rank by popularity
+    #[serde(default)]
+    pub sold_count: i64,
+    
     // Relationships
     #[serde(rename = "category_id")]
     pub category_id: ObjectId,
     #[serde(rename = "brand_id")]
     pub brand_id: ObjectId,
+    // Copy of the brand's name so text search can match on it
+    #[serde(default)]
+    pub brand_name: String,
     
-    // Media
-    pub images: Vec<String>,
+    // Overrides the category's tax class, e.g. "exempt" for educational devices
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tax_class: Option<String>,
+    
+    // Shipping. Older products have neither and ship as weightless parcels.
+    #[serde(default)]
+    pub weight_grams: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<ProductDimensions>,
+    
+    // Gallery in display order; the first image is the primary one
+    pub images: Vec<ProductImage>,
+    
+    // Technical specifications keyed by the category's attribute keys,
+    // e.g. { "ram_gb": 16, "gpu": "rtx-4060" }
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub specs: BTreeMap<String, SpecValue>,
+    
+    // PC components only: what the part is and the attributes the build
+    // compatibility rules read, e.g. a CPU's socket and TDP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component: Option<ComponentProfile>,
+    
+    // Configurations, e.g. RAM and storage sizes. For products sold in variants
+    // `price`, `sale_price` and `stock_quantity` summarise the active variants:
+    // the cheapest one's price and the total stock.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<ProductOption>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<ProductVariant>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_range: Option<PriceRange>,
+    
+    // Approved reviews, kept up to date as they are moderated
+    #[serde(default)]
+    pub rating: ProductRating,
     
     // Status flags for sections
     pub is_active: bool,
@@ -35,6 +83,15 @@ pub struct Product {
     pub is_new_arrival: bool,
     pub is_best_seller: bool,
     
+    // Set when an admin deletes the product. The document is kept so orders,
+    // invoices and reviews can still refer to it.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub deleted_at: Option<DateTime<Utc>>,
+    
     // Timestamps
     #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
     pub created_at: DateTime<Utc>,
@@ -52,24 +109,37 @@ impl Product {
         brand_id: ObjectId,
     ) -> Self {
         let now = Utc::now();
-        let slug = name.to_lowercase().replace(" ", "-");
+        let slug = slugify(&name);
         
         Self {
             id: None,
             name,
             description,
             slug,
+            previous_slugs: Vec::new(),
             price,
             sale_price: None,
             sku,
             stock_quantity: 0,
+            sold_count: 0,
             category_id,
             brand_id,
+            brand_name: String::new(),
+            tax_class: None,
+            weight_grams: 0,
+            dimensions: None,
             images: Vec::new(),
+            specs: BTreeMap::new(),
+            component: None,
+            options: Vec::new(),
+            variants: Vec::new(),
+            price_range: None,
+            rating: ProductRating::default(),
             is_active: true,
             is_featured: false,
             is_new_arrival: true,
             is_best_seller: false,
+            deleted_at: None,
             created_at: now,
             updated_at: now,
         }
@@ -84,4 +154,388 @@ impl Product {
     pub fn display_price(&self) -> i64 {
         self.sale_price.unwrap_or(self.price)
     }
-}
\ No newline at end of file
+    
+    // Helper method to get the image shown in listings
+    pub fn primary_image(&self) -> Option<&ProductImage> {
+        self.images.first()
+    }
+    
+    // Helper method to find a variant by ID
+    pub fn variant(&self, id: &ObjectId) -> Option<&ProductVariant> {
+        self.variants.iter().find(|variant| &variant.id == id)
+    }
+    
+    // Price, sale price and "from" range taken from the cheapest active
+    // variant, or None when no variant is on sale
+    pub fn variant_summary(&self) -> Option<(i64, Option<i64>, PriceRange)> {
+        let active: Vec<&ProductVariant> = self.variants.iter().filter(|variant| variant.is_active).collect();
+        let cheapest = active.iter().min_by_key(|variant| variant.display_price())?;
+        let max = active.iter().map(|variant| variant.display_price()).max()?;
+        Some((cheapest.price, cheapest.sale_price, PriceRange::new(cheapest.display_price(), max)))
+    }
+    
+    // What a cart or order line for this product buys. Products sold in
+    // variants need an active variant chosen; other products take none.
+    pub fn purchase_option(&self, variant_id: Option<&ObjectId>) -> Result<PurchaseOption<'_>, String> {
+        match variant_id {
+            None if !self.variants.is_empty() => Err(format!("Choose which {} you want", self.name)),
+            None => Ok(PurchaseOption { product: self, variant: None }),
+            Some(_) if self.variants.is_empty() => Err(format!("{} has no options to choose from", self.name)),
+            Some(variant_id) => self.variant(variant_id)
+                .filter(|variant| variant.is_active)
+                .map(|variant| PurchaseOption { product: self, variant: Some(variant) })
+                .ok_or_else(|| format!("The chosen {} is no longer available", self.name)),
+        }
+    }
+    
+    // Weight couriers charge for: the actual weight or the volumetric
+    // weight of the packed box, whichever is heavier
+    pub fn shipping_weight_grams(&self) -> i32 {
+        let volumetric = self.dimensions
+            .as_ref()
+            .map(|dimensions| dimensions.volumetric_weight_grams())
+            .unwrap_or(0);
+        self.weight_grams.max(volumetric)
+    }
+}
+
+// A gallery image stored on Cloudinary under products/<product id>
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductImage {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub url: String,
+    
+    // Needed to delete the asset from Cloudinary
+    pub public_id: String,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt_text: Option<String>,
+}
+
+// A specification value; which kind is allowed comes from the category
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum SpecValue {
+    Boolean(bool),
+    Number(f64),
+    Text(String),
+}
+
+// The part of a PC a component product is
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentKind {
+    Cpu,
+    Motherboard,
+    Memory,
+    Psu,
+    Case,
+    Gpu,
+    Storage,
+    Cooler,
+}
+
+impl ComponentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ComponentKind::Cpu => "cpu",
+            ComponentKind::Motherboard => "motherboard",
+            ComponentKind::Memory => "memory",
+            ComponentKind::Psu => "psu",
+            ComponentKind::Case => "case",
+            ComponentKind::Gpu => "gpu",
+            ComponentKind::Storage => "storage",
+            ComponentKind::Cooler => "cooler",
+        }
+    }
+    
+    pub fn label(&self) -> &'static str {
+        match self {
+            ComponentKind::Cpu => "CPU",
+            ComponentKind::Motherboard => "motherboard",
+            ComponentKind::Memory => "memory",
+            ComponentKind::Psu => "power supply",
+            ComponentKind::Case => "case",
+            ComponentKind::Gpu => "graphics card",
+            ComponentKind::Storage => "storage drive",
+            ComponentKind::Cooler => "CPU cooler",
+        }
+    }
+}
+
+// A compatibility attribute. Text and list entries are stored lowercase and
+// hyphenated like enum specs, e.g. "am5" or ["atx", "micro-atx"].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ComponentValue {
+    Number(f64),
+    Text(String),
+    List(Vec<String>),
+}
+
+impl ComponentValue {
+    pub fn label(&self) -> String {
+        match self {
+            ComponentValue::Number(number) if number.fract() == 0.0 => format!("{}", *number as i64),
+            ComponentValue::Number(number) => number.to_string(),
+            ComponentValue::Text(text) => text.clone(),
+            ComponentValue::List(values) => values.join(", "),
+        }
+    }
+}
+
+// Attributes the default compatibility rules read, with the kind of value
+// each takes. Rules added by admins can read any other attribute too.
+pub const COMPONENT_ATTRIBUTES: [(&str, &str); 12] = [
+    ("socket", "text"),
+    ("chipset", "text"),
+    ("supported_chipsets", "list"),
+    ("memory_type", "text"),
+    ("memory_slots", "number"),
+    ("memory_modules", "number"),
+    ("form_factor", "text"),
+    ("supported_form_factors", "list"),
+    ("tdp_watts", "number"),
+    ("wattage", "number"),
+    ("length_mm", "number"),
+    ("max_gpu_length_mm", "number"),
+];
+
+// A component product's kind and compatibility attributes, e.g. a
+// motherboard with { socket: "am5", memory_type: "ddr5", memory_slots: 4 }
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ComponentProfile {
+    pub kind: ComponentKind,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, ComponentValue>,
+}
+
+impl ComponentProfile {
+    // Check the known attributes hold the right kind of value and normalise
+    // the text, so "AM5" and "am5" match
+    pub fn normalized(self) -> Result<Self, String> {
+        let normalize = AttributeDefinition::normalize_value;
+        
+        let mut attributes = BTreeMap::new();
+        for (key, value) in self.attributes {
+            let key = key.trim().to_lowercase();
+            if key.is_empty() {
+                return Err("Component attribute names can't be empty".to_string());
+            }
+            let value = match value {
+                ComponentValue::Number(number) if !number.is_finite() || number < 0.0 => {
+                    return Err(format!("{} must be a positive number", key));
+                }
+                ComponentValue::Number(number) => ComponentValue::Number(number),
+                ComponentValue::Text(text) => ComponentValue::Text(normalize(&text)),
+                ComponentValue::List(values) => ComponentValue::List(values.iter().map(|value| normalize(value)).collect()),
+            };
+            if let Some((_, expected)) = COMPONENT_ATTRIBUTES.iter().find(|(known, _)| *known == key) {
+                let matches = matches!(
+                    (*expected, &value),
+                    ("number", ComponentValue::Number(_)) | ("text", ComponentValue::Text(_)) | ("list", ComponentValue::List(_))
+                );
+                if !matches {
+                    return Err(format!("{} must be a {}", key, expected));
+                }
+            }
+            attributes.insert(key, value);
+        }
+        Ok(Self { kind: self.kind, attributes })
+    }
+    
+    pub fn attribute(&self, key: &str) -> Option<&ComponentValue> {
+        self.attributes.get(key)
+    }
+}
+
+// A choice customers make, e.g. "RAM" with values "8GB", "16GB" and "32GB"
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProductOption {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+// One configuration of a product, e.g. 16GB RAM with a 512GB SSD
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductVariant {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    
+    // Unique across products and every variant
+    pub sku: String,
+    
+    // Option name to chosen value, one entry per product option
+    pub options: BTreeMap<String, String>,
+    
+    // Pricing in kobo
+    pub price: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sale_price: Option<i64>,
+    
+    pub stock_quantity: i32,
+    
+    // Shown instead of the product's images when this variant is selected
+    #[serde(default)]
+    pub images: Vec<String>,
+    
+    pub is_active: bool,
+}
+
+impl ProductVariant {
+    // Helper method to get display price
+    pub fn display_price(&self) -> i64 {
+        self.sale_price.unwrap_or(self.price)
+    }
+    
+    // Helper method to describe the chosen values, e.g. "16GB / 512GB"
+    pub fn label(&self, options: &[ProductOption]) -> String {
+        options.iter()
+            .filter_map(|option| self.options.get(&option.name))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+// Star ratings from approved reviews. `total` is the sum of the stars, so
+// the average can be recomputed in the same update that counts a review.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProductRating {
+    pub average: f64,
+    pub count: i32,
+    pub total: i64,
+    pub histogram: RatingHistogram,
+}
+
+// Number of approved reviews at each star rating
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RatingHistogram {
+    #[serde(rename = "1", default)]
+    pub one: i32,
+    #[serde(rename = "2", default)]
+    pub two: i32,
+    #[serde(rename = "3", default)]
+    pub three: i32,
+    #[serde(rename = "4", default)]
+    pub four: i32,
+    #[serde(rename = "5", default)]
+    pub five: i32,
+}
+
+// Cheapest and dearest active variant, for "from ₦X" listings
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceRange {
+    pub min: i64,
+    pub max: i64,
+    pub label: String,
+}
+
+impl PriceRange {
+    pub fn new(min: i64, max: i64) -> Self {
+        let label = if min == max {
+            format_naira(min)
+        } else {
+            format!("from {}", format_naira(min))
+        };
+        Self { min, max, label }
+    }
+}
+
+// A product, or one of its variants, as bought on a cart or order line
+pub struct PurchaseOption<'a> {
+    pub product: &'a Product,
+    pub variant: Option<&'a ProductVariant>,
+}
+
+impl PurchaseOption<'_> {
+    pub fn unit_price(&self) -> i64 {
+        match self.variant {
+            Some(variant) => variant.display_price(),
+            None => self.product.display_price(),
+        }
+    }
+    
+    pub fn stock_quantity(&self) -> i32 {
+        match self.variant {
+            Some(variant) => variant.stock_quantity,
+            None => self.product.stock_quantity,
+        }
+    }
+    
+    pub fn sku(&self) -> &str {
+        match self.variant {
+            Some(variant) => &variant.sku,
+            None => &self.product.sku,
+        }
+    }
+    
+    // The product name, followed by the variant's values, e.g. "Laptop (16GB / 512GB)"
+    pub fn name(&self) -> String {
+        match self.variant {
+            Some(variant) => format!("{} ({})", self.product.name, variant.label(&self.product.options)),
+            None => self.product.name.clone(),
+        }
+    }
+}
+
+// Packed box size in centimetres
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct ProductDimensions {
+    pub length_cm: f64,
+    pub width_cm: f64,
+    pub height_cm: f64,
+}
+
+impl ProductDimensions {
+    // Standard courier divisor: 5000 cubic centimetres per kilogram
+    pub fn volumetric_weight_grams(&self) -> i32 {
+        (self.length_cm * self.width_cm * self.height_cm / 5.0).ceil() as i32
+    }
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReservationStatus {
+    Active,
+    Committed,
+    Released,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReservedItem {
+    pub product_id: ObjectId,
+    // Set when the product is sold in variants; stock is held on the variant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant_id: Option<ObjectId>,
+    pub quantity: i32,
+}
+
+// Stock held for a purchase. Stock is taken off the product when the
+// reservation is made; committing keeps it off, releasing puts it back.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryReservation {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    // Caller's key for the purchase, e.g. an order number
+    pub reference: String,
+    pub items: Vec<ReservedItem>,
+    pub status: ReservationStatus,
+
+    // Active reservations past this are released by the sweeper
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub expires_at: DateTime<Utc>,
+
+    // Set once committed or released. Released reservations are purged by a
+    // TTL index later; committed ones are kept so their stock can be returned.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub closed_at: Option<DateTime<Utc>>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
@@ -13,7 +13,22 @@ pub fn product_routes(cfg: &mut web::ServiceConfig) {
             .route("/featured", web::get().to(ProductController::get_featured_products_handler))
             .route("/new", web::get().to(ProductController::get_new_arrivals_handler))
             .route("/best-sellers", web::get().to(ProductController::get_best_sellers_handler))
+            .route("/bulk", web::patch().to(ProductController::bulk_update_products_handler))
+            .route("/slug/{slug}", web::get().to(ProductController::get_product_by_slug_handler))
             .route("/{id}", web::get().to(ProductController::get_product_handler))
+            .route("/{id}", web::patch().to(ProductController::update_product_handler))
+            .route("/{id}", web::delete().to(ProductController::delete_product_handler))
+            .route("/{id}/images", web::post().to(ProductController::upload_images_handler))
+            .route("/{id}/images/order", web::put().to(ProductController::reorder_images_handler))
+            .route("/{id}/images/{image_id}/primary", web::put().to(ProductController::set_primary_image_handler))
+            .route("/{id}/images/{image_id}", web::patch().to(ProductController::update_image_handler))
+            .route("/{id}/images/{image_id}", web::delete().to(ProductController::delete_image_handler))
+            .route("/{id}/options", web::put().to(ProductController::set_options_handler))
+            .route("/{id}/variants", web::get().to(ProductController::get_variants_handler))
+            .route("/{id}/variants", web::post().to(ProductController::add_variant_handler))
+            .route("/{id}/variants/select", web::get().to(ProductController::select_variant_handler))
+            .route("/{id}/variants/{variant_id}", web::patch().to(ProductController::update_variant_handler))
+            .route("/{id}/variants/{variant_id}", web::delete().to(ProductController::remove_variant_handler))
             .route("", web::get().to(ProductController::get_products_handler))
     );
 }
\ No newline at end of file
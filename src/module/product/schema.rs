@@ -1,6 +1,31 @@
+use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::module::product::model::{ComponentProfile, PriceRange, ProductDimensions, ProductOption, ProductVariant, SpecValue};
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone, Copy)]
+pub struct ProductDimensionsSchema {
+    #[validate(range(min = 0.1, max = 500.0))]
+    pub length_cm: f64,
+    
+    #[validate(range(min = 0.1, max = 500.0))]
+    pub width_cm: f64,
+    
+    #[validate(range(min = 0.1, max = 500.0))]
+    pub height_cm: f64,
+}
+
+impl From<ProductDimensionsSchema> for ProductDimensions {
+    fn from(data: ProductDimensionsSchema) -> Self {
+        Self {
+            length_cm: data.length_cm,
+            width_cm: data.width_cm,
+            height_cm: data.height_cm,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Validate, Clone)]
 pub struct CreateProductSchema {
     #[validate(length(min = 1, max = 200))]
@@ -24,6 +49,25 @@ pub struct CreateProductSchema {
     pub category_id: String,  // Will be converted to ObjectId
     pub brand_id: String,     // Will be converted to ObjectId
     
+    // Falls back to the category's tax class when omitted
+    #[validate(length(min = 1, max = 30))]
+    pub tax_class: Option<String>,
+    
+    // Shipping weight and packed size
+    #[serde(default)]
+    #[validate(range(min = 0, max = 200000))]
+    pub weight_grams: i32,
+    
+    #[validate(nested)]
+    pub dimensions: Option<ProductDimensionsSchema>,
+    
+    // Checked against the category's attribute schema
+    #[serde(default)]
+    pub specs: BTreeMap<String, SpecValue>,
+    
+    // PC components only, for build compatibility checks
+    pub component: Option<ComponentProfile>,
+    
     pub is_featured: bool,
     pub is_best_seller: bool,
 }
@@ -42,17 +86,210 @@ pub struct UpdateProductSchema {
     #[validate(range(min = 0))]
     pub sale_price: Option<i64>,
     
+    // Ends the sale; can't be sent together with `sale_price`
+    #[serde(default)]
+    pub clear_sale_price: bool,
+    
     #[validate(range(min = 0))]
     pub stock_quantity: Option<i32>,
     
     pub category_id: Option<String>,
     pub brand_id: Option<String>,
     
+    #[validate(length(min = 1, max = 30))]
+    pub tax_class: Option<String>,
+    
+    // Falls back to the category's class; can't be sent together with `tax_class`
+    #[serde(default)]
+    pub clear_tax_class: bool,
+    
+    #[validate(range(min = 0, max = 200000))]
+    pub weight_grams: Option<i32>,
+    
+    #[validate(nested)]
+    pub dimensions: Option<ProductDimensionsSchema>,
+    
+    // Replaces all specs
+    pub specs: Option<BTreeMap<String, SpecValue>>,
+    
+    // Replaces the component profile
+    pub component: Option<ComponentProfile>,
+    
     pub is_active: Option<bool>,
     pub is_featured: Option<bool>,
+    pub is_new_arrival: Option<bool>,
     pub is_best_seller: Option<bool>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct ProductImageUploadSchema {
+    // Base64 encoded image or data URL
+    #[validate(length(min = 1))]
+    pub data: String,
+    
+    #[validate(length(min = 1, max = 200))]
+    pub alt_text: Option<String>,
+}
+
+// Added to the end of the gallery in the order given
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct UploadProductImagesSchema {
+    #[validate(length(min = 1, max = 10, message = "Upload 1 to 10 images at a time"), nested)]
+    pub images: Vec<ProductImageUploadSchema>,
+}
+
+// Every image of the product, in the new order
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReorderProductImagesSchema {
+    pub image_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct UpdateProductImageSchema {
+    // Empty or missing clears it
+    #[validate(length(max = 200))]
+    pub alt_text: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct ProductOptionSchema {
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    
+    #[validate(length(min = 1, max = 50))]
+    pub values: Vec<String>,
+}
+
+// Replaces the product's option axes; existing variants must still fit them
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct ProductOptionsSchema {
+    #[validate(length(max = 5), nested)]
+    pub options: Vec<ProductOptionSchema>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct CreateVariantSchema {
+    #[validate(length(min = 1, max = 100))]
+    pub sku: String,
+    
+    // Option name to value, one entry per product option
+    pub options: BTreeMap<String, String>,
+    
+    #[validate(range(min = 1))]
+    pub price: i64,
+    
+    #[validate(range(min = 0))]
+    pub sale_price: Option<i64>,
+    
+    #[validate(range(min = 0))]
+    pub stock_quantity: i32,
+    
+    #[serde(default)]
+    #[validate(length(max = 10))]
+    pub images: Vec<String>,
+    
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct UpdateVariantSchema {
+    #[validate(length(min = 1, max = 100))]
+    pub sku: Option<String>,
+    
+    pub options: Option<BTreeMap<String, String>>,
+    
+    #[validate(range(min = 1))]
+    pub price: Option<i64>,
+    
+    #[validate(range(min = 0))]
+    pub sale_price: Option<i64>,
+    
+    // Ends the sale; can't be sent together with `sale_price`
+    #[serde(default)]
+    pub clear_sale_price: bool,
+    
+    #[validate(range(min = 0))]
+    pub stock_quantity: Option<i32>,
+    
+    #[validate(length(max = 10))]
+    pub images: Option<Vec<String>>,
+    
+    pub is_active: Option<bool>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VariantListResponse {
+    pub options: Vec<ProductOption>,
+    pub variants: Vec<ProductVariant>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_range: Option<PriceRange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OptionValueAvailability {
+    pub value: String,
+    pub selected: bool,
+    // An active, in-stock variant has this value and the other chosen values
+    pub available: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OptionAvailability {
+    pub name: String,
+    pub values: Vec<OptionValueAvailability>,
+}
+
+// Result of choosing option values, e.g. ?RAM=16GB&Storage=512GB
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VariantSelection {
+    pub selected: BTreeMap<String, String>,
+    // Set once every option is chosen and an active variant matches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<ProductVariant>,
+    pub options: Vec<OptionAvailability>,
+}
+
+// Status flags applied to many products at once; flags left out are unchanged
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct BulkProductUpdateSchema {
+    #[validate(length(min = 1, max = 500))]
+    pub ids: Vec<String>,
+    
+    pub is_active: Option<bool>,
+    pub is_featured: Option<bool>,
+    pub is_new_arrival: Option<bool>,
+    pub is_best_seller: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkProductResult {
+    pub id: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkProductUpdateResponse {
+    pub updated: usize,
+    pub failed: usize,
+    pub results: Vec<BulkProductResult>,
+}
+
+// Query parameters of the listing; any other parameter naming a category
+// attribute is read as a spec filter, so attributes may not use these names
+pub const LISTING_PARAMS: &[&str] = &[
+    "category_id", "brand_id", "is_featured", "is_new_arrival", "is_best_seller", "search",
+    "min_price", "max_price", "in_stock", "sort", "page", "limit", "include_tax",
+];
+
+// Query string for GET /api/products. Prices are in kobo and compare
+// against the price a customer pays, i.e. the sale price when there is one.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProductFilter {
     pub category_id: Option<String>,
@@ -61,4 +298,21 @@ pub struct ProductFilter {
     pub is_new_arrival: Option<bool>,
     pub is_best_seller: Option<bool>,
     pub search: Option<String>,
-}
\ No newline at end of file
+    
+    pub min_price: Option<i64>,
+    pub max_price: Option<i64>,
+    pub in_stock: Option<bool>,
+    
+    // newest (default), price_asc, price_desc, name, popularity or rating
+    pub sort: Option<String>,
+    pub page: Option<u64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductListResponse<T> {
+    pub products: Vec<T>,
+    pub total: u64,
+    pub page: u64,
+    pub limit: i64,
+}
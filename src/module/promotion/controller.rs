@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use bson::{doc, oid::ObjectId, Document};
+use chrono::Utc;
+use validator::Validate;
+use crate::module::product::{crud::ProductCrud, model::Product};
+use crate::module::promotion::{
+    crud::PromotionCrud,
+    interface::PromotionService,
+    model::{Coupon, CouponRedemption, DiscountType, RedemptionStatus},
+    schema::{
+        CouponSchema, DiscountBreakdown, DiscountLine, RedemptionListQuery, RedemptionListResponse,
+        ValidateCouponSchema,
+    },
+};
+use crate::service::{currency_service::format_naira, pagination_service::paginate};
+
+// A basket line priced at the chosen variant, or the product when it has none
+struct BasketLine {
+    product: Product,
+    variant_id: Option<ObjectId>,
+    name: String,
+    unit_price: i64,
+    quantity: i32,
+}
+
+impl BasketLine {
+    fn line_total(&self) -> i64 {
+        self.unit_price * self.quantity as i64
+    }
+}
+
+pub struct PromotionController {
+    crud: PromotionCrud,
+    product_crud: ProductCrud,
+}
+
+impl PromotionController {
+    pub fn new(crud: PromotionCrud, product_crud: ProductCrud) -> Self {
+        Self { crud, product_crud }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    fn parse_ids(ids: &[String], what: &str) -> Result<Vec<ObjectId>, String> {
+        ids.iter().map(|id| Self::parse_id(id, what)).collect()
+    }
+
+    fn normalize_code(code: &str) -> String {
+        code.trim().to_uppercase()
+    }
+
+    // Validate the admin's input and build the coupon it describes
+    fn build_coupon(data: CouponSchema) -> Result<Coupon, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let code = Self::normalize_code(&data.code);
+        if !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err("Codes can only contain letters, numbers, dashes and underscores".to_string());
+        }
+
+        match data.discount_type {
+            DiscountType::Percentage if !(1..=100).contains(&data.value) => {
+                return Err("Percentage discounts must be between 1 and 100".to_string());
+            }
+            DiscountType::FixedAmount if data.value < 1 => {
+                return Err("Fixed discounts must be at least 1 kobo".to_string());
+            }
+            _ => {}
+        }
+
+        let starts_at = data.starts_at.unwrap_or_else(Utc::now);
+        if data.ends_at.is_some_and(|ends_at| ends_at <= starts_at) {
+            return Err("The end date must be after the start date".to_string());
+        }
+
+        let now = Utc::now();
+        Ok(Coupon {
+            id: None,
+            code,
+            description: data.description,
+            discount_type: data.discount_type,
+            value: if data.discount_type == DiscountType::FreeShipping { 0 } else { data.value },
+            max_discount: data.max_discount.filter(|_| data.discount_type == DiscountType::Percentage),
+            min_spend: data.min_spend,
+            starts_at,
+            ends_at: data.ends_at,
+            usage_limit: data.usage_limit,
+            per_user_limit: data.per_user_limit,
+            times_redeemed: 0,
+            product_ids: Self::parse_ids(&data.product_ids, "product")?,
+            category_ids: Self::parse_ids(&data.category_ids, "category")?,
+            brand_ids: Self::parse_ids(&data.brand_ids, "brand")?,
+            is_active: data.is_active,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    // Every setting an admin can change, with unset options written as null so they clear
+    fn settings_document(coupon: &Coupon) -> Document {
+        doc! {
+            "code": &coupon.code,
+            "description": coupon.description.clone(),
+            "discount_type": coupon.discount_type.as_str(),
+            "value": coupon.value,
+            "max_discount": coupon.max_discount,
+            "min_spend": coupon.min_spend,
+            "starts_at": bson::DateTime::from_chrono(coupon.starts_at),
+            "ends_at": coupon.ends_at.map(bson::DateTime::from_chrono),
+            "usage_limit": coupon.usage_limit,
+            "per_user_limit": coupon.per_user_limit,
+            "product_ids": coupon.product_ids.clone(),
+            "category_ids": coupon.category_ids.clone(),
+            "brand_ids": coupon.brand_ids.clone(),
+            "is_active": coupon.is_active,
+            "updated_at": bson::DateTime::from_chrono(coupon.updated_at),
+        }
+    }
+
+    // Whether the code can be used right now, and by this customer. This is
+    // an early answer for the shopper; `redeem` enforces the limits.
+    async fn check_available(&self, coupon: &Coupon, user_id: Option<&ObjectId>) -> Result<(), String> {
+        let now = Utc::now();
+        if !coupon.is_active || now < coupon.starts_at {
+            return Err("This code is not valid".to_string());
+        }
+        if coupon.ends_at.is_some_and(|ends_at| now >= ends_at) {
+            return Err("This code has expired".to_string());
+        }
+        if coupon.usage_limit.is_some_and(|limit| coupon.times_redeemed >= limit) {
+            return Err("This code has reached its usage limit".to_string());
+        }
+
+        if let (Some(limit), Some(user_id), Some(coupon_id)) = (coupon.per_user_limit, user_id, coupon.id) {
+            let used = self.crud.count_user_redemptions(&coupon_id, user_id).await?;
+            if used as i64 >= limit {
+                return Err("You have already used this code".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    // Look up the basket's products, combining repeated lines
+    async fn load_basket(&self, items: Vec<(ObjectId, Option<ObjectId>, i32)>) -> Result<Vec<BasketLine>, String> {
+        let mut quantities: Vec<(ObjectId, Option<ObjectId>, i32)> = Vec::new();
+        for (product_id, variant_id, quantity) in items {
+            match quantities.iter_mut().find(|(id, variant, _)| *id == product_id && *variant == variant_id) {
+                Some((_, _, total)) => *total += quantity,
+                None => quantities.push((product_id, variant_id, quantity)),
+            }
+        }
+
+        let ids: Vec<ObjectId> = quantities.iter().map(|(id, _, _)| *id).collect();
+        let products: HashMap<ObjectId, Product> = self.product_crud.get_products_by_ids(&ids).await?
+            .into_iter()
+            .filter_map(|product| product.id.map(|id| (id, product)))
+            .collect();
+
+        quantities.into_iter()
+            .map(|(product_id, variant_id, quantity)| {
+                let product = products.get(&product_id)
+                    .filter(|product| product.is_active)
+                    .ok_or_else(|| format!("Product {} is not available", product_id.to_hex()))?;
+                let option = product.purchase_option(variant_id.as_ref())?;
+                Ok(BasketLine {
+                    name: option.name(),
+                    unit_price: option.unit_price(),
+                    product: product.clone(),
+                    variant_id,
+                    quantity,
+                })
+            })
+            .collect()
+    }
+
+    // Work out the discount and share it across the qualifying lines in
+    // proportion to their value; the last line takes any rounding remainder
+    fn breakdown(coupon: &Coupon, basket: &[BasketLine]) -> Result<DiscountBreakdown, String> {
+        let subtotal: i64 = basket.iter().map(BasketLine::line_total).sum();
+        if let Some(min_spend) = coupon.min_spend.filter(|min_spend| subtotal < *min_spend) {
+            return Err(format!("Spend at least {} to use this code", format_naira(min_spend)));
+        }
+
+        let eligible: Vec<&BasketLine> = basket.iter()
+            .filter(|line| coupon.applies_to(&line.product))
+            .collect();
+        if eligible.is_empty() {
+            return Err("This code does not apply to any item in your basket".to_string());
+        }
+
+        let eligible_subtotal: i64 = eligible.iter().map(|line| line.line_total()).sum();
+        let discount_total = coupon.discount_for(eligible_subtotal);
+
+        let mut remaining = discount_total;
+        let mut lines = Vec::new();
+        for (index, line) in eligible.iter().enumerate() {
+            let line_total = line.line_total();
+            let discount = if index + 1 == eligible.len() {
+                remaining
+            } else if eligible_subtotal > 0 {
+                discount_total * line_total / eligible_subtotal
+            } else {
+                0
+            };
+            remaining -= discount;
+
+            lines.push(DiscountLine {
+                product_id: line.product.id.map(|id| id.to_hex()).unwrap_or_default(),
+                variant_id: line.variant_id.map(|id| id.to_hex()),
+                name: line.name.clone(),
+                quantity: line.quantity,
+                line_total,
+                discount,
+            });
+        }
+
+        Ok(DiscountBreakdown {
+            code: coupon.code.clone(),
+            discount_type: coupon.discount_type,
+            subtotal,
+            eligible_subtotal,
+            discount_total,
+            free_shipping: coupon.discount_type == DiscountType::FreeShipping,
+            lines,
+        })
+    }
+
+    // Check a code against a basket; used by the validate endpoint and at checkout
+    pub async fn evaluate(&self, code: &str, user_id: Option<&ObjectId>, items: Vec<(ObjectId, Option<ObjectId>, i32)>) -> Result<(Coupon, DiscountBreakdown), String> {
+        let coupon = self.crud.get_coupon_by_code(&Self::normalize_code(code)).await?
+            .ok_or_else(|| "This code is not valid".to_string())?;
+        self.check_available(&coupon, user_id).await?;
+
+        let basket = self.load_basket(items).await?;
+        let breakdown = Self::breakdown(&coupon, &basket)?;
+        Ok((coupon, breakdown))
+    }
+
+    // Record a use of the code against an order. The usage and per-user
+    // limits are enforced again here, as part of the write.
+    pub async fn redeem(&self, coupon: &Coupon, user_id: ObjectId, reference: &str, discount: i64) -> Result<CouponRedemption, String> {
+        let coupon_id = coupon.id.ok_or_else(|| "Coupon has no ID".to_string())?;
+        self.crud.create_redemption(CouponRedemption {
+            id: None,
+            coupon_id,
+            code: coupon.code.clone(),
+            user_id,
+            reference: reference.to_string(),
+            discount,
+            status: RedemptionStatus::Redeemed,
+            slot: None,
+            redeemed_at: Utc::now(),
+            voided_at: None,
+        }, coupon.per_user_limit).await
+    }
+
+    // Give back the uses of any codes redeemed on a cancelled order
+    pub async fn void_redemptions(&self, reference: &str) -> Result<usize, String> {
+        self.crud.void_redemptions(reference).await
+    }
+}
+
+#[async_trait::async_trait]
+impl PromotionService for PromotionController {
+    async fn validate_code(&self, user_id: Option<&str>, data: ValidateCouponSchema) -> Result<DiscountBreakdown, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let user_oid = user_id.map(|id| Self::parse_id(id, "user")).transpose()?;
+        let items = data.items.iter()
+            .map(|item| {
+                let variant_id = item.variant_id.as_deref()
+                    .map(|id| Self::parse_id(id, "variant"))
+                    .transpose()?;
+                Ok((Self::parse_id(&item.product_id, "product")?, variant_id, item.quantity))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let (_, breakdown) = self.evaluate(&data.code, user_oid.as_ref(), items).await?;
+        Ok(breakdown)
+    }
+
+    async fn create_coupon(&self, data: CouponSchema) -> Result<Coupon, String> {
+        let coupon = Self::build_coupon(data)?;
+        if self.crud.get_coupon_by_code(&coupon.code).await?.is_some() {
+            return Err(format!("Code {} already exists", coupon.code));
+        }
+        self.crud.create_coupon(coupon).await
+    }
+
+    async fn get_coupons(&self) -> Result<Vec<Coupon>, String> {
+        self.crud.get_coupons().await
+    }
+
+    async fn get_coupon(&self, id: &str) -> Result<Option<Coupon>, String> {
+        let oid = Self::parse_id(id, "coupon")?;
+        self.crud.get_coupon(&oid).await
+    }
+
+    async fn update_coupon(&self, id: &str, data: CouponSchema) -> Result<Option<Coupon>, String> {
+        let oid = Self::parse_id(id, "coupon")?;
+        let coupon = Self::build_coupon(data)?;
+
+        if let Some(existing) = self.crud.get_coupon_by_code(&coupon.code).await?
+            && existing.id != Some(oid)
+        {
+            return Err(format!("Code {} already exists", coupon.code));
+        }
+        self.crud.update_coupon(&oid, Self::settings_document(&coupon)).await
+    }
+
+    async fn delete_coupon(&self, id: &str) -> Result<bool, String> {
+        let oid = Self::parse_id(id, "coupon")?;
+        if self.crud.delete_unused_coupon(&oid).await? {
+            return Ok(true);
+        }
+        match self.crud.get_coupon(&oid).await? {
+            Some(_) => Err("Coupons that have been used can't be deleted; deactivate it instead".to_string()),
+            None => Ok(false),
+        }
+    }
+
+    async fn get_redemptions(&self, id: &str, query: RedemptionListQuery) -> Result<RedemptionListResponse, String> {
+        let oid = Self::parse_id(id, "coupon")?;
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let (redemptions, total) = self.crud.find_redemptions(&oid, skip, limit).await?;
+        Ok(RedemptionListResponse { redemptions, total, page, limit })
+    }
+}
@@ -0,0 +1,210 @@
+use bson::{doc, oid::ObjectId, Document};
+use chrono::Utc;
+use mongodb::{
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Collection, Database,
+};
+use futures_util::TryStreamExt;
+use crate::module::promotion::model::{Coupon, CouponRedemption, RedemptionStatus, USER_SLOT_INDEX};
+use crate::service::migration_service;
+
+#[derive(Clone)]
+pub struct PromotionCrud {
+    coupons: Collection<Coupon>,
+    redemptions: Collection<CouponRedemption>,
+}
+
+impl PromotionCrud {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            coupons: db.collection("coupons"),
+            redemptions: db.collection("coupon_redemptions"),
+        }
+    }
+
+    pub async fn create_coupon(&self, mut coupon: Coupon) -> Result<Coupon, String> {
+        match self.coupons.insert_one(&coupon).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    coupon.id = Some(id);
+                }
+                Ok(coupon)
+            }
+            Err(e) => Err(format!("Failed to create coupon: {}", e))
+        }
+    }
+
+    pub async fn get_coupon(&self, id: &ObjectId) -> Result<Option<Coupon>, String> {
+        match self.coupons.find_one(doc! { "_id": id }).await {
+            Ok(coupon) => Ok(coupon),
+            Err(e) => Err(format!("Failed to retrieve coupon: {}", e))
+        }
+    }
+
+    pub async fn get_coupon_by_code(&self, code: &str) -> Result<Option<Coupon>, String> {
+        match self.coupons.find_one(doc! { "code": code }).await {
+            Ok(coupon) => Ok(coupon),
+            Err(e) => Err(format!("Failed to retrieve coupon: {}", e))
+        }
+    }
+
+    pub async fn get_coupons(&self) -> Result<Vec<Coupon>, String> {
+        let options = FindOptions::builder().sort(doc! { "created_at": -1 }).build();
+
+        match self.coupons.find(doc! {}).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut coupons = Vec::new();
+                while let Some(coupon) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve coupons: {}", e))? {
+                    coupons.push(coupon);
+                }
+                Ok(coupons)
+            }
+            Err(e) => Err(format!("Failed to retrieve coupons: {}", e))
+        }
+    }
+
+    // Replace a coupon's settings; the redemption count is left alone
+    pub async fn update_coupon(&self, id: &ObjectId, settings: Document) -> Result<Option<Coupon>, String> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        match self.coupons.find_one_and_update(doc! { "_id": id }, doc! { "$set": settings }).with_options(options).await {
+            Ok(coupon) => Ok(coupon),
+            Err(e) => Err(format!("Failed to update coupon: {}", e))
+        }
+    }
+
+    // Coupons that were used are kept for the redemption history
+    pub async fn delete_unused_coupon(&self, id: &ObjectId) -> Result<bool, String> {
+        match self.coupons.delete_one(doc! { "_id": id, "times_redeemed": 0 }).await {
+            Ok(result) => Ok(result.deleted_count == 1),
+            Err(e) => Err(format!("Failed to delete coupon: {}", e))
+        }
+    }
+
+    pub async fn count_user_redemptions(&self, coupon_id: &ObjectId, user_id: &ObjectId) -> Result<u64, String> {
+        self.redemptions
+            .count_documents(doc! {
+                "coupon_id": coupon_id,
+                "user_id": user_id,
+                "status": RedemptionStatus::Redeemed.as_str(),
+            })
+            .await
+            .map_err(|e| format!("Failed to count coupon redemptions: {}", e))
+    }
+
+    // Count one more use, but only while the coupon is live and under its usage limit,
+    // so concurrent checkouts can't go past the limit
+    async fn claim_use(&self, coupon_id: &ObjectId) -> Result<bool, String> {
+        let filter = doc! {
+            "_id": coupon_id,
+            "is_active": true,
+            "$or": [
+                { "usage_limit": null },
+                { "$expr": { "$lt": ["$times_redeemed", "$usage_limit"] } },
+            ],
+        };
+        let update = doc! {
+            "$inc": { "times_redeemed": 1_i64 },
+            "$set": { "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+        };
+
+        match self.coupons.update_one(filter, update).await {
+            Ok(result) => Ok(result.modified_count == 1),
+            Err(e) => Err(format!("Failed to redeem coupon: {}", e))
+        }
+    }
+
+    async fn return_use(&self, coupon_id: &ObjectId) -> Result<(), String> {
+        self.coupons
+            .update_one(
+                doc! { "_id": coupon_id, "times_redeemed": { "$gt": 0 } },
+                doc! { "$inc": { "times_redeemed": -1_i64 } },
+            )
+            .await
+            .map_err(|e| format!("Failed to update coupon usage: {}", e))?;
+        Ok(())
+    }
+
+    // Record a redemption. With a per-user limit the redemption takes the
+    // customer's first free use, so concurrent checkouts can't go past it.
+    pub async fn create_redemption(&self, mut redemption: CouponRedemption, per_user_limit: Option<i64>) -> Result<CouponRedemption, String> {
+        if !self.claim_use(&redemption.coupon_id).await? {
+            return Err("This code has reached its usage limit".to_string());
+        }
+
+        let slots: Vec<Option<i64>> = match per_user_limit {
+            Some(limit) => (1..=limit).map(Some).collect(),
+            None => vec![None],
+        };
+        for slot in slots {
+            redemption.slot = slot;
+            match self.redemptions.insert_one(&redemption).await {
+                Ok(result) => {
+                    if let Some(id) = result.inserted_id.as_object_id() {
+                        redemption.id = Some(id);
+                    }
+                    return Ok(redemption);
+                }
+                Err(e) if migration_service::is_duplicate_key(&e, USER_SLOT_INDEX) => continue,
+                Err(e) => {
+                    self.return_use(&redemption.coupon_id).await?;
+                    return Err(format!("Failed to redeem coupon: {}", e));
+                }
+            }
+        }
+
+        self.return_use(&redemption.coupon_id).await?;
+        Err("You have already used this code".to_string())
+    }
+
+    // Void the redemptions made for an order and give the uses back.
+    // Returns how many were voided.
+    pub async fn void_redemptions(&self, reference: &str) -> Result<usize, String> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let mut count = 0;
+        // One at a time, so each use is handed back exactly once
+        while let Some(redemption) = self.redemptions
+            .find_one_and_update(
+                doc! { "reference": reference, "status": RedemptionStatus::Redeemed.as_str() },
+                doc! { "$set": { "status": RedemptionStatus::Voided.as_str(), "voided_at": bson::DateTime::from_chrono(Utc::now()) } },
+            )
+            .with_options(options.clone())
+            .await
+            .map_err(|e| format!("Failed to void coupon redemption: {}", e))?
+        {
+            self.return_use(&redemption.coupon_id).await?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    pub async fn find_redemptions(&self, coupon_id: &ObjectId, skip: u64, limit: i64) -> Result<(Vec<CouponRedemption>, u64), String> {
+        let filter = doc! { "coupon_id": coupon_id };
+        let total = self.redemptions.count_documents(filter.clone()).await
+            .map_err(|e| format!("Failed to count coupon redemptions: {}", e))?;
+
+        let options = FindOptions::builder()
+            .sort(doc! { "redeemed_at": -1 })
+            .skip(skip)
+            .limit(limit)
+            .build();
+
+        match self.redemptions.find(filter).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut redemptions = Vec::new();
+                while let Some(redemption) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve coupon redemptions: {}", e))? {
+                    redemptions.push(redemption);
+                }
+                Ok((redemptions, total))
+            }
+            Err(e) => Err(format!("Failed to retrieve coupon redemptions: {}", e))
+        }
+    }
+}
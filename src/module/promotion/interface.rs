@@ -0,0 +1,18 @@
+use crate::module::promotion::{
+    model::Coupon,
+    schema::{CouponSchema, DiscountBreakdown, RedemptionListQuery, RedemptionListResponse, ValidateCouponSchema},
+};
+
+#[async_trait::async_trait]
+pub trait PromotionService {
+    // Customer; per customer limits are only checked when signed in
+    async fn validate_code(&self, user_id: Option<&str>, data: ValidateCouponSchema) -> Result<DiscountBreakdown, String>;
+
+    // Admin
+    async fn create_coupon(&self, data: CouponSchema) -> Result<Coupon, String>;
+    async fn get_coupons(&self) -> Result<Vec<Coupon>, String>;
+    async fn get_coupon(&self, id: &str) -> Result<Option<Coupon>, String>;
+    async fn update_coupon(&self, id: &str, data: CouponSchema) -> Result<Option<Coupon>, String>;
+    async fn delete_coupon(&self, id: &str) -> Result<bool, String>;
+    async fn get_redemptions(&self, id: &str, query: RedemptionListQuery) -> Result<RedemptionListResponse, String>;
+}
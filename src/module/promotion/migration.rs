@@ -0,0 +1,39 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::module::promotion::model::{RedemptionStatus, USER_SLOT_INDEX};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("promotion", 1, "unique coupon code index", "coupons", vec![
+            IndexModel::builder()
+                .keys(doc! { "code": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+        ]),
+        Migration::indexes("promotion", 2, "coupon redemption indexes", "coupon_redemptions", vec![
+            // A code can be used once per order
+            IndexModel::builder()
+                .keys(doc! { "reference": 1, "coupon_id": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "coupon_id": 1, "user_id": 1, "status": 1 })
+                .build(),
+        ]),
+        // Each of a customer's limited uses can be held by one live redemption
+        Migration::indexes("promotion", 3, "one redemption per use slot", "coupon_redemptions", vec![
+            IndexModel::builder()
+                .keys(doc! { "coupon_id": 1, "user_id": 1, "slot": 1 })
+                .options(IndexOptions::builder()
+                    .name(USER_SLOT_INDEX.to_string())
+                    .unique(true)
+                    .partial_filter_expression(doc! {
+                        "slot": { "$exists": true },
+                        "status": RedemptionStatus::Redeemed.as_str(),
+                    })
+                    .build())
+                .build(),
+        ]),
+    ]
+}
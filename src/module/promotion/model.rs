@@ -0,0 +1,149 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::module::product::model::Product;
+
+// Unique index that lets each per-user use of a code be taken once
+pub const USER_SLOT_INDEX: &str = "user_slot_unique";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscountType {
+    // `value` is a whole percentage, 1 to 100
+    Percentage,
+    // `value` is an amount in kobo
+    FixedAmount,
+    // Waives delivery; `value` is unused
+    FreeShipping,
+}
+
+impl DiscountType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiscountType::Percentage => "percentage",
+            DiscountType::FixedAmount => "fixed_amount",
+            DiscountType::FreeShipping => "free_shipping",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Coupon {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    // Stored upper case; customers can type it in any case
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    pub discount_type: DiscountType,
+    pub value: i64,
+    // Cap for percentage discounts, in kobo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_discount: Option<i64>,
+    // Basket subtotal needed before the code applies, in kobo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_spend: Option<i64>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub starts_at: DateTime<Utc>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub ends_at: Option<DateTime<Utc>>,
+
+    // Redemptions allowed in total and per customer; None is unlimited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_limit: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_user_limit: Option<i64>,
+    pub times_redeemed: i64,
+
+    // Eligibility. A product qualifies if it matches any list; all empty means every product.
+    #[serde(default)]
+    pub product_ids: Vec<ObjectId>,
+    #[serde(default)]
+    pub category_ids: Vec<ObjectId>,
+    #[serde(default)]
+    pub brand_ids: Vec<ObjectId>,
+
+    pub is_active: bool,
+
+    // Timestamps
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Coupon {
+    pub fn applies_to(&self, product: &Product) -> bool {
+        if self.product_ids.is_empty() && self.category_ids.is_empty() && self.brand_ids.is_empty() {
+            return true;
+        }
+        product.id.is_some_and(|id| self.product_ids.contains(&id))
+            || self.category_ids.contains(&product.category_id)
+            || self.brand_ids.contains(&product.brand_id)
+    }
+
+    // Discount on `eligible_subtotal` kobo of qualifying items
+    pub fn discount_for(&self, eligible_subtotal: i64) -> i64 {
+        match self.discount_type {
+            DiscountType::Percentage => {
+                let discount = eligible_subtotal * self.value / 100;
+                self.max_discount.map_or(discount, |cap| discount.min(cap))
+            }
+            DiscountType::FixedAmount => self.value.min(eligible_subtotal),
+            DiscountType::FreeShipping => 0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RedemptionStatus {
+    Redeemed,
+    // The order was cancelled, so the use no longer counts
+    Voided,
+}
+
+impl RedemptionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RedemptionStatus::Redeemed => "redeemed",
+            RedemptionStatus::Voided => "voided",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CouponRedemption {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub coupon_id: ObjectId,
+    pub code: String,
+    pub user_id: ObjectId,
+    // Order number the code was used on
+    pub reference: String,
+    pub discount: i64, // In kobo
+    pub status: RedemptionStatus,
+    // Which of the customer's allowed uses this is, 1 up to the coupon's
+    // per-user limit. A unique index over live redemptions stops two
+    // checkouts taking the same use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<i64>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub redeemed_at: DateTime<Utc>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub voided_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,145 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::Authentication;
+use crate::module::product::crud::ProductCrud;
+use crate::module::promotion::{
+    controller::PromotionController,
+    crud::PromotionCrud,
+    interface::PromotionService,
+    schema::{CouponSchema, RedemptionListQuery, ValidateCouponSchema},
+};
+
+fn controller(crud: &web::Data<PromotionCrud>, product_crud: &web::Data<ProductCrud>) -> PromotionController {
+    PromotionController::new(crud.get_ref().clone(), product_crud.get_ref().clone())
+}
+
+// POST validate coupon handler (guests and users)
+async fn validate_code_handler(
+    req: HttpRequest,
+    body: web::Json<ValidateCouponSchema>,
+    crud: web::Data<PromotionCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    let user_id = Authentication::optional_user(&req).map(|claims| claims.sub);
+
+    match controller(&crud, &product_crud).validate_code(user_id.as_deref(), body.into_inner()).await {
+        Ok(breakdown) => Ok(HttpResponse::Ok().json(breakdown)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST coupon handler (admin)
+async fn create_coupon_handler(
+    body: web::Json<CouponSchema>,
+    crud: web::Data<PromotionCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).create_coupon(body.into_inner()).await {
+        Ok(coupon) => Ok(HttpResponse::Created().json(coupon)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET all coupons handler (admin)
+async fn get_coupons_handler(
+    crud: web::Data<PromotionCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).get_coupons().await {
+        Ok(coupons) => Ok(HttpResponse::Ok().json(coupons)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET single coupon handler (admin)
+async fn get_coupon_handler(
+    id: web::Path<String>,
+    crud: web::Data<PromotionCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).get_coupon(&id).await {
+        Ok(Some(coupon)) => Ok(HttpResponse::Ok().json(coupon)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Coupon not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// PUT coupon handler (admin)
+async fn update_coupon_handler(
+    id: web::Path<String>,
+    body: web::Json<CouponSchema>,
+    crud: web::Data<PromotionCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).update_coupon(&id, body.into_inner()).await {
+        Ok(Some(coupon)) => Ok(HttpResponse::Ok().json(coupon)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Coupon not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE coupon handler (admin)
+async fn delete_coupon_handler(
+    id: web::Path<String>,
+    crud: web::Data<PromotionCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).delete_coupon(&id).await {
+        Ok(true) => Ok(HttpResponse::NoContent().finish()),
+        Ok(false) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Coupon not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET coupon redemptions handler (admin)
+async fn get_redemptions_handler(
+    id: web::Path<String>,
+    query: web::Query<RedemptionListQuery>,
+    crud: web::Data<PromotionCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).get_redemptions(&id, query.into_inner()).await {
+        Ok(redemptions) => Ok(HttpResponse::Ok().json(redemptions)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// Route configuration function
+pub fn promotion_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/coupons")
+            .route("/validate", web::post().to(validate_code_handler))
+    );
+}
+
+// Admin routes, mounted under the protected /api/admin scope
+pub fn admin_promotion_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/coupons")
+            .route("", web::post().to(create_coupon_handler))
+            .route("", web::get().to(get_coupons_handler))
+            .route("/{id}", web::get().to(get_coupon_handler))
+            .route("/{id}", web::put().to(update_coupon_handler))
+            .route("/{id}", web::delete().to(delete_coupon_handler))
+            .route("/{id}/redemptions", web::get().to(get_redemptions_handler))
+    );
+}
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::module::promotion::model::{CouponRedemption, DiscountType};
+
+// Used both to create a coupon and to replace one
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct CouponSchema {
+    #[validate(length(min = 3, max = 30))]
+    pub code: String,
+
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+
+    pub discount_type: DiscountType,
+
+    #[serde(default)]
+    #[validate(range(min = 0))]
+    pub value: i64,
+
+    #[validate(range(min = 1))]
+    pub max_discount: Option<i64>,
+
+    #[validate(range(min = 0))]
+    pub min_spend: Option<i64>,
+
+    // Defaults to now
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+
+    #[validate(range(min = 1))]
+    pub usage_limit: Option<i64>,
+
+    #[validate(range(min = 1))]
+    pub per_user_limit: Option<i64>,
+
+    #[serde(default)]
+    pub product_ids: Vec<String>,
+    #[serde(default)]
+    pub category_ids: Vec<String>,
+    #[serde(default)]
+    pub brand_ids: Vec<String>,
+
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct BasketItemSchema {
+    pub product_id: String,  // Will be converted to ObjectId
+    pub variant_id: Option<String>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub quantity: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct ValidateCouponSchema {
+    #[validate(length(min = 1, max = 30))]
+    pub code: String,
+
+    #[validate(length(min = 1, max = 50), nested)]
+    pub items: Vec<BasketItemSchema>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiscountLine {
+    pub product_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant_id: Option<String>,
+    pub name: String,
+    pub quantity: i32,
+    pub line_total: i64,
+    pub discount: i64,
+}
+
+// What a code is worth on a basket, split across the lines it applies to
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiscountBreakdown {
+    pub code: String,
+    pub discount_type: DiscountType,
+    pub subtotal: i64,
+    pub eligible_subtotal: i64,
+    pub discount_total: i64,
+    pub free_shipping: bool,
+    pub lines: Vec<DiscountLine>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RedemptionListQuery {
+    pub page: Option<u64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RedemptionListResponse {
+    pub redemptions: Vec<CouponRedemption>,
+    pub total: u64,
+    pub page: u64,
+    pub limit: i64,
+}
+
+fn default_true() -> bool {
+    true
+}
@@ -0,0 +1,307 @@
+use bson::{doc, oid::ObjectId};
+use validator::Validate;
+use crate::module::order::crud::OrderCrud;
+use crate::module::product::crud::ProductCrud;
+use crate::module::question::{
+    crud::QuestionCrud,
+    interface::QuestionService,
+    model::{Answer, AnswerAuthor, QaStatus, Question},
+    schema::{
+        AnswerQuestionSchema, AskQuestionSchema, ProductQuestionsResponse, QaNoteSchema,
+        QaQueueQuery, QuestionListQuery, QuestionListResponse, QuestionResponse,
+    },
+};
+use crate::service::{email_service, email_templates, link_service::storefront_url};
+use crate::service::pagination_service::paginate;
+
+pub struct QuestionController {
+    crud: QuestionCrud,
+    product_crud: ProductCrud,
+    order_crud: OrderCrud,
+}
+
+impl QuestionController {
+    pub fn new(crud: QuestionCrud, product_crud: ProductCrud, order_crud: OrderCrud) -> Self {
+        Self { crud, product_crud, order_crud }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    fn queue_status(status: Option<&str>) -> Result<QaStatus, String> {
+        match status {
+            Some(status) => QaStatus::parse(status)
+                .ok_or_else(|| format!("Unknown status: {}", status)),
+            None => Ok(QaStatus::Pending),
+        }
+    }
+
+    async fn moderate_question(&self, id: &str, next: QaStatus, data: QaNoteSchema) -> Result<Question, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let id = Self::parse_id(id, "question")?;
+        let question = self.crud.get_question(&id).await?
+            .ok_or_else(|| "Question not found".to_string())?;
+        if !question.status.can_transition_to(next) {
+            return Err(format!(
+                "Cannot move question from {} to {}",
+                question.status.as_str(),
+                next.as_str()
+            ));
+        }
+
+        self.crud.transition_status(&id, question.status, next, data.note).await?
+            .ok_or_else(|| "Question was moderated concurrently, please retry".to_string())
+    }
+
+    async fn moderate_answer(&self, id: &str, answer_id: &str, next: QaStatus) -> Result<Question, String> {
+        let id = Self::parse_id(id, "question")?;
+        let answer_id = Self::parse_id(answer_id, "answer")?;
+        let question = self.crud.get_question(&id).await?
+            .ok_or_else(|| "Question not found".to_string())?;
+        let answer = question.answer(&answer_id)
+            .ok_or_else(|| "Answer not found".to_string())?;
+        if !answer.status.can_transition_to(next) {
+            return Err(format!(
+                "Cannot move answer from {} to {}",
+                answer.status.as_str(),
+                next.as_str()
+            ));
+        }
+        // The asker hears about an answer the first time it goes live
+        let first_publication = answer.status == QaStatus::Pending && next == QaStatus::Approved;
+
+        let updated = self.crud.transition_answer(&id, &answer_id, answer.status, next).await?
+            .ok_or_else(|| "Answer was moderated concurrently, please retry".to_string())?;
+        if first_publication && let Some(answer) = updated.answer(&answer_id) {
+            self.notify_asker(&updated, answer).await;
+        }
+        Ok(updated)
+    }
+
+    async fn add_answer(&self, id: &ObjectId, answer: Answer) -> Result<Question, String> {
+        let question = self.crud.add_answer(id, &answer).await?
+            .ok_or_else(|| "Only published questions can be answered".to_string())?;
+        if answer.status == QaStatus::Approved {
+            self.notify_asker(&question, &answer).await;
+        }
+        Ok(question)
+    }
+
+    // Email the asker that their question has a published answer. A failed
+    // email doesn't undo the answer.
+    async fn notify_asker(&self, question: &Question, answer: &Answer) {
+        let product = match self.product_crud.get_product(&question.product_id.to_hex()).await {
+            Ok(Some(product)) => product,
+            _ => return,
+        };
+        let (subject, body) = email_templates::get_question_answered_email_template(
+            &question.author_name,
+            &product.name,
+            &question.body,
+            &answer.body,
+            answer.author_type.label(),
+            &format!("{}/products/{}", storefront_url(), product.slug),
+        );
+        let email = question.author_email.clone();
+        let name = question.author_name.clone();
+
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = email_service::send_email(&email, &name, &subject, &body) {
+                eprintln!("Failed to send question answered email: {}", e);
+            }
+        });
+    }
+
+    // Upvotes only go to published answers, and never to one's own
+    async fn votable_question(&self, user_id: &str, id: &ObjectId, answer_id: &ObjectId) -> Result<Question, String> {
+        let question = self.crud.get_question(id).await?
+            .filter(|question| question.status == QaStatus::Approved)
+            .ok_or_else(|| "Question not found".to_string())?;
+        let answer = question.answer(answer_id)
+            .filter(|answer| answer.status == QaStatus::Approved)
+            .ok_or_else(|| "Answer not found".to_string())?;
+        if answer.author_id == user_id {
+            return Err("You can't upvote your own answer".to_string());
+        }
+        Ok(question)
+    }
+}
+
+#[async_trait::async_trait]
+impl QuestionService for QuestionController {
+    async fn get_product_questions(&self, product_id: &str, query: QuestionListQuery) -> Result<ProductQuestionsResponse, String> {
+        let product = self.product_crud.get_product(product_id).await?
+            .filter(|product| product.deleted_at.is_none())
+            .ok_or_else(|| "Product not found".to_string())?;
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let mut filter = doc! {
+            "product_id": product.id,
+            "status": QaStatus::Approved.as_str(),
+        };
+        match query.answered {
+            Some(true) => {
+                filter.insert("answers.status", QaStatus::Approved.as_str());
+            }
+            Some(false) => {
+                filter.insert("answers", doc! { "$not": { "$elemMatch": { "status": QaStatus::Approved.as_str() } } });
+            }
+            None => {}
+        }
+
+        let (questions, total) = self.crud
+            .find_questions(filter, doc! { "created_at": -1, "_id": -1 }, skip, limit)
+            .await?;
+        Ok(ProductQuestionsResponse {
+            questions: questions.into_iter().map(QuestionResponse::from).collect(),
+            total,
+            page,
+            limit,
+        })
+    }
+
+    async fn ask_question(&self, user_id: &str, author_name: &str, author_email: &str, data: AskQuestionSchema) -> Result<Question, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let product_id = Self::parse_id(&data.product_id, "product")?;
+        self.product_crud.get_product(&data.product_id).await?
+            .filter(|product| product.deleted_at.is_none())
+            .ok_or_else(|| "Product not found".to_string())?;
+
+        let question = Question::new(
+            product_id,
+            user_oid,
+            author_name.to_string(),
+            author_email.to_string(),
+            data.body.trim().to_string(),
+        );
+        self.crud.create_question(question).await
+    }
+
+    async fn get_user_questions(&self, user_id: &str, query: QuestionListQuery) -> Result<QuestionListResponse, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let (questions, total) = self.crud
+            .find_questions(doc! { "user_id": user_oid }, doc! { "created_at": -1 }, skip, limit)
+            .await?;
+        Ok(QuestionListResponse { questions, total, page, limit })
+    }
+
+    async fn answer_as_owner(&self, user_id: &str, author_name: &str, id: &str, data: AnswerQuestionSchema) -> Result<Question, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let id = Self::parse_id(id, "question")?;
+        let question = self.crud.get_question(&id).await?
+            .filter(|question| question.status == QaStatus::Approved)
+            .ok_or_else(|| "Question not found".to_string())?;
+        if question.user_id == user_oid {
+            return Err("You can't answer your own question".to_string());
+        }
+        if self.order_crud.find_delivered_purchase(&user_oid, &question.product_id).await?.is_none() {
+            return Err("Only customers who have received this product can answer".to_string());
+        }
+
+        let answer = Answer::new(
+            user_id.to_string(),
+            author_name.to_string(),
+            AnswerAuthor::VerifiedOwner,
+            data.body.trim().to_string(),
+        );
+        self.add_answer(&id, answer).await
+    }
+
+    async fn upvote_answer(&self, user_id: &str, id: &str, answer_id: &str) -> Result<QuestionResponse, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let id = Self::parse_id(id, "question")?;
+        let answer_id = Self::parse_id(answer_id, "answer")?;
+        let question = self.votable_question(user_id, &id, &answer_id).await?;
+
+        // Upvoting twice leaves the count as it was
+        let question = self.crud.add_upvote(&id, &answer_id, &user_oid).await?.unwrap_or(question);
+        Ok(question.into())
+    }
+
+    async fn remove_upvote(&self, user_id: &str, id: &str, answer_id: &str) -> Result<QuestionResponse, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let id = Self::parse_id(id, "question")?;
+        let answer_id = Self::parse_id(answer_id, "answer")?;
+        let question = self.votable_question(user_id, &id, &answer_id).await?;
+
+        let question = self.crud.remove_upvote(&id, &answer_id, &user_oid).await?.unwrap_or(question);
+        Ok(question.into())
+    }
+
+    async fn question_queue(&self, query: QaQueueQuery) -> Result<QuestionListResponse, String> {
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let status = Self::queue_status(query.status.as_deref())?;
+        let mut filter = doc! { "status": status.as_str() };
+        if let Some(product_id) = &query.product_id {
+            filter.insert("product_id", Self::parse_id(product_id, "product")?);
+        }
+
+        // Oldest first, so questions are dealt with in the order they came in
+        let (questions, total) = self.crud
+            .find_questions(filter, doc! { "created_at": 1 }, skip, limit)
+            .await?;
+        Ok(QuestionListResponse { questions, total, page, limit })
+    }
+
+    async fn answer_queue(&self, query: QaQueueQuery) -> Result<QuestionListResponse, String> {
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        // Questions holding at least one answer in the given status
+        let status = Self::queue_status(query.status.as_deref())?;
+        let mut filter = doc! { "answers.status": status.as_str() };
+        if let Some(product_id) = &query.product_id {
+            filter.insert("product_id", Self::parse_id(product_id, "product")?);
+        }
+
+        let (questions, total) = self.crud
+            .find_questions(filter, doc! { "created_at": 1 }, skip, limit)
+            .await?;
+        Ok(QuestionListResponse { questions, total, page, limit })
+    }
+
+    async fn approve_question(&self, id: &str, data: QaNoteSchema) -> Result<Question, String> {
+        self.moderate_question(id, QaStatus::Approved, data).await
+    }
+
+    async fn reject_question(&self, id: &str, data: QaNoteSchema) -> Result<Question, String> {
+        self.moderate_question(id, QaStatus::Rejected, data).await
+    }
+
+    async fn answer_as_staff(&self, admin_id: &str, author_name: &str, id: &str, data: AnswerQuestionSchema) -> Result<Question, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let id = Self::parse_id(id, "question")?;
+        let answer = Answer::new(
+            admin_id.to_string(),
+            author_name.to_string(),
+            AnswerAuthor::Staff,
+            data.body.trim().to_string(),
+        );
+        self.add_answer(&id, answer).await
+    }
+
+    async fn approve_answer(&self, id: &str, answer_id: &str) -> Result<Question, String> {
+        self.moderate_answer(id, answer_id, QaStatus::Approved).await
+    }
+
+    async fn reject_answer(&self, id: &str, answer_id: &str) -> Result<Question, String> {
+        self.moderate_answer(id, answer_id, QaStatus::Rejected).await
+    }
+}
@@ -0,0 +1,170 @@
+use bson::{doc, oid::ObjectId, Document};
+use chrono::Utc;
+use mongodb::{
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Collection, Database,
+};
+use futures_util::TryStreamExt;
+use crate::module::question::model::{Answer, AnswerVote, QaStatus, Question};
+use crate::service::migration_service;
+
+#[derive(Clone)]
+pub struct QuestionCrud {
+    collection: Collection<Question>,
+    votes: Collection<AnswerVote>,
+}
+
+impl QuestionCrud {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("questions");
+        let votes = db.collection("answer_votes");
+        Self { collection, votes }
+    }
+
+    fn after() -> FindOneAndUpdateOptions {
+        FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build()
+    }
+
+    pub async fn create_question(&self, mut question: Question) -> Result<Question, String> {
+        match self.collection.insert_one(&question).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    question.id = Some(id);
+                }
+                Ok(question)
+            }
+            Err(e) => Err(format!("Failed to create question: {}", e))
+        }
+    }
+
+    pub async fn get_question(&self, id: &ObjectId) -> Result<Option<Question>, String> {
+        match self.collection.find_one(doc! { "_id": id }).await {
+            Ok(question) => Ok(question),
+            Err(e) => Err(format!("Failed to retrieve question: {}", e))
+        }
+    }
+
+    // Page through questions in `sort` order, with the total number of matches
+    pub async fn find_questions(&self, filter: Document, sort: Document, skip: u64, limit: i64) -> Result<(Vec<Question>, u64), String> {
+        let total = self.collection.count_documents(filter.clone()).await
+            .map_err(|e| format!("Failed to count questions: {}", e))?;
+
+        let options = FindOptions::builder()
+            .sort(sort)
+            .skip(skip)
+            .limit(limit)
+            .build();
+
+        match self.collection.find(filter).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut questions = Vec::new();
+                while let Some(question) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve questions: {}", e))? {
+                    questions.push(question);
+                }
+                Ok((questions, total))
+            }
+            Err(e) => Err(format!("Failed to retrieve questions: {}", e))
+        }
+    }
+
+    // Move a question on, but only if nobody else moved it first
+    pub async fn transition_status(
+        &self,
+        id: &ObjectId,
+        from: QaStatus,
+        to: QaStatus,
+        note: Option<String>,
+    ) -> Result<Option<Question>, String> {
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "status": from.as_str() },
+                doc! { "$set": {
+                    "status": to.as_str(),
+                    "moderation_note": note,
+                    "updated_at": bson::DateTime::from_chrono(Utc::now()),
+                } },
+            )
+            .with_options(Self::after())
+            .await
+            .map_err(|e| format!("Failed to update question: {}", e))
+    }
+
+    // Answers can only be added to published questions
+    pub async fn add_answer(&self, id: &ObjectId, answer: &Answer) -> Result<Option<Question>, String> {
+        let answer = bson::to_bson(answer)
+            .map_err(|e| format!("Failed to encode answer: {}", e))?;
+
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "status": QaStatus::Approved.as_str() },
+                doc! {
+                    "$push": { "answers": answer },
+                    "$set": { "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+                },
+            )
+            .with_options(Self::after())
+            .await
+            .map_err(|e| format!("Failed to add answer: {}", e))
+    }
+
+    // Same as `transition_status` for one answer
+    pub async fn transition_answer(
+        &self,
+        id: &ObjectId,
+        answer_id: &ObjectId,
+        from: QaStatus,
+        to: QaStatus,
+    ) -> Result<Option<Question>, String> {
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "answers": { "$elemMatch": { "_id": answer_id, "status": from.as_str() } } },
+                doc! { "$set": {
+                    "answers.$.status": to.as_str(),
+                    "updated_at": bson::DateTime::from_chrono(Utc::now()),
+                } },
+            )
+            .with_options(Self::after())
+            .await
+            .map_err(|e| format!("Failed to update answer: {}", e))
+    }
+
+    // Record the upvote and count it. Returns None when the user had already voted.
+    pub async fn add_upvote(&self, id: &ObjectId, answer_id: &ObjectId, user_id: &ObjectId) -> Result<Option<Question>, String> {
+        let vote = AnswerVote {
+            id: None,
+            answer_id: *answer_id,
+            user_id: *user_id,
+            created_at: Utc::now(),
+        };
+        match self.votes.insert_one(&vote).await {
+            Ok(_) => {}
+            Err(e) if migration_service::is_duplicate_key(&e, "answer_id") => return Ok(None),
+            Err(e) => return Err(format!("Failed to record vote: {}", e)),
+        }
+        self.change_upvote_count(id, answer_id, 1).await
+    }
+
+    // Take back an upvote. Returns None when the user hadn't voted.
+    pub async fn remove_upvote(&self, id: &ObjectId, answer_id: &ObjectId, user_id: &ObjectId) -> Result<Option<Question>, String> {
+        let result = self.votes.delete_one(doc! { "answer_id": answer_id, "user_id": user_id }).await
+            .map_err(|e| format!("Failed to remove vote: {}", e))?;
+        if result.deleted_count == 0 {
+            return Ok(None);
+        }
+        self.change_upvote_count(id, answer_id, -1).await
+    }
+
+    async fn change_upvote_count(&self, id: &ObjectId, answer_id: &ObjectId, change: i32) -> Result<Option<Question>, String> {
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "answers._id": answer_id },
+                doc! { "$inc": { "answers.$.upvote_count": change } },
+            )
+            .with_options(Self::after())
+            .await
+            .map_err(|e| format!("Failed to update answer: {}", e))
+    }
+}
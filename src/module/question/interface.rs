@@ -0,0 +1,27 @@
+use crate::module::question::{
+    model::Question,
+    schema::{
+        AnswerQuestionSchema, AskQuestionSchema, ProductQuestionsResponse, QaNoteSchema,
+        QaQueueQuery, QuestionListQuery, QuestionListResponse, QuestionResponse,
+    },
+};
+
+#[async_trait::async_trait]
+pub trait QuestionService {
+    // Shoppers
+    async fn get_product_questions(&self, product_id: &str, query: QuestionListQuery) -> Result<ProductQuestionsResponse, String>;
+    async fn ask_question(&self, user_id: &str, author_name: &str, author_email: &str, data: AskQuestionSchema) -> Result<Question, String>;
+    async fn get_user_questions(&self, user_id: &str, query: QuestionListQuery) -> Result<QuestionListResponse, String>;
+    async fn answer_as_owner(&self, user_id: &str, author_name: &str, id: &str, data: AnswerQuestionSchema) -> Result<Question, String>;
+    async fn upvote_answer(&self, user_id: &str, id: &str, answer_id: &str) -> Result<QuestionResponse, String>;
+    async fn remove_upvote(&self, user_id: &str, id: &str, answer_id: &str) -> Result<QuestionResponse, String>;
+
+    // Admin
+    async fn question_queue(&self, query: QaQueueQuery) -> Result<QuestionListResponse, String>;
+    async fn answer_queue(&self, query: QaQueueQuery) -> Result<QuestionListResponse, String>;
+    async fn approve_question(&self, id: &str, data: QaNoteSchema) -> Result<Question, String>;
+    async fn reject_question(&self, id: &str, data: QaNoteSchema) -> Result<Question, String>;
+    async fn answer_as_staff(&self, admin_id: &str, author_name: &str, id: &str, data: AnswerQuestionSchema) -> Result<Question, String>;
+    async fn approve_answer(&self, id: &str, answer_id: &str) -> Result<Question, String>;
+    async fn reject_answer(&self, id: &str, answer_id: &str) -> Result<Question, String>;
+}
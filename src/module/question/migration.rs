@@ -0,0 +1,30 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("question", 1, "question and answer vote indexes", "questions", vec![
+            // A product's published questions, newest first
+            IndexModel::builder()
+                .keys(doc! { "product_id": 1, "status": 1, "created_at": -1 })
+                .build(),
+            // Moderation queues for questions and for answers, oldest first
+            IndexModel::builder()
+                .keys(doc! { "status": 1, "created_at": 1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "answers.status": 1, "created_at": 1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "user_id": 1, "created_at": -1 })
+                .build(),
+        ]),
+        Migration::indexes("question", 2, "one upvote per user", "answer_votes", vec![
+            IndexModel::builder()
+                .keys(doc! { "answer_id": 1, "user_id": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+        ]),
+    ]
+}
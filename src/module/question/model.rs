@@ -0,0 +1,151 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Moderation state of a question or an answer
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QaStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl QaStatus {
+    // Anything new waits for a moderator, and a decision can be reversed
+    pub fn can_transition_to(&self, next: QaStatus) -> bool {
+        use QaStatus::*;
+        matches!(
+            (self, next),
+            (Pending, Approved) | (Pending, Rejected) | (Approved, Rejected) | (Rejected, Approved)
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QaStatus::Pending => "pending",
+            QaStatus::Approved => "approved",
+            QaStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(QaStatus::Pending),
+            "approved" => Some(QaStatus::Approved),
+            "rejected" => Some(QaStatus::Rejected),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnswerAuthor {
+    // Our own team; published straight away
+    Staff,
+    // A customer with a delivered order for the product; moderated first
+    VerifiedOwner,
+}
+
+impl AnswerAuthor {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AnswerAuthor::Staff => "our team",
+            AnswerAuthor::VerifiedOwner => "a verified owner",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Answer {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub author_id: String,
+    pub author_name: String,
+    pub author_type: AnswerAuthor,
+    pub body: String,
+    pub status: QaStatus,
+    #[serde(default)]
+    pub upvote_count: i32,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Answer {
+    pub fn new(author_id: String, author_name: String, author_type: AnswerAuthor, body: String) -> Self {
+        let status = match author_type {
+            AnswerAuthor::Staff => QaStatus::Approved,
+            AnswerAuthor::VerifiedOwner => QaStatus::Pending,
+        };
+        Self {
+            id: ObjectId::new(),
+            author_id,
+            author_name,
+            author_type,
+            body,
+            status,
+            upvote_count: 0,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+// A customer's question about a product, with the answers it has had
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Question {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub product_id: ObjectId,
+    pub user_id: ObjectId,
+    pub author_name: String,
+    // Where the "your question was answered" email goes
+    pub author_email: String,
+
+    pub body: String,
+    pub status: QaStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moderation_note: Option<String>,
+
+    pub answers: Vec<Answer>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Question {
+    pub fn new(product_id: ObjectId, user_id: ObjectId, author_name: String, author_email: String, body: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            product_id,
+            user_id,
+            author_name,
+            author_email,
+            body,
+            status: QaStatus::Pending,
+            moderation_note: None,
+            answers: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn answer(&self, answer_id: &ObjectId) -> Option<&Answer> {
+        self.answers.iter().find(|answer| &answer.id == answer_id)
+    }
+}
+
+// One user upvoting one answer
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnswerVote {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub answer_id: ObjectId,
+    pub user_id: ObjectId,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,308 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::Authentication;
+use crate::module::order::crud::OrderCrud;
+use crate::module::product::crud::ProductCrud;
+use crate::module::question::{
+    controller::QuestionController,
+    crud::QuestionCrud,
+    interface::QuestionService,
+    model::Question,
+    schema::{AnswerQuestionSchema, AskQuestionSchema, QaNoteSchema, QaQueueQuery, QuestionListQuery},
+};
+
+fn controller(
+    crud: &web::Data<QuestionCrud>,
+    product_crud: &web::Data<ProductCrud>,
+    order_crud: &web::Data<OrderCrud>,
+) -> QuestionController {
+    QuestionController::new(crud.get_ref().clone(), product_crud.get_ref().clone(), order_crud.get_ref().clone())
+}
+
+// First name and last initial, e.g. "Ada L."
+fn display_name(first_name: &str, last_name: &str) -> String {
+    match last_name.chars().next() {
+        Some(initial) => format!("{} {}.", first_name, initial),
+        None => first_name.to_string(),
+    }
+}
+
+fn question_response(result: std::result::Result<Question, String>) -> HttpResponse {
+    match result {
+        Ok(question) => HttpResponse::Ok().json(question),
+        Err(error) => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        })),
+    }
+}
+
+// GET a product's published questions handler
+async fn get_product_questions_handler(
+    product_id: web::Path<String>,
+    query: web::Query<QuestionListQuery>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud, &order_crud).get_product_questions(&product_id, query.into_inner()).await {
+        Ok(questions) => Ok(HttpResponse::Ok().json(questions)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST question handler
+async fn ask_question_handler(
+    req: HttpRequest,
+    body: web::Json<AskQuestionSchema>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+    let author_name = display_name(&claims.first_name, &claims.last_name);
+
+    match controller(&crud, &product_crud, &order_crud)
+        .ask_question(&claims.sub, &author_name, &claims.email, body.into_inner())
+        .await
+    {
+        Ok(question) => Ok(HttpResponse::Created().json(question)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET user's questions handler
+async fn get_my_questions_handler(
+    req: HttpRequest,
+    query: web::Query<QuestionListQuery>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &order_crud).get_user_questions(&claims.sub, query.into_inner()).await {
+        Ok(questions) => Ok(HttpResponse::Ok().json(questions)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST answer handler (verified owners)
+async fn answer_question_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<AnswerQuestionSchema>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+    let author_name = display_name(&claims.first_name, &claims.last_name);
+
+    match controller(&crud, &product_crud, &order_crud)
+        .answer_as_owner(&claims.sub, &author_name, &id, body.into_inner())
+        .await
+    {
+        Ok(question) => Ok(HttpResponse::Created().json(question)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST answer upvote handler
+async fn upvote_answer_handler(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+    let (id, answer_id) = path.into_inner();
+
+    match controller(&crud, &product_crud, &order_crud).upvote_answer(&claims.sub, &id, &answer_id).await {
+        Ok(question) => Ok(HttpResponse::Ok().json(question)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE answer upvote handler
+async fn remove_upvote_handler(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+    let (id, answer_id) = path.into_inner();
+
+    match controller(&crud, &product_crud, &order_crud).remove_upvote(&claims.sub, &id, &answer_id).await {
+        Ok(question) => Ok(HttpResponse::Ok().json(question)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET question moderation queue handler (admin)
+async fn admin_question_queue_handler(
+    query: web::Query<QaQueueQuery>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud, &order_crud).question_queue(query.into_inner()).await {
+        Ok(questions) => Ok(HttpResponse::Ok().json(questions)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET answer moderation queue handler (admin)
+async fn admin_answer_queue_handler(
+    query: web::Query<QaQueueQuery>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud, &order_crud).answer_queue(query.into_inner()).await {
+        Ok(questions) => Ok(HttpResponse::Ok().json(questions)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST approve question handler (admin)
+async fn admin_approve_question_handler(
+    id: web::Path<String>,
+    body: web::Json<QaNoteSchema>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let result = controller(&crud, &product_crud, &order_crud)
+        .approve_question(&id, body.into_inner())
+        .await;
+    Ok(question_response(result))
+}
+
+// POST reject question handler (admin)
+async fn admin_reject_question_handler(
+    id: web::Path<String>,
+    body: web::Json<QaNoteSchema>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let result = controller(&crud, &product_crud, &order_crud)
+        .reject_question(&id, body.into_inner())
+        .await;
+    Ok(question_response(result))
+}
+
+// POST staff answer handler (admin)
+async fn admin_answer_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<AnswerQuestionSchema>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    // The admin middleware already checked the token; this only reads who it belongs to
+    let (admin_id, author_name) = match Authentication::optional_user(&req) {
+        Some(claims) => (claims.sub, claims.first_name),
+        None => ("unknown".to_string(), String::new()),
+    };
+    let author_name = if author_name.is_empty() { "Store team".to_string() } else { author_name };
+
+    match controller(&crud, &product_crud, &order_crud)
+        .answer_as_staff(&admin_id, &author_name, &id, body.into_inner())
+        .await
+    {
+        Ok(question) => Ok(HttpResponse::Created().json(question)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST approve answer handler (admin)
+async fn admin_approve_answer_handler(
+    path: web::Path<(String, String)>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let (id, answer_id) = path.into_inner();
+    let result = controller(&crud, &product_crud, &order_crud)
+        .approve_answer(&id, &answer_id)
+        .await;
+    Ok(question_response(result))
+}
+
+// POST reject answer handler (admin)
+async fn admin_reject_answer_handler(
+    path: web::Path<(String, String)>,
+    crud: web::Data<QuestionCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let (id, answer_id) = path.into_inner();
+    let result = controller(&crud, &product_crud, &order_crud)
+        .reject_answer(&id, &answer_id)
+        .await;
+    Ok(question_response(result))
+}
+
+// Route configuration function
+pub fn question_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/questions")
+            .route("", web::post().to(ask_question_handler))
+            .route("/mine", web::get().to(get_my_questions_handler))
+            .route("/product/{product_id}", web::get().to(get_product_questions_handler))
+            .route("/{id}/answers", web::post().to(answer_question_handler))
+            .route("/{id}/answers/{answer_id}/upvote", web::post().to(upvote_answer_handler))
+            .route("/{id}/answers/{answer_id}/upvote", web::delete().to(remove_upvote_handler))
+    );
+}
+
+// Admin routes, mounted inside the admin-authenticated `/api/admin` scope
+pub fn admin_question_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/questions")
+            .route("", web::get().to(admin_question_queue_handler))
+            .route("/answers", web::get().to(admin_answer_queue_handler))
+            .route("/{id}/approve", web::post().to(admin_approve_question_handler))
+            .route("/{id}/reject", web::post().to(admin_reject_question_handler))
+            .route("/{id}/answers", web::post().to(admin_answer_handler))
+            .route("/{id}/answers/{answer_id}/approve", web::post().to(admin_approve_answer_handler))
+            .route("/{id}/answers/{answer_id}/reject", web::post().to(admin_reject_answer_handler))
+    );
+}
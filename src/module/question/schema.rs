@@ -0,0 +1,120 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::module::question::model::{Answer, AnswerAuthor, QaStatus, Question};
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct AskQuestionSchema {
+    pub product_id: String,
+
+    #[validate(length(min = 10, max = 1000, message = "Please write 10 to 1000 characters"))]
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct AnswerQuestionSchema {
+    #[validate(length(min = 2, max = 3000, message = "Please write 2 to 3000 characters"))]
+    pub body: String,
+}
+
+// Approve or reject a question or an answer
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct QaNoteSchema {
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuestionListQuery {
+    pub page: Option<u64>,
+    pub limit: Option<i64>,
+    // Only questions that have (true) or still need (false) a published answer
+    pub answered: Option<bool>,
+}
+
+// Query string for the moderation queue
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QaQueueQuery {
+    pub page: Option<u64>,
+    pub limit: Option<i64>,
+    // Defaults to pending
+    pub status: Option<String>,
+    pub product_id: Option<String>,
+}
+
+// An answer as shown on the product page
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnswerResponse {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub author_name: String,
+    pub author_type: AnswerAuthor,
+    pub body: String,
+    pub upvote_count: i32,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Answer> for AnswerResponse {
+    fn from(answer: Answer) -> Self {
+        Self {
+            id: answer.id,
+            author_name: answer.author_name,
+            author_type: answer.author_type,
+            body: answer.body,
+            upvote_count: answer.upvote_count,
+            created_at: answer.created_at,
+        }
+    }
+}
+
+// A question as shown on the product page: published answers only, most
+// upvoted first
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuestionResponse {
+    #[serde(rename = "_id")]
+    pub id: Option<ObjectId>,
+    pub product_id: ObjectId,
+    pub author_name: String,
+    pub body: String,
+    pub answers: Vec<AnswerResponse>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Question> for QuestionResponse {
+    fn from(question: Question) -> Self {
+        let mut answers: Vec<AnswerResponse> = question.answers.into_iter()
+            .filter(|answer| answer.status == QaStatus::Approved)
+            .map(AnswerResponse::from)
+            .collect();
+        answers.sort_by(|a, b| b.upvote_count.cmp(&a.upvote_count).then(a.created_at.cmp(&b.created_at)));
+
+        Self {
+            id: question.id,
+            product_id: question.product_id,
+            author_name: question.author_name,
+            body: question.body,
+            answers,
+            created_at: question.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductQuestionsResponse {
+    pub questions: Vec<QuestionResponse>,
+    pub total: u64,
+    pub page: u64,
+    pub limit: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuestionListResponse {
+    pub questions: Vec<Question>,
+    pub total: u64,
+    pub page: u64,
+    pub limit: i64,
+}
@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+use bson::{doc, oid::ObjectId};
+use chrono::Utc;
+use validator::Validate;
+use crate::module::product::{crud::ProductCrud, model::Product};
+use crate::module::recommendation::{
+    crud::RecommendationCrud,
+    interface::RecommendationService,
+    model::{CrossSells, PRICE_BAND},
+    schema::{CrossSellsResponse, RecommendationQuery, RecommendationsResponse, SetCrossSellsSchema},
+};
+
+pub struct RecommendationController {
+    crud: RecommendationCrud,
+    product_crud: ProductCrud,
+}
+
+impl RecommendationController {
+    pub fn new(crud: RecommendationCrud, product_crud: ProductCrud) -> Self {
+        Self { crud, product_crud }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    // `products` in the order of `ids`, skipping any missing from it
+    fn in_order(ids: &[ObjectId], products: Vec<Product>) -> Vec<Product> {
+        let mut products: Vec<Option<Product>> = products.into_iter().map(Some).collect();
+        ids.iter()
+            .filter_map(|id| {
+                products.iter_mut()
+                    .find(|product| product.as_ref().is_some_and(|product| product.id.as_ref() == Some(id)))
+                    .and_then(Option::take)
+            })
+            .collect()
+    }
+
+    // The first `limit` recommendable products of `ids` not shown yet, in order
+    async fn pick(&self, ids: &[ObjectId], shown: &mut HashSet<ObjectId>, limit: usize) -> Result<Vec<Product>, String> {
+        let ids: Vec<ObjectId> = ids.iter().copied().filter(|id| !shown.contains(id)).collect();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut products = Self::in_order(&ids, self.crud.find_available(&ids).await?);
+        products.truncate(limit);
+        shown.extend(products.iter().filter_map(|product| product.id));
+        Ok(products)
+    }
+
+    // Best sellers in the same category at a similar price, the same brand first
+    async fn related(&self, product: &Product, shown: &mut HashSet<ObjectId>, limit: usize) -> Result<Vec<Product>, String> {
+        let price = product.sale_price.unwrap_or(product.price) as f64;
+        let min_price = (price * (1.0 - PRICE_BAND)).floor() as i64;
+        let max_price = (price * (1.0 + PRICE_BAND)).ceil() as i64;
+
+        let excluded: Vec<ObjectId> = shown.iter().copied().collect();
+        let filter = doc! {
+            "_id": { "$nin": excluded },
+            "category_id": product.category_id,
+            "is_active": true,
+            "deleted_at": null,
+            "stock_quantity": { "$gt": 0 },
+        };
+        // Fetch extra so same-brand products further down can move up
+        let (mut products, _) = self.product_crud
+            .find_products(filter, Some(min_price), Some(max_price), doc! { "sold_count": -1, "_id": -1 }, 0, (limit * 3) as i64)
+            .await?;
+
+        products.sort_by_key(|candidate| candidate.brand_id != product.brand_id);
+        products.truncate(limit);
+        shown.extend(products.iter().filter_map(|product| product.id));
+        Ok(products)
+    }
+
+    async fn cross_sells_response(&self, product_id: ObjectId, ids: &[ObjectId]) -> Result<CrossSellsResponse, String> {
+        let products = self.product_crud.get_products_by_ids(ids).await?;
+        Ok(CrossSellsResponse { product_id, products: Self::in_order(ids, products) })
+    }
+}
+
+#[async_trait::async_trait]
+impl RecommendationService for RecommendationController {
+    async fn get_recommendations(&self, product_id: &str, query: RecommendationQuery) -> Result<RecommendationsResponse, String> {
+        let product = self.product_crud.get_product(product_id).await?
+            .filter(|product| product.deleted_at.is_none())
+            .ok_or_else(|| "Product not found".to_string())?;
+        let id = product.id.ok_or_else(|| "Product has no ID".to_string())?;
+        let limit = query.limit.unwrap_or(8).clamp(1, 20) as usize;
+
+        let mut shown = HashSet::from([id]);
+
+        let curated = self.crud.get_cross_sells(&id).await?
+            .map(|cross_sells| cross_sells.product_ids)
+            .unwrap_or_default();
+        let cross_sells = self.pick(&curated, &mut shown, limit).await?;
+
+        let co_purchased: Vec<ObjectId> = self.crud.get_associations(&id).await?
+            .map(|associations| associations.associations.into_iter().map(|association| association.product_id).collect())
+            .unwrap_or_default();
+        let frequently_bought_together = self.pick(&co_purchased, &mut shown, limit).await?;
+
+        let related = self.related(&product, &mut shown, limit).await?;
+
+        Ok(RecommendationsResponse { cross_sells, frequently_bought_together, related })
+    }
+
+    async fn get_cross_sells(&self, product_id: &str) -> Result<CrossSellsResponse, String> {
+        let id = Self::parse_id(product_id, "product")?;
+        let ids = self.crud.get_cross_sells(&id).await?
+            .map(|cross_sells| cross_sells.product_ids)
+            .unwrap_or_default();
+        self.cross_sells_response(id, &ids).await
+    }
+
+    async fn set_cross_sells(&self, product_id: &str, data: SetCrossSellsSchema, admin_id: &str) -> Result<CrossSellsResponse, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let id = Self::parse_id(product_id, "product")?;
+        self.product_crud.get_product(product_id).await?
+            .filter(|product| product.deleted_at.is_none())
+            .ok_or_else(|| "Product not found".to_string())?;
+
+        let mut ids: Vec<ObjectId> = Vec::new();
+        for value in &data.product_ids {
+            let cross_sell = Self::parse_id(value, "product")?;
+            if cross_sell == id {
+                return Err("A product can't be its own cross-sell".to_string());
+            }
+            if !ids.contains(&cross_sell) {
+                ids.push(cross_sell);
+            }
+        }
+
+        if ids.is_empty() {
+            self.crud.clear_cross_sells(&id).await?;
+            return self.cross_sells_response(id, &ids).await;
+        }
+
+        let found = self.product_crud.get_products_by_ids(&ids).await?;
+        if let Some(missing) = ids.iter().find(|cross_sell| {
+            !found.iter().any(|product| product.id.as_ref() == Some(*cross_sell) && product.deleted_at.is_none())
+        }) {
+            return Err(format!("Product {} not found", missing.to_hex()));
+        }
+
+        self.crud.set_cross_sells(&CrossSells {
+            product_id: id,
+            product_ids: ids.clone(),
+            updated_by: admin_id.to_string(),
+            updated_at: Utc::now(),
+        }).await?;
+        Ok(CrossSellsResponse { product_id: id, products: Self::in_order(&ids, found) })
+    }
+}
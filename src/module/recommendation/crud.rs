@@ -0,0 +1,141 @@
+use bson::{doc, oid::ObjectId, Document};
+use chrono::{DateTime, Utc};
+use mongodb::{Collection, Database};
+use futures_util::TryStreamExt;
+use crate::module::order::model::OrderStatus;
+use crate::module::product::model::Product;
+use crate::module::recommendation::model::{
+    CrossSells, ProductAssociations, ASSOCIATIONS_PER_PRODUCT, MIN_CO_PURCHASES,
+};
+
+#[derive(Clone)]
+pub struct RecommendationCrud {
+    products: Collection<Product>,
+    orders: Collection<Document>,
+    associations: Collection<ProductAssociations>,
+    cross_sells: Collection<CrossSells>,
+}
+
+impl RecommendationCrud {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            products: db.collection("products"),
+            orders: db.collection("orders"),
+            associations: db.collection("product_associations"),
+            cross_sells: db.collection("cross_sells"),
+        }
+    }
+
+    // Products that can be recommended: on sale, not deleted and in stock
+    pub async fn find_available(&self, ids: &[ObjectId]) -> Result<Vec<Product>, String> {
+        let filter = doc! {
+            "_id": { "$in": ids },
+            "is_active": true,
+            "deleted_at": null,
+            "stock_quantity": { "$gt": 0 },
+        };
+        match self.products.find(filter).await {
+            Ok(mut cursor) => {
+                let mut products = Vec::new();
+                while let Some(product) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve products: {}", e))? {
+                    products.push(product);
+                }
+                Ok(products)
+            }
+            Err(e) => Err(format!("Failed to retrieve products: {}", e))
+        }
+    }
+
+    pub async fn get_associations(&self, product_id: &ObjectId) -> Result<Option<ProductAssociations>, String> {
+        self.associations.find_one(doc! { "_id": product_id }).await
+            .map_err(|e| format!("Failed to retrieve product associations: {}", e))
+    }
+
+    pub async fn get_cross_sells(&self, product_id: &ObjectId) -> Result<Option<CrossSells>, String> {
+        self.cross_sells.find_one(doc! { "_id": product_id }).await
+            .map_err(|e| format!("Failed to retrieve cross-sells: {}", e))
+    }
+
+    pub async fn set_cross_sells(&self, cross_sells: &CrossSells) -> Result<(), String> {
+        self.cross_sells
+            .replace_one(doc! { "_id": cross_sells.product_id }, cross_sells)
+            .upsert(true)
+            .await
+            .map_err(|e| format!("Failed to save cross-sells: {}", e))?;
+        Ok(())
+    }
+
+    pub async fn clear_cross_sells(&self, product_id: &ObjectId) -> Result<(), String> {
+        self.cross_sells.delete_one(doc! { "_id": product_id }).await
+            .map_err(|e| format!("Failed to remove cross-sells: {}", e))?;
+        Ok(())
+    }
+
+    // For every product, the products most often in the same purchased order
+    // since `since`. Each order counts once per pair, however many of each it had.
+    pub async fn compute_associations(&self, since: DateTime<Utc>, computed_at: DateTime<Utc>) -> Result<Vec<ProductAssociations>, String> {
+        let purchased = [OrderStatus::Paid, OrderStatus::Processing, OrderStatus::Shipped, OrderStatus::Delivered]
+            .iter()
+            .map(|status| status.as_str())
+            .collect::<Vec<_>>();
+
+        let pipeline = vec![
+            doc! { "$match": {
+                "status": { "$in": purchased },
+                "created_at": { "$gte": bson::DateTime::from_chrono(since) },
+            } },
+            doc! { "$project": { "products": { "$setUnion": ["$items.product_id", []] } } },
+            // Orders with a single product have no pairs
+            doc! { "$match": { "products.1": { "$exists": true } } },
+            doc! { "$addFields": { "others": "$products" } },
+            doc! { "$unwind": "$products" },
+            doc! { "$unwind": "$others" },
+            doc! { "$match": { "$expr": { "$ne": ["$products", "$others"] } } },
+            doc! { "$group": {
+                "_id": { "product_id": "$products", "other_id": "$others" },
+                "order_count": { "$sum": 1_i64 },
+            } },
+            doc! { "$match": { "order_count": { "$gte": MIN_CO_PURCHASES } } },
+            doc! { "$sort": { "_id.product_id": 1, "order_count": -1, "_id.other_id": 1 } },
+            doc! { "$group": {
+                "_id": "$_id.product_id",
+                "associations": { "$push": { "product_id": "$_id.other_id", "order_count": "$order_count" } },
+            } },
+            doc! { "$project": {
+                "associations": { "$slice": ["$associations", ASSOCIATIONS_PER_PRODUCT] },
+                "computed_at": bson::DateTime::from_chrono(computed_at),
+            } },
+        ];
+
+        match self.orders.aggregate(pipeline).allow_disk_use(true).await {
+            Ok(mut cursor) => {
+                let mut associations = Vec::new();
+                while let Some(document) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to compute product associations: {}", e))? {
+                    let product: ProductAssociations = bson::from_document(document)
+                        .map_err(|e| format!("Failed to decode product associations: {}", e))?;
+                    associations.push(product);
+                }
+                Ok(associations)
+            }
+            Err(e) => Err(format!("Failed to compute product associations: {}", e))
+        }
+    }
+
+    // Swap in a new set of statistics. Products no longer in it lose theirs.
+    pub async fn replace_associations(&self, associations: &[ProductAssociations], computed_at: DateTime<Utc>) -> Result<(), String> {
+        for product in associations {
+            self.associations
+                .replace_one(doc! { "_id": product.product_id }, product)
+                .upsert(true)
+                .await
+                .map_err(|e| format!("Failed to save product associations: {}", e))?;
+        }
+        self.associations
+            .delete_many(doc! { "computed_at": { "$lt": bson::DateTime::from_chrono(computed_at) } })
+            .await
+            .map_err(|e| format!("Failed to remove stale product associations: {}", e))?;
+        Ok(())
+    }
+}
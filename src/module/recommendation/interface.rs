@@ -0,0 +1,13 @@
+use crate::module::recommendation::schema::{
+    CrossSellsResponse, RecommendationQuery, RecommendationsResponse, SetCrossSellsSchema,
+};
+
+#[async_trait::async_trait]
+pub trait RecommendationService {
+    // Shoppers
+    async fn get_recommendations(&self, product_id: &str, query: RecommendationQuery) -> Result<RecommendationsResponse, String>;
+
+    // Admin
+    async fn get_cross_sells(&self, product_id: &str) -> Result<CrossSellsResponse, String>;
+    async fn set_cross_sells(&self, product_id: &str, data: SetCrossSellsSchema, admin_id: &str) -> Result<CrossSellsResponse, String>;
+}
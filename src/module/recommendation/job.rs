@@ -0,0 +1,22 @@
+use chrono::Utc;
+use crate::module::recommendation::{crud::RecommendationCrud, model::association_lookback};
+
+/// Rebuilds the "frequently bought together" statistics in
+/// `product_associations` from recent purchased orders.
+pub struct ProductAssociationJob {
+    crud: RecommendationCrud,
+}
+
+impl ProductAssociationJob {
+    pub fn new(crud: RecommendationCrud) -> Self {
+        Self { crud }
+    }
+
+    /// Runs one pass and returns the number of products with associations.
+    pub async fn run_once(&self) -> Result<usize, String> {
+        let now = Utc::now();
+        let associations = self.crud.compute_associations(now - association_lookback(), now).await?;
+        self.crud.replace_associations(&associations, now).await?;
+        Ok(associations.len())
+    }
+}
@@ -0,0 +1,14 @@
+use bson::doc;
+use mongodb::IndexModel;
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        // Stale statistics are removed by when they were computed
+        Migration::indexes("recommendation", 1, "product association indexes", "product_associations", vec![
+            IndexModel::builder()
+                .keys(doc! { "computed_at": 1 })
+                .build(),
+        ]),
+    ]
+}
@@ -0,0 +1,54 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+// Related products cost within this share of the product's price either way
+pub const PRICE_BAND: f64 = 0.25;
+
+// Pairs bought together fewer times than this are treated as chance
+pub const MIN_CO_PURCHASES: i64 = 2;
+
+// Co-purchased products kept per product
+pub const ASSOCIATIONS_PER_PRODUCT: i64 = 20;
+
+/// How far back orders count towards co-purchase statistics, read from
+/// `PRODUCT_ASSOCIATION_LOOKBACK_DAYS`. Defaults to 180 days.
+pub fn association_lookback() -> Duration {
+    let days = std::env::var("PRODUCT_ASSOCIATION_LOOKBACK_DAYS")
+        .ok()
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(180);
+    Duration::days(days)
+}
+
+// Another product seen in the same orders
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssociatedProduct {
+    pub product_id: ObjectId,
+    // Orders that had both products
+    pub order_count: i64,
+}
+
+// Co-purchase statistics for one product, most frequent first. Rebuilt by
+// the association job; products with no frequent pairs have no document.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductAssociations {
+    #[serde(rename = "_id")]
+    pub product_id: ObjectId,
+    pub associations: Vec<AssociatedProduct>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub computed_at: DateTime<Utc>,
+}
+
+// Add-ons an admin picked for a product, e.g. bags and mice for a laptop,
+// in display order
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CrossSells {
+    #[serde(rename = "_id")]
+    pub product_id: ObjectId,
+    pub product_ids: Vec<ObjectId>,
+    pub updated_by: String,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
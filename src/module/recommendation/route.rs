@@ -0,0 +1,80 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::Authentication;
+use crate::module::product::crud::ProductCrud;
+use crate::module::recommendation::{
+    controller::RecommendationController,
+    crud::RecommendationCrud,
+    interface::RecommendationService,
+    schema::{RecommendationQuery, SetCrossSellsSchema},
+};
+
+fn controller(crud: &web::Data<RecommendationCrud>, product_crud: &web::Data<ProductCrud>) -> RecommendationController {
+    RecommendationController::new(crud.get_ref().clone(), product_crud.get_ref().clone())
+}
+
+// GET a product's recommendations handler
+async fn get_recommendations_handler(
+    product_id: web::Path<String>,
+    query: web::Query<RecommendationQuery>,
+    crud: web::Data<RecommendationCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).get_recommendations(&product_id, query.into_inner()).await {
+        Ok(recommendations) => Ok(HttpResponse::Ok().json(recommendations)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET a product's cross-sells handler (admin)
+async fn admin_get_cross_sells_handler(
+    product_id: web::Path<String>,
+    crud: web::Data<RecommendationCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).get_cross_sells(&product_id).await {
+        Ok(cross_sells) => Ok(HttpResponse::Ok().json(cross_sells)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// PUT a product's cross-sells handler (admin)
+async fn admin_set_cross_sells_handler(
+    req: HttpRequest,
+    product_id: web::Path<String>,
+    body: web::Json<SetCrossSellsSchema>,
+    crud: web::Data<RecommendationCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    // The admin middleware already checked the token; this only reads who it belongs to
+    let admin_id = Authentication::optional_user(&req)
+        .map(|claims| claims.sub)
+        .unwrap_or_else(|| "unknown".to_string());
+
+    match controller(&crud, &product_crud).set_cross_sells(&product_id, body.into_inner(), &admin_id).await {
+        Ok(cross_sells) => Ok(HttpResponse::Ok().json(cross_sells)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// Route configuration function
+pub fn recommendation_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/recommendations")
+            .route("/product/{product_id}", web::get().to(get_recommendations_handler))
+    );
+}
+
+// Admin routes, mounted inside the admin-authenticated `/api/admin` scope
+pub fn admin_recommendation_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/recommendations")
+            .route("/product/{product_id}/cross-sells", web::get().to(admin_get_cross_sells_handler))
+            .route("/product/{product_id}/cross-sells", web::put().to(admin_set_cross_sells_handler))
+    );
+}
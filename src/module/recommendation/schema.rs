@@ -0,0 +1,34 @@
+use bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::module::product::model::Product;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecommendationQuery {
+    // Products per section
+    pub limit: Option<i64>,
+}
+
+// Each section leaves out products already shown in an earlier one
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecommendationsResponse {
+    pub cross_sells: Vec<Product>,
+    pub frequently_bought_together: Vec<Product>,
+    pub related: Vec<Product>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SetCrossSellsSchema {
+    // In display order; an empty list removes the cross-sells
+    #[validate(length(max = 20, message = "At most 20 cross-sells per product"))]
+    pub product_ids: Vec<String>,
+}
+
+// A product's cross-sells as an admin sees them, whether or not they can be
+// shown right now
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CrossSellsResponse {
+    pub product_id: ObjectId,
+    pub products: Vec<Product>,
+}
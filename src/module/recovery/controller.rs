@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+use chrono::{Duration, Utc};
+use crate::module::recovery::{
+    crud::RecoveryCrud,
+    interface::RecoveryService,
+    model::RecoveryStatus,
+    schema::{RecoveryReport, RecoveryReportQuery, StepReport},
+};
+
+pub struct RecoveryController {
+    crud: RecoveryCrud,
+}
+
+impl RecoveryController {
+    pub fn new(crud: RecoveryCrud) -> Self {
+        Self { crud }
+    }
+}
+
+#[async_trait::async_trait]
+impl RecoveryService for RecoveryController {
+    async fn unsubscribe(&self, token: &str) -> Result<bool, String> {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err("Unsubscribe token is required".to_string());
+        }
+        Ok(self.crud.unsubscribe(token).await?.is_some())
+    }
+
+    async fn get_report(&self, query: RecoveryReportQuery) -> Result<RecoveryReport, String> {
+        let to = query.to.unwrap_or_else(Utc::now);
+        let from = query.from.unwrap_or(to - Duration::days(30));
+        if from > to {
+            return Err("'from' must be before 'to'".to_string());
+        }
+
+        let baskets = self.crud.find_created_between(from, to).await?;
+
+        let mut by_step: BTreeMap<u32, u64> = BTreeMap::new();
+        let mut reminded = 0;
+        let (mut active, mut recovered, mut resumed, mut completed, mut unsubscribed) = (0, 0, 0, 0, 0);
+        let mut recovered_value = 0;
+        for basket in &baskets {
+            for reminder in &basket.reminders {
+                *by_step.entry(reminder.step).or_default() += 1;
+            }
+            if !basket.reminders.is_empty() {
+                reminded += 1;
+            }
+            match basket.status {
+                RecoveryStatus::Active => active += 1,
+                RecoveryStatus::Recovered => {
+                    recovered += 1;
+                    recovered_value += basket.recovered_value.unwrap_or(0);
+                }
+                RecoveryStatus::Resumed => resumed += 1,
+                RecoveryStatus::Completed => completed += 1,
+                RecoveryStatus::Unsubscribed => unsubscribed += 1,
+            }
+        }
+
+        Ok(RecoveryReport {
+            from,
+            to,
+            baskets: baskets.len() as u64,
+            reminders_sent: by_step.values().sum(),
+            reminders_by_step: by_step.into_iter().map(|(step, sent)| StepReport { step, sent }).collect(),
+            active,
+            recovered,
+            recovered_value,
+            resumed,
+            completed,
+            unsubscribed,
+            recovery_rate: if reminded == 0 { 0.0 } else { recovered as f64 / reminded as f64 },
+        })
+    }
+}
@@ -0,0 +1,180 @@
+use bson::{doc, oid::ObjectId};
+use chrono::{DateTime, Utc};
+use mongodb::{
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Collection, Database,
+};
+use futures_util::TryStreamExt;
+use uuid::Uuid;
+use crate::module::recovery::model::{
+    AbandonedBasket, BasketSource, RecoveryStatus, ReminderSent, ReminderSubscription,
+};
+
+#[derive(Clone)]
+pub struct RecoveryCrud {
+    baskets: Collection<AbandonedBasket>,
+    subscriptions: Collection<ReminderSubscription>,
+}
+
+impl RecoveryCrud {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            baskets: db.collection("abandoned_baskets"),
+            subscriptions: db.collection("reminder_subscriptions"),
+        }
+    }
+
+    async fn collect(&self, filter: bson::Document, options: FindOptions) -> Result<Vec<AbandonedBasket>, String> {
+        match self.baskets.find(filter).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut baskets = Vec::new();
+                while let Some(basket) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve abandoned baskets: {}", e))? {
+                    baskets.push(basket);
+                }
+                Ok(baskets)
+            }
+            Err(e) => Err(format!("Failed to retrieve abandoned baskets: {}", e))
+        }
+    }
+
+    pub async fn find_basket(
+        &self,
+        user_id: &ObjectId,
+        source: BasketSource,
+        wishlist_id: Option<ObjectId>,
+        last_activity_at: DateTime<Utc>,
+    ) -> Result<Option<AbandonedBasket>, String> {
+        let filter = doc! {
+            "user_id": user_id,
+            "source": source.as_str(),
+            "wishlist_id": wishlist_id,
+            "last_activity_at": bson::DateTime::from_chrono(last_activity_at),
+        };
+
+        match self.baskets.find_one(filter).await {
+            Ok(basket) => Ok(basket),
+            Err(e) => Err(format!("Failed to retrieve abandoned basket: {}", e))
+        }
+    }
+
+    pub async fn create_basket(&self, mut basket: AbandonedBasket) -> Result<AbandonedBasket, String> {
+        match self.baskets.insert_one(&basket).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    basket.id = Some(id);
+                }
+                Ok(basket)
+            }
+            Err(e) => Err(format!("Failed to record abandoned basket: {}", e))
+        }
+    }
+
+    pub async fn find_active(&self, limit: i64) -> Result<Vec<AbandonedBasket>, String> {
+        let options = FindOptions::builder().sort(doc! { "updated_at": 1 }).limit(limit).build();
+        self.collect(doc! { "status": RecoveryStatus::Active.as_str() }, options).await
+    }
+
+    pub async fn find_created_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<AbandonedBasket>, String> {
+        let filter = doc! {
+            "created_at": {
+                "$gte": bson::DateTime::from_chrono(from),
+                "$lte": bson::DateTime::from_chrono(to),
+            },
+        };
+        self.collect(filter, FindOptions::builder().sort(doc! { "created_at": 1 }).build()).await
+    }
+
+    pub async fn record_reminder(&self, id: &ObjectId, reminder: &ReminderSent) -> Result<(), String> {
+        let reminder = bson::to_bson(reminder)
+            .map_err(|e| format!("Failed to encode reminder: {}", e))?;
+
+        self.baskets
+            .update_one(
+                doc! { "_id": id },
+                doc! {
+                    "$push": { "reminders": reminder },
+                    "$set": { "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to record reminder: {}", e))?;
+        Ok(())
+    }
+
+    // End an active record, noting the order when the basket was recovered
+    pub async fn close_basket(&self, id: &ObjectId, status: RecoveryStatus, recovered: Option<(String, i64)>) -> Result<(), String> {
+        let mut set = doc! {
+            "status": status.as_str(),
+            "updated_at": bson::DateTime::from_chrono(Utc::now()),
+        };
+        if let Some((order_number, value)) = recovered {
+            set.insert("recovered_order_number", order_number);
+            set.insert("recovered_value", value);
+        }
+
+        self.baskets
+            .update_one(doc! { "_id": id, "status": RecoveryStatus::Active.as_str() }, doc! { "$set": set })
+            .await
+            .map_err(|e| format!("Failed to update abandoned basket: {}", e))?;
+        Ok(())
+    }
+
+    // The user's preference, created with a fresh token the first time
+    pub async fn get_or_create_subscription(&self, user_id: &ObjectId) -> Result<ReminderSubscription, String> {
+        let options = FindOneAndUpdateOptions::builder()
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .build();
+        let update = doc! {
+            "$setOnInsert": {
+                "token": Uuid::new_v4().simple().to_string(),
+                "created_at": bson::DateTime::from_chrono(Utc::now()),
+            },
+        };
+
+        self.subscriptions
+            .find_one_and_update(doc! { "user_id": user_id }, update)
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to retrieve reminder subscription: {}", e))?
+            .ok_or_else(|| "Failed to retrieve reminder subscription".to_string())
+    }
+
+    pub async fn get_subscription(&self, user_id: &ObjectId) -> Result<Option<ReminderSubscription>, String> {
+        match self.subscriptions.find_one(doc! { "user_id": user_id }).await {
+            Ok(subscription) => Ok(subscription),
+            Err(e) => Err(format!("Failed to retrieve reminder subscription: {}", e))
+        }
+    }
+
+    pub async fn unsubscribe(&self, token: &str) -> Result<Option<ReminderSubscription>, String> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let subscription = self.subscriptions
+            .find_one_and_update(
+                doc! { "token": token },
+                doc! { "$set": { "unsubscribed_at": bson::DateTime::from_chrono(Utc::now()) } },
+            )
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to unsubscribe: {}", e))?;
+
+        // Stop any sequence already under way
+        if let Some(subscription) = &subscription {
+            self.baskets
+                .update_many(
+                    doc! { "user_id": subscription.user_id, "status": RecoveryStatus::Active.as_str() },
+                    doc! { "$set": {
+                        "status": RecoveryStatus::Unsubscribed.as_str(),
+                        "updated_at": bson::DateTime::from_chrono(Utc::now()),
+                    } },
+                )
+                .await
+                .map_err(|e| format!("Failed to stop reminders: {}", e))?;
+        }
+        Ok(subscription)
+    }
+}
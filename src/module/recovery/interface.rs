@@ -0,0 +1,10 @@
+use crate::module::recovery::schema::{RecoveryReport, RecoveryReportQuery};
+
+#[async_trait::async_trait]
+pub trait RecoveryService {
+    // Public, from the link in a reminder email
+    async fn unsubscribe(&self, token: &str) -> Result<bool, String>;
+
+    // Admin
+    async fn get_report(&self, query: RecoveryReportQuery) -> Result<RecoveryReport, String>;
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use bson::oid::ObjectId;
+use chrono::{DateTime, Duration, Utc};
+use crate::module::cart::{crud::CartCrud, model::CartOwner};
+use crate::module::order::crud::OrderCrud;
+use crate::module::product::crud::ProductCrud;
+use crate::module::recovery::{
+    crud::RecoveryCrud,
+    model::{reminder_min_gap, reminder_windows, AbandonedBasket, BasketSource, RecoveryStatus, ReminderSent},
+};
+use crate::module::user::{crud::UserCrud, interface::UserRepository, model::User};
+use crate::module::wishlist::crud::WishlistCrud;
+use crate::service::{
+    currency_service::format_naira,
+    email_service,
+    email_templates::{self, ReminderEmailItem},
+    link_service::{public_api_url, storefront_url},
+};
+
+// Baskets looked at per run; the rest are picked up on the next tick
+const BATCH_SIZE: i64 = 200;
+
+// A basket as it is now: its lines and when it was last changed
+struct BasketSnapshot {
+    updated_at: DateTime<Utc>,
+    lines: Vec<(ObjectId, i32)>,
+}
+
+/// Finds carts and wishlists left idle by verified users and emails them a
+/// reminder after each configured window, until they order or unsubscribe.
+pub struct BasketReminderJob {
+    crud: RecoveryCrud,
+    cart_crud: CartCrud,
+    wishlist_crud: WishlistCrud,
+    product_crud: ProductCrud,
+    order_crud: OrderCrud,
+    user_crud: UserCrud,
+}
+
+impl BasketReminderJob {
+    pub fn new(
+        crud: RecoveryCrud,
+        cart_crud: CartCrud,
+        wishlist_crud: WishlistCrud,
+        product_crud: ProductCrud,
+        order_crud: OrderCrud,
+        user_crud: UserCrud,
+    ) -> Self {
+        Self { crud, cart_crud, wishlist_crud, product_crud, order_crud, user_crud }
+    }
+
+    /// Runs one pass and returns the number of reminders sent.
+    pub async fn run_once(&self) -> Result<usize, String> {
+        let windows = reminder_windows();
+        let now = Utc::now();
+        let mut sent = 0;
+
+        // Move sequences already under way along, or close them
+        for basket in self.crud.find_active(BATCH_SIZE).await? {
+            if self.advance(&basket, &windows, now).await? {
+                sent += 1;
+            }
+        }
+
+        // Start sequences for baskets that just went idle. Anything idle for
+        // longer than the whole sequence plus a day is left alone.
+        let changed_after = now - windows[windows.len() - 1] - Duration::days(1);
+        let changed_before = now - windows[0];
+
+        let mut candidates = Vec::new();
+        for cart in self.cart_crud.find_idle_user_carts(changed_after, changed_before, BATCH_SIZE).await? {
+            if let Some(user_id) = cart.user_id {
+                candidates.push((user_id, BasketSource::Cart, None, cart.updated_at));
+            }
+        }
+        for wishlist in self.wishlist_crud.find_idle_wishlists(changed_after, changed_before, BATCH_SIZE).await? {
+            candidates.push((wishlist.user_id, BasketSource::Wishlist, wishlist.id, wishlist.updated_at));
+        }
+
+        for (user_id, source, wishlist_id, last_activity_at) in candidates {
+            if self.crud.find_basket(&user_id, source, wishlist_id, last_activity_at).await?.is_some() {
+                continue;
+            }
+            if self.order_crud.find_user_order_since(&user_id, last_activity_at).await?.is_some() {
+                continue;
+            }
+            if self.reachable_user(&user_id).await?.is_none() {
+                continue;
+            }
+
+            let basket = self.crud
+                .create_basket(AbandonedBasket::new(user_id, source, wishlist_id, last_activity_at))
+                .await?;
+            if self.advance(&basket, &windows, now).await? {
+                sent += 1;
+            }
+        }
+
+        Ok(sent)
+    }
+
+    // Close the record if the sequence should stop, otherwise send the next
+    // reminder once its window has passed. Returns whether one was sent.
+    async fn advance(&self, basket: &AbandonedBasket, windows: &[Duration], now: DateTime<Utc>) -> Result<bool, String> {
+        let id = basket.id.ok_or_else(|| "Abandoned basket has no ID".to_string())?;
+
+        if let Some(order) = self.order_crud.find_user_order_since(&basket.user_id, basket.last_activity_at).await? {
+            // Only an order placed after a reminder counts as a recovery
+            if basket.reminders.is_empty() {
+                self.crud.close_basket(&id, RecoveryStatus::Resumed, None).await?;
+            } else {
+                self.crud.close_basket(&id, RecoveryStatus::Recovered, Some((order.order_number, order.total))).await?;
+            }
+            return Ok(false);
+        }
+
+        let user = match self.reachable_user(&basket.user_id).await? {
+            Some(user) => user,
+            None => {
+                self.crud.close_basket(&id, RecoveryStatus::Unsubscribed, None).await?;
+                return Ok(false);
+            }
+        };
+
+        let snapshot = match self.snapshot(basket).await? {
+            Some(snapshot) if snapshot.updated_at == basket.last_activity_at && !snapshot.lines.is_empty() => snapshot,
+            _ => {
+                self.crud.close_basket(&id, RecoveryStatus::Resumed, None).await?;
+                return Ok(false);
+            }
+        };
+
+        let step = basket.reminders.len();
+        if step >= windows.len() {
+            self.crud.close_basket(&id, RecoveryStatus::Completed, None).await?;
+            return Ok(false);
+        }
+        if now - basket.last_activity_at < windows[step] {
+            return Ok(false);
+        }
+        if basket.reminders.last().is_some_and(|last| now - last.sent_at < reminder_min_gap()) {
+            return Ok(false);
+        }
+
+        let reminder = match self.send_reminder(basket, &user, &snapshot, step, windows.len()).await? {
+            Some(reminder) => reminder,
+            None => return Ok(false),
+        };
+        self.crud.record_reminder(&id, &reminder).await?;
+        Ok(true)
+    }
+
+    // The user if reminders may be sent to them: verified, active and subscribed
+    async fn reachable_user(&self, user_id: &ObjectId) -> Result<Option<User>, String> {
+        let user = match self.user_crud.find_by_id(user_id).await
+            .map_err(|e| format!("Failed to retrieve user: {}", e))? {
+            Some(user) if user.email_verified && user.is_active => user,
+            _ => return Ok(None),
+        };
+
+        let unsubscribed = self.crud.get_subscription(user_id).await?
+            .is_some_and(|subscription| subscription.unsubscribed_at.is_some());
+        Ok((!unsubscribed).then_some(user))
+    }
+
+    async fn snapshot(&self, basket: &AbandonedBasket) -> Result<Option<BasketSnapshot>, String> {
+        match (basket.source, basket.wishlist_id) {
+            (BasketSource::Cart, _) => {
+                let cart = self.cart_crud.find_by_owner(&CartOwner::User(basket.user_id)).await?;
+                Ok(cart.map(|cart| BasketSnapshot {
+                    updated_at: cart.updated_at,
+                    lines: cart.items.iter().map(|item| (item.product_id, item.quantity)).collect(),
+                }))
+            }
+            (BasketSource::Wishlist, Some(wishlist_id)) => {
+                let wishlist = self.wishlist_crud.get_user_wishlist(&wishlist_id, &basket.user_id).await?;
+                Ok(wishlist.map(|wishlist| BasketSnapshot {
+                    updated_at: wishlist.updated_at,
+                    lines: wishlist.items.iter().map(|item| (item.product_id, 1)).collect(),
+                }))
+            }
+            (BasketSource::Wishlist, None) => Ok(None),
+        }
+    }
+
+    // Email the reminder at today's prices. Returns None when nothing in the
+    // basket is still on sale or the email couldn't be sent.
+    async fn send_reminder(
+        &self,
+        basket: &AbandonedBasket,
+        user: &User,
+        snapshot: &BasketSnapshot,
+        step: usize,
+        steps: usize,
+    ) -> Result<Option<ReminderSent>, String> {
+        let product_ids: Vec<ObjectId> = snapshot.lines.iter().map(|(product_id, _)| *product_id).collect();
+        let products: HashMap<ObjectId, _> = self.product_crud.get_products_by_ids(&product_ids).await?
+            .into_iter()
+            .filter(|product| product.is_active)
+            .filter_map(|product| Some((product.id?, product)))
+            .collect();
+
+        let mut items = Vec::new();
+        let mut item_count = 0;
+        let mut basket_value = 0;
+        for (product_id, quantity) in &snapshot.lines {
+            let Some(product) = products.get(product_id) else { continue };
+            let line_total = product.display_price() * *quantity as i64;
+            item_count += quantity;
+            basket_value += line_total;
+            items.push(ReminderEmailItem {
+                name: product.name.clone(),
+                image_url: product.primary_image().map(|image| image.url.clone()),
+                quantity: *quantity,
+                price: format_naira(line_total),
+            });
+        }
+        if items.is_empty() {
+            return Ok(None);
+        }
+
+        let (noun, basket_url) = match (basket.source, basket.wishlist_id) {
+            (BasketSource::Wishlist, Some(wishlist_id)) => ("wishlist", format!("{}/wishlists/{}", storefront_url(), wishlist_id.to_hex())),
+            _ => ("cart", format!("{}/cart", storefront_url())),
+        };
+        let (subject, message) = if step == 0 {
+            (
+                format!("You left something in your {}", noun),
+                format!("You left these items in your {}. They're still available if you'd like to pick up where you left off.", noun),
+            )
+        } else if step + 1 == steps {
+            (
+                format!("Last reminder about your {}", noun),
+                format!("This is the last reminder about the items in your {}. Stock is limited, so they may not be around for long.", noun),
+            )
+        } else {
+            (
+                format!("Your {} is waiting for you", noun),
+                format!("The items in your {} are still waiting for you.", noun),
+            )
+        };
+
+        let subscription = self.crud.get_or_create_subscription(&basket.user_id).await?;
+        let unsubscribe_url = format!("{}/api/basket-reminders/unsubscribe?token={}", public_api_url(), subscription.token);
+
+        let name = format!("{} {}", user.first_name, user.last_name);
+        let (subject, body) = email_templates::get_basket_reminder_email_template(
+            &user.first_name,
+            &subject,
+            &message,
+            &items,
+            &basket_url,
+            &unsubscribe_url,
+        );
+        let email = user.email.clone();
+
+        let delivered = tokio::task::spawn_blocking(move || {
+            email_service::send_email(&email, &name, &subject, &body)
+                .map_err(|e| e.to_string())
+        })
+        .await
+        .map_err(|e| format!("Failed to send basket reminder: {}", e))?;
+        if let Err(e) = delivered {
+            eprintln!("Failed to send basket reminder: {}", e);
+            return Ok(None);
+        }
+
+        Ok(Some(ReminderSent {
+            step: step as u32,
+            item_count,
+            basket_value,
+            sent_at: Utc::now(),
+        }))
+    }
+}
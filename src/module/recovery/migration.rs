@@ -0,0 +1,31 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("recovery", 1, "abandoned basket indexes", "abandoned_baskets", vec![
+            // One record per idle spell of a basket
+            IndexModel::builder()
+                .keys(doc! { "user_id": 1, "source": 1, "wishlist_id": 1, "last_activity_at": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "status": 1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "created_at": -1 })
+                .build(),
+        ]),
+        Migration::indexes("recovery", 2, "reminder subscription indexes", "reminder_subscriptions", vec![
+            IndexModel::builder()
+                .keys(doc! { "user_id": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "token": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+        ]),
+    ]
+}
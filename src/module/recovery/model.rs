@@ -0,0 +1,165 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+// Reads a duration such as "30m", "24h" or "3d"
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let unit = value.chars().last()?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()].parse().ok().filter(|amount| *amount > 0)?;
+    match unit {
+        'm' => Some(Duration::minutes(amount)),
+        'h' => Some(Duration::hours(amount)),
+        'd' => Some(Duration::days(amount)),
+        _ => None,
+    }
+}
+
+/// How long a basket must sit untouched before each reminder, read from
+/// `BASKET_REMINDER_WINDOWS` as a comma separated list such as "1h,24h,72h"
+/// (units: m, h, d). Invalid entries are skipped; an empty list uses the default.
+pub fn reminder_windows() -> Vec<Duration> {
+    let mut windows: Vec<Duration> = std::env::var("BASKET_REMINDER_WINDOWS")
+        .map(|value| value.split(',').filter_map(parse_duration).collect())
+        .unwrap_or_default();
+    if windows.is_empty() {
+        windows = vec![Duration::hours(1), Duration::hours(24), Duration::hours(72)];
+    }
+    windows.sort();
+    windows
+}
+
+/// The least time between two reminders about the same basket, from
+/// `BASKET_REMINDER_MIN_GAP` (same format, default 12h). Stops a late run
+/// from sending several steps back to back.
+pub fn reminder_min_gap() -> Duration {
+    std::env::var("BASKET_REMINDER_MIN_GAP")
+        .ok()
+        .and_then(|value| parse_duration(&value))
+        .unwrap_or_else(|| Duration::hours(12))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BasketSource {
+    Cart,
+    Wishlist,
+}
+
+impl BasketSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BasketSource::Cart => "cart",
+            BasketSource::Wishlist => "wishlist",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryStatus {
+    // Reminders may still go out
+    Active,
+    // The customer ordered after being reminded
+    Recovered,
+    // The customer changed or emptied the basket themselves
+    Resumed,
+    // Every reminder went out without a purchase
+    Completed,
+    Unsubscribed,
+}
+
+impl RecoveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecoveryStatus::Active => "active",
+            RecoveryStatus::Recovered => "recovered",
+            RecoveryStatus::Resumed => "resumed",
+            RecoveryStatus::Completed => "completed",
+            RecoveryStatus::Unsubscribed => "unsubscribed",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReminderSent {
+    // 0 for the first reminder in the sequence
+    pub step: u32,
+    pub item_count: i32,
+    // Basket value at current prices, in kobo
+    pub basket_value: i64,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub sent_at: DateTime<Utc>,
+}
+
+// One idle spell of a basket and the reminders sent about it. The basket's
+// `updated_at` identifies the spell: once the customer changes the basket,
+// a later idle spell gets a new record and a fresh reminder sequence.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AbandonedBasket {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub user_id: ObjectId,
+    pub source: BasketSource,
+    // The wishlist; carts are found by user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wishlist_id: Option<ObjectId>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub last_activity_at: DateTime<Utc>,
+
+    pub status: RecoveryStatus,
+    pub reminders: Vec<ReminderSent>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recovered_order_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recovered_value: Option<i64>,
+
+    // Timestamps
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AbandonedBasket {
+    pub fn new(user_id: ObjectId, source: BasketSource, wishlist_id: Option<ObjectId>, last_activity_at: DateTime<Utc>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            user_id,
+            source,
+            wishlist_id,
+            last_activity_at,
+            status: RecoveryStatus::Active,
+            reminders: Vec::new(),
+            recovered_order_number: None,
+            recovered_value: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+// A user's reminder preference. The token goes into every reminder's
+// unsubscribe link, so it has to stay the same between emails.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReminderSubscription {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub user_id: ObjectId,
+    pub token: String,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "bson::serde_helpers::chrono_datetime_as_bson_datetime_optional"
+    )]
+    pub unsubscribed_at: Option<DateTime<Utc>>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,65 @@
+use actix_web::{web, HttpResponse, Result};
+use crate::module::recovery::{
+    controller::RecoveryController,
+    crud::RecoveryCrud,
+    interface::RecoveryService,
+    schema::{RecoveryReportQuery, UnsubscribeQuery},
+};
+use crate::service::{email_templates, link_service::public_api_url};
+
+fn controller(crud: &web::Data<RecoveryCrud>) -> RecoveryController {
+    RecoveryController::new(crud.get_ref().clone())
+}
+
+fn html(mut response: actix_web::HttpResponseBuilder, body: String) -> HttpResponse {
+    response.content_type("text/html; charset=utf-8").body(body)
+}
+
+// GET unsubscribe page, linked from every reminder email. Only asks for
+// confirmation; link checkers opening it don't unsubscribe anyone.
+async fn unsubscribe_page_handler(query: web::Query<UnsubscribeQuery>) -> Result<HttpResponse> {
+    let action_url = format!("{}/api/basket-reminders/unsubscribe", public_api_url());
+    Ok(html(HttpResponse::Ok(), email_templates::get_unsubscribe_page(&action_url, &query.token)))
+}
+
+// POST unsubscribe handler, submitted from the confirmation page
+async fn unsubscribe_handler(
+    form: web::Form<UnsubscribeQuery>,
+    crud: web::Data<RecoveryCrud>,
+) -> Result<HttpResponse> {
+    let (response, message) = match controller(&crud).unsubscribe(&form.token).await {
+        Ok(true) => (HttpResponse::Ok(), "You will no longer receive basket reminders".to_string()),
+        Ok(false) => (HttpResponse::NotFound(), "Unsubscribe link is invalid".to_string()),
+        Err(error) => (HttpResponse::BadRequest(), error),
+    };
+    Ok(html(response, email_templates::get_unsubscribe_result_page(&message)))
+}
+
+// GET recovery report handler (admin)
+async fn report_handler(
+    query: web::Query<RecoveryReportQuery>,
+    crud: web::Data<RecoveryCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud).get_report(query.into_inner()).await {
+        Ok(report) => Ok(HttpResponse::Ok().json(report)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+pub fn recovery_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/basket-reminders")
+            .route("/unsubscribe", web::get().to(unsubscribe_page_handler))
+            .route("/unsubscribe", web::post().to(unsubscribe_handler))
+    );
+}
+
+// Admin routes, mounted under the protected /api/admin scope
+pub fn admin_recovery_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/basket-reminders")
+            .route("/report", web::get().to(report_handler))
+    );
+}
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnsubscribeQuery {
+    pub token: String,
+}
+
+// Defaults to the last 30 days
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecoveryReportQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StepReport {
+    // 0 for the first reminder in the sequence
+    pub step: u32,
+    pub sent: u64,
+}
+
+// Baskets that went idle in the period and what became of them. Values in kobo.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecoveryReport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub baskets: u64,
+    pub reminders_sent: u64,
+    pub reminders_by_step: Vec<StepReport>,
+    pub active: u64,
+    pub recovered: u64,
+    pub recovered_value: i64,
+    pub resumed: u64,
+    pub completed: u64,
+    pub unsubscribed: u64,
+    // Share of reminded baskets that turned into an order, 0.0 to 1.0
+    pub recovery_rate: f64,
+}
@@ -0,0 +1,416 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use bson::{doc, oid::ObjectId, Document};
+use chrono::{Duration, Utc};
+use validator::Validate;
+use crate::module::order::{
+    crud::OrderCrud,
+    model::{Order, OrderActor, OrderStatus},
+};
+use crate::module::payment::{controller::PaymentController, crud::PaymentCrud};
+use crate::module::product::crud::ProductCrud;
+use crate::module::returns::{
+    crud::ReturnCrud,
+    interface::ReturnService,
+    model::{ReturnDecision, ReturnEvent, ReturnItem, ReturnRequest, ReturnResolution, ReturnStatus},
+    schema::{
+        CreateReturnSchema, InspectReturnSchema, ResolveReturnSchema, ReturnListQuery,
+        ReturnListResponse, ReturnNoteSchema,
+    },
+};
+use crate::service::{
+    currency_service::format_naira, email_service, email_templates,
+    payment::PaymentProvider,
+    upload::{product::UploadedImage, returns::ReturnUploadService},
+};
+use crate::service::pagination_service::paginate;
+
+// How long after delivery a return can be opened
+fn return_window_days() -> i64 {
+    std::env::var("RETURN_WINDOW_DAYS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(30)
+}
+
+pub struct ReturnController {
+    crud: ReturnCrud,
+    order_crud: OrderCrud,
+    product_crud: ProductCrud,
+    payment_controller: PaymentController,
+}
+
+impl ReturnController {
+    pub fn new(
+        crud: ReturnCrud,
+        order_crud: OrderCrud,
+        product_crud: ProductCrud,
+        payment_crud: PaymentCrud,
+        provider: Arc<dyn PaymentProvider>,
+    ) -> Self {
+        let payment_controller = PaymentController::new(payment_crud, product_crud.clone(), order_crud.clone(), provider);
+        Self { crud, order_crud, product_crud, payment_controller }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    async fn load(&self, id: &str) -> Result<ReturnRequest, String> {
+        self.get_return(id).await?
+            .ok_or_else(|| "Return request not found".to_string())
+    }
+
+    // Match the requested SKUs against the order, allowing for earlier returns
+    async fn build_items(&self, order: &Order, data: &CreateReturnSchema) -> Result<Vec<ReturnItem>, String> {
+        let order_id = order.id.ok_or_else(|| "Order has no ID".to_string())?;
+
+        let mut already_returned: HashMap<String, i32> = HashMap::new();
+        for request in self.crud.get_order_returns(&order_id).await? {
+            for item in request.items {
+                *already_returned.entry(item.sku).or_default() += item.quantity;
+            }
+        }
+
+        let mut items: Vec<ReturnItem> = Vec::new();
+        for requested in &data.items {
+            let ordered = order.items.iter()
+                .find(|item| item.sku == requested.sku)
+                .ok_or_else(|| format!("{} is not part of this order", requested.sku))?;
+
+            match items.iter_mut().find(|item| item.sku == requested.sku) {
+                Some(item) => item.quantity += requested.quantity,
+                None => items.push(ReturnItem {
+                    product_id: ordered.product_id,
+                    variant_id: ordered.variant_id,
+                    sku: ordered.sku.clone(),
+                    name: ordered.name.clone(),
+                    quantity: requested.quantity,
+                    unit_price: ordered.unit_price,
+                }),
+            }
+
+            let returnable = ordered.quantity - already_returned.get(&ordered.sku).copied().unwrap_or(0);
+            let total = items.iter().find(|item| item.sku == requested.sku).map(|item| item.quantity).unwrap_or(0);
+            if total > returnable {
+                return Err(format!("Only {} of {} can still be returned", returnable.max(0), ordered.name));
+            }
+        }
+        Ok(items)
+    }
+
+    // Apply a workflow step and record who made it
+    async fn transition(
+        &self,
+        request: ReturnRequest,
+        next: ReturnStatus,
+        actor: OrderActor,
+        note: Option<String>,
+        fields: Document,
+    ) -> Result<ReturnRequest, String> {
+        if !request.status.can_transition_to(next) {
+            return Err(format!(
+                "Cannot move return from {} to {}",
+                request.status.as_str(),
+                next.as_str()
+            ));
+        }
+
+        let id = request.id.ok_or_else(|| "Return request has no ID".to_string())?;
+        let event = ReturnEvent {
+            from: Some(request.status),
+            to: next,
+            actor,
+            note,
+            at: Utc::now(),
+        };
+
+        self.crud.transition_status(&id, event, fields).await?
+            .ok_or_else(|| "Return status changed concurrently, please retry".to_string())
+    }
+
+    // Best effort: photos of a request that wasn't saved are only clutter
+    async fn delete_photos(upload_service: &ReturnUploadService, photos: &[UploadedImage]) {
+        for photo in photos {
+            if let Err(e) = upload_service.delete_return_photo(&photo.public_id).await {
+                eprintln!("Failed to delete return photo {}: {:?}", photo.public_id, e);
+            }
+        }
+    }
+
+    // Upload the photos, deleting any already uploaded if one fails
+    async fn upload_photos(upload_service: &ReturnUploadService, photos: &[String], rma_number: &str) -> Result<Vec<UploadedImage>, String> {
+        let mut uploaded = Vec::new();
+        for photo in photos {
+            match upload_service.upload_return_photo(photo, rma_number).await {
+                Ok(image) => uploaded.push(image),
+                Err(e) => {
+                    Self::delete_photos(upload_service, &uploaded).await;
+                    return Err(format!("Photo upload failed: {:?}", e));
+                }
+            }
+        }
+        Ok(uploaded)
+    }
+
+    // Save a new request, unless another return was opened against the order
+    // after `order` was read; its items were checked against the returns before
+    async fn save_return(&self, order: &Order, request: ReturnRequest) -> Result<ReturnRequest, String> {
+        let order_id = order.id.ok_or_else(|| "Order has no ID".to_string())?;
+        let request = self.crud.create_return(request).await?;
+
+        match self.order_crud.bump_returns_version(&order_id, order.returns_version).await {
+            Ok(true) => Ok(request),
+            result => {
+                if let Some(id) = request.id
+                    && let Err(e) = self.crud.delete_return(&id).await
+                {
+                    eprintln!("Failed to remove return {}: {}", request.rma_number, e);
+                }
+                result?;
+                Err("Another return was opened for this order at the same time, please try again".to_string())
+            }
+        }
+    }
+
+    // Email the customer about a step, in the background
+    fn notify(&self, request: &ReturnRequest, headline: &str, message: String, note: Option<&str>) {
+        let message = match note {
+            Some(note) => format!("{} Note from our team: {}", message, note),
+            None => message,
+        };
+        let (subject, body) = email_templates::get_return_status_email_template(
+            &request.customer_name,
+            &request.rma_number,
+            headline,
+            &message,
+        );
+        let email = request.customer_email.clone();
+        let name = request.customer_name.clone();
+
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = email_service::send_email(&email, &name, &subject, &body) {
+                eprintln!("Failed to send return status email: {}", e);
+            }
+        });
+    }
+}
+
+#[async_trait::async_trait]
+impl ReturnService for ReturnController {
+    async fn create_return(&self, user_id: &str, email: &str, name: &str, data: CreateReturnSchema) -> Result<ReturnRequest, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let order_id = Self::parse_id(&data.order_id, "order")?;
+        let order = self.order_crud.get_user_order(&order_id, &user_oid).await?
+            .ok_or_else(|| "Order not found".to_string())?;
+
+        if order.status != OrderStatus::Delivered {
+            return Err("Only delivered orders can be returned".to_string());
+        }
+        let delivered_at = order.status_history.iter()
+            .rev()
+            .find(|transition| transition.to == OrderStatus::Delivered)
+            .map(|transition| transition.at)
+            .unwrap_or(order.updated_at);
+        if Utc::now() > delivered_at + Duration::days(return_window_days()) {
+            return Err("The return window for this order has closed".to_string());
+        }
+
+        let items = self.build_items(&order, &data).await?;
+        let mut request = ReturnRequest::new(
+            &order,
+            email.to_string(),
+            name.to_string(),
+            items,
+            data.reason,
+            data.description,
+            data.preferred_resolution,
+        );
+
+        // Photos go up once the request is known to be valid
+        let upload_service = ReturnUploadService::new();
+        let photos = Self::upload_photos(&upload_service, &data.photos, &request.rma_number).await?;
+        request.photos = photos.iter().map(|photo| photo.url.clone()).collect();
+
+        let request = match self.save_return(&order, request).await {
+            Ok(request) => request,
+            Err(e) => {
+                Self::delete_photos(&upload_service, &photos).await;
+                return Err(e);
+            }
+        };
+        self.notify(
+            &request,
+            "Return request received",
+            format!("We've received your return request for order {} and will review it shortly.", request.order_number),
+            None,
+        );
+        Ok(request)
+    }
+
+    async fn get_user_returns(&self, user_id: &str, query: ReturnListQuery) -> Result<ReturnListResponse, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let (returns, total) = self.crud
+            .find_returns(doc! { "user_id": user_oid }, skip, limit)
+            .await?;
+        Ok(ReturnListResponse { returns, total, page, limit })
+    }
+
+    async fn get_user_return(&self, user_id: &str, id: &str) -> Result<Option<ReturnRequest>, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let id = Self::parse_id(id, "return")?;
+        self.crud.get_user_return(&id, &user_oid).await
+    }
+
+    async fn list_returns(&self, query: ReturnListQuery) -> Result<ReturnListResponse, String> {
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let mut filter = doc! {};
+        if let Some(status) = &query.status {
+            let status = ReturnStatus::parse(status)
+                .ok_or_else(|| format!("Unknown return status: {}", status))?;
+            filter.insert("status", status.as_str());
+        }
+
+        let (returns, total) = self.crud.find_returns(filter, skip, limit).await?;
+        Ok(ReturnListResponse { returns, total, page, limit })
+    }
+
+    async fn get_return(&self, id: &str) -> Result<Option<ReturnRequest>, String> {
+        let id = Self::parse_id(id, "return")?;
+        self.crud.get_return(&id).await
+    }
+
+    async fn approve_return(&self, id: &str, data: ReturnNoteSchema, actor: OrderActor) -> Result<ReturnRequest, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let request = self.load(id).await?;
+        let request = self.transition(request, ReturnStatus::Approved, actor, data.note.clone(), doc! {}).await?;
+        self.notify(
+            &request,
+            "Return approved",
+            "Your return has been approved. Please send the items back with the return number below included in the package.".to_string(),
+            data.note.as_deref(),
+        );
+        Ok(request)
+    }
+
+    async fn reject_return(&self, id: &str, data: ReturnNoteSchema, actor: OrderActor) -> Result<ReturnRequest, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let request = self.load(id).await?;
+        let request = self.transition(request, ReturnStatus::Rejected, actor, data.note.clone(), doc! {}).await?;
+        self.notify(
+            &request,
+            "Return not accepted",
+            "Unfortunately we can't accept this return.".to_string(),
+            data.note.as_deref(),
+        );
+        Ok(request)
+    }
+
+    async fn receive_return(&self, id: &str, data: ReturnNoteSchema, actor: OrderActor) -> Result<ReturnRequest, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let request = self.load(id).await?;
+        let request = self.transition(request, ReturnStatus::Received, actor, data.note.clone(), doc! {}).await?;
+        self.notify(
+            &request,
+            "Items received",
+            "We've received your returned items and will inspect them shortly.".to_string(),
+            data.note.as_deref(),
+        );
+        Ok(request)
+    }
+
+    async fn inspect_return(&self, id: &str, data: InspectReturnSchema, actor: OrderActor) -> Result<ReturnRequest, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let request = self.load(id).await?;
+        let fields = doc! { "inspection_notes": &data.notes };
+        let request = self.transition(request, ReturnStatus::Inspected, actor, None, fields).await?;
+
+        if data.restock {
+            for item in &request.items {
+                if let Err(e) = self.product_crud.restock(&item.product_id, item.variant_id.as_ref(), item.quantity).await {
+                    eprintln!("Failed to restock {} from return {}: {}", item.sku, request.rma_number, e);
+                }
+            }
+        }
+
+        self.notify(
+            &request,
+            "Inspection complete",
+            "We've inspected your returned items and will let you know the outcome shortly.".to_string(),
+            None,
+        );
+        Ok(request)
+    }
+
+    async fn resolve_return(&self, id: &str, data: ResolveReturnSchema, actor: OrderActor) -> Result<ReturnRequest, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let request = self.load(id).await?;
+        let value = request.items_value();
+        let amount = match data.resolution {
+            ReturnResolution::Replacement => None,
+            ReturnResolution::Refund => {
+                let amount = data.amount.unwrap_or(value);
+                if amount > value {
+                    return Err(format!("Amount can't be more than the returned items' value of {}", format_naira(value)));
+                }
+                Some(amount)
+            }
+        };
+
+        let decision = ReturnDecision {
+            resolution: data.resolution,
+            amount,
+            note: data.note.clone(),
+        };
+        let decision_doc = bson::to_bson(&decision)
+            .map_err(|e| format!("Failed to encode decision: {}", e))?;
+        // Resolving first means only one admin can issue the refund
+        let request = self.transition(request, ReturnStatus::Resolved, actor, data.note.clone(), doc! { "decision": decision_doc }).await?;
+
+        if let (ReturnResolution::Refund, Some(amount)) = (decision.resolution, decision.amount)
+            && amount > 0
+            && let Err(e) = self.payment_controller.refund_order(&request.order_number, amount).await
+        {
+            // Back to inspected, so the refund can be tried again
+            let id = request.id.ok_or_else(|| "Return request has no ID".to_string())?;
+            let event = ReturnEvent {
+                from: Some(ReturnStatus::Resolved),
+                to: ReturnStatus::Inspected,
+                actor: OrderActor::system(),
+                note: Some(format!("Refund failed: {}", e)),
+                at: Utc::now(),
+            };
+            self.crud.transition_status(&id, event, doc! { "decision": null }).await?;
+            return Err(format!("Refund failed: {}", e));
+        }
+
+        let message = match (decision.resolution, decision.amount) {
+            (ReturnResolution::Refund, Some(amount)) => format!("We've issued a refund of {} to your original payment method.", format_naira(amount)),
+            (resolution, _) => format!("Your return has been resolved with a {}; we'll be in touch with the details.", resolution.label()),
+        };
+        self.notify(&request, "Return resolved", message, data.note.as_deref());
+        Ok(request)
+    }
+}
@@ -0,0 +1,159 @@
+use bson::{doc, oid::ObjectId, Document};
+use chrono::Utc;
+use mongodb::{
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Collection, Database,
+};
+use futures_util::TryStreamExt;
+use crate::module::order::model::OrderActor;
+use crate::module::returns::model::{ReturnEvent, ReturnRequest, ReturnStatus};
+
+#[derive(Clone)]
+pub struct ReturnCrud {
+    collection: Collection<ReturnRequest>,
+}
+
+impl ReturnCrud {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("returns");
+        Self { collection }
+    }
+
+    pub async fn create_return(&self, mut request: ReturnRequest) -> Result<ReturnRequest, String> {
+        match self.collection.insert_one(&request).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    request.id = Some(id);
+                }
+                Ok(request)
+            }
+            Err(e) => Err(format!("Failed to create return request: {}", e))
+        }
+    }
+
+    // Take back a request that couldn't be completed
+    pub async fn delete_return(&self, id: &ObjectId) -> Result<(), String> {
+        self.collection.delete_one(doc! { "_id": id }).await
+            .map_err(|e| format!("Failed to delete return request: {}", e))?;
+        Ok(())
+    }
+
+    pub async fn get_return(&self, id: &ObjectId) -> Result<Option<ReturnRequest>, String> {
+        match self.collection.find_one(doc! { "_id": id }).await {
+            Ok(request) => Ok(request),
+            Err(e) => Err(format!("Failed to retrieve return request: {}", e))
+        }
+    }
+
+    pub async fn get_user_return(&self, id: &ObjectId, user_id: &ObjectId) -> Result<Option<ReturnRequest>, String> {
+        match self.collection.find_one(doc! { "_id": id, "user_id": user_id }).await {
+            Ok(request) => Ok(request),
+            Err(e) => Err(format!("Failed to retrieve return request: {}", e))
+        }
+    }
+
+    // Returns against an order that are still open or were accepted
+    pub async fn get_order_returns(&self, order_id: &ObjectId) -> Result<Vec<ReturnRequest>, String> {
+        let filter = doc! { "order_id": order_id, "status": { "$ne": "rejected" } };
+        match self.collection.find(filter).await {
+            Ok(mut cursor) => {
+                let mut requests = Vec::new();
+                while let Some(request) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve return requests: {}", e))? {
+                    requests.push(request);
+                }
+                Ok(requests)
+            }
+            Err(e) => Err(format!("Failed to retrieve return requests: {}", e))
+        }
+    }
+
+    // Newest first, with the total number of matches for pagination
+    pub async fn find_returns(&self, filter: Document, skip: u64, limit: i64) -> Result<(Vec<ReturnRequest>, u64), String> {
+        let total = self.collection.count_documents(filter.clone()).await
+            .map_err(|e| format!("Failed to count return requests: {}", e))?;
+
+        let options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .skip(skip)
+            .limit(limit)
+            .build();
+
+        match self.collection.find(filter).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut requests = Vec::new();
+                while let Some(request) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve return requests: {}", e))? {
+                    requests.push(request);
+                }
+                Ok((requests, total))
+            }
+            Err(e) => Err(format!("Failed to retrieve return requests: {}", e))
+        }
+    }
+
+    // Move a request on, but only if nobody else moved it first. `fields` are
+    // set alongside the new status (e.g. inspection notes or the decision).
+    pub async fn transition_status(
+        &self,
+        id: &ObjectId,
+        event: ReturnEvent,
+        mut fields: Document,
+    ) -> Result<Option<ReturnRequest>, String> {
+        let from = event.from.map(|status| status.as_str()).unwrap_or_default();
+        let event_doc = bson::to_bson(&event)
+            .map_err(|e| format!("Failed to encode return event: {}", e))?;
+
+        fields.insert("status", event.to.as_str());
+        fields.insert("updated_at", bson::DateTime::from_chrono(Utc::now()));
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "status": from },
+                doc! { "$set": fields, "$push": { "history": event_doc } },
+            )
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to update return request: {}", e))
+    }
+
+    // Store credit was offered before there was a ledger to hold it. Asking
+    // for it becomes asking for a refund, and requests resolved with it go
+    // back to inspection so they can be resolved with a refund or replacement.
+    pub async fn drop_store_credit(&self) -> Result<u64, String> {
+        let raw = self.collection.clone_with_type::<Document>();
+        let preferred = raw
+            .update_many(
+                doc! { "preferred_resolution": "store_credit" },
+                doc! { "$set": { "preferred_resolution": "refund" } },
+            )
+            .await
+            .map_err(|e| format!("Failed to update return requests: {}", e))?;
+
+        let event = ReturnEvent {
+            from: Some(ReturnStatus::Resolved),
+            to: ReturnStatus::Inspected,
+            actor: OrderActor::system(),
+            note: Some("Store credit isn't available; resolve again with a refund or replacement".to_string()),
+            at: Utc::now(),
+        };
+        let event_doc = bson::to_bson(&event)
+            .map_err(|e| format!("Failed to encode return event: {}", e))?;
+        let resolved = raw
+            .update_many(
+                doc! { "status": "resolved", "decision.resolution": "store_credit" },
+                doc! {
+                    "$set": { "status": "inspected", "updated_at": bson::DateTime::from_chrono(Utc::now()) },
+                    "$unset": { "decision": "" },
+                    "$push": { "history": event_doc },
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to update return requests: {}", e))?;
+
+        Ok(preferred.modified_count + resolved.modified_count)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::module::order::model::OrderActor;
+use crate::module::returns::{
+    model::ReturnRequest,
+    schema::{
+        CreateReturnSchema, InspectReturnSchema, ResolveReturnSchema, ReturnListQuery,
+        ReturnListResponse, ReturnNoteSchema,
+    },
+};
+
+#[async_trait::async_trait]
+pub trait ReturnService {
+    // Customer
+    async fn create_return(&self, user_id: &str, email: &str, name: &str, data: CreateReturnSchema) -> Result<ReturnRequest, String>;
+    async fn get_user_returns(&self, user_id: &str, query: ReturnListQuery) -> Result<ReturnListResponse, String>;
+    async fn get_user_return(&self, user_id: &str, id: &str) -> Result<Option<ReturnRequest>, String>;
+
+    // Admin
+    async fn list_returns(&self, query: ReturnListQuery) -> Result<ReturnListResponse, String>;
+    async fn get_return(&self, id: &str) -> Result<Option<ReturnRequest>, String>;
+    async fn approve_return(&self, id: &str, data: ReturnNoteSchema, actor: OrderActor) -> Result<ReturnRequest, String>;
+    async fn reject_return(&self, id: &str, data: ReturnNoteSchema, actor: OrderActor) -> Result<ReturnRequest, String>;
+    async fn receive_return(&self, id: &str, data: ReturnNoteSchema, actor: OrderActor) -> Result<ReturnRequest, String>;
+    async fn inspect_return(&self, id: &str, data: InspectReturnSchema, actor: OrderActor) -> Result<ReturnRequest, String>;
+    async fn resolve_return(&self, id: &str, data: ResolveReturnSchema, actor: OrderActor) -> Result<ReturnRequest, String>;
+}
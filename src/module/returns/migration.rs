@@ -0,0 +1,27 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::module::returns::crud::ReturnCrud;
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("returns", 1, "RMA number and listing indexes", "returns", vec![
+            IndexModel::builder()
+                .keys(doc! { "rma_number": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "user_id": 1, "created_at": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "status": 1, "created_at": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "order_id": 1 })
+                .build(),
+        ]),
+        Migration::data("returns", 2, "drop store credit resolutions", |db| async move {
+            ReturnCrud::new(&db).drop_store_credit().await
+        }),
+    ]
+}
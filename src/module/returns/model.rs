@@ -0,0 +1,206 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::module::order::model::{Order, OrderActor};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnReason {
+    DeadOnArrival,
+    DeadPixels,
+    Defective,
+    DamagedInShipping,
+    WrongItem,
+    NotAsDescribed,
+    ChangedMind,
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnResolution {
+    Refund,
+    Replacement,
+}
+
+impl ReturnResolution {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReturnResolution::Refund => "refund",
+            ReturnResolution::Replacement => "replacement",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnStatus {
+    Requested,
+    Approved,
+    Rejected,
+    Received,
+    Inspected,
+    Resolved,
+}
+
+impl ReturnStatus {
+    // Requested -> Approved -> Received -> Inspected -> Resolved, and a request
+    // can be turned down when it is first reviewed or after inspection
+    pub fn can_transition_to(&self, next: ReturnStatus) -> bool {
+        use ReturnStatus::*;
+        matches!(
+            (self, next),
+            (Requested, Approved)
+                | (Requested, Rejected)
+                | (Approved, Received)
+                | (Received, Inspected)
+                | (Inspected, Resolved)
+                | (Inspected, Rejected)
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReturnStatus::Requested => "requested",
+            ReturnStatus::Approved => "approved",
+            ReturnStatus::Rejected => "rejected",
+            ReturnStatus::Received => "received",
+            ReturnStatus::Inspected => "inspected",
+            ReturnStatus::Resolved => "resolved",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "requested" => Some(ReturnStatus::Requested),
+            "approved" => Some(ReturnStatus::Approved),
+            "rejected" => Some(ReturnStatus::Rejected),
+            "received" => Some(ReturnStatus::Received),
+            "inspected" => Some(ReturnStatus::Inspected),
+            "resolved" => Some(ReturnStatus::Resolved),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReturnItem {
+    pub product_id: ObjectId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant_id: Option<ObjectId>,
+    pub sku: String,
+    pub name: String,
+    pub quantity: i32,
+    pub unit_price: i64, // What the customer paid, in kobo
+}
+
+// The outcome once the goods have been inspected
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReturnDecision {
+    pub resolution: ReturnResolution,
+    // Money refunded, in kobo; None for replacements
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReturnEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<ReturnStatus>,
+    pub to: ReturnStatus,
+    pub actor: OrderActor,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub at: DateTime<Utc>,
+}
+
+// A return merchandise authorization (RMA) against a delivered order
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReturnRequest {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub rma_number: String,
+    pub order_id: ObjectId,
+    pub order_number: String,
+    pub user_id: ObjectId,
+
+    // Where status emails go
+    pub customer_email: String,
+    pub customer_name: String,
+
+    pub items: Vec<ReturnItem>,
+    pub reason: ReturnReason,
+    pub description: String,
+    pub photos: Vec<String>, // Cloudinary URLs
+    pub preferred_resolution: ReturnResolution,
+
+    pub status: ReturnStatus,
+    pub history: Vec<ReturnEvent>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inspection_notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision: Option<ReturnDecision>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ReturnRequest {
+    pub fn new(
+        order: &Order,
+        customer_email: String,
+        customer_name: String,
+        items: Vec<ReturnItem>,
+        reason: ReturnReason,
+        description: String,
+        preferred_resolution: ReturnResolution,
+    ) -> Self {
+        let now = Utc::now();
+        let rma_number = format!(
+            "RMA-{}-{}",
+            now.format("%Y%m%d"),
+            Uuid::new_v4().simple().to_string()[..8].to_uppercase()
+        );
+
+        Self {
+            id: None,
+            rma_number,
+            order_id: order.id.unwrap_or_default(),
+            order_number: order.order_number.clone(),
+            user_id: order.user_id,
+            customer_email,
+            customer_name,
+            items,
+            reason,
+            description,
+            photos: Vec::new(),
+            preferred_resolution,
+            status: ReturnStatus::Requested,
+            history: vec![ReturnEvent {
+                from: None,
+                to: ReturnStatus::Requested,
+                actor: OrderActor::user(&order.user_id.to_hex()),
+                note: None,
+                at: now,
+            }],
+            inspection_notes: None,
+            decision: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    // Most that can be refunded: what was paid for the returned items
+    pub fn items_value(&self) -> i64 {
+        self.items.iter().map(|item| item.unit_price * item.quantity as i64).sum()
+    }
+}
@@ -0,0 +1,270 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::Authentication;
+use crate::module::order::{crud::OrderCrud, model::OrderActor};
+use crate::module::payment::crud::PaymentCrud;
+use crate::module::product::crud::ProductCrud;
+use crate::module::returns::{
+    controller::ReturnController,
+    crud::ReturnCrud,
+    interface::ReturnService,
+    model::ReturnRequest,
+    schema::{CreateReturnSchema, InspectReturnSchema, ResolveReturnSchema, ReturnListQuery, ReturnNoteSchema},
+};
+use crate::service::payment::PaymentProvider;
+
+fn controller(
+    crud: &web::Data<ReturnCrud>,
+    order_crud: &web::Data<OrderCrud>,
+    product_crud: &web::Data<ProductCrud>,
+    payment_crud: &web::Data<PaymentCrud>,
+    provider: &web::Data<dyn PaymentProvider>,
+) -> ReturnController {
+    ReturnController::new(
+        crud.get_ref().clone(),
+        order_crud.get_ref().clone(),
+        product_crud.get_ref().clone(),
+        payment_crud.get_ref().clone(),
+        provider.clone().into_inner(),
+    )
+}
+
+// The admin middleware already checked the token; this only reads who it belongs to
+fn admin_actor(req: &HttpRequest) -> OrderActor {
+    let admin_id = Authentication::optional_user(req)
+        .map(|claims| claims.sub)
+        .unwrap_or_else(|| "unknown".to_string());
+    OrderActor::admin(&admin_id)
+}
+
+fn step_response(result: std::result::Result<ReturnRequest, String>) -> HttpResponse {
+    match result {
+        Ok(request) => HttpResponse::Ok().json(request),
+        Err(error) => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        })),
+    }
+}
+
+// POST return request handler
+async fn create_return_handler(
+    req: HttpRequest,
+    body: web::Json<CreateReturnSchema>,
+    crud: web::Data<ReturnCrud>,
+    order_crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    payment_crud: web::Data<PaymentCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+    let name = format!("{} {}", claims.first_name, claims.last_name);
+
+    match controller(&crud, &order_crud, &product_crud, &payment_crud, &provider).create_return(&claims.sub, &claims.email, &name, body.into_inner()).await {
+        Ok(request) => Ok(HttpResponse::Created().json(request)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET user's return requests handler
+async fn get_returns_handler(
+    req: HttpRequest,
+    query: web::Query<ReturnListQuery>,
+    crud: web::Data<ReturnCrud>,
+    order_crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    payment_crud: web::Data<PaymentCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &order_crud, &product_crud, &payment_crud, &provider).get_user_returns(&claims.sub, query.into_inner()).await {
+        Ok(returns) => Ok(HttpResponse::Ok().json(returns)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET single return request handler
+async fn get_return_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    crud: web::Data<ReturnCrud>,
+    order_crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    payment_crud: web::Data<PaymentCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &order_crud, &product_crud, &payment_crud, &provider).get_user_return(&claims.sub, &id).await {
+        Ok(Some(request)) => Ok(HttpResponse::Ok().json(request)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Return request not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET all return requests handler (admin)
+async fn admin_list_returns_handler(
+    query: web::Query<ReturnListQuery>,
+    crud: web::Data<ReturnCrud>,
+    order_crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    payment_crud: web::Data<PaymentCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &order_crud, &product_crud, &payment_crud, &provider).list_returns(query.into_inner()).await {
+        Ok(returns) => Ok(HttpResponse::Ok().json(returns)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET single return request handler (admin)
+async fn admin_get_return_handler(
+    id: web::Path<String>,
+    crud: web::Data<ReturnCrud>,
+    order_crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    payment_crud: web::Data<PaymentCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &order_crud, &product_crud, &payment_crud, &provider).get_return(&id).await {
+        Ok(Some(request)) => Ok(HttpResponse::Ok().json(request)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Return request not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST approve return handler (admin)
+#[allow(clippy::too_many_arguments)]
+async fn admin_approve_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<ReturnNoteSchema>,
+    crud: web::Data<ReturnCrud>,
+    order_crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    payment_crud: web::Data<PaymentCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let result = controller(&crud, &order_crud, &product_crud, &payment_crud, &provider)
+        .approve_return(&id, body.into_inner(), admin_actor(&req))
+        .await;
+    Ok(step_response(result))
+}
+
+// POST reject return handler (admin)
+#[allow(clippy::too_many_arguments)]
+async fn admin_reject_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<ReturnNoteSchema>,
+    crud: web::Data<ReturnCrud>,
+    order_crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    payment_crud: web::Data<PaymentCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let result = controller(&crud, &order_crud, &product_crud, &payment_crud, &provider)
+        .reject_return(&id, body.into_inner(), admin_actor(&req))
+        .await;
+    Ok(step_response(result))
+}
+
+// POST mark return received handler (admin)
+#[allow(clippy::too_many_arguments)]
+async fn admin_receive_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<ReturnNoteSchema>,
+    crud: web::Data<ReturnCrud>,
+    order_crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    payment_crud: web::Data<PaymentCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let result = controller(&crud, &order_crud, &product_crud, &payment_crud, &provider)
+        .receive_return(&id, body.into_inner(), admin_actor(&req))
+        .await;
+    Ok(step_response(result))
+}
+
+// POST inspection result handler (admin)
+#[allow(clippy::too_many_arguments)]
+async fn admin_inspect_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<InspectReturnSchema>,
+    crud: web::Data<ReturnCrud>,
+    order_crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    payment_crud: web::Data<PaymentCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let result = controller(&crud, &order_crud, &product_crud, &payment_crud, &provider)
+        .inspect_return(&id, body.into_inner(), admin_actor(&req))
+        .await;
+    Ok(step_response(result))
+}
+
+// POST resolution decision handler (admin)
+#[allow(clippy::too_many_arguments)]
+async fn admin_resolve_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<ResolveReturnSchema>,
+    crud: web::Data<ReturnCrud>,
+    order_crud: web::Data<OrderCrud>,
+    product_crud: web::Data<ProductCrud>,
+    payment_crud: web::Data<PaymentCrud>,
+    provider: web::Data<dyn PaymentProvider>,
+) -> Result<HttpResponse> {
+    let result = controller(&crud, &order_crud, &product_crud, &payment_crud, &provider)
+        .resolve_return(&id, body.into_inner(), admin_actor(&req))
+        .await;
+    Ok(step_response(result))
+}
+
+// Route configuration function
+pub fn return_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/returns")
+            .route("", web::post().to(create_return_handler))
+            .route("", web::get().to(get_returns_handler))
+            .route("/{id}", web::get().to(get_return_handler))
+    );
+}
+
+// Admin routes, mounted inside the admin-authenticated `/api/admin` scope
+pub fn admin_return_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/returns")
+            .route("", web::get().to(admin_list_returns_handler))
+            .route("/{id}", web::get().to(admin_get_return_handler))
+            .route("/{id}/approve", web::post().to(admin_approve_handler))
+            .route("/{id}/reject", web::post().to(admin_reject_handler))
+            .route("/{id}/receive", web::post().to(admin_receive_handler))
+            .route("/{id}/inspect", web::post().to(admin_inspect_handler))
+            .route("/{id}/resolve", web::post().to(admin_resolve_handler))
+    );
+}
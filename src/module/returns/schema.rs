@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::module::returns::model::{ReturnReason, ReturnRequest, ReturnResolution};
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct ReturnItemSchema {
+    #[validate(length(min = 1, max = 50))]
+    pub sku: String,
+
+    #[validate(range(min = 1, max = 100))]
+    pub quantity: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateReturnSchema {
+    pub order_id: String,
+
+    #[validate(length(min = 1, max = 50), nested)]
+    pub items: Vec<ReturnItemSchema>,
+
+    pub reason: ReturnReason,
+
+    #[validate(length(min = 10, max = 2000, message = "Please describe the problem in 10 to 2000 characters"))]
+    pub description: String,
+
+    // Base64 encoded images of the fault or damage
+    #[serde(default)]
+    #[validate(length(max = 5, message = "At most 5 photos can be attached"))]
+    pub photos: Vec<String>,
+
+    pub preferred_resolution: ReturnResolution,
+}
+
+// Approve, reject or mark as received
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ReturnNoteSchema {
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct InspectReturnSchema {
+    #[validate(length(min = 1, max = 2000))]
+    pub notes: String,
+
+    // Put the returned units back into sellable stock
+    #[serde(default)]
+    pub restock: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ResolveReturnSchema {
+    pub resolution: ReturnResolution,
+
+    // Refund in kobo; defaults to the value of the returned items
+    #[validate(range(min = 0))]
+    pub amount: Option<i64>,
+
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReturnListQuery {
+    pub page: Option<u64>,
+    pub limit: Option<i64>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReturnListResponse {
+    pub returns: Vec<ReturnRequest>,
+    pub total: u64,
+    pub page: u64,
+    pub limit: i64,
+}
@@ -0,0 +1,250 @@
+use bson::{doc, oid::ObjectId, Document};
+use validator::Validate;
+use crate::module::order::crud::OrderCrud;
+use crate::module::product::crud::ProductCrud;
+use crate::module::review::{
+    crud::ReviewCrud,
+    interface::ReviewService,
+    model::{Review, ReviewStatus},
+    schema::{
+        CreateReviewSchema, ModerationQueueQuery, ProductReviewsResponse, ReviewListQuery,
+        ReviewListResponse, ReviewNoteSchema, ReviewResponse,
+    },
+};
+use crate::service::upload::{product::UploadedImage, reviews::ReviewUploadService};
+use crate::service::pagination_service::paginate;
+
+pub struct ReviewController {
+    crud: ReviewCrud,
+    product_crud: ProductCrud,
+    order_crud: OrderCrud,
+}
+
+impl ReviewController {
+    pub fn new(crud: ReviewCrud, product_crud: ProductCrud, order_crud: OrderCrud) -> Self {
+        Self { crud, product_crud, order_crud }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    // Best effort: photos of a review that wasn't saved are only clutter
+    async fn delete_photos(upload_service: &ReviewUploadService, photos: &[UploadedImage]) {
+        for photo in photos {
+            if let Err(e) = upload_service.delete_review_photo(&photo.public_id).await {
+                eprintln!("Failed to delete review photo {}: {:?}", photo.public_id, e);
+            }
+        }
+    }
+
+    fn sort_for(sort: Option<&str>) -> Result<Document, String> {
+        match sort.unwrap_or("newest") {
+            "newest" => Ok(doc! { "created_at": -1, "_id": -1 }),
+            "helpful" => Ok(doc! { "helpful_count": -1, "created_at": -1 }),
+            "highest" => Ok(doc! { "rating": -1, "created_at": -1 }),
+            "lowest" => Ok(doc! { "rating": 1, "created_at": -1 }),
+            other => Err(format!("Unknown sort '{}'", other)),
+        }
+    }
+
+    // Apply a moderation decision and keep the product's rating in step:
+    // a review counts only while it is approved
+    async fn moderate(&self, id: &str, next: ReviewStatus, data: ReviewNoteSchema, admin_id: &str) -> Result<Review, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let id = Self::parse_id(id, "review")?;
+        let review = self.crud.get_review(&id).await?
+            .ok_or_else(|| "Review not found".to_string())?;
+        if !review.status.can_transition_to(next) {
+            return Err(format!(
+                "Cannot move review from {} to {}",
+                review.status.as_str(),
+                next.as_str()
+            ));
+        }
+
+        let updated = self.crud.transition_status(&id, review.status, next, data.note, admin_id).await?
+            .ok_or_else(|| "Review was moderated concurrently, please retry".to_string())?;
+
+        if next == ReviewStatus::Approved {
+            self.product_crud.apply_review_rating(&updated.product_id, updated.rating, 1).await?;
+        } else if review.status == ReviewStatus::Approved {
+            self.product_crud.apply_review_rating(&updated.product_id, updated.rating, -1).await?;
+        }
+        Ok(updated)
+    }
+
+    // Votes only go to published reviews, and never to one's own
+    async fn votable_review(&self, user_id: &ObjectId, id: &ObjectId) -> Result<Review, String> {
+        let review = self.crud.get_review(id).await?
+            .filter(|review| review.status == ReviewStatus::Approved)
+            .ok_or_else(|| "Review not found".to_string())?;
+        if &review.user_id == user_id {
+            return Err("You can't vote on your own review".to_string());
+        }
+        Ok(review)
+    }
+}
+
+#[async_trait::async_trait]
+impl ReviewService for ReviewController {
+    async fn get_product_reviews(&self, product_id: &str, query: ReviewListQuery) -> Result<ProductReviewsResponse, String> {
+        let product = self.product_crud.get_product(product_id).await?
+            .filter(|product| product.deleted_at.is_none())
+            .ok_or_else(|| "Product not found".to_string())?;
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let mut filter = doc! {
+            "product_id": product.id,
+            "status": ReviewStatus::Approved.as_str(),
+        };
+        if let Some(rating) = query.rating {
+            if !(1..=5).contains(&rating) {
+                return Err("Rating must be between 1 and 5 stars".to_string());
+            }
+            filter.insert("rating", rating);
+        }
+
+        let sort = Self::sort_for(query.sort.as_deref())?;
+        let (reviews, total) = self.crud.find_reviews(filter, sort, skip, limit).await?;
+        Ok(ProductReviewsResponse {
+            rating: product.rating,
+            reviews: reviews.into_iter().map(ReviewResponse::from).collect(),
+            total,
+            page,
+            limit,
+        })
+    }
+
+    async fn create_review(&self, user_id: &str, author_name: &str, data: CreateReviewSchema) -> Result<Review, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let product_id = Self::parse_id(&data.product_id, "product")?;
+        self.product_crud.get_product(&data.product_id).await?
+            .filter(|product| product.deleted_at.is_none())
+            .ok_or_else(|| "Product not found".to_string())?;
+        // Checked before uploading; the unique index still decides a race
+        if self.crud.has_reviewed(&product_id, &user_oid).await? {
+            return Err("You have already reviewed this product".to_string());
+        }
+
+        let order = self.order_crud.find_delivered_purchase(&user_oid, &product_id).await?;
+        let mut review = Review::new(
+            product_id,
+            user_oid,
+            author_name.to_string(),
+            data.rating,
+            data.title.trim().to_string(),
+            data.body.trim().to_string(),
+            order.and_then(|order| order.id),
+        );
+
+        if data.photos.is_empty() {
+            return self.crud.create_review(review).await;
+        }
+
+        // Photos uploaded for a review that isn't saved are deleted again
+        let upload_service = ReviewUploadService::new();
+        let mut uploaded = Vec::new();
+        for photo in &data.photos {
+            match upload_service.upload_review_photo(photo, &data.product_id).await {
+                Ok(image) => uploaded.push(image),
+                Err(e) => {
+                    Self::delete_photos(&upload_service, &uploaded).await;
+                    return Err(format!("Photo upload failed: {:?}", e));
+                }
+            }
+        }
+        review.photos = uploaded.iter().map(|image| image.url.clone()).collect();
+
+        match self.crud.create_review(review).await {
+            Ok(review) => Ok(review),
+            Err(e) => {
+                Self::delete_photos(&upload_service, &uploaded).await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn get_user_reviews(&self, user_id: &str, query: ReviewListQuery) -> Result<ReviewListResponse, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let (reviews, total) = self.crud
+            .find_reviews(doc! { "user_id": user_oid }, doc! { "created_at": -1 }, skip, limit)
+            .await?;
+        Ok(ReviewListResponse { reviews, total, page, limit })
+    }
+
+    async fn delete_review(&self, user_id: &str, id: &str) -> Result<bool, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let id = Self::parse_id(id, "review")?;
+
+        match self.crud.delete_user_review(&id, &user_oid).await? {
+            Some(review) => {
+                if review.status == ReviewStatus::Approved {
+                    self.product_crud.apply_review_rating(&review.product_id, review.rating, -1).await?;
+                }
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn vote_helpful(&self, user_id: &str, id: &str) -> Result<ReviewResponse, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let id = Self::parse_id(id, "review")?;
+        let review = self.votable_review(&user_oid, &id).await?;
+
+        // Voting twice leaves the count as it was
+        let review = self.crud.add_helpful_vote(&id, &user_oid).await?.unwrap_or(review);
+        Ok(review.into())
+    }
+
+    async fn remove_helpful_vote(&self, user_id: &str, id: &str) -> Result<ReviewResponse, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let id = Self::parse_id(id, "review")?;
+        let review = self.votable_review(&user_oid, &id).await?;
+
+        let review = self.crud.remove_helpful_vote(&id, &user_oid).await?.unwrap_or(review);
+        Ok(review.into())
+    }
+
+    async fn moderation_queue(&self, query: ModerationQueueQuery) -> Result<ReviewListResponse, String> {
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let status = match query.status.as_deref() {
+            Some(status) => ReviewStatus::parse(status)
+                .ok_or_else(|| format!("Unknown review status: {}", status))?,
+            None => ReviewStatus::Pending,
+        };
+        let mut filter = doc! { "status": status.as_str() };
+        if let Some(product_id) = &query.product_id {
+            filter.insert("product_id", Self::parse_id(product_id, "product")?);
+        }
+
+        // Oldest first, so reviews are dealt with in the order they came in
+        let (reviews, total) = self.crud
+            .find_reviews(filter, doc! { "created_at": 1 }, skip, limit)
+            .await?;
+        Ok(ReviewListResponse { reviews, total, page, limit })
+    }
+
+    async fn approve_review(&self, id: &str, data: ReviewNoteSchema, admin_id: &str) -> Result<Review, String> {
+        self.moderate(id, ReviewStatus::Approved, data, admin_id).await
+    }
+
+    async fn reject_review(&self, id: &str, data: ReviewNoteSchema, admin_id: &str) -> Result<Review, String> {
+        self.moderate(id, ReviewStatus::Rejected, data, admin_id).await
+    }
+
+    async fn flag_review(&self, id: &str, data: ReviewNoteSchema, admin_id: &str) -> Result<Review, String> {
+        self.moderate(id, ReviewStatus::Flagged, data, admin_id).await
+    }
+}
@@ -0,0 +1,156 @@
+use bson::{doc, oid::ObjectId, Document};
+use chrono::Utc;
+use mongodb::{
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Collection, Database,
+};
+use futures_util::TryStreamExt;
+use crate::module::review::model::{HelpfulVote, Review, ReviewStatus};
+use crate::service::migration_service;
+
+#[derive(Clone)]
+pub struct ReviewCrud {
+    collection: Collection<Review>,
+    votes: Collection<HelpfulVote>,
+}
+
+impl ReviewCrud {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("reviews");
+        let votes = db.collection("review_votes");
+        Self { collection, votes }
+    }
+
+    pub async fn create_review(&self, mut review: Review) -> Result<Review, String> {
+        match self.collection.insert_one(&review).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    review.id = Some(id);
+                }
+                Ok(review)
+            }
+            Err(e) if migration_service::is_duplicate_key(&e, "product_id") => {
+                Err("You have already reviewed this product".to_string())
+            }
+            Err(e) => Err(format!("Failed to create review: {}", e))
+        }
+    }
+
+    pub async fn has_reviewed(&self, product_id: &ObjectId, user_id: &ObjectId) -> Result<bool, String> {
+        match self.collection.count_documents(doc! { "product_id": product_id, "user_id": user_id }).await {
+            Ok(count) => Ok(count > 0),
+            Err(e) => Err(format!("Failed to check for an existing review: {}", e))
+        }
+    }
+
+    pub async fn get_review(&self, id: &ObjectId) -> Result<Option<Review>, String> {
+        match self.collection.find_one(doc! { "_id": id }).await {
+            Ok(review) => Ok(review),
+            Err(e) => Err(format!("Failed to retrieve review: {}", e))
+        }
+    }
+
+    // Page through reviews in `sort` order, with the total number of matches
+    pub async fn find_reviews(&self, filter: Document, sort: Document, skip: u64, limit: i64) -> Result<(Vec<Review>, u64), String> {
+        let total = self.collection.count_documents(filter.clone()).await
+            .map_err(|e| format!("Failed to count reviews: {}", e))?;
+
+        let options = FindOptions::builder()
+            .sort(sort)
+            .skip(skip)
+            .limit(limit)
+            .build();
+
+        match self.collection.find(filter).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut reviews = Vec::new();
+                while let Some(review) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve reviews: {}", e))? {
+                    reviews.push(review);
+                }
+                Ok((reviews, total))
+            }
+            Err(e) => Err(format!("Failed to retrieve reviews: {}", e))
+        }
+    }
+
+    // Move a review on, but only if nobody else moved it first
+    pub async fn transition_status(
+        &self,
+        id: &ObjectId,
+        from: ReviewStatus,
+        to: ReviewStatus,
+        note: Option<String>,
+        moderated_by: &str,
+    ) -> Result<Option<Review>, String> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "status": from.as_str() },
+                doc! { "$set": {
+                    "status": to.as_str(),
+                    "moderation_note": note,
+                    "moderated_by": moderated_by,
+                    "updated_at": bson::DateTime::from_chrono(Utc::now()),
+                } },
+            )
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to update review: {}", e))
+    }
+
+    // Removes the user's own review and its votes, returning what was removed
+    pub async fn delete_user_review(&self, id: &ObjectId, user_id: &ObjectId) -> Result<Option<Review>, String> {
+        let review = self.collection
+            .find_one_and_delete(doc! { "_id": id, "user_id": user_id })
+            .await
+            .map_err(|e| format!("Failed to delete review: {}", e))?;
+
+        if review.is_some() {
+            self.votes.delete_many(doc! { "review_id": id }).await
+                .map_err(|e| format!("Failed to delete review votes: {}", e))?;
+        }
+        Ok(review)
+    }
+
+    // Record the vote and count it. Returns None when the user had already voted.
+    pub async fn add_helpful_vote(&self, id: &ObjectId, user_id: &ObjectId) -> Result<Option<Review>, String> {
+        let vote = HelpfulVote {
+            id: None,
+            review_id: *id,
+            user_id: *user_id,
+            created_at: Utc::now(),
+        };
+        match self.votes.insert_one(&vote).await {
+            Ok(_) => {}
+            Err(e) if migration_service::is_duplicate_key(&e, "review_id") => return Ok(None),
+            Err(e) => return Err(format!("Failed to record vote: {}", e)),
+        }
+        self.change_helpful_count(id, 1).await
+    }
+
+    // Take back a vote. Returns None when the user hadn't voted.
+    pub async fn remove_helpful_vote(&self, id: &ObjectId, user_id: &ObjectId) -> Result<Option<Review>, String> {
+        let result = self.votes.delete_one(doc! { "review_id": id, "user_id": user_id }).await
+            .map_err(|e| format!("Failed to remove vote: {}", e))?;
+        if result.deleted_count == 0 {
+            return Ok(None);
+        }
+        self.change_helpful_count(id, -1).await
+    }
+
+    async fn change_helpful_count(&self, id: &ObjectId, change: i32) -> Result<Option<Review>, String> {
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection
+            .find_one_and_update(doc! { "_id": id }, doc! { "$inc": { "helpful_count": change } })
+            .with_options(options)
+            .await
+            .map_err(|e| format!("Failed to update review: {}", e))
+    }
+}
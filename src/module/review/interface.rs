@@ -0,0 +1,24 @@
+use crate::module::review::{
+    model::Review,
+    schema::{
+        CreateReviewSchema, ModerationQueueQuery, ProductReviewsResponse, ReviewListQuery,
+        ReviewListResponse, ReviewNoteSchema, ReviewResponse,
+    },
+};
+
+#[async_trait::async_trait]
+pub trait ReviewService {
+    // Shoppers
+    async fn get_product_reviews(&self, product_id: &str, query: ReviewListQuery) -> Result<ProductReviewsResponse, String>;
+    async fn create_review(&self, user_id: &str, author_name: &str, data: CreateReviewSchema) -> Result<Review, String>;
+    async fn get_user_reviews(&self, user_id: &str, query: ReviewListQuery) -> Result<ReviewListResponse, String>;
+    async fn delete_review(&self, user_id: &str, id: &str) -> Result<bool, String>;
+    async fn vote_helpful(&self, user_id: &str, id: &str) -> Result<ReviewResponse, String>;
+    async fn remove_helpful_vote(&self, user_id: &str, id: &str) -> Result<ReviewResponse, String>;
+
+    // Admin
+    async fn moderation_queue(&self, query: ModerationQueueQuery) -> Result<ReviewListResponse, String>;
+    async fn approve_review(&self, id: &str, data: ReviewNoteSchema, admin_id: &str) -> Result<Review, String>;
+    async fn reject_review(&self, id: &str, data: ReviewNoteSchema, admin_id: &str) -> Result<Review, String>;
+    async fn flag_review(&self, id: &str, data: ReviewNoteSchema, admin_id: &str) -> Result<Review, String>;
+}
@@ -0,0 +1,38 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("review", 1, "review and helpful vote indexes", "reviews", vec![
+            // One review per customer per product
+            IndexModel::builder()
+                .keys(doc! { "product_id": 1, "user_id": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            // A product's approved reviews in each sort order
+            IndexModel::builder()
+                .keys(doc! { "product_id": 1, "status": 1, "created_at": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "product_id": 1, "status": 1, "helpful_count": -1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "product_id": 1, "status": 1, "rating": -1 })
+                .build(),
+            // Moderation queue, oldest first
+            IndexModel::builder()
+                .keys(doc! { "status": 1, "created_at": 1 })
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "user_id": 1, "created_at": -1 })
+                .build(),
+        ]),
+        Migration::indexes("review", 2, "one helpful vote per user", "review_votes", vec![
+            IndexModel::builder()
+                .keys(doc! { "review_id": 1, "user_id": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+        ]),
+    ]
+}
@@ -0,0 +1,130 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Flagged,
+}
+
+impl ReviewStatus {
+    // New reviews wait for a moderator. Flagged ones are held back for a
+    // closer look, and any decision can be revisited later.
+    pub fn can_transition_to(&self, next: ReviewStatus) -> bool {
+        use ReviewStatus::*;
+        matches!(
+            (self, next),
+            (Pending, Approved)
+                | (Pending, Rejected)
+                | (Pending, Flagged)
+                | (Flagged, Approved)
+                | (Flagged, Rejected)
+                | (Approved, Rejected)
+                | (Approved, Flagged)
+                | (Rejected, Approved)
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewStatus::Pending => "pending",
+            ReviewStatus::Approved => "approved",
+            ReviewStatus::Rejected => "rejected",
+            ReviewStatus::Flagged => "flagged",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(ReviewStatus::Pending),
+            "approved" => Some(ReviewStatus::Approved),
+            "rejected" => Some(ReviewStatus::Rejected),
+            "flagged" => Some(ReviewStatus::Flagged),
+            _ => None,
+        }
+    }
+}
+
+// A customer's review of a product; only approved reviews are shown and
+// counted in the product's rating
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Review {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub product_id: ObjectId,
+    pub user_id: ObjectId,
+    // Shown with the review, e.g. "Ada L."
+    pub author_name: String,
+
+    pub rating: i32, // 1 to 5 stars
+    pub title: String,
+    pub body: String,
+    pub photos: Vec<String>, // Cloudinary URLs
+
+    // The reviewer has a delivered order containing the product
+    pub verified_purchase: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<ObjectId>,
+
+    pub status: ReviewStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moderation_note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moderated_by: Option<String>,
+
+    #[serde(default)]
+    pub helpful_count: i32,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Review {
+    pub fn new(
+        product_id: ObjectId,
+        user_id: ObjectId,
+        author_name: String,
+        rating: i32,
+        title: String,
+        body: String,
+        order_id: Option<ObjectId>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: None,
+            product_id,
+            user_id,
+            author_name,
+            rating,
+            title,
+            body,
+            photos: Vec::new(),
+            verified_purchase: order_id.is_some(),
+            order_id,
+            status: ReviewStatus::Pending,
+            moderation_note: None,
+            moderated_by: None,
+            helpful_count: 0,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+// One user marking one review as helpful
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HelpfulVote {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub review_id: ObjectId,
+    pub user_id: ObjectId,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
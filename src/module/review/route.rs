@@ -0,0 +1,248 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::Authentication;
+use crate::module::order::crud::OrderCrud;
+use crate::module::product::crud::ProductCrud;
+use crate::module::review::{
+    controller::ReviewController,
+    crud::ReviewCrud,
+    interface::ReviewService,
+    model::Review,
+    schema::{CreateReviewSchema, ModerationQueueQuery, ReviewListQuery, ReviewNoteSchema},
+};
+
+fn controller(
+    crud: &web::Data<ReviewCrud>,
+    product_crud: &web::Data<ProductCrud>,
+    order_crud: &web::Data<OrderCrud>,
+) -> ReviewController {
+    ReviewController::new(crud.get_ref().clone(), product_crud.get_ref().clone(), order_crud.get_ref().clone())
+}
+
+// The admin middleware already checked the token; this only reads who it belongs to
+fn admin_id(req: &HttpRequest) -> String {
+    Authentication::optional_user(req)
+        .map(|claims| claims.sub)
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn moderation_response(result: std::result::Result<Review, String>) -> HttpResponse {
+    match result {
+        Ok(review) => HttpResponse::Ok().json(review),
+        Err(error) => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        })),
+    }
+}
+
+// GET a product's approved reviews handler
+async fn get_product_reviews_handler(
+    product_id: web::Path<String>,
+    query: web::Query<ReviewListQuery>,
+    crud: web::Data<ReviewCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud, &order_crud).get_product_reviews(&product_id, query.into_inner()).await {
+        Ok(reviews) => Ok(HttpResponse::Ok().json(reviews)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST review handler
+async fn create_review_handler(
+    req: HttpRequest,
+    body: web::Json<CreateReviewSchema>,
+    crud: web::Data<ReviewCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+    // First name and last initial, e.g. "Ada L."
+    let author_name = match claims.last_name.chars().next() {
+        Some(initial) => format!("{} {}.", claims.first_name, initial),
+        None => claims.first_name.clone(),
+    };
+
+    match controller(&crud, &product_crud, &order_crud).create_review(&claims.sub, &author_name, body.into_inner()).await {
+        Ok(review) => Ok(HttpResponse::Created().json(review)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET user's reviews handler
+async fn get_my_reviews_handler(
+    req: HttpRequest,
+    query: web::Query<ReviewListQuery>,
+    crud: web::Data<ReviewCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &order_crud).get_user_reviews(&claims.sub, query.into_inner()).await {
+        Ok(reviews) => Ok(HttpResponse::Ok().json(reviews)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE own review handler
+async fn delete_review_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    crud: web::Data<ReviewCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &order_crud).delete_review(&claims.sub, &id).await {
+        Ok(true) => Ok(HttpResponse::NoContent().finish()),
+        Ok(false) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Review not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST helpful vote handler
+async fn vote_helpful_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    crud: web::Data<ReviewCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &order_crud).vote_helpful(&claims.sub, &id).await {
+        Ok(review) => Ok(HttpResponse::Ok().json(review)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE helpful vote handler
+async fn remove_helpful_vote_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    crud: web::Data<ReviewCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &order_crud).remove_helpful_vote(&claims.sub, &id).await {
+        Ok(review) => Ok(HttpResponse::Ok().json(review)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET moderation queue handler (admin)
+async fn admin_queue_handler(
+    query: web::Query<ModerationQueueQuery>,
+    crud: web::Data<ReviewCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud, &order_crud).moderation_queue(query.into_inner()).await {
+        Ok(reviews) => Ok(HttpResponse::Ok().json(reviews)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST approve review handler (admin)
+async fn admin_approve_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<ReviewNoteSchema>,
+    crud: web::Data<ReviewCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let result = controller(&crud, &product_crud, &order_crud)
+        .approve_review(&id, body.into_inner(), &admin_id(&req))
+        .await;
+    Ok(moderation_response(result))
+}
+
+// POST reject review handler (admin)
+async fn admin_reject_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<ReviewNoteSchema>,
+    crud: web::Data<ReviewCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let result = controller(&crud, &product_crud, &order_crud)
+        .reject_review(&id, body.into_inner(), &admin_id(&req))
+        .await;
+    Ok(moderation_response(result))
+}
+
+// POST flag review handler (admin)
+async fn admin_flag_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    body: web::Json<ReviewNoteSchema>,
+    crud: web::Data<ReviewCrud>,
+    product_crud: web::Data<ProductCrud>,
+    order_crud: web::Data<OrderCrud>,
+) -> Result<HttpResponse> {
+    let result = controller(&crud, &product_crud, &order_crud)
+        .flag_review(&id, body.into_inner(), &admin_id(&req))
+        .await;
+    Ok(moderation_response(result))
+}
+
+// Route configuration function
+pub fn review_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/reviews")
+            .route("", web::post().to(create_review_handler))
+            .route("/mine", web::get().to(get_my_reviews_handler))
+            .route("/product/{product_id}", web::get().to(get_product_reviews_handler))
+            .route("/{id}", web::delete().to(delete_review_handler))
+            .route("/{id}/helpful", web::post().to(vote_helpful_handler))
+            .route("/{id}/helpful", web::delete().to(remove_helpful_vote_handler))
+    );
+}
+
+// Admin routes, mounted inside the admin-authenticated `/api/admin` scope
+pub fn admin_review_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/reviews")
+            .route("", web::get().to(admin_queue_handler))
+            .route("/{id}/approve", web::post().to(admin_approve_handler))
+            .route("/{id}/reject", web::post().to(admin_reject_handler))
+            .route("/{id}/flag", web::post().to(admin_flag_handler))
+    );
+}
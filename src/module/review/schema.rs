@@ -0,0 +1,106 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::module::product::model::ProductRating;
+use crate::module::review::model::Review;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateReviewSchema {
+    pub product_id: String,
+
+    #[validate(range(min = 1, max = 5, message = "Rating must be between 1 and 5 stars"))]
+    pub rating: i32,
+
+    #[validate(length(min = 1, max = 150))]
+    pub title: String,
+
+    #[validate(length(min = 10, max = 5000, message = "Please write 10 to 5000 characters"))]
+    pub body: String,
+
+    // Base64 encoded images
+    #[serde(default)]
+    #[validate(length(max = 5, message = "At most 5 photos can be attached"))]
+    pub photos: Vec<String>,
+}
+
+// Approve, reject or flag
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ReviewNoteSchema {
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+// Query string for a product's reviews
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewListQuery {
+    pub page: Option<u64>,
+    pub limit: Option<i64>,
+    // newest (default), helpful, highest or lowest
+    pub sort: Option<String>,
+    // Only reviews with this many stars
+    pub rating: Option<i32>,
+}
+
+// Query string for the moderation queue
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModerationQueueQuery {
+    pub page: Option<u64>,
+    pub limit: Option<i64>,
+    // Defaults to pending
+    pub status: Option<String>,
+    pub product_id: Option<String>,
+}
+
+// A review as shown to shoppers, without who moderated it or which order it
+// was verified against
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewResponse {
+    #[serde(rename = "_id")]
+    pub id: Option<ObjectId>,
+    pub product_id: ObjectId,
+    pub author_name: String,
+    pub rating: i32,
+    pub title: String,
+    pub body: String,
+    pub photos: Vec<String>,
+    pub verified_purchase: bool,
+    pub helpful_count: i32,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Review> for ReviewResponse {
+    fn from(review: Review) -> Self {
+        Self {
+            id: review.id,
+            product_id: review.product_id,
+            author_name: review.author_name,
+            rating: review.rating,
+            title: review.title,
+            body: review.body,
+            photos: review.photos,
+            verified_purchase: review.verified_purchase,
+            helpful_count: review.helpful_count,
+            created_at: review.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductReviewsResponse {
+    pub rating: ProductRating,
+    pub reviews: Vec<ReviewResponse>,
+    pub total: u64,
+    pub page: u64,
+    pub limit: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewListResponse {
+    pub reviews: Vec<Review>,
+    pub total: u64,
+    pub page: u64,
+    pub limit: i64,
+}
@@ -0,0 +1,171 @@
+use bson::{doc, oid::ObjectId};
+use validator::Validate;
+use crate::module::search::{
+    crud::SearchCrud,
+    interface::SearchService,
+    schema::{SearchHighlights, SearchHit, SearchQuery, SearchResponse},
+};
+use crate::service::pagination_service::paginate;
+
+// Words either side of the first match in a description snippet
+const SNIPPET_WORDS_BEFORE: usize = 8;
+const SNIPPET_WORDS_AFTER: usize = 24;
+
+pub struct SearchController {
+    crud: SearchCrud,
+}
+
+impl SearchController {
+    pub fn new(crud: SearchCrud) -> Self {
+        Self { crud }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    // Lowercased search words, without the quotes and minus signs of Mongo's
+    // text search syntax. Excluded words are dropped.
+    fn terms(text: &str) -> Vec<String> {
+        text.split_whitespace()
+            .filter(|word| !word.starts_with('-'))
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+            .filter(|word| word.chars().count() >= 2)
+            .collect()
+    }
+
+    // Split text into runs of word and non-word characters
+    fn tokens(text: &str) -> Vec<&str> {
+        let mut tokens = Vec::new();
+        let mut start = 0;
+        let mut in_word = None;
+        for (index, c) in text.char_indices() {
+            let is_word = c.is_alphanumeric();
+            if in_word.is_some_and(|in_word| in_word != is_word) {
+                tokens.push(&text[start..index]);
+                start = index;
+            }
+            in_word = Some(is_word);
+        }
+        if start < text.len() {
+            tokens.push(&text[start..]);
+        }
+        tokens
+    }
+
+    // Text search stems words, so "phones" finds "phone". Prefix matching in
+    // either direction marks roughly the same words.
+    fn matches(token: &str, terms: &[String]) -> bool {
+        let token = token.to_lowercase();
+        token.chars().next().is_some_and(char::is_alphanumeric)
+            && terms.iter().any(|term| token.starts_with(term.as_str()) || (token.chars().count() >= 3 && term.starts_with(&token)))
+    }
+
+    fn escape_html(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn mark(tokens: &[&str], terms: &[String]) -> String {
+        tokens.iter()
+            .map(|token| {
+                if Self::matches(token, terms) {
+                    format!("<mark>{}</mark>", Self::escape_html(token))
+                } else {
+                    Self::escape_html(token)
+                }
+            })
+            .collect()
+    }
+
+    fn highlight(text: &str, terms: &[String]) -> String {
+        Self::mark(&Self::tokens(text), terms)
+    }
+
+    // A few words around the first match, or the start of the text when only
+    // other fields matched
+    fn snippet(text: &str, terms: &[String]) -> String {
+        let tokens = Self::tokens(text);
+        let word_positions: Vec<usize> = tokens.iter()
+            .enumerate()
+            .filter(|(_, token)| token.chars().next().is_some_and(char::is_alphanumeric))
+            .map(|(index, _)| index)
+            .collect();
+        let first_match = word_positions.iter()
+            .position(|index| Self::matches(tokens[*index], terms))
+            .unwrap_or(0);
+
+        let first_word = first_match.saturating_sub(SNIPPET_WORDS_BEFORE);
+        let last_word = (first_match + SNIPPET_WORDS_AFTER).min(word_positions.len().saturating_sub(1));
+        let (start, end) = match (word_positions.get(first_word), word_positions.get(last_word)) {
+            (Some(start), Some(end)) => (*start, *end + 1),
+            _ => return String::new(),
+        };
+
+        let mut snippet = Self::mark(&tokens[start..end], terms);
+        if start > 0 {
+            snippet.insert_str(0, "… ");
+        }
+        if end < tokens.len() {
+            snippet.push_str(" …");
+        }
+        snippet
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchService for SearchController {
+    async fn search(&self, query: SearchQuery) -> Result<SearchResponse, String> {
+        if let Err(e) = query.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+        let text = query.q.trim().to_string();
+        let terms = Self::terms(&text);
+        if terms.is_empty() {
+            return Err("Search for at least one word of two or more letters".to_string());
+        }
+
+        let (page, limit, skip) = paginate(query.page, query.limit);
+
+        let mut filter = doc! { "is_active": true };
+        if let Some(category_id) = &query.category_id {
+            filter.insert("category_id", Self::parse_id(category_id, "category")?);
+        }
+        if let Some(brand_id) = &query.brand_id {
+            filter.insert("brand_id", Self::parse_id(brand_id, "brand")?);
+        }
+        if query.in_stock == Some(true) {
+            filter.insert("stock_quantity", doc! { "$gt": 0 });
+        }
+
+        if let (Some(min_price), Some(max_price)) = (query.min_price, query.max_price)
+            && min_price > max_price {
+            return Err("min_price can't be more than max_price".to_string());
+        }
+        let mut price_range = doc! {};
+        if let Some(min_price) = query.min_price {
+            price_range.insert("$gte", min_price);
+        }
+        if let Some(max_price) = query.max_price {
+            price_range.insert("$lte", max_price);
+        }
+
+        let (hits, total, facets) = self.crud
+            .search(&text, filter, price_range, skip, limit)
+            .await?;
+
+        let results = hits.into_iter()
+            .map(|(product, score)| {
+                let highlights = SearchHighlights {
+                    name: Self::highlight(&product.name, &terms),
+                    snippet: Self::snippet(&product.description, &terms),
+                };
+                SearchHit { product, score, highlights }
+            })
+            .collect();
+
+        Ok(SearchResponse { query: text, results, total, page, limit, facets })
+    }
+}
@@ -0,0 +1,145 @@
+use bson::{doc, Bson, Document};
+use mongodb::{Collection, Database};
+use futures_util::TryStreamExt;
+use crate::module::product::model::Product;
+use crate::module::search::schema::{AvailabilityFacet, FacetCount, PriceBucket, SearchFacets};
+
+// Lower bounds of the price facet buckets in kobo: ₦0, ₦10k, ₦25k, ₦50k, ₦100k, ₦250k and ₦500k+
+const PRICE_BUCKET_BOUNDARIES: [i64; 7] = [0, 1_000_000, 2_500_000, 5_000_000, 10_000_000, 25_000_000, 50_000_000];
+
+fn count(value: Option<&Bson>) -> u64 {
+    match value {
+        Some(Bson::Int32(value)) => *value as u64,
+        Some(Bson::Int64(value)) => *value as u64,
+        Some(Bson::Double(value)) => *value as u64,
+        _ => 0,
+    }
+}
+
+fn facet<'a>(result: &'a Document, name: &str) -> impl Iterator<Item = &'a Document> {
+    result.get_array(name)
+        .map(|items| items.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(Bson::as_document)
+}
+
+#[derive(Clone)]
+pub struct SearchCrud {
+    products: Collection<Document>,
+}
+
+impl SearchCrud {
+    pub fn new(db: &Database) -> Self {
+        Self { products: db.collection("products") }
+    }
+
+    // Matches ranked by text score, and facet counts over all matches, in one aggregation
+    pub async fn search(
+        &self,
+        text: &str,
+        mut filter: Document,
+        price_range: Document,
+        skip: u64,
+        limit: i64,
+    ) -> Result<(Vec<(Product, f64)>, u64, SearchFacets), String> {
+        filter.insert("$text", doc! { "$search": text });
+
+        let mut pipeline = vec![
+            doc! { "$match": filter },
+            doc! { "$addFields": {
+                "score": { "$meta": "textScore" },
+                "display_price": { "$ifNull": ["$sale_price", "$price"] },
+            } },
+        ];
+        if !price_range.is_empty() {
+            pipeline.push(doc! { "$match": { "display_price": price_range } });
+        }
+        pipeline.push(doc! { "$facet": {
+            "total": [{ "$count": "count" }],
+            "results": [
+                { "$sort": { "score": -1, "sold_count": -1, "_id": 1 } },
+                { "$skip": skip as i64 },
+                { "$limit": limit },
+            ],
+            "categories": [
+                { "$group": { "_id": "$category_id", "count": { "$sum": 1 } } },
+                { "$sort": { "count": -1, "_id": 1 } },
+                { "$lookup": { "from": "categories", "localField": "_id", "foreignField": "_id", "as": "category" } },
+                { "$project": { "count": 1, "name": { "$arrayElemAt": ["$category.name", 0] } } },
+            ],
+            "brands": [
+                { "$group": { "_id": "$brand_id", "name": { "$first": "$brand_name" }, "count": { "$sum": 1 } } },
+                { "$sort": { "count": -1, "_id": 1 } },
+            ],
+            "price_ranges": [
+                { "$bucket": {
+                    "groupBy": "$display_price",
+                    "boundaries": PRICE_BUCKET_BOUNDARIES.iter().copied().chain([i64::MAX]).collect::<Vec<i64>>(),
+                    "default": "other",
+                    "output": { "count": { "$sum": 1 } },
+                } },
+            ],
+            "availability": [
+                { "$group": { "_id": { "$gt": ["$stock_quantity", 0] }, "count": { "$sum": 1 } } },
+            ],
+        } });
+
+        let result = match self.products.aggregate(pipeline).await {
+            Ok(mut cursor) => cursor.try_next().await
+                .map_err(|e| format!("Failed to search products: {}", e))?
+                .unwrap_or_default(),
+            Err(e) => return Err(format!("Failed to search products: {}", e)),
+        };
+
+        let total = facet(&result, "total").next()
+            .map(|total| count(total.get("count")))
+            .unwrap_or(0);
+
+        let mut hits = Vec::new();
+        for document in facet(&result, "results") {
+            let score = document.get_f64("score").unwrap_or(0.0);
+            let product: Product = bson::from_document(document.clone())
+                .map_err(|e| format!("Failed to decode product: {}", e))?;
+            hits.push((product, score));
+        }
+
+        let named_counts = |name: &str| -> Vec<FacetCount> {
+            facet(&result, name)
+                .filter_map(|item| Some(FacetCount {
+                    id: item.get_object_id("_id").ok()?.to_hex(),
+                    name: item.get_str("name").unwrap_or_default().to_string(),
+                    count: count(item.get("count")),
+                }))
+                .collect()
+        };
+
+        let price_ranges = facet(&result, "price_ranges")
+            .filter_map(|bucket| {
+                let min = match bucket.get("_id")? {
+                    Bson::Int64(min) => *min,
+                    Bson::Int32(min) => *min as i64,
+                    _ => return None,
+                };
+                let max = PRICE_BUCKET_BOUNDARIES.iter().copied().find(|boundary| *boundary > min);
+                Some(PriceBucket { min, max, count: count(bucket.get("count")) })
+            })
+            .collect();
+
+        let mut availability = AvailabilityFacet::default();
+        for group in facet(&result, "availability") {
+            match group.get_bool("_id") {
+                Ok(true) => availability.in_stock = count(group.get("count")),
+                _ => availability.out_of_stock += count(group.get("count")),
+            }
+        }
+
+        let facets = SearchFacets {
+            categories: named_counts("categories"),
+            brands: named_counts("brands"),
+            price_ranges,
+            availability,
+        };
+        Ok((hits, total, facets))
+    }
+}
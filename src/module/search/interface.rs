@@ -0,0 +1,6 @@
+use crate::module::search::schema::{SearchQuery, SearchResponse};
+
+#[async_trait::async_trait]
+pub trait SearchService {
+    async fn search(&self, query: SearchQuery) -> Result<SearchResponse, String>;
+}
@@ -0,0 +1,18 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        // A collection can only have one text index, so every searchable field is in it
+        Migration::indexes("search", 1, "product text search index", "products", vec![
+            IndexModel::builder()
+                .keys(doc! { "name": "text", "sku": "text", "brand_name": "text", "description": "text" })
+                .options(IndexOptions::builder()
+                    .name("product_text_search".to_string())
+                    .weights(doc! { "name": 10, "sku": 8, "brand_name": 5, "description": 1 })
+                    .build())
+                .build(),
+        ]),
+    ]
+}
@@ -0,0 +1,6 @@
+pub mod controller;
+pub mod crud;
+pub mod interface;
+pub mod migration;
+pub mod route;
+pub mod schema;
@@ -0,0 +1,26 @@
+use actix_web::{web, HttpResponse, Result};
+use crate::module::search::{
+    controller::SearchController,
+    crud::SearchCrud,
+    interface::SearchService,
+    schema::SearchQuery,
+};
+
+// GET product search handler
+async fn search_handler(
+    query: web::Query<SearchQuery>,
+    crud: web::Data<SearchCrud>,
+) -> Result<HttpResponse> {
+    let controller = SearchController::new(crud.get_ref().clone());
+
+    match controller.search(query.into_inner()).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(response)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+pub fn search_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/search", web::get().to(search_handler));
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::module::product::model::Product;
+
+// Query string for GET /api/search. Prices are in kobo and compare against
+// the price a customer pays, i.e. the sale price when there is one.
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct SearchQuery {
+    #[validate(length(min = 1, max = 100))]
+    pub q: String,
+    
+    pub category_id: Option<String>,
+    pub brand_id: Option<String>,
+    pub min_price: Option<i64>,
+    pub max_price: Option<i64>,
+    pub in_stock: Option<bool>,
+    
+    pub page: Option<u64>,
+    pub limit: Option<i64>,
+}
+
+// Name and description extract with matched words wrapped in <mark> tags.
+// Both are HTML-escaped.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchHighlights {
+    pub name: String,
+    pub snippet: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchHit {
+    pub product: Product,
+    pub score: f64,
+    pub highlights: SearchHighlights,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FacetCount {
+    pub id: String,
+    pub name: String,
+    pub count: u64,
+}
+
+// `max` is exclusive; the top bucket has none
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceBucket {
+    pub min: i64,
+    pub max: Option<i64>,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AvailabilityFacet {
+    pub in_stock: u64,
+    pub out_of_stock: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SearchFacets {
+    pub categories: Vec<FacetCount>,
+    pub brands: Vec<FacetCount>,
+    pub price_ranges: Vec<PriceBucket>,
+    pub availability: AvailabilityFacet,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchResponse {
+    pub query: String,
+    pub results: Vec<SearchHit>,
+    pub total: u64,
+    pub page: u64,
+    pub limit: i64,
+    // Counts over every match, not just this page
+    pub facets: SearchFacets,
+}
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use bson::oid::ObjectId;
+use chrono::{Days, Utc};
+use validator::Validate;
+use crate::module::product::{crud::ProductCrud, model::Product};
+use crate::module::shipping::{
+    crud::ShippingCrud,
+    interface::ShippingService,
+    model::{normalize_state, DeliveryService, ShippingZone},
+    schema::{DeliveryOption, ShippingQuote, ShippingQuoteSchema, ShippingZoneSchema},
+};
+
+pub struct ShippingController {
+    crud: ShippingCrud,
+    product_crud: ProductCrud,
+}
+
+impl ShippingController {
+    pub fn new(crud: ShippingCrud, product_crud: ProductCrud) -> Self {
+        Self { crud, product_crud }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    // Validate the admin's input and build the zone it describes
+    fn build_zone(data: ShippingZoneSchema) -> Result<ShippingZone, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let mut states: Vec<String> = Vec::new();
+        for state in &data.states {
+            let state = normalize_state(state)
+                .ok_or_else(|| format!("Unknown state: {}", state))?;
+            if !states.iter().any(|known| known == state) {
+                states.push(state.to_string());
+            }
+        }
+
+        let mut cities: Vec<String> = Vec::new();
+        for city in &data.cities {
+            let city = city.trim();
+            if city.is_empty() {
+                return Err("City names cannot be empty".to_string());
+            }
+            if !cities.iter().any(|known| known.eq_ignore_ascii_case(city)) {
+                cities.push(city.to_string());
+            }
+        }
+
+        let services: Vec<DeliveryService> = data.services.into_iter().map(Into::into).collect();
+        for (index, service) in services.iter().enumerate() {
+            if services[..index].iter().any(|other| other.code == service.code) {
+                return Err(format!("Delivery service {} is listed twice", service.code));
+            }
+            if service.eta_min_days > service.eta_max_days {
+                return Err(format!("{}: minimum delivery days exceed the maximum", service.name));
+            }
+            let open_ended = service.bands.iter().filter(|band| band.max_weight_grams.is_none()).count();
+            let mut limits: Vec<i32> = service.bands.iter().filter_map(|band| band.max_weight_grams).collect();
+            limits.dedup();
+            if open_ended > 1 || limits.len() + open_ended != service.bands.len() {
+                return Err(format!("{}: weight bands must have distinct upper limits", service.name));
+            }
+        }
+
+        let now = Utc::now();
+        Ok(ShippingZone {
+            id: None,
+            name: data.name.trim().to_string(),
+            states,
+            cities,
+            services,
+            is_active: data.is_active,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    // A state can have one state wide zone, plus city zones that don't overlap
+    async fn check_overlap(&self, zone: &ShippingZone) -> Result<(), String> {
+        if !zone.is_active {
+            return Ok(());
+        }
+
+        for state in &zone.states {
+            let others = self.crud.get_zones_for_state(state).await?;
+            for other in others.iter().filter(|other| other.id != zone.id) {
+                let overlaps = if zone.cities.is_empty() {
+                    other.cities.is_empty()
+                } else {
+                    zone.cities.iter().any(|city| other.covers_city(city))
+                };
+                if overlaps {
+                    return Err(format!("{} already covers part of {}", other.name, state));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // The city zone for the destination if there is one, otherwise the state wide zone
+    async fn find_zone(&self, state: &str, city: Option<&str>) -> Result<ShippingZone, String> {
+        let zones = self.crud.get_zones_for_state(state).await?;
+
+        let city_zone = city.and_then(|city| {
+            zones.iter().find(|zone| !zone.cities.is_empty() && zone.covers_city(city))
+        });
+        city_zone
+            .or_else(|| zones.iter().find(|zone| zone.cities.is_empty()))
+            .cloned()
+            .ok_or_else(|| format!("We do not deliver to {} yet", city.unwrap_or(state)))
+    }
+
+    // Price delivery of the given (product, variant, quantity) lines to a state
+    // and city. Checkout calls this directly with the order's lines.
+    pub async fn quote_items(
+        &self,
+        state: &str,
+        city: Option<&str>,
+        quantities: &[(ObjectId, Option<ObjectId>, i32)],
+    ) -> Result<ShippingQuote, String> {
+        let state = normalize_state(state)
+            .ok_or_else(|| format!("Unknown state: {}", state))?;
+        let zone = self.find_zone(state, city).await?;
+
+        let ids: Vec<ObjectId> = quantities.iter().map(|(id, _, _)| *id).collect();
+        let products: HashMap<ObjectId, Product> = self.product_crud.get_products_by_ids(&ids).await?
+            .into_iter()
+            .filter_map(|product| product.id.map(|id| (id, product)))
+            .collect();
+
+        let mut subtotal = 0_i64;
+        let mut weight_grams = 0_i32;
+        for &(product_id, variant_id, quantity) in quantities {
+            let product = products.get(&product_id)
+                .filter(|product| product.is_active)
+                .ok_or_else(|| format!("Product {} is not available", product_id.to_hex()))?;
+            // Variants share the product's weight and box size
+            subtotal += product.purchase_option(variant_id.as_ref())?.unit_price() * quantity as i64;
+            weight_grams = weight_grams.saturating_add(product.shipping_weight_grams().saturating_mul(quantity));
+        }
+
+        let today = Utc::now().date_naive();
+        let options: Vec<DeliveryOption> = zone.services.iter()
+            .filter_map(|service| {
+                // Services with no band for this weight can't carry the parcel
+                let price = service.price_for(weight_grams)?;
+                let is_free = service.free_shipping_threshold.is_some_and(|threshold| subtotal >= threshold);
+                Some(DeliveryOption {
+                    code: service.code.clone(),
+                    name: service.name.clone(),
+                    price: if is_free { 0 } else { price },
+                    is_free,
+                    eta_min_days: service.eta_min_days,
+                    eta_max_days: service.eta_max_days,
+                    estimated_delivery_from: today + Days::new(service.eta_min_days as u64),
+                    estimated_delivery_to: today + Days::new(service.eta_max_days as u64),
+                })
+            })
+            .collect();
+
+        if options.is_empty() {
+            return Err(format!("No delivery option to {} can carry a {}kg parcel", zone.name, weight_grams as f64 / 1000.0));
+        }
+
+        Ok(ShippingQuote {
+            zone_id: zone.id.map(|id| id.to_hex()).unwrap_or_default(),
+            zone_name: zone.name,
+            state: state.to_string(),
+            subtotal,
+            weight_grams,
+            options,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ShippingService for ShippingController {
+    async fn quote(&self, data: ShippingQuoteSchema) -> Result<ShippingQuote, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let mut quantities: Vec<(ObjectId, Option<ObjectId>, i32)> = Vec::new();
+        for item in data.items {
+            let product_id = Self::parse_id(&item.product_id, "product")?;
+            let variant_id = item.variant_id.as_deref()
+                .map(|id| Self::parse_id(id, "variant"))
+                .transpose()?;
+            match quantities.iter_mut().find(|(id, variant, _)| *id == product_id && *variant == variant_id) {
+                Some((_, _, quantity)) => *quantity += item.quantity,
+                None => quantities.push((product_id, variant_id, item.quantity)),
+            }
+        }
+
+        self.quote_items(&data.state, data.city.as_deref(), &quantities).await
+    }
+
+    async fn create_zone(&self, data: ShippingZoneSchema) -> Result<ShippingZone, String> {
+        let zone = Self::build_zone(data)?;
+        self.check_overlap(&zone).await?;
+        self.crud.create_zone(zone).await
+    }
+
+    async fn get_zones(&self) -> Result<Vec<ShippingZone>, String> {
+        self.crud.get_zones().await
+    }
+
+    async fn get_zone(&self, id: &str) -> Result<Option<ShippingZone>, String> {
+        let oid = Self::parse_id(id, "shipping zone")?;
+        self.crud.get_zone(&oid).await
+    }
+
+    async fn update_zone(&self, id: &str, data: ShippingZoneSchema) -> Result<Option<ShippingZone>, String> {
+        let oid = Self::parse_id(id, "shipping zone")?;
+        let existing = match self.crud.get_zone(&oid).await? {
+            Some(zone) => zone,
+            None => return Ok(None),
+        };
+
+        let mut zone = Self::build_zone(data)?;
+        zone.id = Some(oid);
+        zone.created_at = existing.created_at;
+        self.check_overlap(&zone).await?;
+
+        if self.crud.replace_zone(&zone).await? {
+            Ok(Some(zone))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn delete_zone(&self, id: &str) -> Result<bool, String> {
+        let oid = Self::parse_id(id, "shipping zone")?;
+        self.crud.delete_zone(&oid).await
+    }
+}
@@ -0,0 +1,75 @@
+use bson::{doc, oid::ObjectId};
+use mongodb::{options::FindOptions, Collection, Database};
+use futures_util::TryStreamExt;
+use crate::module::shipping::model::ShippingZone;
+
+#[derive(Clone)]
+pub struct ShippingCrud {
+    collection: Collection<ShippingZone>,
+}
+
+impl ShippingCrud {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("shipping_zones");
+        Self { collection }
+    }
+
+    pub async fn create_zone(&self, mut zone: ShippingZone) -> Result<ShippingZone, String> {
+        match self.collection.insert_one(&zone).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    zone.id = Some(id);
+                }
+                Ok(zone)
+            }
+            Err(e) => Err(format!("Failed to create shipping zone: {}", e))
+        }
+    }
+
+    pub async fn get_zone(&self, id: &ObjectId) -> Result<Option<ShippingZone>, String> {
+        match self.collection.find_one(doc! { "_id": id }).await {
+            Ok(zone) => Ok(zone),
+            Err(e) => Err(format!("Failed to retrieve shipping zone: {}", e))
+        }
+    }
+
+    pub async fn get_zones(&self) -> Result<Vec<ShippingZone>, String> {
+        let options = FindOptions::builder().sort(doc! { "name": 1 }).build();
+        self.find_zones(doc! {}, options).await
+    }
+
+    // Active zones covering a state, city specific and state wide alike
+    pub async fn get_zones_for_state(&self, state: &str) -> Result<Vec<ShippingZone>, String> {
+        let options = FindOptions::builder().sort(doc! { "created_at": 1 }).build();
+        self.find_zones(doc! { "states": state, "is_active": true }, options).await
+    }
+
+    async fn find_zones(&self, filter: bson::Document, options: FindOptions) -> Result<Vec<ShippingZone>, String> {
+        match self.collection.find(filter).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut zones = Vec::new();
+                while let Some(zone) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve shipping zones: {}", e))? {
+                    zones.push(zone);
+                }
+                Ok(zones)
+            }
+            Err(e) => Err(format!("Failed to retrieve shipping zones: {}", e))
+        }
+    }
+
+    pub async fn replace_zone(&self, zone: &ShippingZone) -> Result<bool, String> {
+        let id = zone.id.ok_or_else(|| "Shipping zone has no ID".to_string())?;
+        match self.collection.replace_one(doc! { "_id": id }, zone).await {
+            Ok(result) => Ok(result.matched_count == 1),
+            Err(e) => Err(format!("Failed to update shipping zone: {}", e))
+        }
+    }
+
+    pub async fn delete_zone(&self, id: &ObjectId) -> Result<bool, String> {
+        match self.collection.delete_one(doc! { "_id": id }).await {
+            Ok(result) => Ok(result.deleted_count == 1),
+            Err(e) => Err(format!("Failed to delete shipping zone: {}", e))
+        }
+    }
+}
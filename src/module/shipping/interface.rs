@@ -0,0 +1,17 @@
+use crate::module::shipping::{
+    model::ShippingZone,
+    schema::{ShippingQuote, ShippingQuoteSchema, ShippingZoneSchema},
+};
+
+#[async_trait::async_trait]
+pub trait ShippingService {
+    // Customer
+    async fn quote(&self, data: ShippingQuoteSchema) -> Result<ShippingQuote, String>;
+
+    // Admin
+    async fn create_zone(&self, data: ShippingZoneSchema) -> Result<ShippingZone, String>;
+    async fn get_zones(&self) -> Result<Vec<ShippingZone>, String>;
+    async fn get_zone(&self, id: &str) -> Result<Option<ShippingZone>, String>;
+    async fn update_zone(&self, id: &str, data: ShippingZoneSchema) -> Result<Option<ShippingZone>, String>;
+    async fn delete_zone(&self, id: &str) -> Result<bool, String>;
+}
@@ -0,0 +1,13 @@
+use bson::doc;
+use mongodb::IndexModel;
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("shipping", 1, "shipping zone state index", "shipping_zones", vec![
+            IndexModel::builder()
+                .keys(doc! { "states": 1, "is_active": 1 })
+                .build(),
+        ]),
+    ]
+}
@@ -0,0 +1,85 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// The 36 states and the FCT, as customers write them on addresses
+pub const NIGERIAN_STATES: [&str; 37] = [
+    "Abia", "Adamawa", "Akwa Ibom", "Anambra", "Bauchi", "Bayelsa", "Benue", "Borno",
+    "Cross River", "Delta", "Ebonyi", "Edo", "Ekiti", "Enugu", "FCT", "Gombe", "Imo",
+    "Jigawa", "Kaduna", "Kano", "Katsina", "Kebbi", "Kogi", "Kwara", "Lagos", "Nasarawa",
+    "Niger", "Ogun", "Ondo", "Osun", "Oyo", "Plateau", "Rivers", "Sokoto", "Taraba",
+    "Yobe", "Zamfara",
+];
+
+// Matches "lagos", "LAGOS" or "Lagos State" to "Lagos", and "Abuja" to "FCT"
+pub fn normalize_state(state: &str) -> Option<&'static str> {
+    let state = state.trim().to_lowercase();
+    let state = state.strip_suffix(" state").unwrap_or(&state).trim();
+    if state == "abuja" || state == "federal capital territory" {
+        return Some("FCT");
+    }
+    NIGERIAN_STATES.iter().copied().find(|known| known.to_lowercase() == state)
+}
+
+// Price for parcels up to `max_weight_grams`; the last band may be open ended
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WeightBand {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_weight_grams: Option<i32>,
+    pub price: i64, // In kobo
+}
+
+// A delivery option offered in a zone, e.g. standard or express
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeliveryService {
+    pub code: String,
+    pub name: String,
+    pub eta_min_days: u32,
+    pub eta_max_days: u32,
+
+    // Orders worth at least this much (in kobo) ship free with this service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub free_shipping_threshold: Option<i64>,
+
+    // Sorted by weight, lightest first
+    pub bands: Vec<WeightBand>,
+}
+
+impl DeliveryService {
+    // None when the parcel is heavier than the heaviest band
+    pub fn price_for(&self, weight_grams: i32) -> Option<i64> {
+        self.bands.iter()
+            .find(|band| band.max_weight_grams.is_none_or(|max| weight_grams <= max))
+            .map(|band| band.price)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShippingZone {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub name: String,
+
+    // States covered by the zone
+    pub states: Vec<String>,
+    // Narrows the zone to these cities or areas within its states,
+    // e.g. Lagos Island inside Lagos. Empty covers the whole state.
+    #[serde(default)]
+    pub cities: Vec<String>,
+
+    pub services: Vec<DeliveryService>,
+    pub is_active: bool,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ShippingZone {
+    pub fn covers_city(&self, city: &str) -> bool {
+        let city = city.trim().to_lowercase();
+        self.cities.iter().any(|zone_city| zone_city.to_lowercase() == city)
+    }
+}
@@ -0,0 +1,125 @@
+use actix_web::{web, HttpResponse, Result};
+use crate::module::product::crud::ProductCrud;
+use crate::module::shipping::{
+    controller::ShippingController,
+    crud::ShippingCrud,
+    interface::ShippingService,
+    schema::{ShippingQuoteSchema, ShippingZoneSchema},
+};
+
+fn controller(crud: &web::Data<ShippingCrud>, product_crud: &web::Data<ProductCrud>) -> ShippingController {
+    ShippingController::new(crud.get_ref().clone(), product_crud.get_ref().clone())
+}
+
+// POST delivery quote handler
+async fn quote_handler(
+    body: web::Json<ShippingQuoteSchema>,
+    crud: web::Data<ShippingCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).quote(body.into_inner()).await {
+        Ok(quote) => Ok(HttpResponse::Ok().json(quote)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST shipping zone handler (admin)
+async fn create_zone_handler(
+    body: web::Json<ShippingZoneSchema>,
+    crud: web::Data<ShippingCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).create_zone(body.into_inner()).await {
+        Ok(zone) => Ok(HttpResponse::Created().json(zone)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET all shipping zones handler (admin)
+async fn get_zones_handler(
+    crud: web::Data<ShippingCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).get_zones().await {
+        Ok(zones) => Ok(HttpResponse::Ok().json(zones)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET single shipping zone handler (admin)
+async fn get_zone_handler(
+    id: web::Path<String>,
+    crud: web::Data<ShippingCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).get_zone(&id).await {
+        Ok(Some(zone)) => Ok(HttpResponse::Ok().json(zone)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Shipping zone not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// PUT shipping zone handler (admin)
+async fn update_zone_handler(
+    id: web::Path<String>,
+    body: web::Json<ShippingZoneSchema>,
+    crud: web::Data<ShippingCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).update_zone(&id, body.into_inner()).await {
+        Ok(Some(zone)) => Ok(HttpResponse::Ok().json(zone)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Shipping zone not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE shipping zone handler (admin)
+async fn delete_zone_handler(
+    id: web::Path<String>,
+    crud: web::Data<ShippingCrud>,
+    product_crud: web::Data<ProductCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud).delete_zone(&id).await {
+        Ok(true) => Ok(HttpResponse::NoContent().finish()),
+        Ok(false) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Shipping zone not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// Route configuration function
+pub fn shipping_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/shipping")
+            .route("/quote", web::post().to(quote_handler))
+    );
+}
+
+// Admin routes, mounted under the protected /api/admin scope
+pub fn admin_shipping_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/shipping/zones")
+            .route("", web::post().to(create_zone_handler))
+            .route("", web::get().to(get_zones_handler))
+            .route("/{id}", web::get().to(get_zone_handler))
+            .route("/{id}", web::put().to(update_zone_handler))
+            .route("/{id}", web::delete().to(delete_zone_handler))
+    );
+}
@@ -0,0 +1,126 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::module::shipping::model::{DeliveryService, WeightBand};
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct WeightBandSchema {
+    // Omit on the last band to cover any heavier parcel
+    #[validate(range(min = 1))]
+    pub max_weight_grams: Option<i32>,
+
+    #[validate(range(min = 0))]
+    pub price: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct DeliveryServiceSchema {
+    #[validate(length(min = 1, max = 30))]
+    pub code: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    #[validate(range(max = 60))]
+    pub eta_min_days: u32,
+
+    #[validate(range(max = 60))]
+    pub eta_max_days: u32,
+
+    #[validate(range(min = 0))]
+    pub free_shipping_threshold: Option<i64>,
+
+    #[validate(length(min = 1, max = 20), nested)]
+    pub bands: Vec<WeightBandSchema>,
+}
+
+impl From<DeliveryServiceSchema> for DeliveryService {
+    fn from(data: DeliveryServiceSchema) -> Self {
+        let mut bands: Vec<WeightBand> = data.bands.into_iter()
+            .map(|band| WeightBand {
+                max_weight_grams: band.max_weight_grams,
+                price: band.price,
+            })
+            .collect();
+        // Open ended band last
+        bands.sort_by_key(|band| band.max_weight_grams.unwrap_or(i32::MAX));
+
+        Self {
+            code: data.code.trim().to_lowercase(),
+            name: data.name,
+            eta_min_days: data.eta_min_days,
+            eta_max_days: data.eta_max_days,
+            free_shipping_threshold: data.free_shipping_threshold,
+            bands,
+        }
+    }
+}
+
+// Used both to create a zone and to replace one
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct ShippingZoneSchema {
+    #[validate(length(min = 2, max = 100))]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 37))]
+    pub states: Vec<String>,
+
+    #[serde(default)]
+    #[validate(length(max = 100))]
+    pub cities: Vec<String>,
+
+    #[validate(length(min = 1, max = 10), nested)]
+    pub services: Vec<DeliveryServiceSchema>,
+
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct QuoteItemSchema {
+    pub product_id: String,  // Will be converted to ObjectId
+    pub variant_id: Option<String>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub quantity: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct ShippingQuoteSchema {
+    #[validate(length(min = 2, max = 100))]
+    pub state: String,
+
+    // Needed where a state is split between zones, e.g. Lagos Island and mainland
+    #[validate(length(min = 2, max = 100))]
+    pub city: Option<String>,
+
+    #[validate(length(min = 1, max = 50), nested)]
+    pub items: Vec<QuoteItemSchema>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeliveryOption {
+    pub code: String,
+    pub name: String,
+    pub price: i64, // In kobo
+    pub is_free: bool,
+    pub eta_min_days: u32,
+    pub eta_max_days: u32,
+    pub estimated_delivery_from: NaiveDate,
+    pub estimated_delivery_to: NaiveDate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShippingQuote {
+    pub zone_id: String,
+    pub zone_name: String,
+    pub state: String,
+    pub subtotal: i64,
+    pub weight_grams: i32,
+    pub options: Vec<DeliveryOption>,
+}
+
+fn default_true() -> bool {
+    true
+}
@@ -0,0 +1,94 @@
+use std::env;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::module::tax::model::TaxRate;
+
+const BASIS_POINTS: i128 = 10_000;
+
+// Whether catalogue prices already include VAT (env PRICES_INCLUDE_TAX, default true)
+pub fn prices_include_tax() -> bool {
+    env::var("PRICES_INCLUDE_TAX")
+        .map(|value| !matches!(value.trim().to_lowercase().as_str(), "false" | "0" | "no"))
+        .unwrap_or(true)
+}
+
+// numerator / denominator rounded to the nearest kobo, halves away from zero
+pub fn round_div(numerator: i128, denominator: i128) -> i64 {
+    let half = denominator / 2;
+    let rounded = if numerator >= 0 {
+        (numerator + half) / denominator
+    } else {
+        (numerator - half) / denominator
+    };
+    rounded as i64
+}
+
+// An amount split into its net and tax parts, all in kobo
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TaxBreakdown {
+    pub tax_class: String,
+    pub label: String,
+    pub rate_basis_points: i64,
+    pub net: i64,
+    pub tax: i64,
+    pub gross: i64,
+}
+
+pub struct TaxCalculator {
+    rates: Vec<TaxRate>,
+    prices_include_tax: bool,
+}
+
+impl TaxCalculator {
+    pub fn new(rates: Vec<TaxRate>, prices_include_tax: bool) -> Self {
+        Self { rates, prices_include_tax }
+    }
+
+    pub fn prices_include_tax(&self) -> bool {
+        self.prices_include_tax
+    }
+
+    // The class's rate in force at `at`. A class with no rate is a
+    // misconfiguration, so it's reported rather than guessed at.
+    pub fn rate_for(&self, tax_class: &str, at: DateTime<Utc>) -> Result<&TaxRate, String> {
+        self.rates.iter()
+            .filter(|rate| rate.tax_class == tax_class && rate.effective_from <= at)
+            .max_by_key(|rate| rate.effective_from)
+            .ok_or_else(|| format!("No tax rate in effect for tax class '{}'", tax_class))
+    }
+
+    // Tax contained in a price that already includes it
+    pub fn inclusive_tax(amount: i64, rate_basis_points: i64) -> i64 {
+        let rate = rate_basis_points as i128;
+        round_div(amount as i128 * rate, BASIS_POINTS + rate)
+    }
+
+    // Tax to add on top of a price that excludes it
+    pub fn exclusive_tax(amount: i64, rate_basis_points: i64) -> i64 {
+        round_div(amount as i128 * rate_basis_points as i128, BASIS_POINTS)
+    }
+
+    // Split a catalogue price, read as inclusive or exclusive per configuration.
+    // Tax is rounded once per amount, so callers totalling lines should
+    // break down each line and add the results.
+    pub fn breakdown(&self, amount: i64, tax_class: &str, at: DateTime<Utc>) -> Result<TaxBreakdown, String> {
+        let rate = self.rate_for(tax_class, at)?;
+        let (class, label, rate) = (rate.tax_class.clone(), rate.label.clone(), rate.rate_basis_points);
+
+        let (net, tax) = if self.prices_include_tax {
+            let tax = Self::inclusive_tax(amount, rate);
+            (amount - tax, tax)
+        } else {
+            (amount, Self::exclusive_tax(amount, rate))
+        };
+
+        Ok(TaxBreakdown {
+            tax_class: class,
+            label,
+            rate_basis_points: rate,
+            net,
+            tax,
+            gross: net + tax,
+        })
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use bson::oid::ObjectId;
+use chrono::Utc;
+use validator::Validate;
+use crate::module::category::crud::CategoryCrud;
+use crate::module::product::model::Product;
+use crate::module::tax::{
+    calculator::{prices_include_tax, TaxCalculator},
+    crud::TaxCrud,
+    interface::TaxService,
+    model::{TaxRate, STANDARD_TAX_CLASS},
+    schema::{ProductTaxPrices, ProductWithTax, TaxRateSchema},
+};
+
+pub struct TaxController {
+    crud: TaxCrud,
+    category_crud: CategoryCrud,
+}
+
+impl TaxController {
+    pub fn new(crud: TaxCrud, category_crud: CategoryCrud) -> Self {
+        Self { crud, category_crud }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    pub async fn calculator(&self) -> Result<TaxCalculator, String> {
+        Ok(TaxCalculator::new(self.crud.get_rates().await?, prices_include_tax()))
+    }
+
+    // Each product's tax class by product ID. Products without their own
+    // class take their category's, then the standard class.
+    pub async fn tax_classes(&self, products: &[&Product]) -> Result<HashMap<ObjectId, String>, String> {
+        let mut category_ids: Vec<ObjectId> = products.iter()
+            .filter(|product| product.tax_class.is_none())
+            .map(|product| product.category_id)
+            .collect();
+        category_ids.sort();
+        category_ids.dedup();
+        let category_classes: HashMap<ObjectId, String> = if category_ids.is_empty() {
+            HashMap::new()
+        } else {
+            self.category_crud.get_categories_by_ids(&category_ids).await?
+                .into_iter()
+                .filter_map(|category| Some((category.id?, category.tax_class?)))
+                .collect()
+        };
+
+        Ok(products.iter()
+            .filter_map(|product| {
+                let tax_class = product.tax_class.clone()
+                    .or_else(|| category_classes.get(&product.category_id).cloned())
+                    .unwrap_or_else(|| STANDARD_TAX_CLASS.to_string());
+                Some((product.id?, tax_class))
+            })
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl TaxService for TaxController {
+    async fn price_products(&self, products: Vec<Product>) -> Result<Vec<ProductWithTax>, String> {
+        let calculator = self.calculator().await?;
+        let classes = self.tax_classes(&products.iter().collect::<Vec<_>>()).await?;
+
+        let now = Utc::now();
+        products.into_iter()
+            .map(|product| {
+                let tax_class = product.id
+                    .and_then(|id| classes.get(&id).cloned())
+                    .unwrap_or_else(|| STANDARD_TAX_CLASS.to_string());
+
+                let tax = ProductTaxPrices {
+                    price: calculator.breakdown(product.price, &tax_class, now)?,
+                    sale_price: product.sale_price
+                        .map(|sale_price| calculator.breakdown(sale_price, &tax_class, now))
+                        .transpose()?,
+                    prices_include_tax: calculator.prices_include_tax(),
+                };
+                Ok(ProductWithTax { product, tax })
+            })
+            .collect()
+    }
+
+    async fn create_rate(&self, data: TaxRateSchema) -> Result<TaxRate, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let now = Utc::now();
+        self.crud.create_rate(TaxRate {
+            id: None,
+            tax_class: data.tax_class.trim().to_lowercase(),
+            label: data.label.trim().to_string(),
+            rate_basis_points: data.rate_basis_points,
+            effective_from: data.effective_from.unwrap_or(now),
+            created_at: now,
+        }).await
+    }
+
+    async fn get_rates(&self) -> Result<Vec<TaxRate>, String> {
+        self.crud.get_rates().await
+    }
+
+    // Rates that have taken effect stay, so past prices can still be explained;
+    // only scheduled ones can be withdrawn
+    async fn delete_rate(&self, id: &str) -> Result<bool, String> {
+        let oid = Self::parse_id(id, "tax rate")?;
+        let rate = match self.crud.get_rate(&oid).await? {
+            Some(rate) => rate,
+            None => return Ok(false),
+        };
+        if rate.effective_from <= Utc::now() {
+            return Err("Rates already in effect can't be deleted; schedule a new rate instead".to_string());
+        }
+        self.crud.delete_rate(&oid).await
+    }
+}
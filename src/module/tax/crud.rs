@@ -0,0 +1,117 @@
+use bson::{doc, oid::ObjectId};
+use chrono::{TimeZone, Utc};
+use mongodb::{options::FindOptions, Collection, Database};
+use futures_util::TryStreamExt;
+use crate::module::tax::model::{TaxRate, EXEMPT_TAX_CLASS, STANDARD_TAX_CLASS};
+
+#[derive(Clone)]
+pub struct TaxCrud {
+    collection: Collection<TaxRate>,
+}
+
+impl TaxCrud {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("tax_rates");
+        Self { collection }
+    }
+
+    // Start an empty table with Nigeria's 7.5% VAT (in force since 1 February 2020)
+    // and a zero rate for exempt goods
+    pub async fn seed_default_rates(&self) -> Result<u64, String> {
+        let existing = self.collection.count_documents(doc! {}).await
+            .map_err(|e| format!("Failed to count tax rates: {}", e))?;
+        if existing > 0 {
+            return Ok(0);
+        }
+
+        let effective_from = Utc.with_ymd_and_hms(2020, 2, 1, 0, 0, 0).unwrap();
+        let now = Utc::now();
+        let defaults = vec![
+            TaxRate {
+                id: None,
+                tax_class: STANDARD_TAX_CLASS.to_string(),
+                label: "VAT".to_string(),
+                rate_basis_points: 750,
+                effective_from,
+                created_at: now,
+            },
+            TaxRate {
+                id: None,
+                tax_class: EXEMPT_TAX_CLASS.to_string(),
+                label: "VAT exempt".to_string(),
+                rate_basis_points: 0,
+                effective_from,
+                created_at: now,
+            },
+        ];
+
+        let result = self.collection.insert_many(defaults).await
+            .map_err(|e| format!("Failed to seed tax rates: {}", e))?;
+        Ok(result.inserted_ids.len() as u64)
+    }
+
+    pub async fn create_rate(&self, mut rate: TaxRate) -> Result<TaxRate, String> {
+        match self.collection.insert_one(&rate).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    rate.id = Some(id);
+                }
+                Ok(rate)
+            }
+            Err(e) => Err(format!("Failed to create tax rate: {}", e))
+        }
+    }
+
+    pub async fn get_rates(&self) -> Result<Vec<TaxRate>, String> {
+        let options = FindOptions::builder()
+            .sort(doc! { "tax_class": 1, "effective_from": -1 })
+            .build();
+
+        match self.collection.find(doc! {}).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut rates = Vec::new();
+                while let Some(rate) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve tax rates: {}", e))? {
+                    rates.push(rate);
+                }
+                Ok(rates)
+            }
+            Err(e) => Err(format!("Failed to retrieve tax rates: {}", e))
+        }
+    }
+
+    // Every class the rate table has an entry for
+    pub async fn get_tax_classes(&self) -> Result<Vec<String>, String> {
+        let mut classes: Vec<String> = self.collection.distinct("tax_class", doc! {}).await
+            .map_err(|e| format!("Failed to retrieve tax classes: {}", e))?
+            .into_iter()
+            .filter_map(|class| class.as_str().map(str::to_string))
+            .collect();
+        classes.sort();
+        Ok(classes)
+    }
+
+    // The class as stored on products and categories, if the rate table knows it
+    pub async fn check_tax_class(&self, tax_class: &str) -> Result<String, String> {
+        let tax_class = tax_class.trim().to_lowercase();
+        let classes = self.get_tax_classes().await?;
+        if !classes.contains(&tax_class) {
+            return Err(format!("Unknown tax class {}; use one of: {}", tax_class, classes.join(", ")));
+        }
+        Ok(tax_class)
+    }
+
+    pub async fn get_rate(&self, id: &ObjectId) -> Result<Option<TaxRate>, String> {
+        match self.collection.find_one(doc! { "_id": id }).await {
+            Ok(rate) => Ok(rate),
+            Err(e) => Err(format!("Failed to retrieve tax rate: {}", e))
+        }
+    }
+
+    pub async fn delete_rate(&self, id: &ObjectId) -> Result<bool, String> {
+        match self.collection.delete_one(doc! { "_id": id }).await {
+            Ok(result) => Ok(result.deleted_count == 1),
+            Err(e) => Err(format!("Failed to delete tax rate: {}", e))
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::module::product::model::Product;
+use crate::module::tax::{
+    model::TaxRate,
+    schema::{ProductWithTax, TaxRateSchema},
+};
+
+#[async_trait::async_trait]
+pub trait TaxService {
+    // Catalogue
+    async fn price_products(&self, products: Vec<Product>) -> Result<Vec<ProductWithTax>, String>;
+
+    // Admin
+    async fn create_rate(&self, data: TaxRateSchema) -> Result<TaxRate, String>;
+    async fn get_rates(&self) -> Result<Vec<TaxRate>, String>;
+    async fn delete_rate(&self, id: &str) -> Result<bool, String>;
+}
@@ -0,0 +1,17 @@
+use bson::doc;
+use mongodb::IndexModel;
+use crate::module::tax::crud::TaxCrud;
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("tax", 1, "tax rate lookup index", "tax_rates", vec![
+            IndexModel::builder()
+                .keys(doc! { "tax_class": 1, "effective_from": -1 })
+                .build(),
+        ]),
+        Migration::data("tax", 2, "default VAT rates", |db| async move {
+            TaxCrud::new(&db).seed_default_rates().await
+        }),
+    ]
+}
@@ -0,0 +1,8 @@
+pub mod calculator;
+pub mod controller;
+pub mod crud;
+pub mod interface;
+pub mod migration;
+pub mod model;
+pub mod route;
+pub mod schema;
@@ -0,0 +1,28 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Class used when neither the product nor its category names one
+pub const STANDARD_TAX_CLASS: &str = "standard";
+pub const EXEMPT_TAX_CLASS: &str = "exempt";
+
+// One entry in a tax class's rate timeline. A class's rate at any moment is
+// the entry with the latest `effective_from` that has already started, so a
+// rate change is scheduled by adding an entry with a future date.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaxRate {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub tax_class: String,
+    // Shown on invoices and price breakdowns, e.g. "VAT"
+    pub label: String,
+    // Hundredths of a percent: 750 is 7.5%
+    pub rate_basis_points: i64,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub effective_from: DateTime<Utc>,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+}
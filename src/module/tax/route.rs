@@ -0,0 +1,66 @@
+use actix_web::{web, HttpResponse, Result};
+use crate::module::category::crud::CategoryCrud;
+use crate::module::tax::{
+    controller::TaxController,
+    crud::TaxCrud,
+    interface::TaxService,
+    schema::TaxRateSchema,
+};
+
+fn controller(crud: &web::Data<TaxCrud>, category_crud: &web::Data<CategoryCrud>) -> TaxController {
+    TaxController::new(crud.get_ref().clone(), category_crud.get_ref().clone())
+}
+
+// POST tax rate handler (admin)
+async fn create_rate_handler(
+    body: web::Json<TaxRateSchema>,
+    crud: web::Data<TaxCrud>,
+    category_crud: web::Data<CategoryCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &category_crud).create_rate(body.into_inner()).await {
+        Ok(rate) => Ok(HttpResponse::Created().json(rate)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET tax rate table handler (admin)
+async fn get_rates_handler(
+    crud: web::Data<TaxCrud>,
+    category_crud: web::Data<CategoryCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &category_crud).get_rates().await {
+        Ok(rates) => Ok(HttpResponse::Ok().json(rates)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE scheduled tax rate handler (admin)
+async fn delete_rate_handler(
+    id: web::Path<String>,
+    crud: web::Data<TaxCrud>,
+    category_crud: web::Data<CategoryCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &category_crud).delete_rate(&id).await {
+        Ok(true) => Ok(HttpResponse::NoContent().finish()),
+        Ok(false) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Tax rate not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// Admin routes, mounted under the protected /api/admin scope
+pub fn admin_tax_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/tax/rates")
+            .route("", web::post().to(create_rate_handler))
+            .route("", web::get().to(get_rates_handler))
+            .route("/{id}", web::delete().to(delete_rate_handler))
+    );
+}
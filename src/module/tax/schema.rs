@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::module::product::model::Product;
+use crate::module::tax::calculator::TaxBreakdown;
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct TaxRateSchema {
+    #[validate(length(min = 1, max = 30))]
+    pub tax_class: String,
+
+    #[validate(length(min = 1, max = 30))]
+    pub label: String,
+
+    // Hundredths of a percent: 750 is 7.5%
+    #[validate(range(min = 0, max = 10000))]
+    pub rate_basis_points: i64,
+
+    // Defaults to now
+    pub effective_from: Option<DateTime<Utc>>,
+}
+
+// Product endpoints add tax breakdowns when called with ?include_tax=true
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaxPriceQuery {
+    #[serde(default)]
+    pub include_tax: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductTaxPrices {
+    pub price: TaxBreakdown,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sale_price: Option<TaxBreakdown>,
+    // Whether the catalogue prices are the `gross` or the `net` figures
+    pub prices_include_tax: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductWithTax {
+    #[serde(flatten)]
+    pub product: Product,
+    pub tax: ProductTaxPrices,
+}
@@ -6,6 +6,7 @@ use tokio;
 use bcrypt::verify;
 use uuid::Uuid;
 
+use crate::module::cart::{controller::CartController, interface::CartService};
 use crate::module::user::{
     crud::UserCrud,
     interface::{UserRepository, UserService, UserValidation},
@@ -14,26 +15,38 @@ use crate::module::user::{
 };
 use crate::service::{
     email_service, email_templates, jwt_service::JwtService,
-    google_oauth_service::GoogleOauthService,
+    google_oauth_service::GoogleOauthService, migration_service,
 };
 use crate::service::jwt_service::Role;
 
 pub struct UserController {
     crud: UserCrud,
+    cart_controller: CartController,
     jwt_service: JwtService,
     google_oauth_service: GoogleOauthService,
 }
 
 impl UserController {
-    pub fn new(crud: UserCrud) -> Self {
+    pub fn new(crud: UserCrud, cart_controller: CartController) -> Self {
         Self { 
             crud,
+            cart_controller,
             jwt_service: JwtService::new(),
             google_oauth_service: GoogleOauthService::new(),
         }
     }
 
-    pub async fn handle_google_oauth(&self, data: Value) -> web::Json<Value> {
+    // Fold the caller's guest cart into their user cart. A failed merge
+    // shouldn't block sign-in; the error is returned so the response can
+    // tell the client its guest cart is still separate.
+    async fn merge_guest_cart(&self, cart_token: Option<String>, user_id: &str) -> Option<String> {
+        let token = cart_token?;
+        self.cart_controller.merge_guest_cart(&token, user_id).await
+            .err()
+            .map(|e| format!("Failed to merge guest cart: {}", e))
+    }
+
+    pub async fn handle_google_oauth(&self, data: Value, cart_token: Option<String>) -> web::Json<Value> {
         let code = match data.get("code").and_then(|v| v.as_str()) {
             Some(c) => c,
             None => {
@@ -98,6 +111,7 @@ impl UserController {
         // At this point, `user` is either the existing or newly created user.
         // Generate JWT token
         let user_id = user.id.map(|id| id.to_string()).unwrap_or_else(|| "unknown".to_string());
+        let cart_merge_error = self.merge_guest_cart(cart_token, &user_id).await;
         
         let token = match self.jwt_service.generate_token(
             user_id.clone(),
@@ -126,7 +140,8 @@ impl UserController {
                     "first_name": user.first_name,
                     "last_name": user.last_name,
                     "is_verified": user.email_verified
-                }
+                },
+                "cart_merge_error": cart_merge_error
             }
         }))
     }
@@ -143,7 +158,8 @@ impl UserController {
             }
         };
 
-        // Process registration
+        // Process registration. The guest cart stays with its token until the
+        // verified user first signs in, so an unverified account can't claim it.
         match self.register_user(registration_data).await {
             Ok(user) => web::Json(json!({
                 "status": "success",
@@ -227,7 +243,7 @@ impl UserController {
         }
     }
 
-    pub async fn handle_login(&self, data: Value) -> web::Json<Value> {
+    pub async fn handle_login(&self, data: Value, cart_token: Option<String>) -> web::Json<Value> {
         // Parse and validate input
         let login_data = match serde_json::from_value::<LoginSchema>(data) {
             Ok(data) => data,
@@ -245,6 +261,7 @@ impl UserController {
                 // Generate JWT token
                 let user_id = user.id.map(|id| id.to_string())
                     .unwrap_or_else(|| "unknown".to_string());
+                let cart_merge_error = self.merge_guest_cart(cart_token, &user_id).await;
                 
                 let token = match self.jwt_service.generate_token(
                     user_id.clone(),
@@ -273,7 +290,8 @@ impl UserController {
                             "first_name": user.first_name,
                             "last_name": user.last_name,
                             "is_verified": user.email_verified
-                        }
+                        },
+                        "cart_merge_error": cart_merge_error
                     }
                 }))
             },
@@ -322,7 +340,16 @@ impl UserService for UserController {
         user.verification_code_expires_at = Some(expires_at);
 
         // Save user to database
-        let saved_user = self.crud.create_user(user).await.map_err(|e| format!("Failed to create user: {}", e))?;
+        // Someone registering at the same moment may have taken the email or phone
+        let saved_user = self.crud.create_user(user).await.map_err(|e| {
+            if migration_service::is_duplicate_key(&e, "email") {
+                "Email already registered".to_string()
+            } else if migration_service::is_duplicate_key(&e, "phone_number") {
+                "Phone number already registered".to_string()
+            } else {
+                format!("Failed to create user: {}", e)
+            }
+        })?;
 
         // Send verification email
         let (subject, body) = email_templates::get_verification_email_template(&data.first_name, &verification_code);
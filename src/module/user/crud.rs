@@ -1,4 +1,4 @@
-use mongodb::{Collection, Database, bson::{doc, DateTime as BsonDateTime}};
+use mongodb::{Collection, Database, bson::{doc, oid::ObjectId, DateTime as BsonDateTime}};
 use bcrypt::{hash, DEFAULT_COST};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -34,6 +34,11 @@ impl UserRepository for UserCrud {
         })
     }
 
+    async fn find_by_id(&self, id: &ObjectId) -> Result<Option<User>, mongodb::error::Error> {
+        self.collection
+            .find_one(doc! { "_id": id }).await
+    }
+
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, mongodb::error::Error> {
         self.collection
             .find_one(doc! { "email": email }).await
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use mongodb::error::Error as MongoError;
+use mongodb::{bson::oid::ObjectId, error::Error as MongoError};
 use chrono::{DateTime, Utc};
 
 use crate::module::user::{
@@ -10,6 +10,7 @@ use crate::module::user::{
 #[async_trait]
 pub trait UserRepository {
     async fn create_user(&self, user: User) -> Result<User, MongoError>;
+    async fn find_by_id(&self, id: &ObjectId) -> Result<Option<User>, MongoError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, MongoError>;
     async fn find_by_phone(&self, phone: &str) -> Result<Option<User>, MongoError>;
     async fn update_user_verification_status(&self, email: &str, status: bool) -> Result<(), MongoError>;
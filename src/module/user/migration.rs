@@ -0,0 +1,22 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        // Google sign-ups have no phone number, so only real ones must be unique
+        Migration::indexes("user", 1, "unique email and phone number indexes", "users", vec![
+            IndexModel::builder()
+                .keys(doc! { "email": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "phone_number": 1 })
+                .options(IndexOptions::builder()
+                    .unique(true)
+                    .partial_filter_expression(doc! { "phone_number": { "$type": "string", "$gt": "" } })
+                    .build())
+                .build(),
+        ]),
+    ]
+}
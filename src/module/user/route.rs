@@ -1,5 +1,6 @@
-use actix_web::{web, post};
+use actix_web::{web, post, HttpRequest};
 use crate::module::user::controller::UserController;
+use crate::service::cart_token_service::CartTokenService;
 
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -18,10 +19,11 @@ pub fn config(cfg: &mut web::ServiceConfig) {
 
 #[post("/google/callback")]
 pub async fn google_oauth_callback(
+    req: HttpRequest,
     data: web::Json<serde_json::Value>,
     user_controller: web::Data<UserController>,
 ) -> web::Json<serde_json::Value> {
-    user_controller.handle_google_oauth(data.into_inner()).await
+    user_controller.handle_google_oauth(data.into_inner(), CartTokenService::token_from_request(&req)).await
 }
 
 #[post("/register")]
@@ -42,10 +44,11 @@ pub async fn verify_email(
 
 #[post("/login")]
 pub async fn login(
+    req: HttpRequest,
     data: web::Json<serde_json::Value>,
     user_controller: web::Data<UserController>,
 ) -> web::Json<serde_json::Value> {
-    user_controller.handle_login(data.into_inner()).await
+    user_controller.handle_login(data.into_inner(), CartTokenService::token_from_request(&req)).await
 }
 
 #[post("/resend-verification")]
@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use bson::oid::ObjectId;
+use chrono::Utc;
+use validator::Validate;
+use crate::module::cart::{
+    controller::CartController,
+    crud::CartCrud,
+    interface::CartService,
+    model::CartOwner,
+    schema::{AddCartItemSchema, CartResponse},
+};
+use crate::module::product::{crud::ProductCrud, model::{Product, PurchaseOption}};
+use crate::module::wishlist::{
+    crud::WishlistCrud,
+    interface::WishlistService,
+    model::{Wishlist, WishlistItem, SAVED_FOR_LATER},
+    schema::{
+        AddWishlistItemSchema, CreateWishlistSchema, MoveToCartSchema, SaveForLaterSchema,
+        UpdateWishlistSchema, WishlistItemResponse, WishlistResponse,
+    },
+};
+
+pub struct WishlistController {
+    crud: WishlistCrud,
+    product_crud: ProductCrud,
+    cart_controller: CartController,
+}
+
+impl WishlistController {
+    pub fn new(crud: WishlistCrud, product_crud: ProductCrud, cart_crud: CartCrud) -> Self {
+        let cart_controller = CartController::new(cart_crud, product_crud.clone());
+        Self { crud, product_crud, cart_controller }
+    }
+
+    fn parse_id(id: &str, what: &str) -> Result<ObjectId, String> {
+        ObjectId::parse_str(id).map_err(|_| format!("Invalid {} ID", what))
+    }
+
+    async fn load_wishlist(&self, user_id: &ObjectId, id: &str) -> Result<Wishlist, String> {
+        let id = Self::parse_id(id, "wishlist")?;
+        self.crud.get_user_wishlist(&id, user_id).await?
+            .ok_or_else(|| "Wishlist not found".to_string())
+    }
+
+    async fn save(&self, wishlist: &mut Wishlist) -> Result<(), String> {
+        wishlist.updated_at = Utc::now();
+        self.crud.save_wishlist(wishlist).await
+    }
+
+    // Attach current product details so the UI can show price movements
+    async fn to_wishlist_responses(&self, wishlists: Vec<Wishlist>) -> Result<Vec<WishlistResponse>, String> {
+        let ids: Vec<ObjectId> = wishlists.iter()
+            .flat_map(|wishlist| wishlist.items.iter().map(|item| item.product_id))
+            .collect();
+        let products: HashMap<ObjectId, Product> = self.product_crud.get_products_by_ids(&ids).await?
+            .into_iter()
+            .filter_map(|product| product.id.map(|id| (id, product)))
+            .collect();
+
+        Ok(wishlists.into_iter()
+            .map(|wishlist| WishlistResponse {
+                id: wishlist.id.map(|id| id.to_hex()).unwrap_or_default(),
+                name: wishlist.name,
+                is_shared: wishlist.share_token.is_some(),
+                share_token: wishlist.share_token,
+                items: wishlist.items.iter()
+                    .map(|item| {
+                        let product = products.get(&item.product_id).filter(|product| product.is_active);
+                        // Items saved with a variant show that variant's name, price and stock
+                        let option = product.and_then(|product| match item.variant_id {
+                            Some(variant_id) => product.purchase_option(Some(&variant_id)).ok(),
+                            None => Some(PurchaseOption { product, variant: None }),
+                        });
+                        let current_price = option.as_ref().map(|option| option.unit_price());
+
+                        WishlistItemResponse {
+                            product_id: item.product_id.to_hex(),
+                            variant_id: item.variant_id.map(|id| id.to_hex()),
+                            name: option.as_ref().map(|option| option.name()),
+                            image: product.and_then(|product| product.primary_image().map(|image| image.url.clone())),
+                            price_when_added: item.price_when_added,
+                            current_price,
+                            price_change: current_price.map(|price| price - item.price_when_added),
+                            in_stock: option.as_ref().map(|option| option.stock_quantity() > 0).unwrap_or(false),
+                            available: option.is_some(),
+                            added_at: item.added_at.to_rfc3339(),
+                        }
+                    })
+                    .collect(),
+                created_at: wishlist.created_at.to_rfc3339(),
+                updated_at: wishlist.updated_at.to_rfc3339(),
+            })
+            .collect())
+    }
+
+    async fn to_wishlist_response(&self, wishlist: Wishlist) -> Result<WishlistResponse, String> {
+        self.to_wishlist_responses(vec![wishlist]).await?
+            .pop()
+            .ok_or_else(|| "Failed to build wishlist".to_string())
+    }
+
+    // Add a product, or one of its variants, to a list, remembering the given
+    // price; no-op if it is already there
+    async fn add_to_list(&self, wishlist: &mut Wishlist, product_id: ObjectId, variant_id: Option<ObjectId>, price: i64) -> Result<(), String> {
+        let listed = wishlist.items.iter()
+            .any(|item| item.product_id == product_id && item.variant_id == variant_id);
+        if !listed {
+            wishlist.items.push(WishlistItem {
+                product_id,
+                variant_id,
+                price_when_added: price,
+                added_at: Utc::now(),
+            });
+            self.save(wishlist).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl WishlistService for WishlistController {
+    async fn create_wishlist(&self, user_id: &str, data: CreateWishlistSchema) -> Result<WishlistResponse, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let user_id = Self::parse_id(user_id, "user")?;
+        if self.crud.get_user_wishlist_by_name(&user_id, &data.name).await?.is_some() {
+            return Err("A wishlist with this name already exists".to_string());
+        }
+
+        let mut wishlist = Wishlist::new(user_id, data.name);
+        wishlist.set_shared(data.is_shared);
+
+        let wishlist = self.crud.create_wishlist(wishlist).await?;
+        self.to_wishlist_response(wishlist).await
+    }
+
+    async fn get_wishlists(&self, user_id: &str) -> Result<Vec<WishlistResponse>, String> {
+        let user_id = Self::parse_id(user_id, "user")?;
+        let wishlists = self.crud.get_user_wishlists(&user_id).await?;
+        self.to_wishlist_responses(wishlists).await
+    }
+
+    async fn get_wishlist(&self, user_id: &str, id: &str) -> Result<Option<WishlistResponse>, String> {
+        let user_id = Self::parse_id(user_id, "user")?;
+        let id = Self::parse_id(id, "wishlist")?;
+
+        match self.crud.get_user_wishlist(&id, &user_id).await? {
+            Some(wishlist) => Ok(Some(self.to_wishlist_response(wishlist).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn update_wishlist(&self, user_id: &str, id: &str, data: UpdateWishlistSchema) -> Result<WishlistResponse, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let user_id = Self::parse_id(user_id, "user")?;
+        let mut wishlist = self.load_wishlist(&user_id, id).await?;
+
+        if let Some(name) = data.name {
+            if name != wishlist.name && self.crud.get_user_wishlist_by_name(&user_id, &name).await?.is_some() {
+                return Err("A wishlist with this name already exists".to_string());
+            }
+            wishlist.name = name;
+        }
+        if let Some(is_shared) = data.is_shared {
+            // Keep the existing link when the list is already shared
+            if is_shared != wishlist.share_token.is_some() {
+                wishlist.set_shared(is_shared);
+            }
+        }
+
+        self.save(&mut wishlist).await?;
+        self.to_wishlist_response(wishlist).await
+    }
+
+    async fn delete_wishlist(&self, user_id: &str, id: &str) -> Result<bool, String> {
+        let user_id = Self::parse_id(user_id, "user")?;
+        let id = Self::parse_id(id, "wishlist")?;
+        self.crud.delete_user_wishlist(&id, &user_id).await
+    }
+
+    async fn get_shared_wishlist(&self, token: &str) -> Result<Option<WishlistResponse>, String> {
+        match self.crud.get_by_share_token(token).await? {
+            Some(wishlist) => {
+                let mut response = self.to_wishlist_response(wishlist).await?;
+                // Viewers of a shared link shouldn't be able to pass it on as the owner's
+                response.share_token = None;
+                Ok(Some(response))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn add_item(&self, user_id: &str, id: &str, data: AddWishlistItemSchema) -> Result<WishlistResponse, String> {
+        let user_id = Self::parse_id(user_id, "user")?;
+        let product_id = Self::parse_id(&data.product_id, "product")?;
+        let mut wishlist = self.load_wishlist(&user_id, id).await?;
+
+        let product = self.product_crud.get_product(&data.product_id).await?
+            .filter(|product| product.is_active)
+            .ok_or_else(|| "Product not found".to_string())?;
+
+        self.add_to_list(&mut wishlist, product_id, None, product.display_price()).await?;
+        self.to_wishlist_response(wishlist).await
+    }
+
+    async fn remove_item(&self, user_id: &str, id: &str, product_id: &str) -> Result<WishlistResponse, String> {
+        let user_id = Self::parse_id(user_id, "user")?;
+        let product_id = Self::parse_id(product_id, "product")?;
+        let mut wishlist = self.load_wishlist(&user_id, id).await?;
+
+        if !wishlist.contains(&product_id) {
+            return Err("Product is not in the wishlist".to_string());
+        }
+        wishlist.items.retain(|item| item.product_id != product_id);
+
+        self.save(&mut wishlist).await?;
+        self.to_wishlist_response(wishlist).await
+    }
+
+    async fn move_to_cart(&self, user_id: &str, id: &str, product_id: &str, data: MoveToCartSchema) -> Result<CartResponse, String> {
+        if let Err(e) = data.validate() {
+            return Err(format!("Validation error: {}", e));
+        }
+
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let product_oid = Self::parse_id(product_id, "product")?;
+        let variant_oid = data.variant_id.as_deref()
+            .map(|variant_id| Self::parse_id(variant_id, "variant"))
+            .transpose()?;
+        let mut wishlist = self.load_wishlist(&user_oid, id).await?;
+
+        // The variant saved with the item is the one the customer chose,
+        // unless they pick another now
+        let item = wishlist.find_item(&product_oid, variant_oid.as_ref())
+            .or_else(|| wishlist.find_item(&product_oid, None))
+            .ok_or_else(|| "Product is not in the wishlist".to_string())?;
+        let saved_variant = item.variant_id;
+        let variant_oid = variant_oid.or(saved_variant);
+
+        // Add to the cart first so a stock failure leaves the wishlist untouched
+        let cart = self.cart_controller.add_item(&CartOwner::User(user_oid), AddCartItemSchema {
+            product_id: product_id.to_string(),
+            variant_id: variant_oid.map(|id| id.to_hex()),
+            quantity: data.quantity.unwrap_or(1),
+        }).await?;
+
+        wishlist.items.retain(|item| item.product_id != product_oid || item.variant_id != saved_variant);
+        self.save(&mut wishlist).await?;
+
+        Ok(cart)
+    }
+
+    async fn save_for_later(&self, user_id: &str, data: SaveForLaterSchema) -> Result<WishlistResponse, String> {
+        let user_oid = Self::parse_id(user_id, "user")?;
+        let product_oid = Self::parse_id(&data.product_id, "product")?;
+        let variant_oid = data.variant_id.as_deref()
+            .map(|variant_id| Self::parse_id(variant_id, "variant"))
+            .transpose()?;
+        let owner = CartOwner::User(user_oid);
+
+        let cart = self.cart_controller.get_cart(&owner).await?;
+        let line = cart.items.iter()
+            .find(|item| item.product_id == data.product_id && item.variant_id == data.variant_id)
+            .ok_or_else(|| "Product is not in the cart".to_string())?;
+
+        let mut wishlist = match data.wishlist_id {
+            Some(id) => self.load_wishlist(&user_oid, &id).await?,
+            None => match self.crud.get_user_wishlist_by_name(&user_oid, SAVED_FOR_LATER).await? {
+                Some(wishlist) => wishlist,
+                None => self.crud.create_wishlist(Wishlist::new(user_oid, SAVED_FOR_LATER.to_string())).await?,
+            },
+        };
+
+        // Keep the price the customer first saw, so the price movement stays meaningful
+        self.add_to_list(&mut wishlist, product_oid, variant_oid, line.price_when_added).await?;
+        self.cart_controller.remove_item(&owner, &data.product_id, data.variant_id.as_deref()).await?;
+
+        self.to_wishlist_response(wishlist).await
+    }
+}
@@ -0,0 +1,114 @@
+use bson::{doc, oid::ObjectId};
+use chrono::{DateTime, Utc};
+use mongodb::{
+    options::FindOptions,
+    Collection, Database,
+};
+use futures_util::TryStreamExt;
+use crate::module::wishlist::model::Wishlist;
+
+#[derive(Clone)]
+pub struct WishlistCrud {
+    collection: Collection<Wishlist>,
+}
+
+impl WishlistCrud {
+    pub fn new(db: &Database) -> Self {
+        let collection = db.collection("wishlists");
+        Self { collection }
+    }
+
+    pub async fn create_wishlist(&self, mut wishlist: Wishlist) -> Result<Wishlist, String> {
+        match self.collection.insert_one(&wishlist).await {
+            Ok(result) => {
+                if let Some(id) = result.inserted_id.as_object_id() {
+                    wishlist.id = Some(id);
+                }
+                Ok(wishlist)
+            }
+            Err(e) => Err(format!("Failed to create wishlist: {}", e))
+        }
+    }
+
+    pub async fn get_user_wishlist(&self, id: &ObjectId, user_id: &ObjectId) -> Result<Option<Wishlist>, String> {
+        match self.collection.find_one(doc! { "_id": id, "user_id": user_id }).await {
+            Ok(wishlist) => Ok(wishlist),
+            Err(e) => Err(format!("Failed to retrieve wishlist: {}", e))
+        }
+    }
+
+    pub async fn get_user_wishlist_by_name(&self, user_id: &ObjectId, name: &str) -> Result<Option<Wishlist>, String> {
+        match self.collection.find_one(doc! { "user_id": user_id, "name": name }).await {
+            Ok(wishlist) => Ok(wishlist),
+            Err(e) => Err(format!("Failed to retrieve wishlist: {}", e))
+        }
+    }
+
+    pub async fn get_user_wishlists(&self, user_id: &ObjectId) -> Result<Vec<Wishlist>, String> {
+        let options = FindOptions::builder().sort(doc! { "created_at": 1 }).build();
+
+        match self.collection.find(doc! { "user_id": user_id }).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut wishlists = Vec::new();
+                while let Some(wishlist) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve wishlists: {}", e))? {
+                    wishlists.push(wishlist);
+                }
+                Ok(wishlists)
+            }
+            Err(e) => Err(format!("Failed to retrieve wishlists: {}", e))
+        }
+    }
+
+    pub async fn get_by_share_token(&self, token: &str) -> Result<Option<Wishlist>, String> {
+        match self.collection.find_one(doc! { "share_token": token }).await {
+            Ok(wishlist) => Ok(wishlist),
+            Err(e) => Err(format!("Failed to retrieve wishlist: {}", e))
+        }
+    }
+
+    pub async fn save_wishlist(&self, wishlist: &Wishlist) -> Result<(), String> {
+        let id = wishlist.id.ok_or_else(|| "Wishlist has no ID".to_string())?;
+
+        match self.collection.replace_one(doc! { "_id": id }, wishlist).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Failed to update wishlist: {}", e))
+        }
+    }
+
+    pub async fn delete_user_wishlist(&self, id: &ObjectId, user_id: &ObjectId) -> Result<bool, String> {
+        match self.collection.delete_one(doc! { "_id": id, "user_id": user_id }).await {
+            Ok(result) => Ok(result.deleted_count > 0),
+            Err(e) => Err(format!("Failed to delete wishlist: {}", e))
+        }
+    }
+
+    // Wishlists that still hold items and were last changed in the window
+    pub async fn find_idle_wishlists(
+        &self,
+        changed_after: DateTime<Utc>,
+        changed_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Wishlist>, String> {
+        let filter = doc! {
+            "items.0": { "$exists": true },
+            "updated_at": {
+                "$gt": bson::DateTime::from_chrono(changed_after),
+                "$lte": bson::DateTime::from_chrono(changed_before),
+            },
+        };
+        let options = FindOptions::builder().sort(doc! { "updated_at": 1 }).limit(limit).build();
+
+        match self.collection.find(filter).with_options(options).await {
+            Ok(mut cursor) => {
+                let mut wishlists = Vec::new();
+                while let Some(wishlist) = cursor.try_next().await
+                    .map_err(|e| format!("Failed to retrieve wishlists: {}", e))? {
+                    wishlists.push(wishlist);
+                }
+                Ok(wishlists)
+            }
+            Err(e) => Err(format!("Failed to retrieve wishlists: {}", e))
+        }
+    }
+}
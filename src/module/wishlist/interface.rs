@@ -0,0 +1,26 @@
+use crate::module::wishlist::schema::{
+    AddWishlistItemSchema, CreateWishlistSchema, MoveToCartSchema, SaveForLaterSchema,
+    UpdateWishlistSchema, WishlistResponse,
+};
+use crate::module::cart::schema::CartResponse;
+
+#[async_trait::async_trait]
+pub trait WishlistService {
+    // List management
+    async fn create_wishlist(&self, user_id: &str, data: CreateWishlistSchema) -> Result<WishlistResponse, String>;
+    async fn get_wishlists(&self, user_id: &str) -> Result<Vec<WishlistResponse>, String>;
+    async fn get_wishlist(&self, user_id: &str, id: &str) -> Result<Option<WishlistResponse>, String>;
+    async fn update_wishlist(&self, user_id: &str, id: &str, data: UpdateWishlistSchema) -> Result<WishlistResponse, String>;
+    async fn delete_wishlist(&self, user_id: &str, id: &str) -> Result<bool, String>;
+
+    // Public, read-only view of a shared list
+    async fn get_shared_wishlist(&self, token: &str) -> Result<Option<WishlistResponse>, String>;
+
+    // Items
+    async fn add_item(&self, user_id: &str, id: &str, data: AddWishlistItemSchema) -> Result<WishlistResponse, String>;
+    async fn remove_item(&self, user_id: &str, id: &str, product_id: &str) -> Result<WishlistResponse, String>;
+
+    // Moving between cart and wishlist
+    async fn move_to_cart(&self, user_id: &str, id: &str, product_id: &str, data: MoveToCartSchema) -> Result<CartResponse, String>;
+    async fn save_for_later(&self, user_id: &str, data: SaveForLaterSchema) -> Result<WishlistResponse, String>;
+}
@@ -0,0 +1,18 @@
+use bson::doc;
+use mongodb::{options::IndexOptions, IndexModel};
+use crate::service::migration_service::Migration;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::indexes("wishlist", 1, "wishlist name and share token indexes", "wishlists", vec![
+            IndexModel::builder()
+                .keys(doc! { "user_id": 1, "name": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "share_token": 1 })
+                .options(IndexOptions::builder().unique(true).sparse(true).build())
+                .build(),
+        ]),
+    ]
+}
@@ -0,0 +1,82 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WishlistItem {
+    pub product_id: ObjectId,
+
+    // Set when the item was saved from a cart line for one of the product's variants
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant_id: Option<ObjectId>,
+
+    // Display price in kobo when the item was saved
+    pub price_when_added: i64,
+
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Wishlist {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub user_id: ObjectId,
+    pub name: String,
+
+    // Set only while the list is shared; anyone holding it can view the list
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub share_token: Option<String>,
+
+    pub items: Vec<WishlistItem>,
+
+    // Timestamps
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+// Name of the list that "save for later" uses when no list is given
+pub const SAVED_FOR_LATER: &str = "Saved for later";
+
+impl Wishlist {
+    pub fn new(user_id: ObjectId, name: String) -> Self {
+        let now = Utc::now();
+
+        Self {
+            id: None,
+            user_id,
+            name,
+            share_token: None,
+            items: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    // Helper method to share or unshare the list. Sharing again issues a new
+    // token, so links handed out earlier stop working.
+    pub fn set_shared(&mut self, shared: bool) {
+        self.share_token = if shared {
+            Some(Uuid::new_v4().simple().to_string())
+        } else {
+            None
+        };
+    }
+
+    // Helper method to check whether a product is on the list
+    pub fn contains(&self, product_id: &ObjectId) -> bool {
+        self.items.iter().any(|item| &item.product_id == product_id)
+    }
+
+    // Helper method to find the item for a product, or for one of its variants
+    // when a variant is given
+    pub fn find_item(&self, product_id: &ObjectId, variant_id: Option<&ObjectId>) -> Option<&WishlistItem> {
+        self.items.iter().find(|item| {
+            &item.product_id == product_id && variant_id.is_none_or(|id| item.variant_id.as_ref() == Some(id))
+        })
+    }
+}
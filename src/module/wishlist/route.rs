@@ -0,0 +1,252 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use crate::middleware::Authentication;
+use crate::module::cart::crud::CartCrud;
+use crate::module::product::crud::ProductCrud;
+use crate::module::wishlist::{
+    controller::WishlistController,
+    crud::WishlistCrud,
+    interface::WishlistService,
+    schema::{AddWishlistItemSchema, CreateWishlistSchema, MoveToCartSchema, SaveForLaterSchema, UpdateWishlistSchema},
+};
+
+fn controller(
+    crud: &web::Data<WishlistCrud>,
+    product_crud: &web::Data<ProductCrud>,
+    cart_crud: &web::Data<CartCrud>,
+) -> WishlistController {
+    WishlistController::new(crud.get_ref().clone(), product_crud.get_ref().clone(), cart_crud.get_ref().clone())
+}
+
+// GET user's wishlists handler
+async fn get_wishlists_handler(
+    req: HttpRequest,
+    crud: web::Data<WishlistCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &cart_crud).get_wishlists(&claims.sub).await {
+        Ok(wishlists) => Ok(HttpResponse::Ok().json(wishlists)),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST wishlist handler
+async fn create_wishlist_handler(
+    req: HttpRequest,
+    wishlist: web::Json<CreateWishlistSchema>,
+    crud: web::Data<WishlistCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &cart_crud).create_wishlist(&claims.sub, wishlist.into_inner()).await {
+        Ok(wishlist) => Ok(HttpResponse::Created().json(wishlist)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET single wishlist handler
+async fn get_wishlist_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    crud: web::Data<WishlistCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &cart_crud).get_wishlist(&claims.sub, &id).await {
+        Ok(Some(wishlist)) => Ok(HttpResponse::Ok().json(wishlist)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Wishlist not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// PATCH wishlist handler (rename, share or unshare)
+async fn update_wishlist_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    wishlist: web::Json<UpdateWishlistSchema>,
+    crud: web::Data<WishlistCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &cart_crud).update_wishlist(&claims.sub, &id, wishlist.into_inner()).await {
+        Ok(wishlist) => Ok(HttpResponse::Ok().json(wishlist)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE wishlist handler
+async fn delete_wishlist_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    crud: web::Data<WishlistCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &cart_crud).delete_wishlist(&claims.sub, &id).await {
+        Ok(true) => Ok(HttpResponse::NoContent().finish()),
+        Ok(false) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Wishlist not found"
+        }))),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// GET shared wishlist handler (public)
+async fn get_shared_wishlist_handler(
+    token: web::Path<String>,
+    crud: web::Data<WishlistCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+) -> Result<HttpResponse> {
+    match controller(&crud, &product_crud, &cart_crud).get_shared_wishlist(&token).await {
+        Ok(Some(wishlist)) => Ok(HttpResponse::Ok().json(wishlist)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Wishlist not found"
+        }))),
+        Err(error) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST wishlist item handler
+async fn add_item_handler(
+    req: HttpRequest,
+    id: web::Path<String>,
+    item: web::Json<AddWishlistItemSchema>,
+    crud: web::Data<WishlistCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &cart_crud).add_item(&claims.sub, &id, item.into_inner()).await {
+        Ok(wishlist) => Ok(HttpResponse::Ok().json(wishlist)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// DELETE wishlist item handler
+async fn remove_item_handler(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    crud: web::Data<WishlistCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+    let (id, product_id) = path.into_inner();
+
+    match controller(&crud, &product_crud, &cart_crud).remove_item(&claims.sub, &id, &product_id).await {
+        Ok(wishlist) => Ok(HttpResponse::Ok().json(wishlist)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST move wishlist item to cart handler
+async fn move_to_cart_handler(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    body: web::Json<MoveToCartSchema>,
+    crud: web::Data<WishlistCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+    let (id, product_id) = path.into_inner();
+
+    match controller(&crud, &product_crud, &cart_crud).move_to_cart(&claims.sub, &id, &product_id, body.into_inner()).await {
+        Ok(cart) => Ok(HttpResponse::Ok().json(cart)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// POST save cart item for later handler
+async fn save_for_later_handler(
+    req: HttpRequest,
+    body: web::Json<SaveForLaterSchema>,
+    crud: web::Data<WishlistCrud>,
+    product_crud: web::Data<ProductCrud>,
+    cart_crud: web::Data<CartCrud>,
+) -> Result<HttpResponse> {
+    let claims = match Authentication::check_user(&req).await {
+        Ok(claims) => claims,
+        Err(e) => return Ok(e.error_response()),
+    };
+
+    match controller(&crud, &product_crud, &cart_crud).save_for_later(&claims.sub, body.into_inner()).await {
+        Ok(wishlist) => Ok(HttpResponse::Ok().json(wishlist)),
+        Err(error) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        }))),
+    }
+}
+
+// Route configuration function
+pub fn wishlist_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/wishlists")
+            .route("", web::get().to(get_wishlists_handler))
+            .route("", web::post().to(create_wishlist_handler))
+            .route("/shared/{token}", web::get().to(get_shared_wishlist_handler))
+            .route("/save-for-later", web::post().to(save_for_later_handler))
+            .route("/{id}", web::get().to(get_wishlist_handler))
+            .route("/{id}", web::patch().to(update_wishlist_handler))
+            .route("/{id}", web::delete().to(delete_wishlist_handler))
+            .route("/{id}/items", web::post().to(add_item_handler))
+            .route("/{id}/items/{product_id}", web::delete().to(remove_item_handler))
+            .route("/{id}/items/{product_id}/move-to-cart", web::post().to(move_to_cart_handler))
+    );
+}
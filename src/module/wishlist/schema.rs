@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct CreateWishlistSchema {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    #[serde(default)]
+    pub is_shared: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct UpdateWishlistSchema {
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+
+    pub is_shared: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AddWishlistItemSchema {
+    pub product_id: String,  // Will be converted to ObjectId
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Clone)]
+pub struct MoveToCartSchema {
+    #[validate(range(min = 1, max = 100))]
+    pub quantity: Option<i32>,
+    // Defaults to the variant the item was saved with
+    pub variant_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveForLaterSchema {
+    pub product_id: String,
+    // The cart line's variant, when the product is sold in variants
+    pub variant_id: Option<String>,
+    // Defaults to the user's "Saved for later" list
+    pub wishlist_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WishlistItemResponse {
+    pub product_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    pub price_when_added: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_price: Option<i64>,
+    // Negative when the product got cheaper
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_change: Option<i64>,
+    pub in_stock: bool,
+    pub available: bool,
+    pub added_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WishlistResponse {
+    pub id: String,
+    pub name: String,
+    pub is_shared: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub share_token: Option<String>,
+    pub items: Vec<WishlistItemResponse>,
+    pub created_at: String,
+    pub updated_at: String,
+}
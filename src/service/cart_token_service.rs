@@ -0,0 +1,90 @@
+use actix_web::HttpRequest;
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use std::env;
+use uuid::Uuid;
+
+pub const CART_TOKEN_HEADER: &str = "X-Cart-Token";
+pub const CART_TOKEN_COOKIE: &str = "cart_token";
+const CART_TOKEN_TYPE: &str = "cart";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CartTokenClaims {
+    pub sub: String,  // Guest cart ID
+    pub exp: i64,     // Expiration time
+    pub iat: i64,     // Issued at
+    #[serde(default)]
+    pub typ: String,  // Always "cart", so a user auth token is never accepted here
+}
+
+/// Issues and verifies the signed tokens that identify anonymous (guest) carts.
+pub struct CartTokenService {
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    ttl: Duration,
+}
+
+impl CartTokenService {
+    pub fn new() -> Self {
+        // Fall back to the JWT secret so a dedicated key is optional
+        let secret = env::var("CART_TOKEN_SECRET")
+            .or_else(|_| env::var("JWT_SECRET"))
+            .expect("CART_TOKEN_SECRET or JWT_SECRET must be set");
+
+        Self {
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            ttl: Duration::days(guest_cart_ttl_days()),
+        }
+    }
+
+    /// Creates a new guest cart ID and returns it together with its signed token.
+    pub fn issue(&self) -> Result<(String, String), String> {
+        let now = Utc::now();
+        let cart_id = Uuid::new_v4().to_string();
+        let claims = CartTokenClaims {
+            sub: cart_id.clone(),
+            exp: (now + self.ttl).timestamp(),
+            iat: now.timestamp(),
+            typ: CART_TOKEN_TYPE.to_string(),
+        };
+
+        let token = encode(&Header::default(), &claims, &self.encoding_key)
+            .map_err(|e| format!("Failed to sign cart token: {}", e))?;
+        Ok((cart_id, token))
+    }
+
+    /// Returns the guest cart ID carried by a valid token.
+    pub fn verify(&self, token: &str) -> Result<String, String> {
+        decode::<CartTokenClaims>(token, &self.decoding_key, &Validation::default())
+            .ok()
+            .filter(|data| data.claims.typ == CART_TOKEN_TYPE)
+            .map(|data| data.claims.sub)
+            .ok_or_else(|| "Invalid or expired cart token".to_string())
+    }
+
+    /// Reads the cart token from the `X-Cart-Token` header, falling back to the `cart_token` cookie.
+    pub fn token_from_request(req: &HttpRequest) -> Option<String> {
+        req.headers()
+            .get(CART_TOKEN_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .map(|s| s.to_string())
+            .or_else(|| req.cookie(CART_TOKEN_COOKIE).map(|c| c.value().to_string()))
+            .filter(|s| !s.is_empty())
+    }
+}
+
+impl Default for CartTokenService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How long a guest cart lives without activity, from `GUEST_CART_TTL_DAYS` (default 30).
+pub fn guest_cart_ttl_days() -> i64 {
+    env::var("GUEST_CART_TTL_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(30)
+}
@@ -0,0 +1,25 @@
+/// Formats kobo as naira for customers, e.g. 123456789 -> "₦1,234,567.89".
+pub fn format_naira(kobo: i64) -> String {
+    format_kobo(kobo, "₦")
+}
+
+/// Formats kobo with the currency code, e.g. 123456789 -> "NGN 1,234,567.89".
+/// For PDFs, whose built-in fonts have no naira sign.
+pub fn format_naira_code(kobo: i64) -> String {
+    format_kobo(kobo, "NGN ")
+}
+
+fn format_kobo(kobo: i64, prefix: &str) -> String {
+    let sign = if kobo < 0 { "-" } else { "" };
+    let kobo = kobo.unsigned_abs();
+    let naira = (kobo / 100).to_string();
+
+    let mut grouped = String::new();
+    for (index, digit) in naira.chars().enumerate() {
+        if index > 0 && (naira.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{}{}{}.{:02}", sign, prefix, grouped, kobo % 100)
+}
@@ -1,4 +1,5 @@
 use lettre::{
+    message::{header::ContentType, Attachment, MultiPart, SinglePart},
     transport::smtp::{
         authentication::Credentials,
         SmtpTransport,
@@ -8,8 +9,26 @@ use lettre::{
 };
 use std::env;
 
+/// A file sent along with an email, e.g. a PDF invoice.
+pub struct EmailAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
 /// Sends an email using the configured Gmail SMTP server.
 pub fn send_email(to: &str, to_name: &str, subject: &str, body: &str) -> Result<(), Box<dyn std::error::Error>> {
+    send_email_with_attachments(to, to_name, subject, body, Vec::new())
+}
+
+/// Sends an email with files attached.
+pub fn send_email_with_attachments(
+    to: &str,
+    to_name: &str,
+    subject: &str,
+    body: &str,
+    attachments: Vec<EmailAttachment>,
+) -> Result<(), Box<dyn std::error::Error>> {
     let smtp_server = env::var("SMTP_SERVER").expect("SMTP_SERVER must be set");
     let smtp_port: u16 = env::var("SMTP_PORT").expect("SMTP_PORT must be set").parse()?;
     let smtp_user = env::var("SMTP_USER").expect("SMTP_USER must be set");
@@ -17,12 +36,25 @@ pub fn send_email(to: &str, to_name: &str, subject: &str, body: &str) -> Result<
 
     let from_name = env::var("FROM_NAME").unwrap_or_else(|_| "Account Verification".to_string());
 
-    let email = Message::builder()
+    let builder = Message::builder()
         .from(format!("{} <{}>", from_name, smtp_user).parse()?)
         .to(format!("{} <{}>", to_name, to).parse()?)
-        .subject(subject)
-        .header(lettre::message::header::ContentType::TEXT_HTML)
-        .body(body.to_string())?;
+        .subject(subject);
+
+    let email = if attachments.is_empty() {
+        builder
+            .header(ContentType::TEXT_HTML)
+            .body(body.to_string())?
+    } else {
+        let mut parts = MultiPart::mixed().singlepart(SinglePart::html(body.to_string()));
+        for attachment in attachments {
+            parts = parts.singlepart(
+                Attachment::new(attachment.filename)
+                    .body(attachment.data, ContentType::parse(&attachment.content_type)?),
+            );
+        }
+        builder.multipart(parts)?
+    };
 
     let creds = Credentials::new(smtp_user, smtp_password);
     
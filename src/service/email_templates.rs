@@ -88,3 +88,427 @@ pub fn get_verification_email_template(name: &str, code: &str) -> (String, Strin
 
     (subject, body)
 }
+
+/// Creates the HTML content for a return (RMA) status update email.
+pub fn get_return_status_email_template(name: &str, rma_number: &str, headline: &str, message: &str) -> (String, String) {
+    let subject = format!("Return {}: {}", rma_number, headline);
+
+    let body = format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Return Update</title>
+    <style>
+        body {{
+            font-family: Arial, sans-serif;
+            line-height: 1.6;
+            color: #333333;
+            max-width: 600px;
+            margin: 0 auto;
+            padding: 20px;
+        }}
+        .container {{
+            background-color: #ffffff;
+            border-radius: 5px;
+            padding: 30px;
+            box-shadow: 0 2px 5px rgba(0,0,0,0.1);
+        }}
+        .header {{
+            text-align: center;
+            padding-bottom: 20px;
+            border-bottom: 1px solid #eeeeee;
+        }}
+        .rma-number {{
+            background-color: #f5f5f5;
+            font-size: 20px;
+            font-weight: bold;
+            text-align: center;
+            padding: 15px;
+            margin: 20px 0;
+            letter-spacing: 2px;
+            border-radius: 5px;
+        }}
+        .footer {{
+            margin-top: 30px;
+            font-size: 12px;
+            color: #666666;
+            text-align: center;
+        }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <div class="header">
+            <h2>{}</h2>
+        </div>
+        
+        <p>Hello {},</p>
+        
+        <p>{}</p>
+        
+        <div class="rma-number">
+            {}
+        </div>
+        
+        <p>Please quote this return number if you contact us about this request.</p>
+        
+        <div class="footer">
+            <p>This is an automated message, please do not reply to this email.</p>
+            <p>&copy; 2024 Your Application Name. All rights reserved.</p>
+        </div>
+    </div>
+</body>
+</html>"#,
+        headline, name, message, rma_number
+    );
+
+    (subject, body)
+}
+
+/// Creates the HTML content for an email carrying an invoice or receipt PDF.
+pub fn get_invoice_email_template(name: &str, document: &str, number: &str, order_number: &str) -> (String, String) {
+    let subject = format!("Your {} {} for order {}", document, number, order_number);
+
+    let body = format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Your {}</title>
+    <style>
+        body {{
+            font-family: Arial, sans-serif;
+            line-height: 1.6;
+            color: #333333;
+            max-width: 600px;
+            margin: 0 auto;
+            padding: 20px;
+        }}
+        .container {{
+            background-color: #ffffff;
+            border-radius: 5px;
+            padding: 30px;
+            box-shadow: 0 2px 5px rgba(0,0,0,0.1);
+        }}
+        .footer {{
+            margin-top: 30px;
+            font-size: 12px;
+            color: #666666;
+            text-align: center;
+        }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <p>Hello {},</p>
+        
+        <p>Please find attached your {} <strong>{}</strong> for order <strong>{}</strong>.</p>
+        
+        <div class="footer">
+            <p>This is an automated message, please do not reply to this email.</p>
+            <p>&copy; 2024 Your Application Name. All rights reserved.</p>
+        </div>
+    </div>
+</body>
+</html>"#,
+        document, name, document, number, order_number
+    );
+
+    (subject, body)
+}
+
+/// A product line shown in a basket reminder email.
+pub struct ReminderEmailItem {
+    pub name: String,
+    pub image_url: Option<String>,
+    pub quantity: i32,
+    pub price: String,
+}
+
+/// Creates the HTML content for an abandoned basket or wishlist reminder.
+pub fn get_basket_reminder_email_template(
+    name: &str,
+    subject: &str,
+    message: &str,
+    items: &[ReminderEmailItem],
+    basket_url: &str,
+    unsubscribe_url: &str,
+) -> (String, String) {
+    let rows: String = items.iter()
+        .map(|item| {
+            let image = match &item.image_url {
+                Some(url) => format!(
+                    r#"<img src="{}" alt="{}" width="80" style="border-radius: 4px;">"#,
+                    escape_html(url),
+                    escape_html(&item.name)
+                ),
+                None => String::new(),
+            };
+            format!(
+                r#"<tr><td class="image">{}</td><td>{}<br><span class="quantity">Qty {}</span></td><td class="price">{}</td></tr>"#,
+                image, escape_html(&item.name), item.quantity, escape_html(&item.price)
+            )
+        })
+        .collect();
+
+    let body = format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>{}</title>
+    <style>
+        body {{
+            font-family: Arial, sans-serif;
+            line-height: 1.6;
+            color: #333333;
+            max-width: 600px;
+            margin: 0 auto;
+            padding: 20px;
+        }}
+        .container {{
+            background-color: #ffffff;
+            border-radius: 5px;
+            padding: 30px;
+            box-shadow: 0 2px 5px rgba(0,0,0,0.1);
+        }}
+        table {{
+            width: 100%;
+            border-collapse: collapse;
+            margin: 20px 0;
+        }}
+        td {{
+            padding: 10px 5px;
+            border-bottom: 1px solid #eeeeee;
+            vertical-align: middle;
+        }}
+        .image {{
+            width: 90px;
+        }}
+        .quantity {{
+            color: #666666;
+            font-size: 13px;
+        }}
+        .price {{
+            text-align: right;
+            font-weight: bold;
+            white-space: nowrap;
+        }}
+        .button {{
+            display: inline-block;
+            background-color: #1c4073;
+            color: #ffffff;
+            padding: 12px 24px;
+            border-radius: 5px;
+            text-decoration: none;
+        }}
+        .footer {{
+            margin-top: 30px;
+            font-size: 12px;
+            color: #666666;
+            text-align: center;
+        }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <p>Hello {},</p>
+        
+        <p>{}</p>
+        
+        <table>{}</table>
+        
+        <p style="text-align: center;"><a class="button" href="{}">Continue shopping</a></p>
+        
+        <div class="footer">
+            <p>Prices shown are today's prices and may change.</p>
+            <p><a href="{}">Stop these reminders</a></p>
+            <p>&copy; 2024 Your Application Name. All rights reserved.</p>
+        </div>
+    </div>
+</body>
+</html>"#,
+        escape_html(subject),
+        escape_html(name),
+        escape_html(message),
+        rows,
+        escape_html(basket_url),
+        escape_html(unsubscribe_url)
+    );
+
+    (subject.to_string(), body)
+}
+
+/// The page an unsubscribe link opens. Opening the link changes nothing (mail
+/// scanners follow links); the customer confirms with the button, which posts
+/// the token back to `action_url`.
+pub fn get_unsubscribe_page(action_url: &str, token: &str) -> String {
+    unsubscribe_page_layout(&format!(
+        r#"<p>Stop emails reminding you about items left in your cart or wishlist?</p>
+        <form method="post" action="{}">
+            <input type="hidden" name="token" value="{}">
+            <button class="button" type="submit">Unsubscribe</button>
+        </form>"#,
+        escape_html(action_url),
+        escape_html(token)
+    ))
+}
+
+/// The page shown once an unsubscribe request has been handled.
+pub fn get_unsubscribe_result_page(message: &str) -> String {
+    unsubscribe_page_layout(&format!("<p>{}</p>", escape_html(message)))
+}
+
+fn unsubscribe_page_layout(content: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Basket reminders</title>
+    <style>
+        body {{
+            font-family: Arial, sans-serif;
+            line-height: 1.6;
+            color: #333333;
+            max-width: 600px;
+            margin: 0 auto;
+            padding: 20px;
+        }}
+        .container {{
+            background-color: #ffffff;
+            border-radius: 5px;
+            padding: 30px;
+            box-shadow: 0 2px 5px rgba(0,0,0,0.1);
+            text-align: center;
+        }}
+        .button {{
+            background-color: #1c4073;
+            color: #ffffff;
+            padding: 12px 24px;
+            border: none;
+            border-radius: 5px;
+            font-size: 15px;
+            cursor: pointer;
+        }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        {}
+    </div>
+</body>
+</html>"#,
+        content
+    )
+}
+
+// Customers' own words go into the HTML, so they are escaped
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Creates the HTML content for telling a customer their product question was answered.
+pub fn get_question_answered_email_template(
+    name: &str,
+    product_name: &str,
+    question: &str,
+    answer: &str,
+    answered_by: &str,
+    product_url: &str,
+) -> (String, String) {
+    let subject = format!("Your question about {} has been answered", product_name);
+
+    let body = format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Your Question Was Answered</title>
+    <style>
+        body {{
+            font-family: Arial, sans-serif;
+            line-height: 1.6;
+            color: #333333;
+            max-width: 600px;
+            margin: 0 auto;
+            padding: 20px;
+        }}
+        .container {{
+            background-color: #ffffff;
+            border-radius: 5px;
+            padding: 30px;
+            box-shadow: 0 2px 5px rgba(0,0,0,0.1);
+        }}
+        .question {{
+            background-color: #f5f5f5;
+            padding: 15px;
+            margin: 20px 0 10px;
+            border-radius: 5px;
+            font-weight: bold;
+        }}
+        .answer {{
+            padding: 15px;
+            border-left: 3px solid #1c4073;
+            margin-bottom: 20px;
+        }}
+        .answered-by {{
+            color: #666666;
+            font-size: 13px;
+        }}
+        .button {{
+            display: inline-block;
+            background-color: #1c4073;
+            color: #ffffff;
+            padding: 12px 24px;
+            border-radius: 5px;
+            text-decoration: none;
+        }}
+        .footer {{
+            margin-top: 30px;
+            font-size: 12px;
+            color: #666666;
+            text-align: center;
+        }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <p>Hello {},</p>
+        
+        <p>There's a new answer to the question you asked about <strong>{}</strong>.</p>
+        
+        <div class="question">{}</div>
+        <div class="answer">
+            {}
+            <p class="answered-by">Answered by {}</p>
+        </div>
+        
+        <p style="text-align: center;"><a class="button" href="{}">See all answers</a></p>
+        
+        <div class="footer">
+            <p>This is an automated message, please do not reply to this email.</p>
+            <p>&copy; 2024 Your Application Name. All rights reserved.</p>
+        </div>
+    </div>
+</body>
+</html>"#,
+        escape_html(name),
+        escape_html(product_name),
+        escape_html(question),
+        escape_html(answer),
+        escape_html(answered_by),
+        product_url
+    );
+
+    (subject, body)
+}
@@ -0,0 +1,15 @@
+// Base URLs for links in emails and API responses (env STOREFRONT_URL and
+// PUBLIC_API_URL), without a trailing slash
+pub fn storefront_url() -> String {
+    std::env::var("STOREFRONT_URL")
+        .unwrap_or_else(|_| "http://localhost:3000".to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+pub fn public_api_url() -> String {
+    std::env::var("PUBLIC_API_URL")
+        .unwrap_or_else(|_| "http://127.0.0.1:8080".to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
@@ -0,0 +1,220 @@
+use std::collections::HashSet;
+use std::future::Future;
+use bson::doc;
+use chrono::{DateTime, Utc};
+use futures_util::{future::BoxFuture, TryStreamExt};
+use mongodb::{
+    error::{Error, ErrorKind},
+    options::IndexOptions,
+    Collection, Database, IndexModel,
+};
+use serde::{Deserialize, Serialize};
+use crate::module::{
+    admin, banner, brand, cart, category, comparison, compatibility, invoice, order, payment, product, promotion, question,
+    recommendation, recovery, returns, review, search, shipping, tax, user, wishlist,
+};
+
+// Server error codes for an index that clashes with one already built
+const INDEX_OPTIONS_CONFLICT: i32 = 85;
+const INDEX_KEY_SPECS_CONFLICT: i32 = 86;
+
+/// A data migration: reads the database and returns how many documents it changed.
+pub type DataStep = Box<dyn Fn(Database) -> BoxFuture<'static, Result<u64, String>> + Send + Sync>;
+
+pub enum MigrationStep {
+    /// Builds indexes on a collection. Building an index that already exists
+    /// with the same options does nothing.
+    CreateIndexes { collection: &'static str, indexes: Vec<IndexModel> },
+    Data(DataStep),
+}
+
+/// One versioned change to the database, owned by a module. Versions start
+/// at 1 per module and are applied in order, each at most once.
+pub struct Migration {
+    pub module: &'static str,
+    pub version: u32,
+    pub description: &'static str,
+    pub step: MigrationStep,
+}
+
+impl Migration {
+    pub fn indexes(
+        module: &'static str,
+        version: u32,
+        description: &'static str,
+        collection: &'static str,
+        indexes: Vec<IndexModel>,
+    ) -> Self {
+        Self { module, version, description, step: MigrationStep::CreateIndexes { collection, indexes } }
+    }
+
+    pub fn data<F, Fut>(module: &'static str, version: u32, description: &'static str, run: F) -> Self
+    where
+        F: Fn(Database) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<u64, String>> + Send + 'static,
+    {
+        let step: DataStep = Box::new(move |db| Box::pin(run(db)));
+        Self { module, version, description, step: MigrationStep::Data(step) }
+    }
+
+    fn name(&self) -> String {
+        format!("{} v{} ({})", self.module, self.version, self.description)
+    }
+}
+
+/// A migration recorded in the `migrations` collection once it has run.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppliedMigration {
+    pub module: String,
+    pub version: u32,
+    pub description: String,
+    // Documents changed by a data migration; 0 for indexes
+    pub changed: u64,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    pub applied_at: DateTime<Utc>,
+}
+
+/// Every module's migrations. Modules whose data others read come first, so
+/// brands exist before product brand names are copied from them.
+pub fn all_migrations() -> Vec<Migration> {
+    [
+        user::migration::migrations(),
+        admin::migration::migrations(),
+        banner::migration::migrations(),
+        brand::migration::migrations(),
+        category::migration::migrations(),
+        product::migration::migrations(),
+        review::migration::migrations(),
+        question::migration::migrations(),
+        recommendation::migration::migrations(),
+        comparison::migration::migrations(),
+        compatibility::migration::migrations(),
+        search::migration::migrations(),
+        cart::migration::migrations(),
+        wishlist::migration::migrations(),
+        order::migration::migrations(),
+        promotion::migration::migrations(),
+        shipping::migration::migrations(),
+        tax::migration::migrations(),
+        payment::migration::migrations(),
+        returns::migration::migrations(),
+        invoice::migration::migrations(),
+        recovery::migration::migrations(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Whether a write failed because another document already has the value of
+/// `field`, going by the default index name, e.g. `email_1`.
+pub fn is_duplicate_key(error: &Error, field: &str) -> bool {
+    let message = error.to_string();
+    message.contains("E11000") && message.contains(&format!("index: {}_", field))
+}
+
+// Versions must run 1, 2, 3, ... within a module with no repeats
+fn check_declared(migrations: &[Migration]) -> Result<(), String> {
+    let mut modules: Vec<&str> = migrations.iter().map(|migration| migration.module).collect();
+    modules.dedup();
+    for module in modules {
+        let versions: Vec<u32> = migrations.iter()
+            .filter(|migration| migration.module == module)
+            .map(|migration| migration.version)
+            .collect();
+        if versions.iter().copied().ne(1..=versions.len() as u32) {
+            return Err(format!("Migrations for {} must be numbered 1, 2, 3, ... in order, found {:?}", module, versions));
+        }
+    }
+    Ok(())
+}
+
+// Explain the errors an operator can do something about
+fn describe_error(error: &Error) -> String {
+    let code = match error.kind.as_ref() {
+        ErrorKind::Command(command) => Some(command.code),
+        _ => None,
+    };
+    match code {
+        Some(INDEX_OPTIONS_CONFLICT) | Some(INDEX_KEY_SPECS_CONFLICT) => format!(
+            "an index with the same name or keys already exists with different options; drop it and run again ({})",
+            error,
+        ),
+        _ if error.to_string().contains("E11000") => format!(
+            "existing documents share a value that must be unique; remove the duplicates and run again ({})",
+            error,
+        ),
+        _ => error.to_string(),
+    }
+}
+
+async fn apply(db: &Database, migration: &Migration) -> Result<u64, String> {
+    match &migration.step {
+        MigrationStep::CreateIndexes { collection, indexes } => {
+            db.collection::<bson::Document>(collection)
+                .create_indexes(indexes.clone())
+                .await
+                .map_err(|e| describe_error(&e))?;
+            Ok(0)
+        }
+        MigrationStep::Data(run) => run(db.clone()).await,
+    }
+}
+
+/// Runs the migrations this database hasn't had yet, in order, and returns
+/// the names of those applied. Fails without going further on the first error.
+/// A recorded migration this build doesn't know about only gets a warning, so
+/// an older build can still start during a rollback.
+///
+/// Every step can safely run twice, so two servers starting together may both
+/// apply a step; only one record is kept.
+pub async fn run_migrations(db: &Database, migrations: Vec<Migration>) -> Result<Vec<String>, String> {
+    check_declared(&migrations)?;
+
+    let applied_collection: Collection<AppliedMigration> = db.collection("migrations");
+    let index = IndexModel::builder()
+        .keys(doc! { "module": 1, "version": 1 })
+        .options(IndexOptions::builder().unique(true).build())
+        .build();
+    applied_collection.create_index(index).await
+        .map_err(|e| format!("Failed to create migration indexes: {}", describe_error(&e)))?;
+
+    let mut cursor = applied_collection.find(doc! {}).await
+        .map_err(|e| format!("Failed to read applied migrations: {}", e))?;
+    let mut applied = HashSet::new();
+    while let Some(record) = cursor.try_next().await
+        .map_err(|e| format!("Failed to read applied migrations: {}", e))? {
+        applied.insert((record.module, record.version));
+    }
+
+    // A record with no migration behind it means a newer build has already
+    // changed this database
+    for (module, version) in applied.iter()
+        .filter(|(module, version)| !migrations.iter().any(|m| m.module == module && m.version == *version)) {
+        eprintln!(
+            "Warning: the database has migration {} v{}, which this build doesn't know about",
+            module, version,
+        );
+    }
+
+    let mut names = Vec::new();
+    for migration in migrations.iter().filter(|m| !applied.contains(&(m.module.to_string(), m.version))) {
+        let changed = apply(db, migration).await
+            .map_err(|e| format!("Migration {} failed: {}", migration.name(), e))?;
+
+        let record = AppliedMigration {
+            module: migration.module.to_string(),
+            version: migration.version,
+            description: migration.description.to_string(),
+            changed,
+            applied_at: Utc::now(),
+        };
+        match applied_collection.insert_one(&record).await {
+            Ok(_) => {}
+            Err(e) if is_duplicate_key(&e, "module") => {}
+            Err(e) => return Err(format!("Failed to record migration {}: {}", migration.name(), e)),
+        }
+        names.push(migration.name());
+    }
+    Ok(names)
+}
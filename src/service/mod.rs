@@ -3,3 +3,11 @@ pub mod email_templates;
 pub mod google_oauth_service;
 pub mod jwt_service;
 pub mod upload;
+pub mod cart_token_service;
+pub mod payment;
+pub mod pdf_service;
+pub mod slug_service;
+pub mod currency_service;
+pub mod migration_service;
+pub mod pagination_service;
+pub mod link_service;
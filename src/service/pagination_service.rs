@@ -0,0 +1,15 @@
+// Page size used when a list request doesn't ask for one
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+// Deepest page a list can be read to. Keeps the skip small enough to compute
+// without overflowing, and stops scans of millions of skipped documents.
+pub const MAX_PAGE: u64 = 10_000;
+
+/// Reads the `page` and `limit` query parameters into (page, limit, skip).
+/// Pages start at 1 and are capped at `MAX_PAGE`; limits are clamped to 1..=100.
+pub fn paginate(page: Option<u64>, limit: Option<i64>) -> (u64, i64, u64) {
+    let page = page.unwrap_or(1).clamp(1, MAX_PAGE);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    (page, limit, (page - 1) * limit as u64)
+}
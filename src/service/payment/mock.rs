@@ -0,0 +1,79 @@
+use chrono::Utc;
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+
+use crate::service::payment::provider::{
+    signature_matches, InitializePayment, PaymentProvider, PaymentSession, PaymentVerification,
+    ProviderPaymentStatus, RefundResult,
+};
+
+/// In-memory gateway for local development: every initialized payment
+/// verifies as paid. Webhooks are signed with PAYMENT_MOCK_SECRET.
+pub struct MockPaymentProvider {
+    secret: String,
+    payments: Mutex<HashMap<String, (i64, String)>>,
+}
+
+impl MockPaymentProvider {
+    pub fn new() -> Self {
+        Self {
+            secret: env::var("PAYMENT_MOCK_SECRET").unwrap_or_else(|_| "mock_secret".to_string()),
+            payments: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for MockPaymentProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentProvider for MockPaymentProvider {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn initialize(&self, request: &InitializePayment) -> Result<PaymentSession, String> {
+        self.payments.lock()
+            .map_err(|_| "Mock payment store is unavailable".to_string())?
+            .insert(request.reference.clone(), (request.amount, request.currency.clone()));
+
+        Ok(PaymentSession {
+            reference: request.reference.clone(),
+            authorization_url: format!("https://mock.payments.local/pay/{}", request.reference),
+            access_code: format!("mock_{}", request.reference),
+        })
+    }
+
+    async fn verify(&self, reference: &str) -> Result<PaymentVerification, String> {
+        let payments = self.payments.lock()
+            .map_err(|_| "Mock payment store is unavailable".to_string())?;
+        let (amount, currency) = payments.get(reference)
+            .cloned()
+            .ok_or_else(|| "Transaction reference not found".to_string())?;
+
+        Ok(PaymentVerification {
+            reference: reference.to_string(),
+            status: ProviderPaymentStatus::Success,
+            amount,
+            currency,
+            paid_at: Some(Utc::now()),
+        })
+    }
+
+    async fn refund(&self, reference: &str, amount: Option<i64>) -> Result<RefundResult, String> {
+        let paid = self.verify(reference).await?.amount;
+        Ok(RefundResult {
+            reference: reference.to_string(),
+            status: "processed".to_string(),
+            amount: amount.unwrap_or(paid),
+        })
+    }
+
+    fn verify_webhook_signature(&self, payload: &[u8], signature: &str) -> bool {
+        signature_matches(&self.secret, payload, signature)
+    }
+}
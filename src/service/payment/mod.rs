@@ -0,0 +1,19 @@
+pub mod mock;
+pub mod paystack;
+pub mod provider;
+
+use std::env;
+use std::sync::Arc;
+
+pub use mock::MockPaymentProvider;
+pub use paystack::PaystackProvider;
+pub use provider::PaymentProvider;
+
+/// Picks the gateway from PAYMENT_PROVIDER ("paystack" or "mock", default paystack).
+/// Fails when the chosen gateway isn't configured.
+pub fn provider_from_env() -> Result<Arc<dyn PaymentProvider>, String> {
+    match env::var("PAYMENT_PROVIDER").as_deref() {
+        Ok("mock") => Ok(Arc::new(MockPaymentProvider::new())),
+        _ => Ok(Arc::new(PaystackProvider::from_env()?)),
+    }
+}
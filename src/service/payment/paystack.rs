@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use std::env;
+
+use crate::service::payment::provider::{
+    signature_matches, InitializePayment, PaymentProvider, PaymentSession, PaymentVerification,
+    ProviderPaymentStatus, RefundResult,
+};
+
+const DEFAULT_BASE_URL: &str = "https://api.paystack.co";
+
+// Every Paystack response is wrapped in this envelope
+#[derive(Debug, Deserialize)]
+struct PaystackResponse<T> {
+    status: bool,
+    message: String,
+    data: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InitializeData {
+    authorization_url: String,
+    access_code: String,
+    reference: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerifyData {
+    reference: String,
+    status: String,
+    amount: i64,
+    currency: String,
+    paid_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RefundData {
+    status: String,
+    amount: i64,
+}
+
+/// Paystack, or anything that speaks its API (e.g. a local mock server).
+pub struct PaystackProvider {
+    client: Client,
+    base_url: String,
+    secret_key: String,
+}
+
+impl PaystackProvider {
+    pub fn new(secret_key: String, base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            secret_key,
+        }
+    }
+
+    pub fn from_env() -> Result<Self, String> {
+        let secret_key = env::var("PAYSTACK_SECRET_KEY")
+            .map_err(|_| "PAYSTACK_SECRET_KEY must be set, or PAYMENT_PROVIDER=mock for local development".to_string())?;
+        let base_url = env::var("PAYSTACK_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Ok(Self::new(secret_key, base_url))
+    }
+
+    // Unwrap the envelope, turning `status: false` into an error
+    async fn parse<T: for<'de> Deserialize<'de>>(response: reqwest::Response, action: &str) -> Result<T, String> {
+        let http_status = response.status();
+        let body: PaystackResponse<T> = response.json().await
+            .map_err(|e| format!("Failed to read Paystack {} response ({}): {}", action, http_status, e))?;
+
+        if !body.status {
+            return Err(format!("Paystack {} failed: {}", action, body.message));
+        }
+        body.data.ok_or_else(|| format!("Paystack {} returned no data", action))
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentProvider for PaystackProvider {
+    fn name(&self) -> &'static str {
+        "paystack"
+    }
+
+    async fn initialize(&self, request: &InitializePayment) -> Result<PaymentSession, String> {
+        let response = self.client
+            .post(format!("{}/transaction/initialize", self.base_url))
+            .bearer_auth(&self.secret_key)
+            .json(&json!({
+                "reference": request.reference,
+                "email": request.email,
+                "amount": request.amount,
+                "currency": request.currency,
+                "callback_url": request.callback_url,
+                "metadata": request.metadata,
+            }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Paystack: {}", e))?;
+
+        let data: InitializeData = Self::parse(response, "initialize").await?;
+        Ok(PaymentSession {
+            reference: data.reference,
+            authorization_url: data.authorization_url,
+            access_code: data.access_code,
+        })
+    }
+
+    async fn verify(&self, reference: &str) -> Result<PaymentVerification, String> {
+        let response = self.client
+            .get(format!("{}/transaction/verify/{}", self.base_url, reference))
+            .bearer_auth(&self.secret_key)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Paystack: {}", e))?;
+
+        let data: VerifyData = Self::parse(response, "verify").await?;
+        Ok(PaymentVerification {
+            reference: data.reference,
+            status: ProviderPaymentStatus::parse(&data.status),
+            amount: data.amount,
+            currency: data.currency,
+            paid_at: data.paid_at,
+        })
+    }
+
+    async fn refund(&self, reference: &str, amount: Option<i64>) -> Result<RefundResult, String> {
+        let mut body = json!({ "transaction": reference });
+        if let Some(amount) = amount {
+            body["amount"] = json!(amount);
+        }
+
+        let response = self.client
+            .post(format!("{}/refund", self.base_url))
+            .bearer_auth(&self.secret_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Paystack: {}", e))?;
+
+        let data: RefundData = Self::parse(response, "refund").await?;
+        Ok(RefundResult {
+            reference: reference.to_string(),
+            status: data.status,
+            amount: data.amount,
+        })
+    }
+
+    fn verify_webhook_signature(&self, payload: &[u8], signature: &str) -> bool {
+        signature_matches(&self.secret_key, payload, signature)
+    }
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha512;
+
+/// What we ask a gateway to collect. Amounts are in kobo.
+#[derive(Debug, Clone, Serialize)]
+pub struct InitializePayment {
+    pub reference: String,
+    pub email: String,
+    pub amount: i64,
+    pub currency: String,
+    pub callback_url: Option<String>,
+    pub metadata: serde_json::Value,
+}
+
+/// Where to send the customer to pay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentSession {
+    pub reference: String,
+    pub authorization_url: String,
+    pub access_code: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderPaymentStatus {
+    Success,
+    Failed,
+    Abandoned,
+    Pending,
+}
+
+impl ProviderPaymentStatus {
+    /// Maps a gateway status string; anything unknown is still in progress.
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "success" => ProviderPaymentStatus::Success,
+            "failed" | "reversed" => ProviderPaymentStatus::Failed,
+            "abandoned" => ProviderPaymentStatus::Abandoned,
+            _ => ProviderPaymentStatus::Pending,
+        }
+    }
+}
+
+/// The gateway's view of a transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentVerification {
+    pub reference: String,
+    pub status: ProviderPaymentStatus,
+    pub amount: i64,
+    pub currency: String,
+    pub paid_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundResult {
+    pub reference: String,
+    pub status: String,
+    pub amount: i64,
+}
+
+/// A payment gateway. Implementations talk to one provider's API.
+#[async_trait::async_trait]
+pub trait PaymentProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn initialize(&self, request: &InitializePayment) -> Result<PaymentSession, String>;
+    async fn verify(&self, reference: &str) -> Result<PaymentVerification, String>;
+
+    /// Refunds the whole transaction when `amount` is None.
+    async fn refund(&self, reference: &str, amount: Option<i64>) -> Result<RefundResult, String>;
+
+    /// Checks a webhook body against the signature header the gateway sent.
+    fn verify_webhook_signature(&self, payload: &[u8], signature: &str) -> bool;
+}
+
+/// Constant-time check of a hex HMAC-SHA512 signature, as Paystack signs webhooks.
+pub fn signature_matches(secret: &str, payload: &[u8], signature: &str) -> bool {
+    let Ok(expected) = hex::decode(signature.trim()) else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha512>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    mac.verify_slice(&expected).is_ok()
+}
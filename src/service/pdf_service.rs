@@ -0,0 +1,256 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// A minimal PDF 1.4 writer: text in the built-in Helvetica fonts, lines and
+/// filled boxes on A4 pages. Enough for invoices without external binaries.
+/// Coordinates are in points from the bottom-left corner.
+pub const PAGE_WIDTH: f32 = 595.0;
+pub const PAGE_HEIGHT: f32 = 842.0;
+
+// Helvetica glyph widths (per 1000 units of font size) for WinAnsiEncoding
+// codes 32..=255; codes without a glyph are 0
+const HELVETICA_WIDTHS: [u16; 224] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, 0,
+    556, 0, 222, 556, 333, 1000, 556, 556, 333, 1000, 667, 333, 1000, 0, 611, 0,
+    0, 222, 222, 333, 333, 350, 556, 1000, 333, 1000, 500, 333, 944, 0, 500, 667,
+    278, 333, 556, 556, 556, 556, 260, 556, 333, 737, 370, 556, 584, 333, 737, 333,
+    400, 584, 333, 333, 333, 556, 537, 278, 333, 333, 365, 556, 834, 834, 834, 611,
+    667, 667, 667, 667, 667, 667, 1000, 722, 667, 667, 667, 667, 278, 278, 278, 278,
+    722, 722, 778, 778, 778, 778, 778, 584, 778, 722, 722, 722, 722, 667, 667, 611,
+    556, 556, 556, 556, 556, 556, 889, 500, 556, 556, 556, 556, 278, 278, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 584, 611, 556, 556, 556, 556, 500, 556, 500,
+];
+
+const HELVETICA_BOLD_WIDTHS: [u16; 224] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584, 0,
+    556, 0, 278, 556, 500, 1000, 556, 556, 333, 1000, 667, 333, 1000, 0, 611, 0,
+    0, 278, 278, 500, 500, 350, 556, 1000, 333, 1000, 556, 333, 944, 0, 500, 667,
+    278, 333, 556, 556, 556, 556, 280, 556, 333, 737, 370, 556, 584, 333, 737, 333,
+    400, 584, 333, 333, 333, 611, 556, 278, 333, 333, 365, 556, 834, 834, 834, 611,
+    722, 722, 722, 722, 722, 722, 1000, 722, 667, 667, 667, 667, 278, 278, 278, 278,
+    722, 722, 778, 778, 778, 778, 778, 584, 778, 722, 722, 722, 722, 667, 667, 611,
+    556, 556, 556, 556, 556, 556, 889, 556, 556, 556, 556, 556, 278, 278, 278, 278,
+    611, 611, 611, 611, 611, 611, 611, 584, 611, 611, 611, 611, 611, 556, 611, 556,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+
+    fn widths(&self) -> &'static [u16; 224] {
+        match self {
+            Font::Regular => &HELVETICA_WIDTHS,
+            Font::Bold => &HELVETICA_BOLD_WIDTHS,
+        }
+    }
+}
+
+/// Width of `text` in points when set in `font` at `size`.
+pub fn text_width(text: &str, font: Font, size: f32) -> f32 {
+    let units: u32 = encode(text)
+        .into_iter()
+        .map(|byte| font.widths()[(byte as usize) - 32] as u32)
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+// WinAnsiEncoding code of a character the standard fonts have a glyph for
+fn win_ansi(c: char) -> Option<u8> {
+    let code = match c {
+        ' '..='~' | '\u{a0}'..='\u{ff}' => c as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        'ƒ' => 0x83,
+        '„' => 0x84,
+        '…' => 0x85,
+        '†' => 0x86,
+        '‡' => 0x87,
+        'ˆ' => 0x88,
+        '‰' => 0x89,
+        'Š' => 0x8a,
+        '‹' => 0x8b,
+        'Œ' => 0x8c,
+        'Ž' => 0x8e,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '˜' => 0x98,
+        '™' => 0x99,
+        'š' => 0x9a,
+        '›' => 0x9b,
+        'œ' => 0x9c,
+        'ž' => 0x9e,
+        'Ÿ' => 0x9f,
+        _ => return None,
+    };
+    Some(code)
+}
+
+// Text as WinAnsiEncoding bytes. Accented letters outside Latin-1 lose
+// their accents, so "Ọlọ́run" prints as "Olorun"; symbols with no close
+// match become '?'.
+fn encode(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.nfc() {
+        if let Some(code) = win_ansi(c) {
+            bytes.push(code);
+            continue;
+        }
+        match c {
+            '₦' => bytes.push(b'N'),
+            '‐' | '‑' | '‒' | '−' => bytes.push(b'-'),
+            '′' => bytes.push(b'\''),
+            '″' => bytes.push(b'"'),
+            // An accent left over once its letter is written
+            _ if is_combining_mark(c) => {}
+            _ => {
+                let base: Vec<u8> = c.nfd().filter_map(win_ansi).collect();
+                if base.is_empty() {
+                    bytes.push(b'?');
+                } else {
+                    bytes.extend(base);
+                }
+            }
+        }
+    }
+    bytes
+}
+
+// A PDF string literal body; bytes outside ASCII are written as octal escapes
+// so the content stream stays plain text
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for byte in encode(text) {
+        match byte {
+            b'\\' | b'(' | b')' => {
+                escaped.push('\\');
+                escaped.push(byte as char);
+            }
+            0x80.. => escaped.push_str(&format!("\\{:03o}", byte)),
+            _ => escaped.push(byte as char),
+        }
+    }
+    escaped
+}
+
+pub struct PdfDocument {
+    pages: Vec<String>,
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self { pages: vec![String::new()] }
+    }
+
+    pub fn new_page(&mut self) {
+        self.pages.push(String::new());
+    }
+
+    fn content(&mut self) -> &mut String {
+        self.pages.last_mut().expect("a document always has a page")
+    }
+
+    /// Draws text with its left edge at `x` and baseline at `y`.
+    pub fn text(&mut self, x: f32, y: f32, font: Font, size: f32, text: &str) {
+        let op = format!(
+            "BT /{} {:.1} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+            font.resource(), size, x, y, escape(text)
+        );
+        self.content().push_str(&op);
+    }
+
+    /// Draws text with its right edge at `x`, for amounts in columns.
+    pub fn text_right(&mut self, x: f32, y: f32, font: Font, size: f32, text: &str) {
+        let width = text_width(text, font, size);
+        self.text(x - width, y, font, size, text);
+    }
+
+    /// Sets the colour for following text and fills (0.0 to 1.0 per channel).
+    pub fn fill_color(&mut self, r: f32, g: f32, b: f32) {
+        let op = format!("{:.3} {:.3} {:.3} rg\n", r, g, b);
+        self.content().push_str(&op);
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, width: f32) {
+        let op = format!("{:.2} w {:.2} {:.2} m {:.2} {:.2} l S\n", width, x1, y1, x2, y2);
+        self.content().push_str(&op);
+    }
+
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        let op = format!("{:.2} {:.2} {:.2} {:.2} re f\n", x, y, width, height);
+        self.content().push_str(&op);
+    }
+
+    /// Serializes the document.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Objects 1-4 are fixed; each page then adds a page object and its content stream
+        let mut objects: Vec<String> = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            String::new(), // Page tree, filled in once the page ids are known
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+        ];
+
+        let mut page_ids = Vec::new();
+        for content in &self.pages {
+            let page_id = objects.len() + 1;
+            page_ids.push(format!("{} 0 R", page_id));
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH, PAGE_HEIGHT, page_id + 1
+            ));
+            objects.push(format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content));
+        }
+        objects[1] = format!("<< /Type /Pages /Kids [{}] /Count {} >>", page_ids.join(" "), self.pages.len());
+
+        let mut out = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+        }
+
+        let xref_start = out.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            xref.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        xref.push_str(&format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_start
+        ));
+        out.extend_from_slice(xref.as_bytes());
+        out
+    }
+}
+
+impl Default for PdfDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashSet;
+use bson::{doc, oid::ObjectId, Document};
+use futures_util::TryStreamExt;
+use mongodb::{
+    options::{FindOptions, IndexOptions},
+    Collection, IndexModel,
+};
+
+// Longest slug generated from a name, before any "-2" suffix
+const MAX_SLUG_LENGTH: usize = 80;
+
+// Used when a name has nothing that can be written in ASCII
+const FALLBACK_SLUG: &str = "item";
+
+/// Turns a name into a URL slug: ASCII only, lowercase, words joined by single
+/// hyphens, e.g. "Café Gaming PC & Monitor (2024)" becomes "cafe-gaming-pc-and-monitor-2024".
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut pending_hyphen = false;
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        // Apostrophes join words, so "Dell's" becomes "dells"
+        if matches!(c, '\'' | '’' | '‘') {
+            continue;
+        }
+        let ascii = match c {
+            'a'..='z' | '0'..='9' => None,
+            _ => Some(transliterate(c)),
+        };
+        match ascii {
+            None => {
+                if pending_hyphen && !slug.is_empty() {
+                    slug.push('-');
+                }
+                pending_hyphen = false;
+                slug.push(c);
+            }
+            Some(Some(word)) => {
+                // Symbols spelled out as words stand apart from their neighbours
+                let is_word = word.len() > 1 && !c.is_alphabetic();
+                if (pending_hyphen || is_word) && !slug.is_empty() {
+                    slug.push('-');
+                }
+                pending_hyphen = is_word;
+                slug.push_str(word);
+            }
+            Some(None) => pending_hyphen = true,
+        }
+    }
+
+    if slug.len() > MAX_SLUG_LENGTH {
+        let cut = slug[..MAX_SLUG_LENGTH].rfind('-').unwrap_or(MAX_SLUG_LENGTH);
+        slug.truncate(cut);
+    }
+    slug
+}
+
+// ASCII spelling of a lowercase letter or symbol; None makes it a word break
+fn transliterate(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        '&' => "and",
+        '+' => "plus",
+        '@' => "at",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+// Whether `slug` is `base` or `base` with a dedup suffix, e.g. "dell-xps-2"
+fn has_base(slug: &str, base: &str) -> bool {
+    match slug.strip_prefix(base) {
+        Some("") => true,
+        Some(rest) => rest.strip_prefix('-')
+            .is_some_and(|suffix| !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_digit())),
+        None => false,
+    }
+}
+
+/// Whether a document's current slug still fits its name, so a rename that
+/// only changes case or punctuation keeps the URL.
+pub fn slug_fits(slug: &str, name: &str) -> bool {
+    has_base(slug, &base_slug(name))
+}
+
+fn base_slug(text: &str) -> String {
+    let slug = slugify(text);
+    if slug.is_empty() { FALLBACK_SLUG.to_string() } else { slug }
+}
+
+// The first of "base", "base-2", "base-3", ... not in `taken`
+fn first_free(base: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or_default()
+}
+
+/// Matches a document by its current slug or one it had before a rename.
+pub fn slug_filter(slug: &str) -> Document {
+    doc! { "$or": [{ "slug": slug }, { "previous_slugs": slug }] }
+}
+
+/// Unique indexes for `slug` and `previous_slugs`, so no two documents
+/// answer to the same slug. Only documents with old slugs are indexed on them.
+pub fn slug_indexes() -> Vec<IndexModel> {
+    vec![
+        IndexModel::builder()
+            .keys(doc! { "slug": 1 })
+            .options(IndexOptions::builder().unique(true).build())
+            .build(),
+        IndexModel::builder()
+            .keys(doc! { "previous_slugs": 1 })
+            .options(IndexOptions::builder()
+                .unique(true)
+                .partial_filter_expression(doc! { "previous_slugs": { "$type": "string" } })
+                .build())
+            .build(),
+    ]
+}
+
+/// Whether a write failed because another document already has the slug.
+pub fn is_duplicate_slug(error: &mongodb::error::Error) -> bool {
+    let message = error.to_string();
+    message.contains("E11000") && message.contains("slug")
+}
+
+/// A slug for `name` that no other document in the collection uses, now or
+/// as an old slug: "name", then "name-2", "name-3" and so on.
+pub async fn unique_slug(collection: &Collection<Document>, name: &str, except: Option<&ObjectId>) -> Result<String, String> {
+    let base = base_slug(name);
+    let pattern = format!("^{}(-[0-9]+)?$", base);
+    let mut filter = doc! {
+        "$or": [
+            { "slug": { "$regex": &pattern } },
+            { "previous_slugs": { "$regex": &pattern } },
+        ],
+    };
+    if let Some(id) = except {
+        filter.insert("_id", doc! { "$ne": id });
+    }
+    let options = FindOptions::builder()
+        .projection(doc! { "slug": 1, "previous_slugs": 1 })
+        .build();
+
+    let mut cursor = collection.find(filter).with_options(options).await
+        .map_err(|e| format!("Failed to check slugs: {}", e))?;
+    let mut taken = HashSet::new();
+    while let Some(document) = cursor.try_next().await
+        .map_err(|e| format!("Failed to check slugs: {}", e))? {
+        taken.extend(document_slugs(&document));
+    }
+    Ok(first_free(&base, &taken))
+}
+
+/// Old slugs after moving from `current` to `next`: the current one is kept
+/// for redirects, and `next` is dropped in case the name went back to it.
+pub fn previous_slugs_after(previous: &[String], current: &str, next: &str) -> Vec<String> {
+    let mut slugs: Vec<String> = previous.iter()
+        .filter(|slug| slug.as_str() != next)
+        .cloned()
+        .collect();
+    if current != next && !current.is_empty() && !slugs.iter().any(|slug| slug == current) {
+        slugs.push(current.to_string());
+    }
+    slugs
+}
+
+fn previous_slugs(document: &Document) -> Vec<String> {
+    document.get_array("previous_slugs")
+        .map(|previous| previous.iter().filter_map(|slug| slug.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+// The current slug and old slugs of a document
+fn document_slugs(document: &Document) -> Vec<String> {
+    let mut slugs = previous_slugs(document);
+    if let Ok(slug) = document.get_str("slug") {
+        slugs.push(slug.to_string());
+    }
+    slugs
+}
+
+/// Gives every document a clean slug no other document shares, oldest first,
+/// keeping any replaced slug as a redirect. Run before the unique indexes are
+/// created. Returns the number of documents changed.
+pub async fn backfill_slugs(collection: &Collection<Document>, name_field: &str) -> Result<u64, String> {
+    let options = FindOptions::builder()
+        .projection(doc! { name_field: 1, "slug": 1, "previous_slugs": 1 })
+        .sort(doc! { "created_at": 1, "_id": 1 })
+        .build();
+    let mut cursor = collection.find(doc! {}).with_options(options).await
+        .map_err(|e| format!("Failed to read slugs: {}", e))?;
+    let mut documents = Vec::new();
+    while let Some(document) = cursor.try_next().await
+        .map_err(|e| format!("Failed to read slugs: {}", e))? {
+        documents.push(document);
+    }
+
+    // Old slugs stay with their documents; clean current ones are kept by
+    // whichever document had them first
+    let mut taken: HashSet<String> = HashSet::new();
+    for document in &documents {
+        taken.extend(previous_slugs(document));
+    }
+    let mut to_fix = Vec::new();
+    for document in documents {
+        let slug = document.get_str("slug").unwrap_or_default().to_string();
+        if !slug.is_empty() && slugify(&slug) == slug && taken.insert(slug) {
+            continue;
+        }
+        to_fix.push(document);
+    }
+
+    let mut changed = 0;
+    for document in to_fix {
+        let id = document.get_object_id("_id")
+            .map_err(|e| format!("Document has no ID: {}", e))?;
+        let current = document.get_str("slug").unwrap_or_default().to_string();
+        let slug = first_free(&base_slug(document.get_str(name_field).unwrap_or_default()), &taken);
+        taken.insert(slug.clone());
+
+        let mut previous = previous_slugs(&document);
+        // A slug another document now answers to can't also redirect here
+        if !current.is_empty() && !taken.contains(&current) {
+            previous = previous_slugs_after(&previous, &current, &slug);
+            taken.insert(current);
+        }
+
+        collection
+            .update_one(doc! { "_id": id }, doc! { "$set": { "slug": &slug, "previous_slugs": previous } })
+            .await
+            .map_err(|e| format!("Failed to update slug: {}", e))?;
+        changed += 1;
+    }
+    Ok(changed)
+}
@@ -1,5 +1,8 @@
 pub mod config;
 pub mod banner;
 pub mod brand;
+pub mod product;
+pub mod returns;
+pub mod reviews;
 
 pub use banner::{BannerUploadService, UploadError};
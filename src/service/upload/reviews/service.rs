@@ -0,0 +1,70 @@
+use cloudinary::upload::{OptionalParameters, Source, Upload};
+use std::collections::BTreeSet;
+
+use crate::service::upload::config::get_cloudinary_uploader;
+use crate::service::upload::product::UploadedImage;
+use crate::service::upload::UploadError;
+use cloudinary::upload::result::UploadResult;
+
+pub struct ReviewUploadService {
+    uploader: Upload,
+}
+
+impl ReviewUploadService {
+    pub fn new() -> Self {
+        Self {
+            uploader: get_cloudinary_uploader(),
+        }
+    }
+
+    /// Uploads a photo attached to a product review.
+    pub async fn upload_review_photo(&self, image_data: &str, product_id: &str) -> Result<UploadedImage, UploadError> {
+        let data_url = if image_data.starts_with("data:image") {
+            image_data.to_string()
+        } else {
+            format!("data:image/png;base64,{}", image_data)
+        };
+
+        let mut options = BTreeSet::new();
+        options.insert(OptionalParameters::Folder(format!("reviews/{}", product_id)));
+
+        let result = self
+            .uploader
+            .image(Source::DataUrl(data_url), &options)
+            .await
+            .map_err(|e| UploadError::CloudinaryError(e.to_string()))?;
+
+        match result {
+            UploadResult::Response(response) => Ok(UploadedImage {
+                url: response.secure_url,
+                public_id: response.public_id,
+            }),
+            UploadResult::ResponseWithImageMetadata(response) => Ok(UploadedImage {
+                url: response.secure_url,
+                public_id: response.public_id,
+            }),
+            UploadResult::Error(err) => Err(UploadError::CloudinaryError(err.error.message)),
+        }
+    }
+
+    /// Removes a photo whose review was never saved. An asset that is already
+    /// gone counts as deleted.
+    pub async fn delete_review_photo(&self, public_id: &str) -> Result<(), UploadError> {
+        let result = self
+            .uploader
+            .destroy(public_id.to_string())
+            .await
+            .map_err(|e| UploadError::CloudinaryError(e.to_string()))?;
+
+        match result.result.as_str() {
+            "ok" | "not found" => Ok(()),
+            other => Err(UploadError::CloudinaryError(format!("Failed to delete {}: {}", public_id, other))),
+        }
+    }
+}
+
+impl Default for ReviewUploadService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
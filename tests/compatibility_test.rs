@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+use bson::oid::ObjectId;
+use computers::module::compatibility::{
+    engine::{evaluate, BuildPart},
+    model::{default_rules, RuleSeverity},
+    schema::BuildIssue,
+};
+use computers::module::product::model::{ComponentKind, ComponentProfile, ComponentValue};
+
+fn number(value: f64) -> ComponentValue {
+    ComponentValue::Number(value)
+}
+
+fn text(value: &str) -> ComponentValue {
+    ComponentValue::Text(value.to_string())
+}
+
+fn list(values: &[&str]) -> ComponentValue {
+    ComponentValue::List(values.iter().map(|value| value.to_string()).collect())
+}
+
+fn part(name: &str, kind: ComponentKind, quantity: i32, attributes: Vec<(&str, ComponentValue)>) -> BuildPart {
+    BuildPart {
+        product_id: ObjectId::new(),
+        name: name.to_string(),
+        profile: ComponentProfile {
+            kind,
+            attributes: attributes.into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<BTreeMap<_, _>>(),
+        },
+        quantity,
+    }
+}
+
+fn cpu(socket: &str, tdp: f64) -> BuildPart {
+    part("Ryzen 7", ComponentKind::Cpu, 1, vec![
+        ("socket", text(socket)),
+        ("supported_chipsets", list(&["b650", "x670"])),
+        ("tdp_watts", number(tdp)),
+    ])
+}
+
+fn motherboard(socket: &str, form_factor: &str) -> BuildPart {
+    part("B650 board", ComponentKind::Motherboard, 1, vec![
+        ("socket", text(socket)),
+        ("chipset", text("b650")),
+        ("form_factor", text(form_factor)),
+        ("memory_type", text("ddr5")),
+        ("memory_slots", number(4.0)),
+    ])
+}
+
+fn case(form_factors: &[&str], max_gpu_length: ComponentValue) -> BuildPart {
+    part("Mid tower", ComponentKind::Case, 1, vec![
+        ("supported_form_factors", list(form_factors)),
+        ("max_gpu_length_mm", max_gpu_length),
+    ])
+}
+
+fn psu(wattage: f64) -> BuildPart {
+    part("PSU", ComponentKind::Psu, 1, vec![("wattage", number(wattage))])
+}
+
+fn gpu(tdp: f64) -> BuildPart {
+    part("RTX card", ComponentKind::Gpu, 1, vec![("tdp_watts", number(tdp)), ("length_mm", number(300.0))])
+}
+
+// A build, and the rule keys expected to fail as errors and as warnings
+type Case = (&'static str, Vec<BuildPart>, Vec<&'static str>, Vec<&'static str>);
+
+// Rule keys of the issues with the given severity, sorted
+fn keys(issues: &[BuildIssue], severity: RuleSeverity) -> Vec<String> {
+    let mut keys: Vec<String> = issues.iter()
+        .filter(|issue| issue.severity == severity)
+        .map(|issue| issue.rule.clone())
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn default_rules_table() {
+    let atx_case = || case(&["atx", "micro-atx"], number(350.0));
+
+    let cases: Vec<Case> = vec![
+        (
+            "matching parts",
+            vec![cpu("am5", 120.0), motherboard("am5", "atx"), atx_case(), psu(750.0)],
+            vec![],
+            vec![],
+        ),
+        (
+            "socket mismatch",
+            vec![cpu("lga1700", 120.0), motherboard("am5", "atx"), atx_case(), psu(750.0)],
+            vec!["cpu-motherboard-socket"],
+            vec![],
+        ),
+        (
+            "board too big for the case",
+            vec![cpu("am5", 120.0), motherboard("am5", "e-atx"), atx_case(), psu(750.0)],
+            vec!["case-form-factor"],
+            vec![],
+        ),
+        (
+            "power supply without headroom",
+            vec![cpu("am5", 120.0), gpu(320.0), motherboard("am5", "atx"), atx_case(), psu(500.0)],
+            vec![],
+            vec!["psu-headroom"],
+        ),
+        (
+            "power supply too small",
+            vec![cpu("am5", 120.0), gpu(320.0), motherboard("am5", "atx"), atx_case(), psu(400.0)],
+            vec!["psu-wattage"],
+            vec!["psu-headroom"],
+        ),
+        (
+            "two CPUs",
+            vec![cpu("am5", 120.0), cpu("am5", 120.0), motherboard("am5", "atx"), atx_case(), psu(750.0)],
+            vec!["single-cpu"],
+            vec![],
+        ),
+        (
+            "quantity counts towards the limit",
+            vec![part("PSU", ComponentKind::Psu, 2, vec![("wattage", number(750.0))]), cpu("am5", 120.0)],
+            vec!["single-psu"],
+            vec![],
+        ),
+        (
+            "board missing its socket",
+            vec![
+                cpu("am5", 120.0),
+                part("Mystery board", ComponentKind::Motherboard, 1, vec![("form_factor", text("atx")), ("chipset", text("b650"))]),
+                atx_case(),
+                psu(750.0),
+            ],
+            vec![],
+            vec!["cpu-motherboard-socket"],
+        ),
+        (
+            "clearance given as text",
+            vec![gpu(200.0), case(&["atx"], text("long cards")), psu(750.0)],
+            vec![],
+            vec!["gpu-length"],
+        ),
+        (
+            "no parts a rule reads",
+            vec![part("SSD", ComponentKind::Storage, 1, vec![])],
+            vec![],
+            vec![],
+        ),
+    ];
+
+    let rules = default_rules();
+    for (description, build, errors, warnings) in cases {
+        let issues = evaluate(&rules, &build);
+        assert_eq!(keys(&issues, RuleSeverity::Error), errors, "errors for {}", description);
+        assert_eq!(keys(&issues, RuleSeverity::Warning), warnings, "warnings for {}", description);
+    }
+}
+
+#[test]
+fn errors_come_first_and_name_the_parts() {
+    let cpu = cpu("lga1700", 120.0);
+    let board = motherboard("am5", "atx");
+    let build = vec![
+        part("Mystery case", ComponentKind::Case, 1, vec![]),
+        cpu.clone(),
+        board.clone(),
+    ];
+
+    let issues = evaluate(&default_rules(), &build);
+    assert_eq!(issues[0].severity, RuleSeverity::Error);
+    assert_eq!(issues[0].rule, "cpu-motherboard-socket");
+    assert!(issues[0].product_ids.contains(&cpu.product_id));
+    assert!(issues[0].product_ids.contains(&board.product_id));
+    assert!(issues[0].message.contains("lga1700") && issues[0].message.contains("am5"));
+    assert!(issues.iter().skip(1).all(|issue| issue.severity == RuleSeverity::Warning));
+}
+
+#[test]
+fn inactive_rules_are_skipped() {
+    let mut rules = default_rules();
+    for rule in rules.iter_mut() {
+        rule.is_active = false;
+    }
+    let build = vec![cpu("lga1700", 120.0), motherboard("am5", "atx")];
+    assert!(evaluate(&rules, &build).is_empty());
+}
@@ -0,0 +1,64 @@
+use bson::{doc, oid::ObjectId, Document};
+use chrono::Duration;
+use computers::module::product::{crud::ProductCrud, migration, model::ReservedItem};
+use computers::service::migration_service::run_migrations;
+use mongodb::Client;
+use std::env;
+
+#[tokio::test]
+#[ignore = "needs MongoDB; set MONGODB_URL and run with --ignored"]
+async fn concurrent_reservations_never_oversell() {
+    dotenv::dotenv().ok();
+    let url = env::var("MONGODB_URL").expect("MONGODB_URL must be set for this test");
+
+    let client = Client::with_uri_str(&url).await.expect("Failed to connect to MongoDB");
+    let db = client.database(&format!("inventory_test_{}", uuid::Uuid::new_v4().simple()));
+    let products = db.collection::<Document>("products");
+
+    let product_id = ObjectId::new();
+    products
+        .insert_one(doc! { "_id": product_id, "name": "Test GPU", "is_active": true, "stock_quantity": 10 })
+        .await
+        .expect("Failed to insert product");
+
+    run_migrations(&db, migration::migrations()).await.expect("Failed to create indexes");
+    let crud = ProductCrud::new(&db);
+
+    // 50 shoppers race for 10 units
+    let attempts: Vec<_> = (0..50)
+        .map(|i| {
+            let crud = crud.clone();
+            tokio::spawn(async move {
+                let items = [ReservedItem { product_id, variant_id: None, quantity: 1 }];
+                crud.reserve_stock(&format!("ORDER-{}", i), &items, Duration::minutes(30)).await
+            })
+        })
+        .collect();
+
+    let mut reserved = Vec::new();
+    for (i, attempt) in attempts.into_iter().enumerate() {
+        if attempt.await.expect("Reservation task panicked").is_ok() {
+            reserved.push(format!("ORDER-{}", i));
+        }
+    }
+
+    let stock = |doc: Option<Document>| doc.and_then(|doc| doc.get_i32("stock_quantity").ok());
+    let after_reserve = stock(products.find_one(doc! { "_id": product_id }).await.unwrap());
+
+    assert_eq!(reserved.len(), 10, "exactly the available stock should be reserved");
+    assert_eq!(after_reserve, Some(0), "stock should be used up, never negative");
+
+    // Committed stock stays taken until released; active holds go straight back
+    crud.commit_reservation(&reserved[0]).await.expect("Failed to commit reservation");
+    crud.commit_reservation(&reserved[0]).await.expect("committing twice should succeed");
+    for reference in &reserved {
+        let released = crud.release_reservation(reference).await.expect("Failed to release reservation");
+        assert!(released.is_some());
+        assert!(crud.release_reservation(reference).await.unwrap().is_none(), "release must be idempotent");
+    }
+
+    let after_release = stock(products.find_one(doc! { "_id": product_id }).await.unwrap());
+    db.drop().await.ok();
+
+    assert_eq!(after_release, Some(10), "releasing should restore all stock");
+}
@@ -0,0 +1,117 @@
+use bson::oid::ObjectId;
+use chrono::Utc;
+use computers::environment::CompanyDetails;
+use computers::module::invoice::{
+    model::{Invoice, InvoiceKind, InvoiceLine, TaxLine},
+    pdf::render_invoice,
+};
+use computers::module::order::model::ShippingAddress;
+use computers::service::currency_service::format_naira_code;
+use computers::service::pdf_service::{text_width, Font, PdfDocument};
+
+fn company() -> CompanyDetails {
+    CompanyDetails {
+        name: "Computers Store".to_string(),
+        address_lines: vec!["12 Allen Avenue".to_string(), "Ikeja, Lagos".to_string()],
+        email: "sales@example.com".to_string(),
+        phone: "08012345678".to_string(),
+        vat_number: Some("VAT-123".to_string()),
+    }
+}
+
+fn invoice(tax_lines: Vec<TaxLine>, lines: usize) -> Invoice {
+    let tax: i64 = tax_lines.iter().map(|tax| tax.amount).sum();
+    Invoice {
+        id: None,
+        kind: InvoiceKind::Invoice,
+        sequence: 42,
+        invoice_number: "INV-000042".to_string(),
+        order_id: ObjectId::new(),
+        order_number: "ORD-20260101-ABCDEF12".to_string(),
+        user_id: ObjectId::new(),
+        bill_to: ShippingAddress {
+            full_name: "Ada Obi".to_string(),
+            phone_number: "08098765432".to_string(),
+            address_line1: "5 Marina Road".to_string(),
+            address_line2: None,
+            city: "Lagos".to_string(),
+            state: "Lagos".to_string(),
+            postal_code: None,
+            country: "Nigeria".to_string(),
+        },
+        lines: (0..lines)
+            .map(|i| InvoiceLine {
+                name: format!("Graphics card {}", i),
+                sku: format!("GPU-{}", i),
+                unit_price: 125_000_000,
+                quantity: 1,
+                line_total: 125_000_000,
+            })
+            .collect(),
+        subtotal: 125_000_000 * lines as i64,
+        shipping_fee: 0,
+        discount_total: 0,
+        total: 125_000_000 * lines as i64 + tax,
+        tax_lines,
+        paid_at: Some(Utc::now()),
+        issued_at: Utc::now(),
+    }
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle.as_bytes())
+}
+
+#[test]
+fn formats_kobo_as_grouped_naira() {
+    assert_eq!(format_naira_code(0), "NGN 0.00");
+    assert_eq!(format_naira_code(5), "NGN 0.05");
+    assert_eq!(format_naira_code(99_999), "NGN 999.99");
+    assert_eq!(format_naira_code(100_000), "NGN 1,000.00");
+    assert_eq!(format_naira_code(125_000_000), "NGN 1,250,000.00");
+    assert_eq!(format_naira_code(123_456_789), "NGN 1,234,567.89");
+    assert_eq!(format_naira_code(-150_050), "-NGN 1,500.50");
+}
+
+#[test]
+fn renders_a_pdf_with_the_number_lines_and_totals() {
+    let vat = TaxLine { label: "VAT".to_string(), amount: 9_375_000 };
+    let pdf = render_invoice(&invoice(vec![vat], 2), &company());
+
+    assert!(pdf.starts_with(b"%PDF-1.4"));
+    assert!(pdf.ends_with(b"%%EOF\n"));
+    assert!(contains(&pdf, "INV-000042"));
+    assert!(contains(&pdf, "Graphics card 1"));
+    assert!(contains(&pdf, "(VAT)"));
+    assert!(contains(&pdf, "NGN 93,750.00"));
+    assert!(contains(&pdf, "NGN 2,593,750.00"));
+}
+
+#[test]
+fn leaves_out_the_vat_line_when_no_tax_was_charged() {
+    let pdf = render_invoice(&invoice(Vec::new(), 1), &company());
+    assert!(!contains(&pdf, "(VAT)"));
+}
+
+#[test]
+fn long_invoices_run_onto_more_pages() {
+    let pdf = render_invoice(&invoice(Vec::new(), 80), &company());
+    assert!(contains(&pdf, "/Count 2") || contains(&pdf, "/Count 3"));
+    assert!(contains(&pdf, "Graphics card 79"));
+}
+
+#[test]
+fn writes_latin_1_in_win_ansi_and_drops_other_accents() {
+    let mut document = PdfDocument::new();
+    document.text(50.0, 700.0, Font::Regular, 10.0, "Café “Ọlọ́run” – ₦5 😀");
+    let pdf = document.to_bytes();
+
+    assert!(contains(&pdf, r"(Caf\351 \223Olorun\224 \226 N5 ?) Tj"));
+}
+
+#[test]
+fn accented_letters_are_as_wide_as_their_base_letters() {
+    assert_eq!(text_width("é", Font::Regular, 10.0), text_width("e", Font::Regular, 10.0));
+    assert_eq!(text_width("Ọ", Font::Bold, 10.0), text_width("O", Font::Bold, 10.0));
+    assert!(text_width("€", Font::Regular, 10.0) > 0.0);
+}
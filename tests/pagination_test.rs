@@ -0,0 +1,16 @@
+use computers::service::pagination_service::{paginate, MAX_PAGE};
+
+#[test]
+fn paginate_defaults_and_clamps() {
+    assert_eq!(paginate(None, None), (1, 20, 0));
+    assert_eq!(paginate(Some(0), Some(0)), (1, 1, 0));
+    assert_eq!(paginate(Some(3), Some(50)), (3, 50, 100));
+    assert_eq!(paginate(Some(2), Some(1_000)), (2, 100, 100));
+}
+
+#[test]
+fn huge_pages_are_capped_instead_of_overflowing() {
+    let (page, limit, skip) = paginate(Some(u64::MAX), Some(i64::MAX));
+    assert_eq!((page, limit), (MAX_PAGE, 100));
+    assert_eq!(skip, (MAX_PAGE - 1) * 100);
+}
@@ -0,0 +1,297 @@
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+use bson::{doc, oid::ObjectId};
+use chrono::Duration;
+use computers::module::order::crud::OrderCrud;
+use computers::module::payment::{
+    controller::PaymentController,
+    crud::PaymentCrud,
+    interface::PaymentService,
+    model::{PaymentIntent, PaymentIntentStatus},
+    schema::{CreatePaymentIntentSchema, PaymentIntentItemSchema},
+};
+use computers::module::product::{
+    crud::ProductCrud,
+    model::{Product, ReservationStatus, ReservedItem},
+};
+use computers::service::migration_service::{all_migrations, run_migrations};
+use computers::service::payment::{
+    provider::{InitializePayment, ProviderPaymentStatus},
+    MockPaymentProvider, PaymentProvider, PaystackProvider,
+};
+use hmac::{Hmac, Mac};
+use mongodb::{Client, Database};
+use serde_json::{json, Value};
+use sha2::Sha512;
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, Mutex};
+
+const SECRET: &str = "sk_test_mock";
+
+// Transactions the mock gateway knows about: reference -> amount
+type Transactions = web::Data<Mutex<HashMap<String, i64>>>;
+
+fn authorized(req: &HttpRequest) -> bool {
+    req.headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value == format!("Bearer {}", SECRET))
+}
+
+fn rejected(message: &str) -> HttpResponse {
+    HttpResponse::Unauthorized().json(json!({ "status": false, "message": message }))
+}
+
+async fn initialize(req: HttpRequest, body: web::Json<Value>, transactions: Transactions) -> HttpResponse {
+    if !authorized(&req) {
+        return rejected("Invalid key");
+    }
+    let reference = body["reference"].as_str().unwrap_or_default().to_string();
+    let amount = body["amount"].as_i64().unwrap_or_default();
+    transactions.lock().unwrap().insert(reference.clone(), amount);
+
+    HttpResponse::Ok().json(json!({
+        "status": true,
+        "message": "Authorization URL created",
+        "data": {
+            "authorization_url": format!("https://checkout.paystack.com/{}", reference),
+            "access_code": "access_123",
+            "reference": reference,
+        }
+    }))
+}
+
+async fn verify(req: HttpRequest, reference: web::Path<String>, transactions: Transactions) -> HttpResponse {
+    if !authorized(&req) {
+        return rejected("Invalid key");
+    }
+    match transactions.lock().unwrap().get(reference.as_str()) {
+        Some(amount) => HttpResponse::Ok().json(json!({
+            "status": true,
+            "message": "Verification successful",
+            "data": {
+                "reference": reference.as_str(),
+                "status": "success",
+                "amount": amount,
+                "currency": "NGN",
+                "paid_at": "2026-01-15T10:30:00.000Z",
+            }
+        })),
+        None => HttpResponse::BadRequest().json(json!({
+            "status": false,
+            "message": "Transaction reference not found",
+        })),
+    }
+}
+
+async fn refund(req: HttpRequest, body: web::Json<Value>, transactions: Transactions) -> HttpResponse {
+    if !authorized(&req) {
+        return rejected("Invalid key");
+    }
+    let reference = body["transaction"].as_str().unwrap_or_default();
+    let paid = match transactions.lock().unwrap().get(reference) {
+        Some(amount) => *amount,
+        None => return HttpResponse::NotFound().json(json!({ "status": false, "message": "Transaction not found" })),
+    };
+
+    HttpResponse::Ok().json(json!({
+        "status": true,
+        "message": "Refund has been queued for processing",
+        "data": { "status": "pending", "amount": body["amount"].as_i64().unwrap_or(paid) }
+    }))
+}
+
+// Start a Paystack look-alike on a free port and return its base URL
+fn start_mock_gateway() -> String {
+    let transactions: Transactions = web::Data::new(Mutex::new(HashMap::new()));
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(transactions.clone())
+            .route("/transaction/initialize", web::post().to(initialize))
+            .route("/transaction/verify/{reference}", web::get().to(verify))
+            .route("/refund", web::post().to(refund))
+    })
+    .workers(1)
+    .bind("127.0.0.1:0")
+    .expect("Failed to bind mock gateway");
+
+    let address = server.addrs()[0];
+    actix_web::rt::spawn(server.run());
+    format!("http://{}", address)
+}
+
+fn payment(reference: &str, amount: i64) -> InitializePayment {
+    InitializePayment {
+        reference: reference.to_string(),
+        email: "buyer@example.com".to_string(),
+        amount,
+        currency: "NGN".to_string(),
+        callback_url: None,
+        metadata: json!({}),
+    }
+}
+
+fn sign(secret: &str, payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha512>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[actix_web::test]
+async fn paystack_provider_round_trip() {
+    let provider = PaystackProvider::new(SECRET.to_string(), start_mock_gateway());
+
+    let session = provider.initialize(&payment("PAY-1", 250_000)).await.expect("initialize failed");
+    assert_eq!(session.reference, "PAY-1");
+    assert!(session.authorization_url.ends_with("/PAY-1"));
+
+    let verification = provider.verify("PAY-1").await.expect("verify failed");
+    assert_eq!(verification.status, ProviderPaymentStatus::Success);
+    assert_eq!(verification.amount, 250_000);
+    assert!(verification.paid_at.is_some());
+
+    let partial = provider.refund("PAY-1", Some(50_000)).await.expect("refund failed");
+    assert_eq!(partial.amount, 50_000);
+    let full = provider.refund("PAY-1", None).await.expect("refund failed");
+    assert_eq!(full.amount, 250_000);
+
+    let missing = provider.verify("PAY-unknown").await.unwrap_err();
+    assert!(missing.contains("Transaction reference not found"), "{}", missing);
+}
+
+#[actix_web::test]
+async fn paystack_provider_reports_rejected_key() {
+    let provider = PaystackProvider::new("sk_test_wrong".to_string(), start_mock_gateway());
+
+    let error = provider.initialize(&payment("PAY-2", 1_000)).await.unwrap_err();
+    assert!(error.contains("Invalid key"), "{}", error);
+}
+
+#[actix_web::test]
+async fn webhook_signatures_are_checked() {
+    let provider = PaystackProvider::new(SECRET.to_string(), "http://127.0.0.1:1".to_string());
+    let payload = br#"{"event":"charge.success","data":{"reference":"PAY-1","amount":250000}}"#;
+
+    assert!(provider.verify_webhook_signature(payload, &sign(SECRET, payload)));
+    assert!(!provider.verify_webhook_signature(payload, &sign("another_secret", payload)));
+    assert!(!provider.verify_webhook_signature(b"{\"event\":\"tampered\"}", &sign(SECRET, payload)));
+    assert!(!provider.verify_webhook_signature(payload, "not-hex"));
+    assert!(!provider.verify_webhook_signature(payload, ""));
+}
+
+#[actix_web::test]
+async fn mock_provider_pays_everything_it_initialized() {
+    let provider = MockPaymentProvider::new();
+
+    provider.initialize(&payment("PAY-3", 9_900)).await.expect("initialize failed");
+    let verification = provider.verify("PAY-3").await.expect("verify failed");
+    assert_eq!(verification.status, ProviderPaymentStatus::Success);
+    assert_eq!(verification.amount, 9_900);
+
+    assert!(provider.verify("PAY-unknown").await.is_err());
+    assert_eq!(provider.refund("PAY-3", None).await.unwrap().amount, 9_900);
+}
+
+// A fresh database with one product in stock, and a controller paying through the mock gateway
+async fn payment_setup(stock: i32) -> (Database, PaymentController, ProductCrud, ObjectId) {
+    dotenv::dotenv().ok();
+    let url = env::var("MONGODB_URL").expect("MONGODB_URL must be set for this test");
+    let client = Client::with_uri_str(&url).await.expect("Failed to connect to MongoDB");
+    let db = client.database(&format!("payment_test_{}", uuid::Uuid::new_v4().simple()));
+    run_migrations(&db, all_migrations()).await.expect("Failed to migrate test database");
+
+    let mut product = Product::new(
+        "Test Laptop".to_string(),
+        "A laptop".to_string(),
+        50_000_000,
+        format!("SKU-{}", uuid::Uuid::new_v4().simple()),
+        ObjectId::new(),
+        ObjectId::new(),
+    );
+    product.stock_quantity = stock;
+    let product_id = db.collection::<Product>("products").insert_one(&product).await
+        .expect("Failed to insert product")
+        .inserted_id.as_object_id().unwrap();
+
+    let product_crud = ProductCrud::new(&db);
+    let controller = PaymentController::new(
+        PaymentCrud::new(&db),
+        product_crud.clone(),
+        OrderCrud::new(&db),
+        Arc::new(MockPaymentProvider::new()),
+    );
+    (db, controller, product_crud, product_id)
+}
+
+async fn pay_for(controller: &PaymentController, product_id: ObjectId) -> PaymentIntent {
+    let data = CreatePaymentIntentSchema {
+        items: vec![PaymentIntentItemSchema { product_id: product_id.to_hex(), variant_id: None, quantity: 1 }],
+        order_id: None,
+        callback_url: None,
+    };
+    controller.create_intent(&ObjectId::new().to_hex(), "buyer@example.com", data).await
+        .expect("Failed to create payment intent")
+}
+
+async fn send_success(controller: &PaymentController, intent: &PaymentIntent) {
+    let payload = json!({
+        "event": "charge.success",
+        "data": { "reference": intent.reference, "amount": intent.amount, "paid_at": "2026-01-15T10:30:00.000Z" },
+    }).to_string();
+    let secret = env::var("PAYMENT_MOCK_SECRET").unwrap_or_else(|_| "mock_secret".to_string());
+    let signature = sign(&secret, payload.as_bytes());
+    controller.handle_webhook(payload.as_bytes(), &signature).await
+        .expect("a payment that landed late should still be settled");
+}
+
+async fn stock_of(db: &Database, product_id: ObjectId) -> i32 {
+    db.collection::<Product>("products").find_one(doc! { "_id": product_id }).await
+        .unwrap()
+        .unwrap()
+        .stock_quantity
+}
+
+#[tokio::test]
+#[ignore = "needs MongoDB; set MONGODB_URL and run with --ignored"]
+async fn late_success_takes_the_stock_again_when_it_is_there() {
+    let (db, controller, product_crud, product_id) = payment_setup(1).await;
+    let intent = pay_for(&controller, product_id).await;
+
+    // The payment window closes before the gateway reports back
+    product_crud.release_reservation(&intent.reference).await.unwrap();
+    send_success(&controller, &intent).await;
+
+    let paid = controller.get_intent(&intent.user_id.to_hex(), &intent.id.unwrap().to_hex()).await.unwrap().unwrap();
+    let reservation = product_crud.get_reservation(&intent.reference).await.unwrap().unwrap();
+    let stock = stock_of(&db, product_id).await;
+    db.drop().await.ok();
+
+    assert_eq!(paid.status, PaymentIntentStatus::Succeeded);
+    assert_eq!(reservation.status, ReservationStatus::Committed);
+    assert_eq!(stock, 0);
+}
+
+#[tokio::test]
+#[ignore = "needs MongoDB; set MONGODB_URL and run with --ignored"]
+async fn late_success_is_refunded_when_the_stock_is_gone() {
+    let (db, controller, product_crud, product_id) = payment_setup(1).await;
+    let intent = pay_for(&controller, product_id).await;
+
+    // The hold expires and someone else buys the last unit
+    product_crud.release_reservation(&intent.reference).await.unwrap();
+    let items = [ReservedItem { product_id, variant_id: None, quantity: 1 }];
+    product_crud.reserve_stock("OTHER-BUYER", &items, Duration::minutes(30)).await.unwrap();
+
+    send_success(&controller, &intent).await;
+    // A replay of the same event is settled too, without refunding twice
+    send_success(&controller, &intent).await;
+
+    let refunded = controller.get_intent(&intent.user_id.to_hex(), &intent.id.unwrap().to_hex()).await.unwrap().unwrap();
+    let stock = stock_of(&db, product_id).await;
+    db.drop().await.ok();
+
+    assert_eq!(refunded.status, PaymentIntentStatus::Refunded);
+    assert_eq!(refunded.refunded_amount, refunded.amount);
+    assert!(refunded.paid_at.is_some());
+    assert_eq!(stock, 0, "the other buyer keeps their unit");
+}
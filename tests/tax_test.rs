@@ -0,0 +1,116 @@
+use chrono::{TimeZone, Utc};
+use computers::module::tax::{
+    calculator::{round_div, TaxCalculator},
+    crud::TaxCrud,
+    model::TaxRate,
+};
+use mongodb::Client;
+use std::env;
+
+fn rate(tax_class: &str, rate_basis_points: i64, year: i32) -> TaxRate {
+    TaxRate {
+        id: None,
+        tax_class: tax_class.to_string(),
+        label: "VAT".to_string(),
+        rate_basis_points,
+        effective_from: Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap(),
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn round_div_rounds_halves_away_from_zero() {
+    let cases = [
+        (10, 4, 3),    // 2.5
+        (9, 4, 2),     // 2.25
+        (11, 4, 3),    // 2.75
+        (-10, 4, -3),  // -2.5
+        (-9, 4, -2),   // -2.25
+        (0, 7, 0),
+        (21, 7, 3),
+    ];
+    for (numerator, denominator, expected) in cases {
+        assert_eq!(round_div(numerator, denominator), expected, "{} / {}", numerator, denominator);
+    }
+}
+
+#[test]
+fn inclusive_tax_is_the_share_already_in_the_price() {
+    let cases = [
+        (10_750, 750, 750),        // ₦107.50 at 7.5% holds ₦7.50
+        (100_000_000, 750, 6_976_744), // 6,976,744.19 rounds down
+        (1, 750, 0),
+        (0, 750, 0),
+        (10_000, 0, 0),
+        (-10_750, 750, -750),
+    ];
+    for (amount, rate, expected) in cases {
+        assert_eq!(TaxCalculator::inclusive_tax(amount, rate), expected, "{} at {}bp", amount, rate);
+    }
+}
+
+#[test]
+fn exclusive_tax_is_added_on_top() {
+    let cases = [
+        (10_000, 750, 750),
+        (125_000_000, 750, 9_375_000),
+        (7, 750, 1),   // 0.525 rounds up
+        (6, 750, 0),   // 0.45 rounds down
+        (10_000, 0, 0),
+        (-10_000, 750, -750),
+    ];
+    for (amount, rate, expected) in cases {
+        assert_eq!(TaxCalculator::exclusive_tax(amount, rate), expected, "{} at {}bp", amount, rate);
+    }
+}
+
+#[test]
+fn breakdown_uses_the_latest_rate_in_effect() {
+    let calculator = TaxCalculator::new(
+        vec![rate("standard", 500, 2015), rate("standard", 750, 2020), rate("standard", 1_000, 2099)],
+        false,
+    );
+
+    let breakdown = calculator.breakdown(10_000, "standard", Utc::now()).unwrap();
+    assert_eq!(breakdown.rate_basis_points, 750);
+    assert_eq!((breakdown.net, breakdown.tax, breakdown.gross), (10_000, 750, 10_750));
+
+    let old = Utc.with_ymd_and_hms(2018, 6, 1, 0, 0, 0).unwrap();
+    assert_eq!(calculator.breakdown(10_000, "standard", old).unwrap().tax, 500);
+}
+
+#[test]
+fn inclusive_breakdown_keeps_the_gross_price() {
+    let calculator = TaxCalculator::new(vec![rate("standard", 750, 2020)], true);
+    let breakdown = calculator.breakdown(10_750, "standard", Utc::now()).unwrap();
+    assert_eq!((breakdown.net, breakdown.tax, breakdown.gross), (10_000, 750, 10_750));
+}
+
+#[test]
+fn unknown_tax_class_is_an_error() {
+    let calculator = TaxCalculator::new(vec![rate("standard", 750, 2020)], true);
+    assert!(calculator.rate_for("standrad", Utc::now()).is_err());
+    assert!(calculator.breakdown(10_000, "standrad", Utc::now()).is_err());
+
+    // A class whose only rate is scheduled has no rate yet
+    let calculator = TaxCalculator::new(vec![rate("luxury", 1_500, 2099)], true);
+    assert!(calculator.rate_for("luxury", Utc::now()).is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs MongoDB; set MONGODB_URL and run with --ignored"]
+async fn only_classes_in_the_rate_table_can_be_assigned() {
+    dotenv::dotenv().ok();
+    let url = env::var("MONGODB_URL").expect("MONGODB_URL must be set for this test");
+    let client = Client::with_uri_str(&url).await.expect("Failed to connect to MongoDB");
+    let db = client.database(&format!("tax_test_{}", uuid::Uuid::new_v4().simple()));
+    let crud = TaxCrud::new(&db);
+    crud.seed_default_rates().await.unwrap();
+
+    assert_eq!(crud.check_tax_class(" Standard ").await.unwrap(), "standard");
+    assert_eq!(crud.check_tax_class("exempt").await.unwrap(), "exempt");
+    let error = crud.check_tax_class("standrad").await.unwrap_err();
+    assert!(error.contains("exempt, standard"), "{}", error);
+
+    db.drop().await.ok();
+}